@@ -256,7 +256,13 @@ fn configure_validation(builder: Builder) -> Builder {
             ("Filter.must", ""),
             ("Filter.must_not", ""),
             ("NestedCondition.filter", ""),
+            ("JoinCondition.key", "length(min = 1)"),
+            ("JoinCondition.collection", "length(min = 1, max = 255)"),
+            ("JoinCondition.filter", ""),
+            ("JoinCondition.limit", "range(min = 1, max = 100000)"),
             ("Condition.condition_one_of", ""),
+            ("FuzzyMatch.max_edits", "range(max = 2)"),
+            ("FuzzyMatch.max_expansions", "range(min = 1, max = 1000)"),
             ("PointStruct.vectors", ""),
             ("Vectors.vectors_options", ""),
             ("NamedVectors.vectors", ""),
@@ -276,6 +282,20 @@ fn configure_validation(builder: Builder) -> Builder {
             ("FacetCounts.key", "length(min = 1)"),
             ("FacetCounts.filter", ""),
             ("FacetCounts.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("FacetCounts.geo_grid", ""),
+            ("FacetCounts.pivot", "nested, length(max = 3)"),
+            ("FacetPivot.key", "length(min = 1)"),
+            ("FacetPivot.limit", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("GeoGridParams.precision", "range(min = 1, max = 12)"),
+            ("AggregatePoints.collection_name", "length(min = 1, max = 255)"),
+            ("AggregatePoints.key", "length(min = 1)"),
+            ("AggregatePoints.filter", ""),
+            ("AggregatePoints.percentiles", "custom(function = \"segment::data_types::aggregations::validate_percentiles\")"),
+            ("AggregatePoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("DistinctCountPoints.collection_name", "length(min = 1, max = 255)"),
+            ("DistinctCountPoints.key", "length(min = 1)"),
+            ("DistinctCountPoints.filter", ""),
+            ("DistinctCountPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("SearchMatrixPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchMatrixPoints.filter", ""),
             ("SearchMatrixPoints.sample", "custom(function = \"crate::grpc::validate::validate_u64_range_min_2\")"),
@@ -320,6 +340,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryBatchPointsInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("FacetCountsInternal.collection_name", "length(min = 1, max = 255)"),
             ("FacetCountsInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("AggregatePointsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("AggregatePointsInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("DistinctCountPointsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("DistinctCountPointsInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
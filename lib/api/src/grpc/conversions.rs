@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::str::FromStr as _;
 use std::time::Instant;
 
@@ -6,46 +6,60 @@ use chrono::{NaiveDateTime, Timelike};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::counter::hardware_counter::HardwareCounterCell;
 use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use segment::common::hyperloglog::HyperLogLog;
 use segment::common::operation_error::OperationError;
+use segment::common::tdigest::{Centroid, TDigest};
 use segment::data_types::index::{
-    BoolIndexType, DatetimeIndexType, FloatIndexType, GeoIndexType, IntegerIndexType,
-    KeywordIndexType, TextIndexType, UuidIndexType,
+    BoolIndexType, DatetimeIndexType, FloatIndexType, GeoIndexType, GeoShapeIndexType,
+    IntegerIndexType, KeywordIndexType, TextIndexType, UuidIndexType,
+};
+use segment::data_types::{
+    aggregations as segment_aggregations, facets as segment_facets, vectors as segment_vectors,
 };
-use segment::data_types::{facets as segment_facets, vectors as segment_vectors};
 use segment::types::{default_quantization_ignore_value, DateTimePayloadType, FloatPayloadType};
 use segment::vector_storage::query as segment_query;
 use sparse::common::sparse_vector::validate_sparse_vector_impl;
 use tonic::Status;
 use uuid::Uuid;
+use validator::Validate as _;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, BoolIndexParams, CompressionRatio,
-    DatetimeIndexParams, DatetimeRange, Direction, FacetHit, FacetHitInternal, FacetValue,
-    FacetValueInternal, FieldType, FloatIndexParams, GeoIndexParams, GeoLineString, GroupId,
-    HardwareUsage, HasVectorCondition, KeywordIndexParams, LookupLocation, MultiVectorComparator,
-    MultiVectorConfig, OrderBy, OrderValue, Range, RawVector, RecommendStrategy, RetrievedPoint,
-    SearchMatrixPair, SearchPointGroups, SearchPoints, ShardKeySelector, SparseIndices, StartFrom,
-    UuidIndexParams, VectorsOutput, WithLookup,
+    raw_query, start_from, AggregateResult, AggregateValue, BinaryQuantization, Bm25,
+    BoolIndexParams, CalendarInterval, CompressionRatio, DatetimeIndexParams, DatetimeRange,
+    DigestCentroid, Direction, DistinctCountResult, DistinctValuesInternal, FacetDatetimeRange,
+    FacetFloatRange, FacetHit, FacetHitInternal, FacetPivot, FacetValue, FacetValueInternal,
+    FacetValueInternalTuple, FacetValueTuple, FieldType, FloatIndexParams, GeoGridParams,
+    GeoIndexParams, GeoLineString, GeoShapeCondition, GeoShapeIndexParams, GroupId,
+    GroupIdComposite, GroupScore, HardwareUsage, HasVectorCondition, HistogramBound,
+    HistogramParams, HistogramRange, HistogramRanges, KeywordIndexParams, KeywordNormalizer,
+    KeywordRange, LookupLocation, MultiVectorComparator, MultiVectorConfig, OrderBy, OrderByKey,
+    OrderCursor, OrderValue, OrderValues, PartialAggregateInternal, PartialDistinctCountInternal,
+    PercentileValue, Range, RawVector, RecommendStrategy, RetrievedPoint, SearchMatrixPair,
+    SearchPointGroups, SearchPoints, ShardKeySelector, SparseIndices, StartFrom, UuidIndexParams,
+    VectorsOutput, WithLookup,
 };
 use crate::conversions::json;
 use crate::grpc::qdrant::condition::ConditionOneOf;
 use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
+use crate::grpc::qdrant::stemming_algorithm::StemmingParams;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius,
-    HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams, IsEmptyCondition,
-    IsNullCondition, ListCollectionsResponse, Match, MinShould, NamedVectors, NestedCondition,
-    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo,
-    PayloadSchemaType, PointId, PointStruct, PointsOperationResponse,
-    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
-    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
-    ScalarQuantization, ScoredPoint, SearchParams, ShardKey, StrictModeConfig, TextIndexParams,
-    TokenizerType, UpdateResult, UpdateResultInternal, ValuesCount, VectorsSelector,
-    WithPayloadSelector, WithVectorsSelector,
+    Condition, Distance, FieldCondition, Filter, FuzzyMatch, GeoBoundingBox, GeoPoint, GeoPolygon,
+    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
+    IsEmptyCondition, IsNullCondition, JoinCondition, ListCollectionsResponse, Match, MinShould,
+    NamedVectors, NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector,
+    PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PhraseMatch, PointId, PointStruct,
+    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
+    QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
+    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SnowballParams,
+    StemmingAlgorithm, StopwordsSet, StrictModeConfig, TextIndexParams, TokenizerType,
+    UpdateResult, UpdateResultInternal, ValuesCount, VectorsSelector, WithPayloadSelector,
+    WithVectorsSelector,
 };
 use crate::rest::models::{CollectionsResponse, VersionInfo};
 use crate::rest::schema as rest;
@@ -135,6 +149,7 @@ impl From<segment::data_types::index::TokenizerType> for TokenizerType {
             segment::data_types::index::TokenizerType::Whitespace => TokenizerType::Whitespace,
             segment::data_types::index::TokenizerType::Multilingual => TokenizerType::Multilingual,
             segment::data_types::index::TokenizerType::Word => TokenizerType::Word,
+            segment::data_types::index::TokenizerType::Ngram => TokenizerType::Ngram,
         }
     }
 }
@@ -145,6 +160,9 @@ impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams
             index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
                 is_tenant: params.is_tenant,
                 on_disk: params.on_disk,
+                normalizer: params
+                    .normalizer
+                    .map(|normalizer| KeywordNormalizer::from(normalizer) as i32),
             })),
         }
     }
@@ -194,11 +212,52 @@ impl From<segment::data_types::index::TextIndexParams> for PayloadIndexParams {
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
                 on_disk: params.on_disk,
+                phrase_matching: params.phrase_matching,
+                stopwords: params.stopwords.map(StopwordsSet::from),
+                stemmer: params.stemmer.map(StemmingAlgorithm::from),
+                ascii_folding: params.ascii_folding,
+                min_ngram: params.min_ngram.map(|x| x as u64),
+                max_ngram: params.max_ngram.map(|x| x as u64),
             })),
         }
     }
 }
 
+impl From<segment::data_types::index::StopwordsInterface> for StopwordsSet {
+    fn from(stopwords: segment::data_types::index::StopwordsInterface) -> Self {
+        match stopwords {
+            segment::data_types::index::StopwordsInterface::Language(language) => StopwordsSet {
+                languages: vec![language.to_string()],
+                custom: vec![],
+            },
+            segment::data_types::index::StopwordsInterface::Set(set) => StopwordsSet {
+                languages: set
+                    .languages
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|language| language.to_string())
+                    .collect(),
+                custom: set.custom.unwrap_or_default().into_iter().collect(),
+            },
+        }
+    }
+}
+
+impl From<segment::data_types::index::StemmingAlgorithm> for StemmingAlgorithm {
+    fn from(stemmer: segment::data_types::index::StemmingAlgorithm) -> Self {
+        let stemming_params = match stemmer {
+            segment::data_types::index::StemmingAlgorithm::Snowball(params) => {
+                StemmingParams::Snowball(SnowballParams {
+                    language: params.language.to_string(),
+                })
+            }
+        };
+        StemmingAlgorithm {
+            stemming_params: Some(stemming_params),
+        }
+    }
+}
+
 impl From<segment::data_types::index::BoolIndexParams> for PayloadIndexParams {
     fn from(_params: segment::data_types::index::BoolIndexParams) -> Self {
         PayloadIndexParams {
@@ -207,6 +266,14 @@ impl From<segment::data_types::index::BoolIndexParams> for PayloadIndexParams {
     }
 }
 
+impl From<segment::data_types::index::GeoShapeIndexParams> for PayloadIndexParams {
+    fn from(_params: segment::data_types::index::GeoShapeIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::GeoShapeIndexParams(GeoShapeIndexParams {})),
+        }
+    }
+}
+
 impl From<segment::data_types::index::UuidIndexParams> for PayloadIndexParams {
     fn from(params: segment::data_types::index::UuidIndexParams) -> Self {
         PayloadIndexParams {
@@ -250,6 +317,7 @@ impl From<segment::types::PayloadSchemaType> for PayloadSchemaType {
             segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
             segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
             segment::types::PayloadSchemaType::Uuid => PayloadSchemaType::Uuid,
+            segment::types::PayloadSchemaType::GeoShape => PayloadSchemaType::GeoShape,
         }
     }
 }
@@ -265,6 +333,7 @@ impl From<segment::types::PayloadSchemaType> for FieldType {
             segment::types::PayloadSchemaType::Bool => FieldType::Bool,
             segment::types::PayloadSchemaType::Datetime => FieldType::Datetime,
             segment::types::PayloadSchemaType::Uuid => FieldType::Uuid,
+            segment::types::PayloadSchemaType::GeoShape => FieldType::GeoShape,
         }
     }
 }
@@ -280,6 +349,7 @@ impl TryFrom<TokenizerType> for segment::data_types::index::TokenizerType {
             }
             TokenizerType::Whitespace => Ok(segment::data_types::index::TokenizerType::Whitespace),
             TokenizerType::Word => Ok(segment::data_types::index::TokenizerType::Word),
+            TokenizerType::Ngram => Ok(segment::data_types::index::TokenizerType::Ngram),
         }
     }
 }
@@ -295,6 +365,7 @@ impl From<segment::types::PayloadSchemaParams> for PayloadIndexParams {
             segment::types::PayloadSchemaParams::Bool(p) => p.into(),
             segment::types::PayloadSchemaParams::Datetime(p) => p.into(),
             segment::types::PayloadSchemaParams::Uuid(p) => p.into(),
+            segment::types::PayloadSchemaParams::GeoShape(p) => p.into(),
         }
     }
 }
@@ -302,14 +373,44 @@ impl From<segment::types::PayloadSchemaParams> for PayloadIndexParams {
 impl TryFrom<KeywordIndexParams> for segment::data_types::index::KeywordIndexParams {
     type Error = Status;
     fn try_from(params: KeywordIndexParams) -> Result<Self, Self::Error> {
+        let normalizer = params
+            .normalizer
+            .map(|normalizer| {
+                KeywordNormalizer::try_from(normalizer)
+                    .map_err(|_| Status::invalid_argument("unknown keyword normalizer"))
+            })
+            .transpose()?
+            .and_then(|normalizer| match normalizer {
+                KeywordNormalizer::NoNormalizer => None,
+                KeywordNormalizer::Lowercase => {
+                    Some(segment::data_types::index::KeywordNormalizer::Lowercase)
+                }
+                KeywordNormalizer::LowercaseNfkc => {
+                    Some(segment::data_types::index::KeywordNormalizer::LowercaseNfkc)
+                }
+            });
         Ok(segment::data_types::index::KeywordIndexParams {
             r#type: KeywordIndexType::Keyword,
             is_tenant: params.is_tenant,
             on_disk: params.on_disk,
+            normalizer,
         })
     }
 }
 
+impl From<segment::data_types::index::KeywordNormalizer> for KeywordNormalizer {
+    fn from(normalizer: segment::data_types::index::KeywordNormalizer) -> Self {
+        match normalizer {
+            segment::data_types::index::KeywordNormalizer::Lowercase => {
+                KeywordNormalizer::Lowercase
+            }
+            segment::data_types::index::KeywordNormalizer::LowercaseNfkc => {
+                KeywordNormalizer::LowercaseNfkc
+            }
+        }
+    }
+}
+
 impl TryFrom<IntegerIndexParams> for segment::data_types::index::IntegerIndexParams {
     type Error = Status;
     fn try_from(params: IntegerIndexParams) -> Result<Self, Self::Error> {
@@ -344,10 +445,46 @@ impl TryFrom<GeoIndexParams> for segment::data_types::index::GeoIndexParams {
     }
 }
 
+fn parse_language(language: &str) -> Result<segment::data_types::index::Language, Status> {
+    language
+        .parse()
+        .map_err(|_| Status::invalid_argument(format!("unknown language: {language}")))
+}
+
+impl TryFrom<StopwordsSet> for segment::data_types::index::StopwordsInterface {
+    type Error = Status;
+    fn try_from(stopwords: StopwordsSet) -> Result<Self, Self::Error> {
+        let StopwordsSet { languages, custom } = stopwords;
+        let languages = languages
+            .iter()
+            .map(|language| parse_language(language))
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        Ok(Self::Set(segment::data_types::index::StopwordsSet {
+            languages: (!languages.is_empty()).then_some(languages),
+            custom: (!custom.is_empty()).then(|| custom.into_iter().collect()),
+        }))
+    }
+}
+
+impl TryFrom<StemmingAlgorithm> for segment::data_types::index::StemmingAlgorithm {
+    type Error = Status;
+    fn try_from(stemmer: StemmingAlgorithm) -> Result<Self, Self::Error> {
+        match stemmer.stemming_params {
+            Some(StemmingParams::Snowball(SnowballParams { language })) => {
+                Ok(Self::Snowball(segment::data_types::index::SnowballParams {
+                    r#type: segment::data_types::index::SnowballType::Snowball,
+                    language: parse_language(&language)?,
+                }))
+            }
+            None => Err(Status::invalid_argument("Malformed StemmingAlgorithm")),
+        }
+    }
+}
+
 impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
     type Error = Status;
     fn try_from(params: TextIndexParams) -> Result<Self, Self::Error> {
-        Ok(segment::data_types::index::TextIndexParams {
+        let params = segment::data_types::index::TextIndexParams {
             r#type: TextIndexType::Text,
             tokenizer: TokenizerType::try_from(params.tokenizer)
                 .map(|x| x.try_into())
@@ -355,8 +492,24 @@ impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            min_ngram: params.min_ngram.map(|x| x as usize),
+            max_ngram: params.max_ngram.map(|x| x as usize),
             on_disk: params.on_disk,
-        })
+            phrase_matching: params.phrase_matching,
+            stopwords: params
+                .stopwords
+                .map(segment::data_types::index::StopwordsInterface::try_from)
+                .transpose()?,
+            stemmer: params
+                .stemmer
+                .map(segment::data_types::index::StemmingAlgorithm::try_from)
+                .transpose()?,
+            ascii_folding: params.ascii_folding,
+        };
+        params
+            .validate()
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        Ok(params)
     }
 }
 
@@ -369,6 +522,15 @@ impl TryFrom<BoolIndexParams> for segment::data_types::index::BoolIndexParams {
     }
 }
 
+impl TryFrom<GeoShapeIndexParams> for segment::data_types::index::GeoShapeIndexParams {
+    type Error = Status;
+    fn try_from(_params: GeoShapeIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::index::GeoShapeIndexParams {
+            r#type: GeoShapeIndexType::GeoShape,
+        })
+    }
+}
+
 impl TryFrom<DatetimeIndexParams> for segment::data_types::index::DatetimeIndexParams {
     type Error = Status;
     fn try_from(params: DatetimeIndexParams) -> Result<Self, Self::Error> {
@@ -420,6 +582,9 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
             IndexParams::UuidIndexParams(p) => {
                 segment::types::PayloadSchemaParams::Uuid(p.try_into()?)
             }
+            IndexParams::GeoShapeIndexParams(p) => {
+                segment::types::PayloadSchemaParams::GeoShape(p.try_into()?)
+            }
         })
     }
 }
@@ -448,6 +613,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                     ));
                 }
                 PayloadSchemaType::Uuid => segment::types::PayloadSchemaType::Uuid,
+                PayloadSchemaType::GeoShape => segment::types::PayloadSchemaType::GeoShape,
             },
         };
         let params = match schema.params {
@@ -651,6 +817,10 @@ impl From<segment::data_types::order_by::OrderValue> for OrderValue {
         let variant = match value {
             segment::OrderValue::Float(value) => Variant::Float(value),
             segment::OrderValue::Int(value) => Variant::Int(value),
+            segment::OrderValue::Keyword(value) => Variant::Keyword(value),
+            segment::OrderValue::Composite(values) => Variant::Composite(OrderValues {
+                values: values.into_iter().map(OrderValue::from).collect(),
+            }),
         };
 
         Self {
@@ -674,6 +844,13 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
         let value = match variant {
             Variant::Float(value) => segment::OrderValue::Float(value),
             Variant::Int(value) => segment::OrderValue::Int(value),
+            Variant::Keyword(value) => segment::OrderValue::Keyword(value),
+            Variant::Composite(OrderValues { values }) => segment::OrderValue::Composite(
+                values
+                    .into_iter()
+                    .map(segment::OrderValue::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
         };
 
         Ok(value)
@@ -727,10 +904,55 @@ impl From<segment::data_types::groups::GroupId> for GroupId {
             segment::data_types::groups::GroupId::NumberI64(n) => Self {
                 kind: Some(crate::grpc::qdrant::group_id::Kind::IntegerValue(n)),
             },
+            segment::data_types::groups::GroupId::Composite(values) => Self {
+                kind: Some(crate::grpc::qdrant::group_id::Kind::CompositeValue(
+                    GroupIdComposite {
+                        values: values.into_iter().map(GroupId::from).collect(),
+                    },
+                )),
+            },
+        }
+    }
+}
+
+impl From<GroupScore> for segment::data_types::groups::GroupScore {
+    fn from(value: GroupScore) -> Self {
+        match value {
+            GroupScore::Max => segment::data_types::groups::GroupScore::Max,
+            GroupScore::Sum => segment::data_types::groups::GroupScore::Sum,
+            GroupScore::Average => segment::data_types::groups::GroupScore::Average,
         }
     }
 }
 
+pub fn group_score_from_proto(
+    value: i32,
+) -> Result<segment::data_types::groups::GroupScore, Status> {
+    let group_score = GroupScore::try_from(value)
+        .map_err(|_| Status::invalid_argument(format!("Unknown group score: {value}")))?;
+    Ok(group_score.into())
+}
+
+/// Fields to group by, from the main field and the additional ones
+pub fn group_by_from_proto(
+    group_by: &str,
+    extra_group_by: &[String],
+) -> Result<rest::GroupByInterface, Status> {
+    let field = json::json_path_from_proto(group_by)?;
+    if extra_group_by.is_empty() {
+        return Ok(rest::GroupByInterface::Field(field));
+    }
+
+    let fields = std::iter::once(Ok(field))
+        .chain(
+            extra_group_by
+                .iter()
+                .map(|extra| json::json_path_from_proto(extra)),
+        )
+        .collect::<Result<_, _>>()?;
+    Ok(rest::GroupByInterface::Fields(fields))
+}
+
 impl TryFrom<NamedVectors> for HashMap<String, segment_vectors::VectorInternal> {
     type Error = Status;
 
@@ -1077,6 +1299,11 @@ impl TryFrom<Condition> for segment::types::Condition {
                         has_vector: has_vector.has_vector,
                     },
                 )),
+                ConditionOneOf::Join(join) => Ok(segment::types::Condition::Join(
+                    segment::types::JoinCondition {
+                        join: join.try_into()?,
+                    },
+                )),
             };
         }
         Err(Status::invalid_argument("Malformed Condition type"))
@@ -1112,6 +1339,9 @@ impl From<segment::types::Condition> for Condition {
                     has_vector: has_vector.has_vector,
                 }))
             }
+            segment::types::Condition::Join(join) => {
+                Some(ConditionOneOf::Join(JoinCondition::from(join.join)))
+            }
         };
 
         Self { condition_one_of }
@@ -1143,6 +1373,50 @@ impl From<segment::types::Nested> for NestedCondition {
     }
 }
 
+impl TryFrom<JoinCondition> for segment::types::Join {
+    type Error = Status;
+
+    fn try_from(value: JoinCondition) -> Result<Self, Self::Error> {
+        let JoinCondition {
+            key,
+            collection,
+            filter,
+            related_key,
+            limit,
+        } = value;
+
+        Ok(Self {
+            key: json::json_path_from_proto(&key)?,
+            collection,
+            filter: filter.map(TryInto::try_into).transpose()?,
+            related_key: related_key
+                .map(|related_key| json::json_path_from_proto(&related_key))
+                .transpose()?,
+            limit: limit.map(|limit| limit as usize),
+        })
+    }
+}
+
+impl From<segment::types::Join> for JoinCondition {
+    fn from(value: segment::types::Join) -> Self {
+        let segment::types::Join {
+            key,
+            collection,
+            filter,
+            related_key,
+            limit,
+        } = value;
+
+        Self {
+            key: key.to_string(),
+            collection,
+            filter: filter.map(Filter::from),
+            related_key: related_key.map(|related_key| related_key.to_string()),
+            limit: limit.map(|limit| limit as u64),
+        }
+    }
+}
+
 impl TryFrom<IsEmptyCondition> for segment::types::IsEmptyCondition {
     type Error = Status;
 
@@ -1216,12 +1490,15 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             values_count,
             geo_polygon,
             datetime_range,
+            keyword_range,
+            geo_shape,
         } = value;
 
         let geo_bounding_box =
             geo_bounding_box.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
         let geo_radius = geo_radius.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
         let geo_polygon = geo_polygon.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
+        let geo_shape = geo_shape.map(TryInto::try_into).transpose()?;
 
         let mut range = range.map(Into::into);
         if range.is_none() {
@@ -1229,6 +1506,9 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
                 .map(segment::types::RangeInterface::try_from)
                 .transpose()?;
         }
+        if range.is_none() {
+            range = keyword_range.map(Into::into);
+        }
 
         Ok(Self {
             key: json::json_path_from_proto(&key)?,
@@ -1237,6 +1517,7 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             geo_bounding_box,
             geo_radius,
             geo_polygon,
+            geo_shape,
             values_count: values_count.map(Into::into),
         })
     }
@@ -1251,13 +1532,19 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_bounding_box,
             geo_radius,
             geo_polygon,
+            geo_shape,
             values_count,
         } = value;
 
-        let (range, datetime_range) = match range {
-            Some(segment::types::RangeInterface::Float(range)) => (Some(range.into()), None),
-            Some(segment::types::RangeInterface::DateTime(range)) => (None, Some(range.into())),
-            None => (None, None),
+        let (range, datetime_range, keyword_range) = match range {
+            Some(segment::types::RangeInterface::Float(range)) => (Some(range.into()), None, None),
+            Some(segment::types::RangeInterface::DateTime(range)) => {
+                (None, Some(range.into()), None)
+            }
+            Some(segment::types::RangeInterface::Keyword(range)) => {
+                (None, None, Some(range.keyword.into()))
+            }
+            None => (None, None, None),
         };
 
         Self {
@@ -1269,6 +1556,42 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_polygon: geo_polygon.map(Into::into),
             values_count: values_count.map(Into::into),
             datetime_range,
+            keyword_range,
+            geo_shape: geo_shape.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<GeoShapeCondition> for segment::types::GeoShapeCondition {
+    type Error = Status;
+
+    fn try_from(value: GeoShapeCondition) -> Result<Self, Self::Error> {
+        use crate::grpc::qdrant::geo_shape_condition::Condition;
+
+        match value.condition {
+            Some(Condition::Contains(point)) => Ok(Self::Contains(point.into())),
+            Some(Condition::Intersects(polygon)) => Ok(Self::Intersects(polygon.try_into()?)),
+            Some(Condition::Within(polygon)) => Ok(Self::Within(polygon.try_into()?)),
+            None => Err(Status::invalid_argument(
+                "Malformed GeoShapeCondition type - field `condition` is required",
+            )),
+        }
+    }
+}
+
+impl From<segment::types::GeoShapeCondition> for GeoShapeCondition {
+    fn from(value: segment::types::GeoShapeCondition) -> Self {
+        use crate::grpc::qdrant::geo_shape_condition::Condition;
+
+        let condition = match value {
+            segment::types::GeoShapeCondition::Contains(point) => Condition::Contains(point.into()),
+            segment::types::GeoShapeCondition::Intersects(polygon) => {
+                Condition::Intersects(polygon.into())
+            }
+            segment::types::GeoShapeCondition::Within(polygon) => Condition::Within(polygon.into()),
+        };
+        Self {
+            condition: Some(condition),
         }
     }
 }
@@ -1435,6 +1758,28 @@ impl From<segment::types::Range<DateTimePayloadType>> for DatetimeRange {
     }
 }
 
+impl From<KeywordRange> for segment::types::RangeInterface {
+    fn from(value: KeywordRange) -> Self {
+        Self::from(segment::types::Range {
+            lt: value.lt,
+            gt: value.gt,
+            gte: value.gte,
+            lte: value.lte,
+        })
+    }
+}
+
+impl From<segment::types::Range<String>> for KeywordRange {
+    fn from(value: segment::types::Range<String>) -> Self {
+        Self {
+            lt: value.lt,
+            gt: value.gt,
+            gte: value.gte,
+            lte: value.lte,
+        }
+    }
+}
+
 impl From<ValuesCount> for segment::types::ValuesCount {
     fn from(value: ValuesCount) -> Self {
         Self {
@@ -1475,6 +1820,32 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::ExceptKeywords(ints) => {
                     segment::types::Match::Except(ints.strings.into())
                 }
+                MatchValue::Phrase(PhraseMatch { phrase, slop }) => {
+                    segment::types::Match::Phrase(segment::types::MatchPhrase {
+                        phrase,
+                        slop: slop.map(|slop| slop as usize),
+                    })
+                }
+                MatchValue::Fuzzy(FuzzyMatch {
+                    fuzzy,
+                    max_edits,
+                    max_expansions,
+                }) => segment::types::Match::Fuzzy(segment::types::MatchFuzzy {
+                    fuzzy,
+                    max_edits: max_edits.map(|max_edits| max_edits as usize),
+                    max_expansions: max_expansions.map(|max_expansions| max_expansions as usize),
+                }),
+                MatchValue::Prefix(prefix) => {
+                    segment::types::Match::Prefix(segment::types::MatchPrefix { prefix })
+                }
+                MatchValue::Wildcard(wildcard) => {
+                    segment::types::Match::Wildcard(segment::types::MatchWildcard { wildcard })
+                }
+                MatchValue::Regex(regex) => {
+                    segment::types::Match::Regex(segment::types::MatchRegex { regex })
+                }
+                MatchValue::AllKeywords(kwds) => segment::types::Match::All(kwds.strings.into()),
+                MatchValue::AllIntegers(ints) => segment::types::Match::All(ints.integers.into()),
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1492,6 +1863,30 @@ impl From<segment::types::Match> for Match {
             segment::types::Match::Text(segment::types::MatchText { text }) => {
                 MatchValue::Text(text)
             }
+            segment::types::Match::Phrase(segment::types::MatchPhrase { phrase, slop }) => {
+                MatchValue::Phrase(PhraseMatch {
+                    phrase,
+                    slop: slop.map(|slop| slop as u32),
+                })
+            }
+            segment::types::Match::Fuzzy(segment::types::MatchFuzzy {
+                fuzzy,
+                max_edits,
+                max_expansions,
+            }) => MatchValue::Fuzzy(FuzzyMatch {
+                fuzzy,
+                max_edits: max_edits.map(|max_edits| max_edits as u32),
+                max_expansions: max_expansions.map(|max_expansions| max_expansions as u32),
+            }),
+            segment::types::Match::Prefix(segment::types::MatchPrefix { prefix }) => {
+                MatchValue::Prefix(prefix)
+            }
+            segment::types::Match::Wildcard(segment::types::MatchWildcard { wildcard }) => {
+                MatchValue::Wildcard(wildcard)
+            }
+            segment::types::Match::Regex(segment::types::MatchRegex { regex }) => {
+                MatchValue::Regex(regex)
+            }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Strings(strings) => {
                     let strings = strings.into_iter().collect();
@@ -1502,6 +1897,16 @@ impl From<segment::types::Match> for Match {
                     MatchValue::Integers(RepeatedIntegers { integers })
                 }
             },
+            segment::types::Match::All(all) => match all.all {
+                segment::types::AnyVariants::Strings(strings) => {
+                    let strings = strings.into_iter().collect();
+                    MatchValue::AllKeywords(RepeatedStrings { strings })
+                }
+                segment::types::AnyVariants::Integers(integers) => {
+                    let integers = integers.into_iter().collect();
+                    MatchValue::AllIntegers(RepeatedIntegers { integers })
+                }
+            },
             segment::types::Match::Except(except) => match except.except {
                 segment::types::AnyVariants::Strings(strings) => {
                     let strings = strings.into_iter().collect();
@@ -1567,14 +1972,29 @@ impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
                             Status::invalid_argument(format!("Malformed datetime: {e}"))
                         })?,
                     )),
+                    Value::Keyword(keyword) => Ok(StartFrom::Keyword(keyword)),
                 }
             })
             .transpose()?;
 
+        let then_by = value
+            .then_by
+            .into_iter()
+            .map(segment::data_types::order_by::OrderByKey::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let start_after = value
+            .start_after
+            .map(segment::data_types::order_by::OrderCursor::try_from)
+            .transpose()?;
+
         Ok(Self {
             key: json::json_path_from_proto(&value.key)?,
             direction,
             start_from,
+            then_by,
+            start_after,
+            distance_from: value.distance_from.map(segment::types::GeoPoint::from),
         })
     }
 }
@@ -1585,6 +2005,85 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
             key: value.key.to_string(),
             direction: value.direction.map(|d| Direction::from(d) as i32),
             start_from: value.start_from.map(|start_from| start_from.into()),
+            then_by: value.then_by.into_iter().map(OrderByKey::from).collect(),
+            start_after: value.start_after.map(OrderCursor::from),
+            distance_from: value.distance_from.map(GeoPoint::from),
+        }
+    }
+}
+
+impl TryFrom<OrderByKey> for segment::data_types::order_by::OrderByKey {
+    type Error = Status;
+
+    fn try_from(value: OrderByKey) -> Result<Self, Self::Error> {
+        let OrderByKey { key, direction } = value;
+        Ok(Self {
+            key: json::json_path_from_proto(&key)?,
+            direction: direction
+                .and_then(|x| Direction::try_from(x).ok())
+                .map(segment::data_types::order_by::Direction::from),
+        })
+    }
+}
+
+impl From<segment::data_types::order_by::OrderByKey> for OrderByKey {
+    fn from(value: segment::data_types::order_by::OrderByKey) -> Self {
+        let segment::data_types::order_by::OrderByKey { key, direction } = value;
+        Self {
+            key: key.to_string(),
+            direction: direction.map(|d| Direction::from(d) as i32),
+        }
+    }
+}
+
+impl TryFrom<OrderCursor> for segment::data_types::order_by::OrderCursor {
+    type Error = Status;
+
+    fn try_from(value: OrderCursor) -> Result<Self, Self::Error> {
+        let OrderCursor { values, id } = value;
+        let id = id.ok_or_else(|| Status::invalid_argument("Cursor must have a point id"))?;
+        Ok(Self {
+            values: values
+                .into_iter()
+                .map(segment::data_types::order_by::OrderValue::try_from)
+                .collect::<Result<_, _>>()?,
+            id: id.try_into()?,
+        })
+    }
+}
+
+impl From<segment::data_types::order_by::OrderCursor> for OrderCursor {
+    fn from(value: segment::data_types::order_by::OrderCursor) -> Self {
+        let segment::data_types::order_by::OrderCursor { values, id } = value;
+        Self {
+            values: values.into_iter().map(OrderValue::from).collect(),
+            id: Some(id.into()),
+        }
+    }
+}
+
+impl TryFrom<Bm25> for segment::data_types::bm25::Bm25Query {
+    type Error = Status;
+
+    fn try_from(value: Bm25) -> Result<Self, Self::Error> {
+        let Bm25 { key, text, k1, b } = value;
+        Ok(Self {
+            key: json::json_path_from_proto(&key)?,
+            text,
+            k1: k1.unwrap_or(Self::DEFAULT_K1),
+            b: b.unwrap_or(Self::DEFAULT_B),
+        })
+    }
+}
+
+impl From<segment::data_types::bm25::Bm25Query> for Bm25 {
+    fn from(value: segment::data_types::bm25::Bm25Query) -> Self {
+        let segment::data_types::bm25::Bm25Query { key, text, k1, b } = value;
+        Self {
+            key: key.to_string(),
+            text,
+            k1: Some(k1),
+            b: Some(b),
         }
     }
 }
@@ -1602,6 +2101,9 @@ impl From<segment::data_types::order_by::StartFrom> for StartFrom {
                 segment::data_types::order_by::StartFrom::Datetime(datetime) => {
                     start_from::Value::Timestamp(date_time_to_proto(datetime))
                 }
+                segment::data_types::order_by::StartFrom::Keyword(keyword) => {
+                    start_from::Value::Keyword(keyword)
+                }
             }),
         }
     }
@@ -1996,7 +2498,8 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             with_vector,
             score_threshold,
             group_request: rest::BaseGroupRequest {
-                group_by: json::json_path_from_proto(&value.group_by)?,
+                group_by: group_by_from_proto(&value.group_by, &value.extra_group_by)?,
+                group_score: value.group_score.map(group_score_from_proto).transpose()?,
                 limit: value.limit,
                 group_size: value.group_size,
                 with_lookup: value
@@ -2055,6 +2558,7 @@ impl TryFrom<FacetHitInternal> for segment_facets::FacetValueHit {
         Ok(Self {
             value: segment_facets::FacetValue::try_from(value)?,
             count: hit.count as usize,
+            centroid: hit.centroid.map(From::from),
         })
     }
 }
@@ -2064,6 +2568,7 @@ impl From<segment_facets::FacetValueHit> for FacetHitInternal {
         Self {
             value: Some(From::from(hit.value)),
             count: hit.count as u64,
+            centroid: hit.centroid.map(From::from),
         }
     }
 }
@@ -2073,6 +2578,63 @@ impl From<segment_facets::FacetValueHit> for FacetHit {
         Self {
             value: Some(hit.value.into()),
             count: hit.count as u64,
+            centroid: hit.centroid.map(From::from),
+        }
+    }
+}
+
+impl From<GeoGridParams> for segment_facets::GeoGridParams {
+    fn from(params: GeoGridParams) -> Self {
+        let GeoGridParams {
+            precision,
+            with_centroid,
+        } = params;
+
+        Self {
+            precision: precision as usize,
+            with_centroid: with_centroid.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<segment_facets::GeoGridParams> for GeoGridParams {
+    fn from(params: segment_facets::GeoGridParams) -> Self {
+        let segment_facets::GeoGridParams {
+            precision,
+            with_centroid,
+        } = params;
+
+        Self {
+            precision: precision as u32,
+            with_centroid: Some(with_centroid),
+        }
+    }
+}
+
+impl TryFrom<FacetPivot> for segment_facets::FacetPivotParams {
+    type Error = Status;
+
+    fn try_from(pivot: FacetPivot) -> Result<Self, Self::Error> {
+        let FacetPivot { key, limit } = pivot;
+
+        Ok(Self {
+            key: json::json_path_from_proto(&key)?,
+            limit: limit
+                .map(usize::try_from)
+                .transpose()
+                .map_err(|_| Status::invalid_argument("could not parse limit param into usize"))?
+                .unwrap_or(segment_facets::FacetParams::DEFAULT_LIMIT),
+        })
+    }
+}
+
+impl From<segment_facets::FacetPivotParams> for FacetPivot {
+    fn from(pivot: segment_facets::FacetPivotParams) -> Self {
+        let segment_facets::FacetPivotParams { key, limit } = pivot;
+
+        Self {
+            key: key.to_string(),
+            limit: Some(limit as u64),
         }
     }
 }
@@ -2097,6 +2659,29 @@ impl TryFrom<FacetValueInternal> for segment_facets::FacetValue {
                 segment_facets::FacetValue::Uuid(Uuid::from_bytes(uuid_bytes).as_u128())
             }
             Variant::BoolValue(value) => segment_facets::FacetValue::Bool(value),
+            Variant::GeoHashValue(value) => segment_facets::FacetValue::GeoHash(value),
+            Variant::FloatRangeValue(FacetFloatRange { from, to }) => {
+                segment_facets::FacetValue::FloatRange(
+                    segment_facets::FacetRange { from, to }.map(OrderedFloat),
+                )
+            }
+            Variant::DatetimeRangeValue(FacetDatetimeRange { from, to }) => {
+                let timestamp = |date_time| {
+                    try_date_time_from_proto(date_time).map(|date_time| date_time.timestamp())
+                };
+                segment_facets::FacetValue::DatetimeRange(segment_facets::FacetRange {
+                    from: from.map(timestamp).transpose()?,
+                    to: to.map(timestamp).transpose()?,
+                })
+            }
+            Variant::TupleValue(FacetValueInternalTuple { values }) => {
+                segment_facets::FacetValue::Tuple(
+                    values
+                        .into_iter()
+                        .map(segment_facets::FacetValue::try_from)
+                        .collect::<Result<_, _>>()?,
+                )
+            }
         })
     }
 }
@@ -2114,6 +2699,18 @@ impl From<segment_facets::FacetValue> for FacetValueInternal {
                     Variant::UuidValue(uuid.as_bytes().to_vec())
                 }
                 segment_facets::FacetValue::Bool(value) => Variant::BoolValue(value),
+                segment_facets::FacetValue::GeoHash(value) => Variant::GeoHashValue(value),
+                segment_facets::FacetValue::FloatRange(range) => {
+                    Variant::FloatRangeValue(FacetFloatRange::from(range))
+                }
+                segment_facets::FacetValue::DatetimeRange(range) => {
+                    Variant::DatetimeRangeValue(FacetDatetimeRange::from(range))
+                }
+                segment_facets::FacetValue::Tuple(values) => {
+                    Variant::TupleValue(FacetValueInternalTuple {
+                        values: values.into_iter().map(FacetValueInternal::from).collect(),
+                    })
+                }
             }),
         }
     }
@@ -2131,11 +2728,332 @@ impl From<segment_facets::FacetValue> for FacetValue {
                     Variant::StringValue(Uuid::from_u128(value).to_string())
                 }
                 segment_facets::FacetValue::Bool(value) => Variant::BoolValue(value),
+                segment_facets::FacetValue::GeoHash(value) => Variant::StringValue(value),
+                segment_facets::FacetValue::FloatRange(range) => {
+                    Variant::FloatRangeValue(FacetFloatRange::from(range))
+                }
+                segment_facets::FacetValue::DatetimeRange(range) => {
+                    Variant::DatetimeRangeValue(FacetDatetimeRange::from(range))
+                }
+                segment_facets::FacetValue::Tuple(values) => Variant::TupleValue(FacetValueTuple {
+                    values: values.into_iter().map(FacetValue::from).collect(),
+                }),
             }),
         }
     }
 }
 
+impl From<segment_facets::FacetRange<OrderedFloat<f64>>> for FacetFloatRange {
+    fn from(range: segment_facets::FacetRange<OrderedFloat<f64>>) -> Self {
+        let segment_facets::FacetRange { from, to } = range;
+        Self {
+            from: from.map(|from| from.0),
+            to: to.map(|to| to.0),
+        }
+    }
+}
+
+impl From<segment_facets::FacetRange<i64>> for FacetDatetimeRange {
+    fn from(range: segment_facets::FacetRange<i64>) -> Self {
+        let timestamp_to_proto = |timestamp| {
+            chrono::DateTime::from_timestamp_micros(timestamp)
+                .map(|date_time| date_time_to_proto(date_time.into()))
+        };
+        let segment_facets::FacetRange { from, to } = range;
+        Self {
+            from: from.and_then(timestamp_to_proto),
+            to: to.and_then(timestamp_to_proto),
+        }
+    }
+}
+
+impl TryFrom<HistogramParams> for segment_facets::HistogramParams {
+    type Error = Status;
+
+    fn try_from(params: HistogramParams) -> Result<Self, Self::Error> {
+        use super::qdrant::histogram_params::Buckets;
+
+        let HistogramParams { buckets } = params;
+        let buckets =
+            buckets.ok_or_else(|| Status::invalid_argument("Histogram buckets are not set"))?;
+
+        Ok(match buckets {
+            Buckets::Interval(interval) => Self::Interval(interval),
+            Buckets::CalendarInterval(interval) => {
+                let interval = CalendarInterval::try_from(interval).map_err(|_| {
+                    Status::invalid_argument(format!("Unknown calendar interval: {interval}"))
+                })?;
+                Self::CalendarInterval(segment_facets::CalendarInterval::from(interval))
+            }
+            Buckets::Ranges(HistogramRanges { ranges }) => Self::Ranges(
+                ranges
+                    .into_iter()
+                    .map(segment_facets::HistogramRange::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+impl From<segment_facets::HistogramParams> for HistogramParams {
+    fn from(params: segment_facets::HistogramParams) -> Self {
+        use super::qdrant::histogram_params::Buckets;
+
+        let buckets = match params {
+            segment_facets::HistogramParams::Interval(interval) => Buckets::Interval(interval),
+            segment_facets::HistogramParams::CalendarInterval(interval) => {
+                Buckets::CalendarInterval(CalendarInterval::from(interval) as i32)
+            }
+            segment_facets::HistogramParams::Ranges(ranges) => Buckets::Ranges(HistogramRanges {
+                ranges: ranges.into_iter().map(HistogramRange::from).collect(),
+            }),
+        };
+
+        Self {
+            buckets: Some(buckets),
+        }
+    }
+}
+
+impl From<CalendarInterval> for segment_facets::CalendarInterval {
+    fn from(interval: CalendarInterval) -> Self {
+        match interval {
+            CalendarInterval::Day => Self::Day,
+            CalendarInterval::Week => Self::Week,
+            CalendarInterval::Month => Self::Month,
+        }
+    }
+}
+
+impl From<segment_facets::CalendarInterval> for CalendarInterval {
+    fn from(interval: segment_facets::CalendarInterval) -> Self {
+        match interval {
+            segment_facets::CalendarInterval::Day => Self::Day,
+            segment_facets::CalendarInterval::Week => Self::Week,
+            segment_facets::CalendarInterval::Month => Self::Month,
+        }
+    }
+}
+
+impl TryFrom<HistogramRange> for segment_facets::HistogramRange {
+    type Error = Status;
+
+    fn try_from(range: HistogramRange) -> Result<Self, Self::Error> {
+        let HistogramRange { from, to } = range;
+        Ok(Self {
+            from: from
+                .map(segment_facets::HistogramBound::try_from)
+                .transpose()?,
+            to: to
+                .map(segment_facets::HistogramBound::try_from)
+                .transpose()?,
+        })
+    }
+}
+
+impl From<segment_facets::HistogramRange> for HistogramRange {
+    fn from(range: segment_facets::HistogramRange) -> Self {
+        let segment_facets::HistogramRange { from, to } = range;
+        Self {
+            from: from.map(HistogramBound::from),
+            to: to.map(HistogramBound::from),
+        }
+    }
+}
+
+impl TryFrom<HistogramBound> for segment_facets::HistogramBound {
+    type Error = Status;
+
+    fn try_from(bound: HistogramBound) -> Result<Self, Self::Error> {
+        use super::qdrant::histogram_bound::Variant;
+
+        let variant = bound
+            .variant
+            .ok_or_else(|| Status::invalid_argument("Histogram bound is not set"))?;
+
+        Ok(match variant {
+            Variant::Float(value) => Self::Float(value),
+            Variant::Datetime(date_time) => Self::Datetime(try_date_time_from_proto(date_time)?),
+        })
+    }
+}
+
+impl From<segment_facets::HistogramBound> for HistogramBound {
+    fn from(bound: segment_facets::HistogramBound) -> Self {
+        use super::qdrant::histogram_bound::Variant;
+
+        let variant = match bound {
+            segment_facets::HistogramBound::Float(value) => Variant::Float(value),
+            segment_facets::HistogramBound::Datetime(date_time) => {
+                Variant::Datetime(date_time_to_proto(date_time))
+            }
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl From<segment_aggregations::AggregateValue> for AggregateValue {
+    fn from(value: segment_aggregations::AggregateValue) -> Self {
+        use super::qdrant::aggregate_value::Variant;
+
+        let variant = match value {
+            segment_aggregations::AggregateValue::Number(number) => Variant::Number(number),
+            segment_aggregations::AggregateValue::Datetime(date_time) => {
+                Variant::Datetime(date_time_to_proto(date_time))
+            }
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl From<segment_aggregations::AggregateResponse> for AggregateResult {
+    fn from(response: segment_aggregations::AggregateResponse) -> Self {
+        let segment_aggregations::AggregateResponse {
+            count,
+            sum,
+            min,
+            max,
+            avg,
+            percentiles,
+        } = response;
+
+        Self {
+            count: count as u64,
+            sum,
+            min: min.map(AggregateValue::from),
+            max: max.map(AggregateValue::from),
+            avg: avg.map(AggregateValue::from),
+            percentiles: percentiles
+                .into_iter()
+                .map(
+                    |segment_aggregations::PercentileValue { percentile, value }| PercentileValue {
+                        percentile,
+                        value: Some(AggregateValue::from(value)),
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+impl From<segment_aggregations::PartialAggregate> for PartialAggregateInternal {
+    fn from(aggregate: segment_aggregations::PartialAggregate) -> Self {
+        let segment_aggregations::PartialAggregate {
+            count,
+            sum,
+            min,
+            max,
+            is_datetime,
+            digest,
+        } = aggregate;
+
+        Self {
+            count: count as u64,
+            sum,
+            min,
+            max,
+            is_datetime,
+            centroids: digest
+                .centroids()
+                .iter()
+                .map(|&Centroid { mean, weight }| DigestCentroid { mean, weight })
+                .collect(),
+        }
+    }
+}
+
+impl From<PartialAggregateInternal> for segment_aggregations::PartialAggregate {
+    fn from(aggregate: PartialAggregateInternal) -> Self {
+        let PartialAggregateInternal {
+            count,
+            sum,
+            min,
+            max,
+            is_datetime,
+            centroids,
+        } = aggregate;
+
+        Self {
+            count: count as usize,
+            sum,
+            min,
+            max,
+            is_datetime,
+            digest: TDigest::from_centroids(
+                centroids
+                    .into_iter()
+                    .map(|DigestCentroid { mean, weight }| Centroid { mean, weight })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<segment_aggregations::DistinctCountResponse> for DistinctCountResult {
+    fn from(response: segment_aggregations::DistinctCountResponse) -> Self {
+        let segment_aggregations::DistinctCountResponse { count } = response;
+
+        Self {
+            count: count as u64,
+        }
+    }
+}
+
+impl From<segment_aggregations::PartialDistinctCount> for PartialDistinctCountInternal {
+    fn from(distinct: segment_aggregations::PartialDistinctCount) -> Self {
+        use super::qdrant::partial_distinct_count_internal::Variant;
+
+        let variant = match distinct {
+            segment_aggregations::PartialDistinctCount::Exact(values) => {
+                Variant::Exact(DistinctValuesInternal {
+                    values: values.into_iter().map(FacetValueInternal::from).collect(),
+                })
+            }
+            segment_aggregations::PartialDistinctCount::Sketch(sketch) => {
+                Variant::Sketch(sketch.registers().to_vec())
+            }
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<PartialDistinctCountInternal> for segment_aggregations::PartialDistinctCount {
+    type Error = Status;
+
+    fn try_from(distinct: PartialDistinctCountInternal) -> Result<Self, Self::Error> {
+        use super::qdrant::partial_distinct_count_internal::Variant;
+
+        let variant = distinct.variant.ok_or_else(|| {
+            Status::internal("expected PartialDistinctCountInternal to have a value")
+        })?;
+
+        Ok(match variant {
+            Variant::Exact(DistinctValuesInternal { values }) => {
+                segment_aggregations::PartialDistinctCount::Exact(
+                    values
+                        .into_iter()
+                        .map(segment_facets::FacetValue::try_from)
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            Variant::Sketch(registers) => segment_aggregations::PartialDistinctCount::Sketch(
+                HyperLogLog::from_registers(registers).ok_or_else(|| {
+                    Status::internal("unexpected number of HyperLogLog registers")
+                })?,
+            ),
+        })
+    }
+}
+
 impl From<rest::SearchMatrixPair> for SearchMatrixPair {
     fn from(pair: rest::SearchMatrixPair) -> Self {
         Self {
@@ -672,6 +672,9 @@ pub struct KeywordIndexParams {
     /// If true - store index on disk.
     #[prost(bool, optional, tag = "2")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Normalization applied to indexed keywords and to matched values.
+    #[prost(enumeration = "KeywordNormalizer", optional, tag = "3")]
+    pub normalizer: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -728,6 +731,61 @@ pub struct TextIndexParams {
     /// If true - store index on disk.
     #[prost(bool, optional, tag = "5")]
     pub on_disk: ::core::option::Option<bool>,
+    /// If true - store token positions to support phrase matching
+    #[prost(bool, optional, tag = "6")]
+    pub phrase_matching: ::core::option::Option<bool>,
+    /// Stopwords to skip
+    #[prost(message, optional, tag = "7")]
+    pub stopwords: ::core::option::Option<StopwordsSet>,
+    /// Algorithm to reduce the tokens to their stem
+    #[prost(message, optional, tag = "8")]
+    pub stemmer: ::core::option::Option<StemmingAlgorithm>,
+    /// If true - fold accented characters to ASCII
+    #[prost(bool, optional, tag = "9")]
+    pub ascii_folding: ::core::option::Option<bool>,
+    /// Minimal n-gram size for the Ngram tokenizer, from 1 to 10
+    #[prost(uint64, optional, tag = "10")]
+    pub min_ngram: ::core::option::Option<u64>,
+    /// Maximal n-gram size for the Ngram tokenizer, from 1 to 10 and not less than min_ngram
+    #[prost(uint64, optional, tag = "11")]
+    pub max_ngram: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StopwordsSet {
+    /// Languages of the predefined stopwords, e.g. "english"
+    #[prost(string, repeated, tag = "1")]
+    pub languages: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Custom stopwords
+    #[prost(string, repeated, tag = "2")]
+    pub custom: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StemmingAlgorithm {
+    #[prost(oneof = "stemming_algorithm::StemmingParams", tags = "1")]
+    pub stemming_params: ::core::option::Option<stemming_algorithm::StemmingParams>,
+}
+/// Nested message and enum types in `StemmingAlgorithm`.
+pub mod stemming_algorithm {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum StemmingParams {
+        /// Snowball stemmer
+        #[prost(message, tag = "1")]
+        Snowball(super::SnowballParams),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnowballParams {
+    /// Language of the stemmer, e.g. "english"
+    #[prost(string, tag = "1")]
+    pub language: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -736,6 +794,10 @@ pub struct BoolIndexParams {}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoShapeIndexParams {}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DatetimeIndexParams {
     /// If true - store index on disk.
     #[prost(bool, optional, tag = "1")]
@@ -761,7 +823,7 @@ pub struct UuidIndexParams {
 pub struct PayloadIndexParams {
     #[prost(
         oneof = "payload_index_params::IndexParams",
-        tags = "3, 2, 4, 5, 1, 6, 7, 8"
+        tags = "3, 2, 4, 5, 1, 6, 7, 8, 9"
     )]
     pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
 }
@@ -795,6 +857,9 @@ pub mod payload_index_params {
         /// Parameters for uuid index
         #[prost(message, tag = "8")]
         UuidIndexParams(super::UuidIndexParams),
+        /// Parameters for geo shape index
+        #[prost(message, tag = "9")]
+        GeoShapeIndexParams(super::GeoShapeIndexParams),
     }
 }
 #[derive(serde::Serialize)]
@@ -1418,6 +1483,7 @@ pub enum PayloadSchemaType {
     Bool = 6,
     Datetime = 7,
     Uuid = 8,
+    GeoShape = 9,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1435,6 +1501,7 @@ impl PayloadSchemaType {
             PayloadSchemaType::Bool => "Bool",
             PayloadSchemaType::Datetime => "Datetime",
             PayloadSchemaType::Uuid => "Uuid",
+            PayloadSchemaType::GeoShape => "GeoShape",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1449,6 +1516,7 @@ impl PayloadSchemaType {
             "Bool" => Some(Self::Bool),
             "Datetime" => Some(Self::Datetime),
             "Uuid" => Some(Self::Uuid),
+            "GeoShape" => Some(Self::GeoShape),
             _ => None,
         }
     }
@@ -1554,6 +1622,7 @@ pub enum TokenizerType {
     Whitespace = 2,
     Word = 3,
     Multilingual = 4,
+    Ngram = 5,
 }
 impl TokenizerType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1567,6 +1636,7 @@ impl TokenizerType {
             TokenizerType::Whitespace => "Whitespace",
             TokenizerType::Word => "Word",
             TokenizerType::Multilingual => "Multilingual",
+            TokenizerType::Ngram => "Ngram",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1577,6 +1647,40 @@ impl TokenizerType {
             "Whitespace" => Some(Self::Whitespace),
             "Word" => Some(Self::Word),
             "Multilingual" => Some(Self::Multilingual),
+            "Ngram" => Some(Self::Ngram),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum KeywordNormalizer {
+    /// Keywords are matched exactly
+    NoNormalizer = 0,
+    /// Lowercase keywords
+    Lowercase = 1,
+    /// Apply Unicode NFKC normalization, then lowercase keywords
+    LowercaseNfkc = 2,
+}
+impl KeywordNormalizer {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            KeywordNormalizer::NoNormalizer => "NoNormalizer",
+            KeywordNormalizer::Lowercase => "Lowercase",
+            KeywordNormalizer::LowercaseNfkc => "LowercaseNfkc",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NoNormalizer" => Some(Self::NoNormalizer),
+            "Lowercase" => Some(Self::Lowercase),
+            "LowercaseNfkc" => Some(Self::LowercaseNfkc),
             _ => None,
         }
     }
@@ -4577,12 +4681,18 @@ pub struct SearchPointGroups {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "16")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Additional payload fields to group by, groups are made of each combination of the values of all the fields
+    #[prost(string, repeated, tag = "17")]
+    pub extra_group_by: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// How to compute the score of a group from the scores of its points
+    #[prost(enumeration = "GroupScore", optional, tag = "18")]
+    pub group_score: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StartFrom {
-    #[prost(oneof = "start_from::Value", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "start_from::Value", tags = "1, 2, 3, 4, 5")]
     pub value: ::core::option::Option<start_from::Value>,
 }
 /// Nested message and enum types in `StartFrom`.
@@ -4599,11 +4709,35 @@ pub mod start_from {
         Timestamp(::prost_wkt_types::Timestamp),
         #[prost(string, tag = "4")]
         Datetime(::prost::alloc::string::String),
+        #[prost(string, tag = "5")]
+        Keyword(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderByKey {
+    /// Payload key to order by
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Ascending or descending order
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderCursor {
+    /// Values of the order keys, one per key
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<OrderValue>,
+    /// Id of the point at this position
+    #[prost(message, optional, tag = "2")]
+    pub id: ::core::option::Option<PointId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OrderBy {
     /// Payload key to order by
     #[prost(string, tag = "1")]
@@ -4614,6 +4748,15 @@ pub struct OrderBy {
     /// Start from this value
     #[prost(message, optional, tag = "3")]
     pub start_from: ::core::option::Option<StartFrom>,
+    /// Keys to order by when the values of all previous keys are equal
+    #[prost(message, repeated, tag = "4")]
+    pub then_by: ::prost::alloc::vec::Vec<OrderByKey>,
+    /// Continue right after this position, takes precedence over `start_from`
+    #[prost(message, optional, tag = "5")]
+    pub start_after: ::core::option::Option<OrderCursor>,
+    /// Order by the distance in meters from this point, `key` must have a geo index
+    #[prost(message, optional, tag = "6")]
+    pub distance_from: ::core::option::Option<GeoPoint>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4826,6 +4969,12 @@ pub struct RecommendPointGroups {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "21")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Additional payload fields to group by, groups are made of each combination of the values of all the fields
+    #[prost(string, repeated, tag = "22")]
+    pub extra_group_by: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// How to compute the score of a group from the scores of its points
+    #[prost(enumeration = "GroupScore", optional, tag = "23")]
+    pub group_score: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5018,8 +5167,199 @@ pub struct ContextInput {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FusionQuery {
+    /// Fusion method
+    #[prost(enumeration = "Fusion", tag = "1")]
+    pub fusion: i32,
+    /// Constant `k` of the Reciprocal Rank Fusion formula `1 / (k + rank)`. Only applicable to RRF. Default is 2
+    #[prost(uint32, optional, tag = "2")]
+    pub k: ::core::option::Option<u32>,
+    /// Weights of the prefetches, in the same order as the prefetches. If empty, all prefetches have a weight of 1.0
+    #[prost(float, repeated, tag = "3")]
+    pub weights: ::prost::alloc::vec::Vec<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Formula {
+    #[prost(message, optional, tag = "1")]
+    pub expression: ::core::option::Option<Expression>,
+    #[prost(map = "string, message", tag = "2")]
+    pub defaults: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Expression {
+    #[prost(
+        oneof = "expression::Variant",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19"
+    )]
+    pub variant: ::core::option::Option<expression::Variant>,
+}
+/// Nested message and enum types in `Expression`.
+pub mod expression {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        #[prost(float, tag = "1")]
+        Constant(f32),
+        /// Payload key or reference to score.
+        #[prost(string, tag = "2")]
+        Variable(::prost::alloc::string::String),
+        /// Payload condition. If true, becomes 1.0; otherwise 0.0
+        #[prost(message, tag = "3")]
+        Condition(super::Condition),
+        /// Multiply
+        #[prost(message, tag = "4")]
+        Mult(super::MultExpression),
+        /// Sum
+        #[prost(message, tag = "5")]
+        Sum(super::SumExpression),
+        /// Divide
+        #[prost(message, tag = "6")]
+        Div(::prost::alloc::boxed::Box<super::DivExpression>),
+        /// Negate
+        #[prost(message, tag = "7")]
+        Neg(::prost::alloc::boxed::Box<super::Expression>),
+        /// Absolute value
+        #[prost(message, tag = "8")]
+        Abs(::prost::alloc::boxed::Box<super::Expression>),
+        /// Square root
+        #[prost(message, tag = "9")]
+        Sqrt(::prost::alloc::boxed::Box<super::Expression>),
+        /// Power
+        #[prost(message, tag = "10")]
+        Pow(::prost::alloc::boxed::Box<super::PowExpression>),
+        /// Exponential
+        #[prost(message, tag = "11")]
+        Exp(::prost::alloc::boxed::Box<super::Expression>),
+        /// Logarithm
+        #[prost(message, tag = "12")]
+        Log10(::prost::alloc::boxed::Box<super::Expression>),
+        /// Natural logarithm
+        #[prost(message, tag = "13")]
+        Ln(::prost::alloc::boxed::Box<super::Expression>),
+        /// Distance in meters between a geo point and a payload key
+        #[prost(message, tag = "14")]
+        GeoDistance(super::GeoDistance),
+        /// RFC 3339 datetime constant, evaluated as seconds since UNIX epoch
+        #[prost(string, tag = "15")]
+        Datetime(::prost::alloc::string::String),
+        /// Payload key with a datetime, evaluated as seconds since UNIX epoch
+        #[prost(string, tag = "16")]
+        DatetimeKey(::prost::alloc::string::String),
+        /// Linear decay function
+        #[prost(message, tag = "17")]
+        LinDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        /// Exponential decay function
+        #[prost(message, tag = "18")]
+        ExpDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+        /// Gaussian decay function
+        #[prost(message, tag = "19")]
+        GaussDecay(::prost::alloc::boxed::Box<super::DecayParamsExpression>),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistance {
+    #[prost(message, optional, tag = "1")]
+    pub origin: ::core::option::Option<GeoPoint>,
+    #[prost(string, tag = "2")]
+    pub to: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub mult: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SumExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub sum: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DivExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(float, optional, tag = "3")]
+    pub by_zero_default: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PowExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub base: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub exponent: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayParamsExpression {
+    /// The variable to decay
+    #[prost(message, optional, boxed, tag = "1")]
+    pub x: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// The target value to start decaying from. Defaults to 0.
+    #[prost(message, optional, boxed, tag = "2")]
+    pub origin: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// The distance from the origin (on top of the offset) at which the function equals `decay`. Must be positive. Defaults to 1.0.
+    #[prost(float, optional, tag = "3")]
+    pub scale: ::core::option::Option<f32>,
+    /// Distance from the origin within which the function is 1.0. Must be non-negative. Defaults to 0.
+    #[prost(float, optional, tag = "4")]
+    pub offset: ::core::option::Option<f32>,
+    /// The value of the function at `scale` distance past the offset. Must be in (0, 1). Defaults to 0.5.
+    #[prost(float, optional, tag = "5")]
+    pub decay: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Mmr {
+    /// Vector to measure the relevance against
+    #[prost(message, optional, tag = "1")]
+    pub vector: ::core::option::Option<VectorInput>,
+    /// Balance between relevance (1.0) and diversity (0.0) of the results. Default is 0.5
+    #[prost(float, optional, tag = "2")]
+    pub lambda: ::core::option::Option<f32>,
+}
+/// Corpus statistics, such as the token rarity, are collected per shard.
+/// Scores are therefore only comparable between points of the same shard, and the merged ranking
+/// of a multi-shard collection is approximate when the text is unevenly distributed across shards.
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bm25 {
+    /// Payload key with a full-text index
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Text to rank the points by
+    #[prost(string, tag = "2")]
+    pub text: ::prost::alloc::string::String,
+    /// Term frequency saturation. Default is 1.2
+    #[prost(float, optional, tag = "3")]
+    pub k1: ::core::option::Option<f32>,
+    /// Document length normalization, from 0.0 (none) to 1.0 (full). Default is 0.75
+    #[prost(float, optional, tag = "4")]
+    pub b: ::core::option::Option<f32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7")]
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
     pub variant: ::core::option::Option<query::Variant>,
 }
 /// Nested message and enum types in `Query`.
@@ -5049,6 +5389,18 @@ pub mod query {
         /// Sample points from the collection.
         #[prost(enumeration = "super::Sample", tag = "7")]
         Sample(i32),
+        /// Score boosting via an arbitrary formula
+        #[prost(message, tag = "8")]
+        Formula(super::Formula),
+        /// Diversify the prefetched points with Maximal Marginal Relevance
+        #[prost(message, tag = "9")]
+        Mmr(super::Mmr),
+        /// Fuse the results of multiple prefetches, with custom parameters
+        #[prost(message, tag = "10")]
+        FusionQuery(super::FusionQuery),
+        /// Rank the points by BM25 relevance of a full-text indexed field to the text
+        #[prost(message, tag = "11")]
+        Bm25(super::Bm25),
     }
 }
 #[derive(serde::Serialize)]
@@ -5210,6 +5562,12 @@ pub struct QueryPointGroups {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "17")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Additional payload fields to group by, groups are made of each combination of the values of all the fields
+    #[prost(string, repeated, tag = "18")]
+    pub extra_group_by: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// How to compute the score of a group from the scores of its points
+    #[prost(enumeration = "GroupScore", optional, tag = "19")]
+    pub group_score: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5244,12 +5602,135 @@ pub struct FacetCounts {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "8")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Count points per geo-hash cell of a geo field, instead of per value
+    #[prost(message, optional, tag = "9")]
+    #[validate(nested)]
+    pub geo_grid: ::core::option::Option<GeoGridParams>,
+    /// Count points per bucket of a numeric or datetime field, instead of per value
+    #[prost(message, optional, tag = "10")]
+    pub histogram: ::core::option::Option<HistogramParams>,
+    /// Count combinations of the values of the key and of these keys, instead of single values. At most 3 keys.
+    #[prost(message, repeated, tag = "11")]
+    #[validate(nested, length(max = 3))]
+    pub pivot: ::prost::alloc::vec::Vec<FacetPivot>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetPivot {
+    /// Payload key of this level of the facet
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Max number of values of this key, for each value of the previous keys. Default is 10.
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub limit: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoGridParams {
+    /// Length of the geo-hash of the cells, from 1 to 12
+    #[prost(uint32, tag = "1")]
+    #[validate(range(min = 1, max = 12))]
+    pub precision: u32,
+    /// Whether to return the centroid of the points in each cell. Default is false.
+    #[prost(bool, optional, tag = "2")]
+    pub with_centroid: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramBound {
+    #[prost(oneof = "histogram_bound::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<histogram_bound::Variant>,
+}
+/// Nested message and enum types in `HistogramBound`.
+pub mod histogram_bound {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Number, in seconds since the epoch for datetime fields
+        #[prost(double, tag = "1")]
+        Float(f64),
+        /// Datetime, only for datetime fields
+        #[prost(message, tag = "2")]
+        Datetime(::prost_wkt_types::Timestamp),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramRange {
+    /// Inclusive lower bound, unbounded if not set
+    #[prost(message, optional, tag = "1")]
+    pub from: ::core::option::Option<HistogramBound>,
+    /// Exclusive upper bound, unbounded if not set
+    #[prost(message, optional, tag = "2")]
+    pub to: ::core::option::Option<HistogramBound>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramRanges {
+    #[prost(message, repeated, tag = "1")]
+    pub ranges: ::prost::alloc::vec::Vec<HistogramRange>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramParams {
+    #[prost(oneof = "histogram_params::Buckets", tags = "1, 2, 3")]
+    pub buckets: ::core::option::Option<histogram_params::Buckets>,
+}
+/// Nested message and enum types in `HistogramParams`.
+pub mod histogram_params {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Buckets {
+        /// Buckets of a fixed width, aligned on zero. In seconds for datetime fields
+        #[prost(double, tag = "1")]
+        Interval(f64),
+        /// Calendar buckets of a datetime field, in UTC
+        #[prost(enumeration = "super::CalendarInterval", tag = "2")]
+        CalendarInterval(i32),
+        /// Explicit buckets, a point is counted in all buckets containing one of its values
+        #[prost(message, tag = "3")]
+        Ranges(super::HistogramRanges),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetFloatRange {
+    /// Inclusive lower bound of the bucket
+    #[prost(double, optional, tag = "1")]
+    pub from: ::core::option::Option<f64>,
+    /// Exclusive upper bound of the bucket
+    #[prost(double, optional, tag = "2")]
+    pub to: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetDatetimeRange {
+    /// Inclusive lower bound of the bucket
+    #[prost(message, optional, tag = "1")]
+    pub from: ::core::option::Option<::prost_wkt_types::Timestamp>,
+    /// Exclusive upper bound of the bucket
+    #[prost(message, optional, tag = "2")]
+    pub to: ::core::option::Option<::prost_wkt_types::Timestamp>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FacetValue {
-    #[prost(oneof = "facet_value::Variant", tags = "1, 2, 3")]
+    #[prost(oneof = "facet_value::Variant", tags = "1, 2, 3, 4, 5, 6")]
     pub variant: ::core::option::Option<facet_value::Variant>,
 }
 /// Nested message and enum types in `FacetValue`.
@@ -5267,11 +5748,28 @@ pub mod facet_value {
         /// Boolean value from the facet
         #[prost(bool, tag = "3")]
         BoolValue(bool),
+        /// Bucket of a numeric histogram facet
+        #[prost(message, tag = "4")]
+        FloatRangeValue(super::FacetFloatRange),
+        /// Bucket of a datetime histogram facet
+        #[prost(message, tag = "5")]
+        DatetimeRangeValue(super::FacetDatetimeRange),
+        /// Combination of values of a pivot facet
+        #[prost(message, tag = "6")]
+        TupleValue(super::FacetValueTuple),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValueTuple {
+    /// One value per key of the pivot facet
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<FacetValue>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FacetHit {
     /// Value from the facet
     #[prost(message, optional, tag = "1")]
@@ -5279,53 +5777,119 @@ pub struct FacetHit {
     /// Number of points with this value
     #[prost(uint64, tag = "2")]
     pub count: u64,
+    /// Mean location of the points, only for geo grid facets
+    #[prost(message, optional, tag = "3")]
+    pub centroid: ::core::option::Option<GeoPoint>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchMatrixPoints {
+pub struct AggregatePoints {
     /// Name of the collection
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
-    /// Filter conditions - return only those points that satisfy the specified conditions.
-    #[prost(message, optional, tag = "2")]
+    /// Payload key of an integer, float or datetime field
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - aggregate only the points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "3")]
     #[validate(nested)]
     pub filter: ::core::option::Option<Filter>,
-    /// How many points to select and search within. Default is 10.
-    #[prost(uint64, optional, tag = "3")]
-    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_2"))]
-    pub sample: ::core::option::Option<u64>,
-    /// How many neighbours per sample to find. Default is 3.
-    #[prost(uint64, optional, tag = "4")]
-    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
-    pub limit: ::core::option::Option<u64>,
-    /// Define which vector to use for querying. If missing, the default vector is is used.
-    #[prost(string, optional, tag = "5")]
-    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Percentiles to approximate, from 0 to 100
+    #[prost(double, repeated, tag = "4")]
+    #[validate(custom(function = "segment::data_types::aggregations::validate_percentiles"))]
+    pub percentiles: ::prost::alloc::vec::Vec<f64>,
     /// If set, overrides global timeout setting for this request. Unit is seconds.
-    #[prost(uint64, optional, tag = "6")]
+    #[prost(uint64, optional, tag = "5")]
     #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
     pub timeout: ::core::option::Option<u64>,
     /// Options for specifying read consistency guarantees
-    #[prost(message, optional, tag = "7")]
+    #[prost(message, optional, tag = "6")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
     /// Specify in which shards to look for the points, if not specified - look in all shards
-    #[prost(message, optional, tag = "8")]
+    #[prost(message, optional, tag = "7")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctCountPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key of a keyword, integer or uuid field
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - count values only of the points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "3")]
+    #[validate(nested)]
+    pub filter: ::core::option::Option<Filter>,
+    /// If true, count the values exactly instead of approximating, up to 100000 distinct values. Default is false.
+    #[prost(bool, optional, tag = "4")]
+    pub exact: ::core::option::Option<bool>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "6")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "2")]
+    #[validate(nested)]
+    pub filter: ::core::option::Option<Filter>,
+    /// How many points to select and search within. Default is 10.
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_2"))]
+    pub sample: ::core::option::Option<u64>,
+    /// How many neighbours per sample to find. Default is 3.
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub limit: ::core::option::Option<u64>,
+    /// Define which vector to use for querying. If missing, the default vector is is used.
+    #[prost(string, optional, tag = "5")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "7")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "8")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairs {
+    /// List of pairs of points with scores
+    #[prost(message, repeated, tag = "1")]
+    pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchMatrixPairs {
-    /// List of pairs of points with scores
-    #[prost(message, repeated, tag = "1")]
-    pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
-}
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchMatrixPair {
     /// first id of the pair
     #[prost(message, optional, tag = "1")]
@@ -5545,7 +6109,7 @@ pub struct UpdateResult {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OrderValue {
-    #[prost(oneof = "order_value::Variant", tags = "1, 2")]
+    #[prost(oneof = "order_value::Variant", tags = "1, 2, 3, 4")]
     pub variant: ::core::option::Option<order_value::Variant>,
 }
 /// Nested message and enum types in `OrderValue`.
@@ -5558,11 +6122,23 @@ pub mod order_value {
         Int(i64),
         #[prost(double, tag = "2")]
         Float(f64),
+        #[prost(string, tag = "3")]
+        Keyword(::prost::alloc::string::String),
+        /// Values of all keys, when ordering by multiple keys
+        #[prost(message, tag = "4")]
+        Composite(super::OrderValues),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderValues {
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<OrderValue>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScoredPoint {
     /// Point id
     #[prost(message, optional, tag = "1")]
@@ -5590,7 +6166,7 @@ pub struct ScoredPoint {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GroupId {
-    #[prost(oneof = "group_id::Kind", tags = "1, 2, 3")]
+    #[prost(oneof = "group_id::Kind", tags = "1, 2, 3, 4")]
     pub kind: ::core::option::Option<group_id::Kind>,
 }
 /// Nested message and enum types in `GroupId`.
@@ -5608,11 +6184,21 @@ pub mod group_id {
         /// Represents a string value.
         #[prost(string, tag = "3")]
         StringValue(::prost::alloc::string::String),
+        /// Represents the values of each field, when grouping by several fields
+        #[prost(message, tag = "4")]
+        CompositeValue(super::GroupIdComposite),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupIdComposite {
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<GroupId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointGroup {
     /// Group id
     #[prost(message, optional, tag = "1")]
@@ -5853,6 +6439,89 @@ pub struct FacetResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateValue {
+    #[prost(oneof = "aggregate_value::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<aggregate_value::Variant>,
+}
+/// Nested message and enum types in `AggregateValue`.
+pub mod aggregate_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Value of an integer or float field
+        #[prost(double, tag = "1")]
+        Number(f64),
+        /// Value of a datetime field
+        #[prost(message, tag = "2")]
+        Datetime(::prost_wkt_types::Timestamp),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PercentileValue {
+    /// Requested percentile, from 0 to 100
+    #[prost(double, tag = "1")]
+    pub percentile: f64,
+    /// Approximate value at this percentile
+    #[prost(message, optional, tag = "2")]
+    pub value: ::core::option::Option<AggregateValue>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResult {
+    /// Number of aggregated values
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    /// Sum of the values, not set for datetime fields
+    #[prost(double, optional, tag = "2")]
+    pub sum: ::core::option::Option<f64>,
+    /// Smallest value
+    #[prost(message, optional, tag = "3")]
+    pub min: ::core::option::Option<AggregateValue>,
+    /// Largest value
+    #[prost(message, optional, tag = "4")]
+    pub max: ::core::option::Option<AggregateValue>,
+    /// Mean of the values
+    #[prost(message, optional, tag = "5")]
+    pub avg: ::core::option::Option<AggregateValue>,
+    /// Approximate percentiles, in the requested order
+    #[prost(message, repeated, tag = "6")]
+    pub percentiles: ::prost::alloc::vec::Vec<PercentileValue>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<AggregateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctCountResult {
+    /// Number of distinct values, approximate unless exact was requested
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctCountResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<DistinctCountResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchMatrixPairsResponse {
     #[prost(message, optional, tag = "1")]
     pub result: ::core::option::Option<SearchMatrixPairs>,
@@ -5909,7 +6578,7 @@ pub struct MinShould {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7")]
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     #[validate(nested)]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
@@ -5933,6 +6602,8 @@ pub mod condition {
         Nested(super::NestedCondition),
         #[prost(message, tag = "7")]
         HasVector(super::HasVectorCondition),
+        #[prost(message, tag = "8")]
+        Join(super::JoinCondition),
     }
 }
 #[derive(serde::Serialize)]
@@ -5976,6 +6647,31 @@ pub struct NestedCondition {
     #[validate(nested)]
     pub filter: ::core::option::Option<Filter>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JoinCondition {
+    /// Payload field holding the ids or keys of the related points
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Name of the collection of the related points
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection: ::prost::alloc::string::String,
+    /// Select only related points which satisfy this filter
+    #[prost(message, optional, tag = "3")]
+    #[validate(nested)]
+    pub filter: ::core::option::Option<Filter>,
+    /// Payload field of the related points to match the key with. If not provided - their ids are used.
+    #[prost(string, optional, tag = "4")]
+    pub related_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Maximum amount of related points. The request fails if more of them match the filter. Default is 10000.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(range(min = 1, max = 100000))]
+    pub limit: ::core::option::Option<u64>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -6003,12 +6699,18 @@ pub struct FieldCondition {
     /// Check if datetime is within a given range
     #[prost(message, optional, tag = "8")]
     pub datetime_range: ::core::option::Option<DatetimeRange>,
+    /// Check if keyword lies in a given lexicographic range
+    #[prost(message, optional, tag = "9")]
+    pub keyword_range: ::core::option::Option<KeywordRange>,
+    /// Check relation of geo shape to a given point or polygon
+    #[prost(message, optional, tag = "10")]
+    pub geo_shape: ::core::option::Option<GeoShapeCondition>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -6041,11 +6743,60 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match phrase
+        #[prost(message, tag = "9")]
+        Phrase(super::PhraseMatch),
+        /// Match text with typos
+        #[prost(message, tag = "10")]
+        Fuzzy(super::FuzzyMatch),
+        /// Match keywords starting with the prefix
+        #[prost(string, tag = "11")]
+        Prefix(::prost::alloc::string::String),
+        /// Match keywords by a wildcard pattern, `*` is any sequence of characters and `?` is a single character
+        #[prost(string, tag = "12")]
+        Wildcard(::prost::alloc::string::String),
+        /// Match keywords by a regular expression, which must match the whole keyword
+        #[prost(string, tag = "13")]
+        Regex(::prost::alloc::string::String),
+        /// Match if all of those keywords are present
+        #[prost(message, tag = "14")]
+        AllKeywords(super::RepeatedStrings),
+        /// Match if all of those integers are present
+        #[prost(message, tag = "15")]
+        AllIntegers(super::RepeatedIntegers),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PhraseMatch {
+    /// Phrase to match, tokens must appear in the same order
+    #[prost(string, tag = "1")]
+    pub phrase: ::prost::alloc::string::String,
+    /// Maximal number of other tokens between the phrase tokens, default is 0
+    #[prost(uint32, optional, tag = "2")]
+    pub slop: ::core::option::Option<u32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FuzzyMatch {
+    /// Text to match, each token may differ from the indexed tokens
+    #[prost(string, tag = "1")]
+    pub fuzzy: ::prost::alloc::string::String,
+    /// Maximal Levenshtein distance of a matching token, default is 1, at most 2
+    #[prost(uint32, optional, tag = "2")]
+    #[validate(range(max = 2))]
+    pub max_edits: ::core::option::Option<u32>,
+    /// Maximal number of indexed tokens each query token expands to, default is 50, at most 1000
+    #[prost(uint32, optional, tag = "3")]
+    #[validate(range(min = 1, max = 1000))]
+    pub max_expansions: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RepeatedStrings {
     #[prost(string, repeated, tag = "1")]
     pub strings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
@@ -6091,6 +6842,19 @@ pub struct DatetimeRange {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KeywordRange {
+    #[prost(string, optional, tag = "1")]
+    pub lt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub gt: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub gte: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub lte: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GeoBoundingBox {
     /// north-west corner
     #[prost(message, optional, tag = "1")]
@@ -6141,6 +6905,30 @@ pub struct GeoPolygon {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoShapeCondition {
+    #[prost(oneof = "geo_shape_condition::Condition", tags = "1, 2, 3")]
+    pub condition: ::core::option::Option<geo_shape_condition::Condition>,
+}
+/// Nested message and enum types in `GeoShapeCondition`.
+pub mod geo_shape_condition {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Condition {
+        /// Shape contains the given point
+        #[prost(message, tag = "1")]
+        Contains(super::GeoPoint),
+        /// Shape and the given polygon have at least one point in common
+        #[prost(message, tag = "2")]
+        Intersects(super::GeoPolygon),
+        /// Shape lies entirely within the given polygon
+        #[prost(message, tag = "3")]
+        Within(super::GeoPolygon),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ValuesCount {
     #[prost(uint64, optional, tag = "1")]
     pub lt: ::core::option::Option<u64>,
@@ -6286,6 +7074,7 @@ pub enum FieldType {
     Bool = 5,
     Datetime = 6,
     Uuid = 7,
+    GeoShape = 8,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -6302,6 +7091,7 @@ impl FieldType {
             FieldType::Bool => "FieldTypeBool",
             FieldType::Datetime => "FieldTypeDatetime",
             FieldType::Uuid => "FieldTypeUuid",
+            FieldType::GeoShape => "FieldTypeGeoShape",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -6315,6 +7105,41 @@ impl FieldType {
             "FieldTypeBool" => Some(Self::Bool),
             "FieldTypeDatetime" => Some(Self::Datetime),
             "FieldTypeUuid" => Some(Self::Uuid),
+            "FieldTypeGeoShape" => Some(Self::GeoShape),
+            _ => None,
+        }
+    }
+}
+/// How to compute the score of a group from the scores of its points, default is `Max`.
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum GroupScore {
+    /// Score of the best point of the group
+    Max = 0,
+    /// Sum of the scores of the returned points of the group
+    Sum = 1,
+    /// Average of the scores of the returned points of the group
+    Average = 2,
+}
+impl GroupScore {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            GroupScore::Max => "Max",
+            GroupScore::Sum => "Sum",
+            GroupScore::Average => "Average",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Max" => Some(Self::Max),
+            "Sum" => Some(Self::Sum),
+            "Average" => Some(Self::Average),
             _ => None,
         }
     }
@@ -6387,6 +7212,10 @@ pub enum Fusion {
     Rrf = 0,
     /// Distribution-Based Score Fusion
     Dbsf = 1,
+    /// Weighted sum of min-max normalized scores
+    MinMax = 2,
+    /// Weighted sum of z-score normalized scores
+    ZScore = 3,
 }
 impl Fusion {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -6397,6 +7226,8 @@ impl Fusion {
         match self {
             Fusion::Rrf => "RRF",
             Fusion::Dbsf => "DBSF",
+            Fusion::MinMax => "MIN_MAX",
+            Fusion::ZScore => "Z_SCORE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -6404,6 +7235,8 @@ impl Fusion {
         match value {
             "RRF" => Some(Self::Rrf),
             "DBSF" => Some(Self::Dbsf),
+            "MIN_MAX" => Some(Self::MinMax),
+            "Z_SCORE" => Some(Self::ZScore),
             _ => None,
         }
     }
@@ -6440,6 +7273,37 @@ impl Sample {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum CalendarInterval {
+    Day = 0,
+    /// Weeks start on Monday
+    Week = 1,
+    Month = 2,
+}
+impl CalendarInterval {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CalendarInterval::Day => "Day",
+            CalendarInterval::Week => "Week",
+            CalendarInterval::Month => "Month",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Day" => Some(Self::Day),
+            "Week" => Some(Self::Week),
+            "Month" => Some(Self::Month),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum UpdateStatus {
     UnknownUpdateStatus = 0,
     /// Update is received, but not processed yet
@@ -7191,6 +8055,55 @@ pub mod points_client {
             req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Facet"));
             self.inner.unary(req, path, codec).await
         }
+        /// Compute count, sum, min, max, average and approximate percentiles of a numeric or datetime field over the points matching the conditions.
+        pub async fn aggregate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AggregatePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Aggregate");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Aggregate"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Count the distinct values of a keyword, integer or uuid field over the points matching the conditions.
+        pub async fn distinct_count(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DistinctCountPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::DistinctCountResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/DistinctCount",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "DistinctCount"));
+            self.inner.unary(req, path, codec).await
+        }
         /// Compute distance matrix for sampled points with a pair based output format
         pub async fn search_matrix_pairs(
             &mut self,
@@ -7456,6 +8369,22 @@ pub mod points_server {
             &self,
             request: tonic::Request<super::FacetCounts>,
         ) -> std::result::Result<tonic::Response<super::FacetResponse>, tonic::Status>;
+        /// Compute count, sum, min, max, average and approximate percentiles of a numeric or datetime field over the points matching the conditions.
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponse>,
+            tonic::Status,
+        >;
+        /// Count the distinct values of a keyword, integer or uuid field over the points matching the conditions.
+        async fn distinct_count(
+            &self,
+            request: tonic::Request<super::DistinctCountPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::DistinctCountResponse>,
+            tonic::Status,
+        >;
         /// Compute distance matrix for sampled points with a pair based output format
         async fn search_matrix_pairs(
             &self,
@@ -8594,11 +9523,99 @@ pub mod points_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QueryBatchPoints>,
+                            request: tonic::Request<super::QueryBatchPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::query_batch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QueryBatchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/QueryGroups" => {
+                    #[allow(non_camel_case_types)]
+                    struct QueryGroupsSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryPointGroups>
+                    for QueryGroupsSvc<T> {
+                        type Response = super::QueryGroupsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryPointGroups>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::query_groups(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QueryGroupsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FacetCounts>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCounts>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::query_batch(&inner, request).await
+                                <T as Points>::facet(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8610,7 +9627,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = QueryBatchSvc(inner);
+                        let method = FacetSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8626,23 +9643,23 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/QueryGroups" => {
+                "/qdrant.Points/Aggregate" => {
                     #[allow(non_camel_case_types)]
-                    struct QueryGroupsSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::QueryPointGroups>
-                    for QueryGroupsSvc<T> {
-                        type Response = super::QueryGroupsResponse;
+                    struct AggregateSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::AggregatePoints>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QueryPointGroups>,
+                            request: tonic::Request<super::AggregatePoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::query_groups(&inner, request).await
+                                <T as Points>::aggregate(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8654,7 +9671,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = QueryGroupsSvc(inner);
+                        let method = AggregateSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8670,23 +9687,25 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
-                "/qdrant.Points/Facet" => {
+                "/qdrant.Points/DistinctCount" => {
                     #[allow(non_camel_case_types)]
-                    struct FacetSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::FacetCounts>
-                    for FacetSvc<T> {
-                        type Response = super::FacetResponse;
+                    struct DistinctCountSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::DistinctCountPoints>
+                    for DistinctCountSvc<T> {
+                        type Response = super::DistinctCountResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FacetCounts>,
+                            request: tonic::Request<super::DistinctCountPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::facet(&inner, request).await
+                                <T as Points>::distinct_count(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -8698,7 +9717,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = FacetSvc(inner);
+                        let method = DistinctCountSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -9351,7 +10370,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -9372,11 +10391,48 @@ pub mod query_shard_points {
             /// Sample points
             #[prost(enumeration = "super::super::Sample", tag = "4")]
             Sample(i32),
+            /// Score boosting via an arbitrary formula
+            #[prost(message, tag = "5")]
+            Formula(super::super::Formula),
+            /// Score against a vector, the results are diversified at collection level
+            #[prost(message, tag = "6")]
+            Mmr(super::Mmr),
+            /// One of the fusion methods, with custom parameters
+            #[prost(message, tag = "7")]
+            FusionQuery(super::super::FusionQuery),
+            /// Rank by BM25 relevance of a full-text indexed field
+            #[prost(message, tag = "8")]
+            Bm25(super::super::Bm25),
+            /// Weighted sum of min-max normalized scores
+            #[prost(message, tag = "9")]
+            MinMaxFusion(super::NormalizedFusion),
+            /// Weighted sum of z-score normalized scores
+            #[prost(message, tag = "10")]
+            ZScoreFusion(super::NormalizedFusion),
         }
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Mmr {
+        #[prost(message, optional, tag = "1")]
+        pub vector: ::core::option::Option<super::RawVector>,
+        #[prost(float, tag = "2")]
+        pub lambda: f32,
+    }
+    /// Normalized fusions get their own fields instead of new `Fusion` values,
+    /// so that peers which don't support them reject the query instead of misreading it
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct NormalizedFusion {
+        /// Weights of the prefetches, empty means equal weights
+        #[prost(float, repeated, tag = "1")]
+        pub weights: ::prost::alloc::vec::Vec<f32>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Prefetch {
         #[prost(message, repeated, tag = "1")]
         pub prefetch: ::prost::alloc::vec::Vec<Prefetch>,
@@ -9457,12 +10513,18 @@ pub struct FacetCountsInternal {
     #[prost(uint64, optional, tag = "7")]
     #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
     pub timeout: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "8")]
+    pub geo_grid: ::core::option::Option<GeoGridParams>,
+    #[prost(message, optional, tag = "9")]
+    pub histogram: ::core::option::Option<HistogramParams>,
+    #[prost(message, repeated, tag = "10")]
+    pub pivot: ::prost::alloc::vec::Vec<FacetPivot>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FacetValueInternal {
-    #[prost(oneof = "facet_value_internal::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "facet_value_internal::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
     pub variant: ::core::option::Option<facet_value_internal::Variant>,
 }
 /// Nested message and enum types in `FacetValueInternal`.
@@ -9479,16 +10541,33 @@ pub mod facet_value_internal {
         UuidValue(::prost::alloc::vec::Vec<u8>),
         #[prost(bool, tag = "4")]
         BoolValue(bool),
+        #[prost(string, tag = "5")]
+        GeoHashValue(::prost::alloc::string::String),
+        #[prost(message, tag = "6")]
+        FloatRangeValue(super::FacetFloatRange),
+        #[prost(message, tag = "7")]
+        DatetimeRangeValue(super::FacetDatetimeRange),
+        #[prost(message, tag = "8")]
+        TupleValue(super::FacetValueInternalTuple),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValueInternalTuple {
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<FacetValueInternal>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FacetHitInternal {
     #[prost(message, optional, tag = "1")]
     pub value: ::core::option::Option<FacetValueInternal>,
     #[prost(uint64, tag = "2")]
     pub count: u64,
+    #[prost(message, optional, tag = "3")]
+    pub centroid: ::core::option::Option<GeoPoint>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -9500,6 +10579,120 @@ pub struct FacetResponseInternal {
     #[prost(double, tag = "2")]
     pub time: f64,
 }
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePointsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    #[prost(uint32, tag = "4")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DigestCentroid {
+    #[prost(double, tag = "1")]
+    pub mean: f64,
+    #[prost(double, tag = "2")]
+    pub weight: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PartialAggregateInternal {
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    #[prost(double, tag = "2")]
+    pub sum: f64,
+    #[prost(double, optional, tag = "3")]
+    pub min: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub max: ::core::option::Option<f64>,
+    /// Values are timestamps in microseconds
+    #[prost(bool, tag = "5")]
+    pub is_datetime: bool,
+    /// Centroids of the t-digest approximating the percentiles
+    #[prost(message, repeated, tag = "6")]
+    pub centroids: ::prost::alloc::vec::Vec<DigestCentroid>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<PartialAggregateInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctCountPointsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    #[prost(bool, tag = "4")]
+    pub exact: bool,
+    #[prost(uint32, tag = "5")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctValuesInternal {
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<FacetValueInternal>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PartialDistinctCountInternal {
+    #[prost(oneof = "partial_distinct_count_internal::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<partial_distinct_count_internal::Variant>,
+}
+/// Nested message and enum types in `PartialDistinctCountInternal`.
+pub mod partial_distinct_count_internal {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Distinct values, when counting exactly
+        #[prost(message, tag = "1")]
+        Exact(super::DistinctValuesInternal),
+        /// Registers of the HyperLogLog sketch of the values
+        #[prost(bytes, tag = "2")]
+        Sketch(::prost::alloc::vec::Vec<u8>),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctCountResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<PartialDistinctCountInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -10025,6 +11218,56 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn aggregate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AggregatePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Aggregate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Aggregate"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn distinct_count(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DistinctCountPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::DistinctCountResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/DistinctCount",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "DistinctCount"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -10151,6 +11394,20 @@ pub mod points_internal_server {
             tonic::Response<super::FacetResponseInternal>,
             tonic::Status,
         >;
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponseInternal>,
+            tonic::Status,
+        >;
+        async fn distinct_count(
+            &self,
+            request: tonic::Request<super::DistinctCountPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::DistinctCountResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -11069,6 +12326,98 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Aggregate" => {
+                    #[allow(non_camel_case_types)]
+                    struct AggregateSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::AggregatePointsInternal>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AggregatePointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::aggregate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AggregateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/DistinctCount" => {
+                    #[allow(non_camel_case_types)]
+                    struct DistinctCountSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::DistinctCountPointsInternal>
+                    for DistinctCountSvc<T> {
+                        type Response = super::DistinctCountResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DistinctCountPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::distinct_count(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DistinctCountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 
 use common::validation::{validate_range_generic, validate_shard_different_peers};
+use segment::data_types::keyword_pattern::validate_keyword_pattern;
 use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::qdrant as grpc;
@@ -186,6 +187,7 @@ impl Validate for grpc::condition::ConditionOneOf {
         match self {
             ConditionOneOf::Field(field_condition) => field_condition.validate(),
             ConditionOneOf::Nested(nested) => nested.validate(),
+            ConditionOneOf::Join(join) => join.validate(),
             ConditionOneOf::Filter(filter) => filter.validate(),
             ConditionOneOf::IsEmpty(_) => Ok(()),
             ConditionOneOf::HasId(_) => Ok(()),
@@ -202,18 +204,22 @@ impl Validate for grpc::FieldCondition {
             r#match,
             range,
             datetime_range,
+            keyword_range,
             geo_bounding_box,
             geo_radius,
             geo_polygon,
+            geo_shape,
             values_count,
         } = self;
 
         let all_fields_none = r#match.is_none()
             && range.is_none()
             && datetime_range.is_none()
+            && keyword_range.is_none()
             && geo_bounding_box.is_none()
             && geo_radius.is_none()
             && geo_polygon.is_none()
+            && geo_shape.is_none()
             && values_count.is_none();
 
         if all_fields_none {
@@ -222,9 +228,21 @@ impl Validate for grpc::FieldCondition {
                 "match",
                 ValidationError::new("At least one field condition must be specified"),
             );
-            Err(errors)
-        } else {
-            Ok(())
+            return Err(errors);
+        }
+
+        match r#match
+            .as_ref()
+            .and_then(|r#match| r#match.match_value.as_ref())
+        {
+            Some(grpc::r#match::MatchValue::Fuzzy(fuzzy)) => fuzzy.validate(),
+            Some(grpc::r#match::MatchValue::Wildcard(wildcard)) => {
+                validate_keyword_pattern(&segment::types::Match::new_wildcard(wildcard))
+            }
+            Some(grpc::r#match::MatchValue::Regex(regex)) => {
+                validate_keyword_pattern(&segment::types::Match::new_regex(regex))
+            }
+            _ => Ok(()),
         }
     }
 }
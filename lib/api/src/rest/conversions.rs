@@ -1,11 +1,15 @@
+use segment::data_types::bm25::Bm25Query;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{VectorInternal, VectorStructInternal};
+use segment::types::DateTimePayloadType;
 use uuid::Uuid;
 
 use super::schema::{ScoredPoint, Vector};
 use super::{
-    FacetRequestInternal, FacetResponse, FacetValue, FacetValueHit, NearestQuery, OrderByInterface,
-    Query, QueryInterface, VectorOutput, VectorStructOutput,
+    AggregateRequestInternal, AggregateResponse, AggregateValue, Bm25Input,
+    DistinctCountRequestInternal, DistinctCountResponse, FacetPivot, FacetRequestInternal,
+    FacetResponse, FacetValue, FacetValueHit, FacetValueRange, NearestQuery, OrderByInterface,
+    PercentileValue, Query, QueryInterface, VectorOutput, VectorStructOutput,
 };
 use crate::rest::{DenseVector, NamedVectorStruct};
 
@@ -104,12 +108,27 @@ impl From<OrderByInterface> for OrderBy {
                 key,
                 direction: None,
                 start_from: None,
+                then_by: vec![],
+                start_after: None,
+                distance_from: None,
             },
             OrderByInterface::Struct(order_by) => order_by,
         }
     }
 }
 
+impl From<Bm25Input> for Bm25Query {
+    fn from(value: Bm25Input) -> Self {
+        let Bm25Input { key, text, k1, b } = value;
+        Bm25Query {
+            key,
+            text,
+            k1: k1.unwrap_or(Bm25Query::DEFAULT_K1),
+            b: b.unwrap_or(Bm25Query::DEFAULT_B),
+        }
+    }
+}
+
 impl From<QueryInterface> for Query {
     fn from(value: QueryInterface) -> Self {
         match value {
@@ -128,6 +147,26 @@ impl From<segment::data_types::facets::FacetValue> for FacetValue {
                 Self::String(Uuid::from_u128(uuid_int).to_string())
             }
             segment::data_types::facets::FacetValue::Bool(b) => Self::Bool(b),
+            segment::data_types::facets::FacetValue::GeoHash(hash) => Self::String(hash),
+            segment::data_types::facets::FacetValue::FloatRange(range) => {
+                Self::FloatRange(FacetValueRange {
+                    from: range.from.map(|from| from.0),
+                    to: range.to.map(|to| to.0),
+                })
+            }
+            segment::data_types::facets::FacetValue::DatetimeRange(range) => {
+                let to_datetime = |timestamp| {
+                    chrono::DateTime::from_timestamp_micros(timestamp)
+                        .map(DateTimePayloadType::from)
+                };
+                Self::DatetimeRange(FacetValueRange {
+                    from: range.from.and_then(to_datetime),
+                    to: range.to.and_then(to_datetime),
+                })
+            }
+            segment::data_types::facets::FacetValue::Tuple(values) => {
+                Self::Tuple(values.into_iter().map(From::from).collect())
+            }
         }
     }
 }
@@ -137,6 +176,7 @@ impl From<segment::data_types::facets::FacetValueHit> for FacetValueHit {
         Self {
             value: From::from(value.value),
             count: value.count,
+            centroid: value.centroid,
         }
     }
 }
@@ -149,6 +189,67 @@ impl From<segment::data_types::facets::FacetResponse> for FacetResponse {
     }
 }
 
+impl From<segment::data_types::aggregations::AggregateValue> for AggregateValue {
+    fn from(value: segment::data_types::aggregations::AggregateValue) -> Self {
+        match value {
+            segment::data_types::aggregations::AggregateValue::Number(number) => {
+                Self::Number(number)
+            }
+            segment::data_types::aggregations::AggregateValue::Datetime(date_time) => {
+                Self::Datetime(date_time)
+            }
+        }
+    }
+}
+
+impl From<segment::data_types::aggregations::AggregateResponse> for AggregateResponse {
+    fn from(value: segment::data_types::aggregations::AggregateResponse) -> Self {
+        Self {
+            count: value.count,
+            sum: value.sum,
+            min: value.min.map(From::from),
+            max: value.max.map(From::from),
+            avg: value.avg.map(From::from),
+            percentiles: value
+                .percentiles
+                .into_iter()
+                .map(|percentile| PercentileValue {
+                    percentile: percentile.percentile,
+                    value: From::from(percentile.value),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<AggregateRequestInternal> for segment::data_types::aggregations::AggregateParams {
+    fn from(value: AggregateRequestInternal) -> Self {
+        Self {
+            key: value.key,
+            filter: value.filter,
+            percentiles: value.percentiles.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<segment::data_types::aggregations::DistinctCountResponse> for DistinctCountResponse {
+    fn from(value: segment::data_types::aggregations::DistinctCountResponse) -> Self {
+        Self { count: value.count }
+    }
+}
+
+impl From<DistinctCountRequestInternal> for segment::data_types::aggregations::DistinctCountParams {
+    fn from(value: DistinctCountRequestInternal) -> Self {
+        Self {
+            key: value.key,
+            filter: value.filter,
+            exact: value
+                .exact
+                .unwrap_or(segment::data_types::aggregations::DistinctCountParams::DEFAULT_EXACT),
+        }
+    }
+}
+
 impl From<FacetRequestInternal> for segment::data_types::facets::FacetParams {
     fn from(value: FacetRequestInternal) -> Self {
         Self {
@@ -156,6 +257,25 @@ impl From<FacetRequestInternal> for segment::data_types::facets::FacetParams {
             limit: value.limit.unwrap_or(Self::DEFAULT_LIMIT),
             filter: value.filter,
             exact: value.exact.unwrap_or(Self::DEFAULT_EXACT),
+            geo_grid: value.geo_grid,
+            histogram: value.histogram,
+            pivot: value
+                .pivot
+                .unwrap_or_default()
+                .into_iter()
+                .map(From::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<FacetPivot> for segment::data_types::facets::FacetPivotParams {
+    fn from(value: FacetPivot) -> Self {
+        Self {
+            key: value.key,
+            limit: value
+                .limit
+                .unwrap_or(segment::data_types::facets::FacetParams::DEFAULT_LIMIT),
         }
     }
 }
@@ -5,11 +5,14 @@ use common::types::ScoreType;
 use common::validation::validate_multi_vector;
 use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
+use segment::data_types::aggregations::validate_percentiles;
+use segment::data_types::facets::{GeoGridParams, HistogramParams};
+use segment::data_types::groups::GroupScore;
 use segment::data_types::order_by::OrderBy;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Filter, IntPayloadType, Payload, PointIdType, SearchParams, ShardKey, WithPayloadInterface,
-    WithVector,
+    Condition, DateTimePayloadType, Filter, FloatPayloadType, GeoPoint, IntPayloadType, Payload,
+    PointIdType, SearchParams, ShardKey, WithPayloadInterface, WithVector,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -352,11 +355,15 @@ pub enum OrderByInterface {
 ///
 /// * `rrf` - Reciprocal Rank Fusion
 /// * `dbsf` - Distribution-Based Score Fusion
+/// * `min_max` - Weighted sum of min-max normalized scores
+/// * `z_score` - Weighted sum of z-score normalized scores
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Fusion {
     Rrf,
     Dbsf,
+    MinMax,
+    ZScore,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -467,6 +474,15 @@ pub enum Query {
 
     /// Sample points from the collection, non-deterministically.
     Sample(SampleQuery),
+
+    /// Score boosting via an arbitrary formula
+    Formula(FormulaQuery),
+
+    /// Diversify the prefetched points with Maximal Marginal Relevance
+    Mmr(MmrQuery),
+
+    /// Rank the points by BM25 relevance of a full-text indexed field to the text
+    Bm25(Bm25Query),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -503,6 +519,12 @@ pub struct OrderByQuery {
 #[serde(rename_all = "snake_case")]
 pub struct FusionQuery {
     pub fusion: Fusion,
+    /// Constant `k` of the Reciprocal Rank Fusion formula `1 / (k + rank)`. Only applicable to `rrf`. Default is 2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k: Option<usize>,
+    /// Weights of the prefetches, in the same order as the prefetches. By default, all prefetches have a weight of 1.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f32>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -511,6 +533,236 @@ pub struct SampleQuery {
     pub sample: Sample,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MmrQuery {
+    pub mmr: MmrInput,
+}
+
+/// Maximal Marginal Relevance parameters.
+///
+/// Points are selected one by one, balancing their similarity to the query vector
+/// against their similarity to the points selected so far.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct MmrInput {
+    /// Vector to measure the relevance against
+    #[validate(nested)]
+    pub vector: VectorInput,
+
+    /// Balance between relevance (1.0) and diversity (0.0) of the results. Default is 0.5
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub lambda: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Bm25Query {
+    pub bm25: Bm25Input,
+}
+
+/// BM25 text relevance parameters.
+///
+/// Only points with at least one of the query tokens are returned, best match first.
+///
+/// Corpus statistics, such as the token rarity, are collected per shard.
+/// Scores are therefore only comparable between points of the same shard, and the merged ranking
+/// of a multi-shard collection is approximate when the text is unevenly distributed across shards.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct Bm25Input {
+    /// Payload key with a full-text index
+    pub key: JsonPath,
+
+    /// Text to rank the points by
+    #[validate(length(min = 1))]
+    pub text: String,
+
+    /// Term frequency saturation. Default is 1.2
+    #[validate(range(min = 0.0))]
+    pub k1: Option<f32>,
+
+    /// Document length normalization, from 0.0 (none) to 1.0 (full). Default is 0.75
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub b: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FormulaQuery {
+    pub formula: Expression,
+
+    /// Default values for the variables used in the formula, when they are missing in a point.
+    /// Keys are payload keys, or `$score[<idx>]` for the score of a prefetch.
+    #[serde(default)]
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Expression {
+    Constant(f32),
+    /// Payload key, or reference to a prefetch score: `$score` or `$score[<idx>]`
+    Variable(String),
+    Mult(MultExpression),
+    Sum(SumExpression),
+    Neg(NegExpression),
+    Abs(AbsExpression),
+    Div(DivExpression),
+    Sqrt(SqrtExpression),
+    Pow(PowExpression),
+    Exp(ExpExpression),
+    Log10(Log10Expression),
+    Ln(LnExpression),
+    GeoDistance(GeoDistance),
+    Datetime(DatetimeExpression),
+    DatetimeKey(DatetimeKeyExpression),
+    LinDecay(LinDecayExpression),
+    ExpDecay(ExpDecayExpression),
+    GaussDecay(GaussDecayExpression),
+    /// Payload condition. If true, becomes 1.0; otherwise 0.0
+    Condition(Box<Condition>),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MultExpression {
+    pub mult: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SumExpression {
+    pub sum: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct NegExpression {
+    pub neg: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AbsExpression {
+    pub abs: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DivExpression {
+    pub div: DivParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DivParams {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Value to use when dividing by zero. If missing, division by zero is an error.
+    pub by_zero_default: Option<ScoreType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SqrtExpression {
+    pub sqrt: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PowExpression {
+    pub pow: PowParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PowParams {
+    pub base: Box<Expression>,
+    pub exponent: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExpExpression {
+    pub exp: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Log10Expression {
+    pub log10: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LnExpression {
+    pub ln: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoDistance {
+    pub geo_distance: GeoDistanceParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoDistanceParams {
+    /// Geo point to measure the distance from
+    pub origin: GeoPoint,
+    /// Payload key with the geo point to measure the distance to. Distance is in meters.
+    pub to: JsonPath,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeExpression {
+    /// Datetime constant in RFC 3339 format. Evaluated as seconds since UNIX epoch.
+    pub datetime: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DatetimeKeyExpression {
+    /// Payload key with a datetime value. Evaluated as seconds since UNIX epoch.
+    pub datetime_key: JsonPath,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LinDecayExpression {
+    pub lin_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExpDecayExpression {
+    pub exp_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GaussDecayExpression {
+    pub gauss_decay: DecayParamsExpression,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DecayParamsExpression {
+    /// The variable to decay
+    pub x: Box<Expression>,
+    /// The target value to start decaying from. Defaults to 0.
+    pub origin: Option<Box<Expression>>,
+    /// The distance from the origin (on top of the offset) at which the function equals `decay`.
+    /// Must be positive. Defaults to 1.0.
+    pub scale: Option<f32>,
+    /// Distance from the origin within which the function is 1.0. Must be non-negative. Defaults to 0.
+    pub offset: Option<f32>,
+    /// The value of the function at `scale` distance past the offset. Must be in (0, 1). Defaults to 0.5.
+    pub decay: Option<f32>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct Prefetch {
     /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
@@ -674,13 +926,35 @@ pub struct LookupLocation {
     pub shard_key: Option<ShardKeySelector>,
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum GroupByInterface {
+    Field(JsonPath),
+    /// Group by the combination of the values of several fields
+    Fields(Vec<JsonPath>),
+}
+
+impl From<GroupByInterface> for Vec<JsonPath> {
+    fn from(group_by: GroupByInterface) -> Self {
+        match group_by {
+            GroupByInterface::Field(field) => vec![field],
+            GroupByInterface::Fields(fields) => fields,
+        }
+    }
+}
+
 #[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct BaseGroupRequest {
     /// Payload field to group by, must be a string or number field.
     /// If the field contains more than 1 value, all values will be used for grouping.
     /// One point can be in multiple groups.
+    /// With several fields, groups are made of each combination of their values.
     #[schemars(length(min = 1))]
-    pub group_by: JsonPath,
+    #[validate(nested)]
+    pub group_by: GroupByInterface,
+
+    /// How to score the groups from the scores of their points. Default is `max`.
+    pub group_score: Option<GroupScore>,
 
     /// Maximum amount of points to return per group
     #[validate(range(min = 1))]
@@ -766,8 +1040,13 @@ pub struct QueryBaseGroupRequest {
     /// Payload field to group by, must be a string or number field.
     /// If the field contains more than 1 value, all values will be used for grouping.
     /// One point can be in multiple groups.
+    /// With several fields, groups are made of each combination of their values.
     #[schemars(length(min = 1))]
-    pub group_by: JsonPath,
+    #[validate(nested)]
+    pub group_by: GroupByInterface,
+
+    /// How to score the groups from the scores of their points. Default is `max`.
+    pub group_score: Option<GroupScore>,
 
     /// Maximum amount of points to return per group. Default is 3.
     #[validate(range(min = 1))]
@@ -912,6 +1191,28 @@ pub struct FacetRequestInternal {
 
     /// Whether to do a more expensive exact count for each of the values in the facet. Default is false.
     pub exact: Option<bool>,
+
+    /// Count points per geo-hash cell of a geo field, instead of per value.
+    #[validate(nested)]
+    pub geo_grid: Option<GeoGridParams>,
+
+    /// Count points per bucket of a numeric or datetime field, instead of per value.
+    pub histogram: Option<HistogramParams>,
+
+    /// Count combinations of the values of `key` and of these keys, instead of single values.
+    /// Values of the hits are then arrays, with one value per key. At most 3 keys.
+    #[validate(nested, length(max = 3))]
+    pub pivot: Option<Vec<FacetPivot>>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct FacetPivot {
+    /// Payload key of this level of the facet.
+    pub key: JsonPath,
+
+    /// Max number of values of this key, for each value of the previous keys. Default is 10.
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -929,12 +1230,103 @@ pub enum FacetValue {
     String(String),
     Integer(IntPayloadType),
     Bool(bool),
+    FloatRange(FacetValueRange<FloatPayloadType>),
+    DatetimeRange(FacetValueRange<DateTimePayloadType>),
+    /// Combination of values of a pivot facet, one per key
+    Tuple(Vec<FacetValue>),
+}
+
+/// Bucket of a histogram facet. `from` is inclusive, `to` is exclusive.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FacetValueRange<T> {
+    pub from: Option<T>,
+    pub to: Option<T>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct AggregateRequestInternal {
+    /// Payload key of an integer, float or datetime field to aggregate.
+    pub key: JsonPath,
+
+    /// Filter conditions - only aggregate the points that satisfy these conditions.
+    pub filter: Option<Filter>,
+
+    /// Percentiles to approximate, from 0 to 100.
+    #[validate(custom(function = "validate_percentiles"))]
+    pub percentiles: Option<Vec<FloatPayloadType>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct AggregateRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub aggregate_request: AggregateRequestInternal,
+
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AggregateValue {
+    Number(FloatPayloadType),
+    Datetime(DateTimePayloadType),
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PercentileValue {
+    /// Requested percentile, from 0 to 100
+    pub percentile: FloatPayloadType,
+    /// Approximate value at this percentile
+    pub value: AggregateValue,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AggregateResponse {
+    /// Number of aggregated values, each value of multi-valued fields is counted
+    pub count: usize,
+    /// Sum of the values, not defined for datetime fields
+    pub sum: Option<FloatPayloadType>,
+    pub min: Option<AggregateValue>,
+    pub max: Option<AggregateValue>,
+    pub avg: Option<AggregateValue>,
+    /// Approximate percentiles, in the requested order
+    pub percentiles: Vec<PercentileValue>,
+}
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct DistinctCountRequestInternal {
+    /// Payload key of a keyword, integer or uuid field to count the distinct values of.
+    pub key: JsonPath,
+
+    /// Filter conditions - only count the values of the points that satisfy these conditions.
+    pub filter: Option<Filter>,
+
+    /// Whether to count the values exactly instead of approximating, up to 100000 distinct values. Default is false.
+    pub exact: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct DistinctCountRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub distinct_count_request: DistinctCountRequestInternal,
+
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DistinctCountResponse {
+    /// Number of distinct values, approximate unless exact was requested
+    pub count: usize,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct FacetValueHit {
     pub value: FacetValue,
     pub count: usize,
+    /// Mean location of the points, only for geo grid facets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub centroid: Option<GeoPoint>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -5,8 +5,11 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::schema::BatchVectorStruct;
 use super::{
-    Batch, ContextInput, Fusion, OrderByInterface, PointVectors, Query, QueryInterface,
-    RecommendInput, Sample, VectorInput,
+    AbsExpression, Batch, ContextInput, DecayParamsExpression, DivExpression, ExpDecayExpression,
+    ExpExpression, Expression, Fusion, FusionQuery, GaussDecayExpression, GroupByInterface,
+    LinDecayExpression, LnExpression, Log10Expression, MultExpression, NegExpression,
+    OrderByInterface, PointVectors, PowExpression, Query, QueryInterface, RecommendInput, Sample,
+    SqrtExpression, SumExpression, VectorInput,
 };
 use crate::rest::NamedVectorStruct;
 
@@ -36,9 +39,58 @@ impl Validate for Query {
             Query::Recommend(recommend) => recommend.recommend.validate(),
             Query::Discover(discover) => discover.discover.validate(),
             Query::Context(context) => context.context.validate(),
-            Query::Fusion(fusion) => fusion.fusion.validate(),
+            Query::Fusion(fusion) => fusion.validate(),
             Query::OrderBy(order_by) => order_by.order_by.validate(),
             Query::Sample(sample) => sample.sample.validate(),
+            Query::Formula(formula) => formula.formula.validate(),
+            Query::Mmr(mmr) => mmr.mmr.validate(),
+            Query::Bm25(bm25) => bm25.bm25.validate(),
+        }
+    }
+}
+
+impl Validate for Expression {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Expression::Constant(_) => Ok(()),
+            Expression::Variable(_) => Ok(()), // validated during parsing
+            Expression::Datetime(_) => Ok(()), // validated during parsing
+            Expression::DatetimeKey(_) => Ok(()),
+            Expression::GeoDistance(_) => Ok(()),
+            Expression::Condition(condition) => condition.validate(),
+            Expression::Mult(MultExpression { mult: expressions })
+            | Expression::Sum(SumExpression { sum: expressions }) => {
+                expressions.iter().try_for_each(Validate::validate)
+            }
+            Expression::Neg(NegExpression { neg: expression })
+            | Expression::Abs(AbsExpression { abs: expression })
+            | Expression::Sqrt(SqrtExpression { sqrt: expression })
+            | Expression::Exp(ExpExpression { exp: expression })
+            | Expression::Log10(Log10Expression { log10: expression })
+            | Expression::Ln(LnExpression { ln: expression }) => expression.validate(),
+            Expression::Div(DivExpression { div }) => {
+                div.left.validate()?;
+                div.right.validate()
+            }
+            Expression::Pow(PowExpression { pow }) => {
+                pow.base.validate()?;
+                pow.exponent.validate()
+            }
+            Expression::LinDecay(LinDecayExpression { lin_decay: params })
+            | Expression::ExpDecay(ExpDecayExpression { exp_decay: params })
+            | Expression::GaussDecay(GaussDecayExpression {
+                gauss_decay: params,
+            }) => {
+                let DecayParamsExpression {
+                    x,
+                    origin,
+                    scale: _,
+                    offset: _,
+                    decay: _,
+                } = params;
+                x.validate()?;
+                origin.as_deref().map_or(Ok(()), Validate::validate)
+            }
         }
     }
 }
@@ -94,7 +146,49 @@ impl Validate for ContextInput {
 impl Validate for Fusion {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
-            Fusion::Rrf | Fusion::Dbsf => Ok(()),
+            Fusion::Rrf | Fusion::Dbsf | Fusion::MinMax | Fusion::ZScore => Ok(()),
+        }
+    }
+}
+
+impl Validate for FusionQuery {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        let FusionQuery { fusion, k, weights } = self;
+
+        fusion.validate()?;
+
+        let mut errors = validator::ValidationErrors::new();
+
+        match (fusion, k) {
+            (_, None) => {}
+            (Fusion::Rrf, Some(k)) => {
+                if *k == 0 {
+                    errors.add("k", ValidationError::new("k must be greater than 0"));
+                }
+            }
+            (Fusion::Dbsf | Fusion::MinMax | Fusion::ZScore, Some(_)) => {
+                errors.add(
+                    "k",
+                    ValidationError::new("k is only applicable to rrf fusion"),
+                );
+            }
+        }
+
+        let invalid_weight = weights
+            .iter()
+            .flatten()
+            .any(|weight| !weight.is_finite() || *weight < 0.0);
+        if invalid_weight {
+            errors.add(
+                "weights",
+                ValidationError::new("weights must be finite non-negative numbers"),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 }
@@ -108,6 +202,23 @@ impl Validate for OrderByInterface {
     }
 }
 
+impl Validate for GroupByInterface {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            GroupByInterface::Field(_) => Ok(()), // validated during parsing
+            GroupByInterface::Fields(fields) if fields.is_empty() => {
+                let mut errors = ValidationErrors::new();
+                errors.add(
+                    "group_by",
+                    ValidationError::new("At least one field to group by is required"),
+                );
+                Err(errors)
+            }
+            GroupByInterface::Fields(_) => Ok(()),
+        }
+    }
+}
+
 impl Validate for Sample {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::FuturesUnordered;
+use futures::TryStreamExt;
+use segment::data_types::aggregations::{
+    AggregateParams, AggregateResponse, DistinctCountParams, DistinctCountResponse,
+    PartialAggregate, PartialDistinctCount,
+};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+
+impl Collection {
+    /// Compute the statistics of a numeric or datetime field over the selected shards.
+    pub async fn aggregate(
+        &self,
+        request: AggregateParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<AggregateResponse> {
+        let percentiles = request.percentiles.clone();
+
+        let aggregate = self
+            .aggregate_partial(request, shard_selection, read_consistency, timeout)
+            .await?;
+
+        Ok(aggregate.finalize(&percentiles))
+    }
+
+    /// Merge the aggregates of the selected shards, before computing the statistics.
+    ///
+    /// Used directly for internal requests to a single shard, as the requesting peer merges
+    /// the aggregates of all shards.
+    pub async fn aggregate_partial(
+        &self,
+        request: AggregateParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        let request = Arc::new(request);
+
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(&shard_selection)?;
+
+        let mut shards_reads_f = target_shards
+            .iter()
+            .map(|(shard, _shard_key)| {
+                shard.aggregate(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut merged = PartialAggregate::default();
+        while let Some(aggregate) = shards_reads_f.try_next().await? {
+            merged.merge(aggregate);
+        }
+
+        Ok(merged)
+    }
+
+    /// Count the distinct values of a keyword, integer or uuid field over the selected shards.
+    pub async fn distinct_count(
+        &self,
+        request: DistinctCountParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<DistinctCountResponse> {
+        let distinct = self
+            .distinct_count_partial(request, shard_selection, read_consistency, timeout)
+            .await?;
+
+        Ok(DistinctCountResponse {
+            count: distinct.count(),
+        })
+    }
+
+    /// Merge the distinct values or sketches of the selected shards, before counting them.
+    pub async fn distinct_count_partial(
+        &self,
+        request: DistinctCountParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        let request = Arc::new(request);
+
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(&shard_selection)?;
+
+        let mut shards_reads_f = target_shards
+            .iter()
+            .map(|(shard, _shard_key)| {
+                shard.distinct_count(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                )
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut merged = PartialDistinctCount::default();
+        while let Some(distinct) = shards_reads_f.try_next().await? {
+            merged.merge(distinct);
+        }
+
+        Ok(merged)
+    }
+}
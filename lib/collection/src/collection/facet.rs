@@ -5,12 +5,12 @@ use std::time::Duration;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt;
 use itertools::Itertools;
-use segment::data_types::facets::{FacetParams, FacetResponse, FacetValueHit};
+use segment::data_types::facets::{top_pivot_hits, FacetCount, FacetParams, FacetResponse};
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionError, CollectionResult};
 
 impl Collection {
     pub async fn facet(
@@ -24,6 +24,25 @@ impl Collection {
             return Ok(FacetResponse { hits: vec![] });
         }
 
+        if request.geo_grid.is_some() && request.histogram.is_some() {
+            return Err(CollectionError::bad_request(
+                "Only one of `geo_grid` and `histogram` can be set",
+            ));
+        }
+
+        if !request.pivot.is_empty() && (request.geo_grid.is_some() || request.histogram.is_some())
+        {
+            return Err(CollectionError::bad_request(
+                "Pivot facets can't be combined with `geo_grid` or `histogram`",
+            ));
+        }
+
+        if request.exact && request.geo_grid.is_some() {
+            return Err(CollectionError::bad_request(
+                "Exact counts are not supported for geo grid facets",
+            ));
+        }
+
         let request = Arc::new(request);
 
         let shard_holder = self.shards_holder.read().await;
@@ -41,18 +60,30 @@ impl Collection {
             })
             .collect::<FuturesUnordered<_>>();
 
-        let mut aggregated_results = HashMap::new();
+        let mut aggregated_results: HashMap<_, FacetCount> = HashMap::new();
         while let Some(response) = shards_reads_f.try_next().await? {
             for hit in response.hits {
-                *aggregated_results.entry(hit.value).or_insert(0) += hit.count;
+                // Centroids of the same cell in different shards are weighted by their counts
+                aggregated_results
+                    .entry(hit.value)
+                    .or_default()
+                    .merge(FacetCount {
+                        count: hit.count,
+                        centroid: hit.centroid,
+                    });
             }
         }
 
         let hits = aggregated_results
             .into_iter()
-            .map(|(value, count)| FacetValueHit { value, count })
-            .k_largest(request.limit)
-            .collect();
+            .map(|(value, count)| count.into_hit(value));
+
+        let hits = if request.pivot.is_empty() {
+            hits.k_largest(request.limit).collect()
+        } else {
+            // Each level is limited within the values of the previous ones
+            top_pivot_hits(hits.collect(), &request.limits())
+        };
 
         Ok(FacetResponse { hits })
     }
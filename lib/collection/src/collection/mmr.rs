@@ -0,0 +1,279 @@
+//! Maximal Marginal Relevance (MMR) diversification of the query results.
+
+use common::types::ScoreType;
+use itertools::Itertools;
+use segment::data_types::vectors::{
+    VectorElementType, VectorInternal, VectorRef, VectorStructInternal,
+};
+use segment::types::{Distance, ScoredPoint, WithVector};
+
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::shard_query::MmrInternal;
+
+/// Candidate for the MMR selection
+struct MmrCandidate<'a> {
+    /// Position in the original list of candidates
+    idx: usize,
+    vector: &'a [VectorElementType],
+    /// Similarity to the query vector
+    relevance: ScoreType,
+    /// Max similarity to the already selected points, `None` if nothing is selected yet
+    max_similarity: Option<ScoreType>,
+}
+
+impl MmrCandidate<'_> {
+    fn mmr_score(&self, lambda: ScoreType) -> ScoreType {
+        match self.max_similarity {
+            Some(max_similarity) => lambda * self.relevance - (1.0 - lambda) * max_similarity,
+            None => self.relevance,
+        }
+    }
+}
+
+/// Selects up to `limit` points from the candidates, one by one, maximizing
+///
+/// `lambda * sim(query, point) - (1 - lambda) * max(sim(point, selected))`
+///
+/// Candidates must contain the vector used by the MMR query, the ones without it are skipped.
+/// Selected points are returned in order of selection, with their original scores.
+pub fn mmr_from_points_with_vector(
+    candidates: Vec<ScoredPoint>,
+    mmr: &MmrInternal,
+    distance: Distance,
+    limit: usize,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    let query_vector = match &mmr.vector {
+        VectorInternal::Dense(vector) => distance.preprocess_vector(vector.clone()),
+        VectorInternal::Sparse(_) | VectorInternal::MultiDense(_) => {
+            return Err(not_dense_error(&mmr.using))
+        }
+    };
+
+    let candidate_vectors: Vec<_> = candidates
+        .iter()
+        .map(|point| {
+            let vector = point
+                .vector
+                .as_ref()
+                .and_then(|vector| vector.get(&mmr.using));
+            match vector {
+                Some(VectorRef::Dense(vector)) => Ok(Some(vector)),
+                Some(VectorRef::Sparse(_) | VectorRef::MultiDense(_)) => {
+                    Err(not_dense_error(&mmr.using))
+                }
+                None => Ok(None),
+            }
+        })
+        .try_collect()?;
+
+    let selected = mmr_selection(
+        &candidate_vectors,
+        &query_vector,
+        distance,
+        mmr.lambda,
+        limit,
+    );
+
+    let mut candidates: Vec<_> = candidates.into_iter().map(Some).collect();
+    Ok(selected
+        .into_iter()
+        .filter_map(|idx| candidates[idx].take())
+        .collect())
+}
+
+/// Returns the positions of the selected candidates, in order of selection
+fn mmr_selection(
+    candidate_vectors: &[Option<&[VectorElementType]>],
+    query_vector: &[VectorElementType],
+    distance: Distance,
+    lambda: ScoreType,
+    limit: usize,
+) -> Vec<usize> {
+    let mut remaining: Vec<_> = candidate_vectors
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, vector)| {
+            vector.map(|vector| MmrCandidate {
+                idx,
+                vector,
+                relevance: distance.similarity(query_vector, vector),
+                max_similarity: None,
+            })
+        })
+        .collect();
+
+    let mut selected = Vec::with_capacity(limit.min(remaining.len()));
+
+    while selected.len() < limit {
+        // `max_by` returns the last of equal elements, iterate in reverse to prefer the earlier candidates
+        let best = remaining
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.mmr_score(lambda).total_cmp(&b.mmr_score(lambda)))
+            .map(|(pos, _)| pos);
+
+        let Some(best) = best else {
+            break;
+        };
+
+        let chosen = remaining.remove(best);
+
+        for candidate in &mut remaining {
+            let similarity = distance.similarity(chosen.vector, candidate.vector);
+            candidate.max_similarity = Some(
+                candidate
+                    .max_similarity
+                    .map_or(similarity, |max_similarity| max_similarity.max(similarity)),
+            );
+        }
+
+        selected.push(chosen.idx);
+    }
+
+    selected
+}
+
+fn not_dense_error(using: &str) -> CollectionError {
+    CollectionError::bad_request(format!(
+        "MMR is only supported for dense vectors, but vector {using:?} is not dense",
+    ))
+}
+
+/// Extends the requested vectors with the one needed for MMR
+pub fn with_mmr_vector(with_vector: WithVector, using: String) -> WithVector {
+    match with_vector {
+        WithVector::Bool(true) => WithVector::Bool(true),
+        WithVector::Bool(false) => WithVector::Selector(vec![using]),
+        WithVector::Selector(mut names) => {
+            if !names.contains(&using) {
+                names.push(using);
+            }
+            WithVector::Selector(names)
+        }
+    }
+}
+
+/// Removes the vector fetched for MMR from the points, if it was not requested
+pub fn strip_mmr_vector(points: &mut [ScoredPoint], with_vector: &WithVector, using: &str) {
+    match with_vector {
+        WithVector::Bool(true) => {}
+        WithVector::Bool(false) => points.iter_mut().for_each(|point| point.vector = None),
+        WithVector::Selector(names) => {
+            if names.iter().any(|name| name == using) {
+                return;
+            }
+            for point in points {
+                match &mut point.vector {
+                    Some(VectorStructInternal::Named(vectors)) => {
+                        vectors.remove(using);
+                    }
+                    // Only the MMR vector was fetched
+                    Some(VectorStructInternal::Single(_) | VectorStructInternal::MultiDense(_)) => {
+                        point.vector = None;
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use segment::types::PointIdType;
+
+    use super::*;
+
+    fn make_point(id: u64, score: ScoreType, vector: Vec<VectorElementType>) -> ScoredPoint {
+        ScoredPoint {
+            id: PointIdType::NumId(id),
+            version: 0,
+            score,
+            payload: None,
+            vector: Some(VectorStructInternal::Named(HashMap::from([(
+                "text".to_string(),
+                VectorInternal::Dense(vector),
+            )]))),
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    fn make_mmr(lambda: f32) -> MmrInternal {
+        MmrInternal {
+            vector: VectorInternal::Dense(vec![1.0, 0.0]),
+            using: "text".to_string(),
+            lambda,
+        }
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<PointIdType> {
+        points.iter().map(|point| point.id).collect()
+    }
+
+    #[test]
+    fn test_mmr_diversifies_results() {
+        // Points 1 and 2 are near-duplicates, point 3 is less relevant but different
+        let candidates = vec![
+            make_point(1, 0.9, vec![0.9, 0.1]),
+            make_point(2, 0.89, vec![0.89, 0.11]),
+            make_point(3, 0.6, vec![0.6, -0.6]),
+        ];
+
+        // Pure relevance keeps the original order
+        let selected =
+            mmr_from_points_with_vector(candidates.clone(), &make_mmr(1.0), Distance::Dot, 3)
+                .unwrap();
+        assert_eq!(ids(&selected), ids(&candidates));
+
+        // Diversity pushes the near-duplicate down
+        let selected =
+            mmr_from_points_with_vector(candidates.clone(), &make_mmr(0.5), Distance::Dot, 2)
+                .unwrap();
+        assert_eq!(
+            ids(&selected),
+            vec![PointIdType::NumId(1), PointIdType::NumId(3)],
+        );
+
+        // Scores are not changed
+        assert_eq!(selected[1].score, 0.6);
+    }
+
+    #[test]
+    fn test_mmr_skips_points_without_vector() {
+        let mut without_vector = make_point(2, 0.9, vec![1.0, 0.0]);
+        without_vector.vector = None;
+
+        let candidates = vec![make_point(1, 1.0, vec![1.0, 0.0]), without_vector];
+
+        let selected =
+            mmr_from_points_with_vector(candidates, &make_mmr(0.5), Distance::Cosine, 10).unwrap();
+        assert_eq!(ids(&selected), vec![PointIdType::NumId(1)]);
+    }
+
+    #[test]
+    fn test_strip_mmr_vector() {
+        let with_vector = with_mmr_vector(WithVector::Bool(false), "text".to_string());
+        assert_eq!(with_vector, WithVector::Selector(vec!["text".to_string()]));
+
+        let mut points = vec![make_point(1, 1.0, vec![1.0, 0.0])];
+        strip_mmr_vector(&mut points, &WithVector::Bool(true), "text");
+        assert!(points[0].vector.is_some());
+
+        strip_mmr_vector(
+            &mut points,
+            &WithVector::Selector(vec!["image".to_string()]),
+            "text",
+        );
+        assert_eq!(
+            points[0].vector,
+            Some(VectorStructInternal::Named(HashMap::new())),
+        );
+
+        strip_mmr_vector(&mut points, &WithVector::Bool(false), "text");
+        assert!(points[0].vector.is_none());
+    }
+}
@@ -1,6 +1,8 @@
+mod aggregate;
 mod collection_ops;
 pub mod distance_matrix;
 mod facet;
+pub mod mmr;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
@@ -6,7 +6,7 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::OrderBy;
 use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
 
 use super::Collection;
@@ -256,7 +256,7 @@ impl Collection {
 
         // Validate user did not try to use an id offset with order_by
         if order_by.is_some() && id_offset.is_some() {
-            return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use `order_by.start_from` and a filter to exclude the IDs that you've already seen for the `order_by.start_from` value".to_string()));
+            return Err(CollectionError::bad_input("Cannot use an `offset` when using `order_by`. The alternative for paging is to use `order_by.start_after` with the `order_value` and `id` of the last point of the previous page".to_string()));
         };
 
         if limit == 0 {
@@ -323,11 +323,11 @@ impl Collection {
                         records.into_iter().map(|mut record| {
                             let value;
                             if local_only {
-                                value = record.order_value.unwrap_or_else(|| {
+                                value = record.order_value.clone().unwrap_or_else(|| {
                                     order_by.get_order_value_from_payload(record.payload.as_ref())
                                 });
                             } else {
-                                value = if let Some(order_value) = record.order_value {
+                                value = if let Some(order_value) = record.order_value.clone() {
                                     order_by
                                         .remove_order_value_from_payload(record.payload.as_mut());
                                     order_value
@@ -345,10 +345,10 @@ impl Collection {
                     })
                     // Get top results
                     .kmerge_by(|(value_a, record_a), (value_b, record_b)| {
-                        match order_by.direction() {
-                            Direction::Asc => (value_a, record_a.id) < (value_b, record_b.id),
-                            Direction::Desc => (value_a, record_a.id) > (value_b, record_b.id),
-                        }
+                        order_by
+                            .cmp_values(value_a, value_b)
+                            .then_with(|| order_by.cmp_ids(&record_a.id, &record_b.id))
+                            .is_lt()
                     })
                     // Only keep the point with the most "valuable" order value
                     .dedup_by(|(_, record_a), (_, record_b)| record_a.id == record_b.id)
@@ -9,11 +9,12 @@ use itertools::{Either, Itertools};
 use rand::Rng;
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::common::score_fusion::{score_fusion, ScoreFusion};
-use segment::types::{Order, ScoredPoint};
+use segment::types::{Order, ScoredPoint, WithVector};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::sync::RwLockReadGuard;
 use tokio::time::Instant;
 
+use super::mmr::{mmr_from_points_with_vector, strip_mmr_vector};
 use super::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
@@ -21,6 +22,7 @@ use crate::common::fetch_vectors::{
 };
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::common::transpose_iterator::transposed_iter;
+use crate::config::CollectionParams;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -122,6 +124,8 @@ impl Collection {
             )
             .await?;
 
+        let collection_params = self.collection_config.read().await.params.clone();
+
         let results_f = transposed_iter(all_shards_results)
             .zip(requests_batch.iter())
             .map(|(shards_results, request)| async {
@@ -136,6 +140,8 @@ impl Collection {
                     request.limit,
                     request.offset,
                     request.score_threshold,
+                    &request.with_vector,
+                    &collection_params,
                 )?;
 
                 let filter_refs = request.filter_refs();
@@ -154,13 +160,35 @@ impl Collection {
         limit: usize,
         offset: usize,
         score_threshold: Option<ScoreType>,
+        with_vector: &WithVector,
+        collection_params: &CollectionParams,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         let result = match query {
             Some(ScoringQuery::Fusion(fusion)) => {
                 // If the root query is a Fusion, the returned results correspond to each the prefetches.
                 let mut fused = match fusion {
-                    FusionInternal::Rrf => rrf_scoring(intermediates),
-                    FusionInternal::Dbsf => score_fusion(intermediates, ScoreFusion::dbsf()),
+                    FusionInternal::Rrf { k, weights } => rrf_scoring(intermediates, *k, weights),
+                    FusionInternal::Dbsf { weights } => score_fusion(
+                        intermediates,
+                        ScoreFusion {
+                            weights: weights.clone(),
+                            ..ScoreFusion::dbsf()
+                        },
+                    ),
+                    FusionInternal::MinMax { weights } => score_fusion(
+                        intermediates,
+                        ScoreFusion {
+                            weights: weights.clone(),
+                            ..ScoreFusion::min_max()
+                        },
+                    ),
+                    FusionInternal::ZScore { weights } => score_fusion(
+                        intermediates,
+                        ScoreFusion {
+                            weights: weights.clone(),
+                            ..ScoreFusion::z_score()
+                        },
+                    ),
                 };
                 if let Some(score_threshold) = score_threshold {
                     fused = fused
@@ -170,6 +198,20 @@ impl Collection {
                 }
                 fused
             }
+            Some(ScoringQuery::Mmr(mmr)) => {
+                // MMR receives all the candidates, and selects the diverse subset of them.
+                debug_assert_eq!(intermediates.len(), 1);
+                let candidates = intermediates.pop().ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Query response was expected to have one list of results.",
+                    )
+                })?;
+                let distance = collection_params.get_distance(&mmr.using)?;
+                let mut selected =
+                    mmr_from_points_with_vector(candidates, mmr, distance, offset + limit)?;
+                strip_mmr_vector(&mut selected, with_vector, &mmr.using);
+                selected
+            }
             _ => {
                 // Otherwise, it will be a list with a single list of scored points.
                 debug_assert_eq!(intermediates.len(), 1);
@@ -350,7 +392,25 @@ impl Collection {
             // if the `kmerge_by` function were able to work with reference predicates.
             // Either::Left and Either::Right are used to allow type inference to work.
             //
-            let intermediate_result = if let Some(order) = order {
+            let intermediate_result = if let Some(ScoringQuery::OrderBy(order_by)) =
+                query_info.scoring_query
+            {
+                // Keys can have different directions, so `order_by` compares the values itself
+                shards_results
+                    .into_iter()
+                    .kmerge_by(|a, b| {
+                        let values_ordering = match (&a.order_value, &b.order_value) {
+                            (Some(value_a), Some(value_b)) => order_by.cmp_values(value_a, value_b),
+                            _ => std::cmp::Ordering::Equal,
+                        };
+                        values_ordering
+                            .then_with(|| order_by.cmp_ids(&a.id, &b.id))
+                            .is_lt()
+                    })
+                    .dedup()
+                    .take(query_info.take)
+                    .collect()
+            } else if let Some(order) = order {
                 match order {
                     Order::LargeBetter => Either::Left(
                         shards_results
@@ -406,9 +466,14 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
             .collect_vec()
     } else {
         // Otherwise, we expect the root result
+        let take = match request.query {
+            // MMR is applied after merging, so it needs all the candidates from the shards
+            Some(ScoringQuery::Mmr(_)) => usize::MAX,
+            _ => request.offset + request.limit,
+        };
         vec![IntermediateQueryInfo {
             scoring_query: request.query.as_ref(),
-            take: request.offset + request.limit,
+            take,
         }]
     }
 }
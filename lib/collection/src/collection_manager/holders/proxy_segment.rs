@@ -9,14 +9,18 @@ use common::tar_ext;
 use common::types::{PointOffsetType, TelemetryDetail};
 use itertools::Itertools;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use segment::common::hyperloglog::HyperLogLog;
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
-use segment::data_types::facets::{FacetParams, FacetValue};
+use segment::data_types::aggregations::{AggregateParams, DistinctCountParams, PartialAggregate};
+use segment::data_types::bm25::{Bm25Query, Bm25Stats};
+use segment::data_types::facets::{FacetCount, FacetParams, FacetValue};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
 use segment::data_types::vectors::{QueryVector, VectorInternal};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::{CardinalityEstimation, FieldIndex};
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
@@ -735,7 +739,7 @@ impl SegmentEntry for ProxySegment {
             .read()
             .read_ordered_filtered(limit, filter, order_by, is_stopped)?;
         read_points.append(&mut write_segment_points);
-        read_points.sort_unstable();
+        read_points.sort_unstable_by(|a, b| order_by.cmp_entries(a, b));
         read_points.dedup();
         Ok(read_points)
     }
@@ -814,7 +818,7 @@ impl SegmentEntry for ProxySegment {
         &self,
         request: &FacetParams,
         is_stopped: &AtomicBool,
-    ) -> OperationResult<HashMap<FacetValue, usize>> {
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>> {
         let deleted_points = self.deleted_points.read();
         let mut hits = if deleted_points.is_empty() {
             self.wrapped_segment
@@ -841,12 +845,183 @@ impl SegmentEntry for ProxySegment {
         write_segment_hits
             .into_iter()
             .for_each(|(facet_value, count)| {
-                *hits.entry(facet_value).or_insert(0) += count;
+                hits.entry(facet_value).or_default().merge(count);
             });
 
         Ok(hits)
     }
 
+    fn aggregate(
+        &self,
+        request: &AggregateParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<PartialAggregate> {
+        let deleted_points = self.deleted_points.read();
+        let mut aggregate = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .aggregate(request, is_stopped)?
+        } else {
+            let wrapped_filter = Self::add_deleted_points_condition_to_filter(
+                request.filter.as_ref(),
+                deleted_points.keys().copied(),
+            );
+            let new_request = AggregateParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .aggregate(&new_request, is_stopped)?
+        };
+
+        aggregate.merge(
+            self.write_segment
+                .get()
+                .read()
+                .aggregate(request, is_stopped)?,
+        );
+
+        Ok(aggregate)
+    }
+
+    fn distinct_count(
+        &self,
+        request: &DistinctCountParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HyperLogLog> {
+        let deleted_points = self.deleted_points.read();
+        let mut sketch = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct_count(request, is_stopped)?
+        } else {
+            let wrapped_filter = Self::add_deleted_points_condition_to_filter(
+                request.filter.as_ref(),
+                deleted_points.keys().copied(),
+            );
+            let new_request = DistinctCountParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct_count(&new_request, is_stopped)?
+        };
+
+        sketch.merge(
+            self.write_segment
+                .get()
+                .read()
+                .distinct_count(request, is_stopped)?,
+        );
+
+        Ok(sketch)
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_results = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().rescore_with_formula(
+                formula,
+                prefetches_results,
+                limit,
+                is_stopped,
+            )?
+        } else {
+            // Points deleted in the proxy must not be rescored from the wrapped segment
+            let wrapped_prefetches_results = prefetches_results
+                .iter()
+                .map(|scored_points| {
+                    scored_points
+                        .iter()
+                        .filter(|scored_point| !deleted_points.contains_key(&scored_point.id))
+                        .cloned()
+                        .collect_vec()
+                })
+                .collect_vec();
+            self.wrapped_segment.get().read().rescore_with_formula(
+                formula,
+                &wrapped_prefetches_results,
+                limit,
+                is_stopped,
+            )?
+        };
+
+        let write_segment_results = self.write_segment.get().read().rescore_with_formula(
+            formula,
+            prefetches_results,
+            limit,
+            is_stopped,
+        )?;
+
+        let mut results = wrapped_results;
+        results.extend(write_segment_results);
+        results.sort_unstable_by(|a, b| b.cmp(a));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
+    fn bm25_stats(&self, query: &Bm25Query) -> OperationResult<Bm25Stats> {
+        // Points moved to the write segment are counted twice, which is negligible for the statistics
+        let mut stats = self.wrapped_segment.get().read().bm25_stats(query)?;
+        stats.merge(self.write_segment.get().read().bm25_stats(query)?);
+        Ok(stats)
+    }
+
+    fn search_bm25(
+        &self,
+        query: &Bm25Query,
+        stats: &Bm25Stats,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+        let wrapped_results = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .search_bm25(query, stats, filter, limit, is_stopped)?
+        } else {
+            let wrapped_filter = Self::add_deleted_points_condition_to_filter(
+                filter,
+                deleted_points.keys().copied(),
+            );
+            self.wrapped_segment.get().read().search_bm25(
+                query,
+                stats,
+                Some(&wrapped_filter),
+                limit,
+                is_stopped,
+            )?
+        };
+
+        let write_segment_results = self
+            .write_segment
+            .get()
+            .read()
+            .search_bm25(query, stats, filter, limit, is_stopped)?;
+
+        let mut results = wrapped_results;
+        results.extend(write_segment_results);
+        results.sort_unstable_by(|a, b| b.cmp(a));
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
     fn has_point(&self, point_id: PointIdType) -> bool {
         return if self.deleted_points.read().contains_key(&point_id) {
             self.write_segment.get().read().has_point(point_id)
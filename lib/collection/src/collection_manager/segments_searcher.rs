@@ -10,9 +10,11 @@ use futures::{FutureExt, TryStreamExt};
 use itertools::Itertools;
 use ordered_float::Float;
 use segment::common::operation_error::OperationError;
+use segment::data_types::bm25::{Bm25Query, Bm25Stats};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
 use segment::data_types::vectors::{QueryVector, VectorStructInternal};
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{
     Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
     WithPayload, WithPayloadInterface, WithVector,
@@ -493,6 +495,106 @@ impl SegmentsSearcher {
             })
             .await?
     }
+
+    /// Rescore the results of the prefetches with a formula, in all segments
+    ///
+    /// Returns the best `limit` points, without payload or vectors.
+    pub async fn rescore_with_formula(
+        segments: LockedSegmentHolder,
+        formula: ParsedFormula,
+        prefetches_results: Vec<Vec<ScoredPoint>>,
+        limit: usize,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let stopping_guard = StoppingGuard::new();
+        runtime_handle
+            .spawn_blocking(move || {
+                let is_stopped = stopping_guard.get_is_stopped();
+                let segments = segments.read();
+
+                let segments_results = segments
+                    .non_appendable_then_appendable_segments()
+                    .map(|segment| {
+                        segment.get().read().rescore_with_formula(
+                            &formula,
+                            &prefetches_results,
+                            limit,
+                            &is_stopped,
+                        )
+                    })
+                    // Wrap into a single-request batch, as expected by the aggregator
+                    .map_ok(|segment_result| vec![segment_result])
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+                aggregator.update_point_versions(&segments_results);
+                for segment_result in segments_results {
+                    aggregator.update_batch_results(0, segment_result.into_iter().flatten());
+                }
+
+                let top = aggregator
+                    .into_topk()
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                Ok(top)
+            })
+            .await?
+    }
+
+    /// Rank the points by BM25 relevance to the query text, in all segments
+    ///
+    /// Statistics are collected from all segments first, so that the scores are comparable across them.
+    /// They only cover this shard, the scores of different shards use different statistics.
+    /// Returns the best `limit` points, without payload or vectors.
+    pub async fn search_bm25(
+        segments: LockedSegmentHolder,
+        query: Bm25Query,
+        filter: Option<Filter>,
+        limit: usize,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let stopping_guard = StoppingGuard::new();
+        runtime_handle
+            .spawn_blocking(move || {
+                let is_stopped = stopping_guard.get_is_stopped();
+                let segments = segments.read();
+
+                let mut stats = Bm25Stats::default();
+                for segment in segments.non_appendable_then_appendable_segments() {
+                    stats.merge(segment.get().read().bm25_stats(&query)?);
+                }
+
+                let segments_results = segments
+                    .non_appendable_then_appendable_segments()
+                    .map(|segment| {
+                        segment.get().read().search_bm25(
+                            &query,
+                            &stats,
+                            filter.as_ref(),
+                            limit,
+                            &is_stopped,
+                        )
+                    })
+                    // Wrap into a single-request batch, as expected by the aggregator
+                    .map_ok(|segment_result| vec![segment_result])
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+                aggregator.update_point_versions(&segments_results);
+                for segment_result in segments_results {
+                    aggregator.update_batch_results(0, segment_result.into_iter().flatten());
+                }
+
+                let top = aggregator
+                    .into_topk()
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                Ok(top)
+            })
+            .await?
+    }
 }
 
 #[derive(PartialEq, Default, Debug)]
@@ -2,8 +2,9 @@ use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 
+use common::types::ScoreType;
 use itertools::Itertools;
-use segment::data_types::groups::GroupId;
+use segment::data_types::groups::{GroupId, GroupScore};
 use segment::json_path::JsonPath;
 use segment::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use segment::types::{ExtendedPointId, Order, PayloadContainer, PointIdType, ScoredPoint};
@@ -15,20 +16,23 @@ type Hits = HashMap<PointIdType, ScoredPoint>;
 pub(super) struct GroupsAggregator {
     groups: HashMap<GroupId, Hits>,
     max_group_size: usize,
-    grouped_by: JsonPath,
+    /// Fields to group by, groups of several fields have composite keys
+    grouped_by: Vec<JsonPath>,
     max_groups: usize,
     full_groups: HashSet<GroupId>,
     group_best_scores: HashMap<GroupId, ScoredPoint>,
     all_ids: HashSet<ExtendedPointId>,
     order: Option<Order>,
+    group_score: GroupScore,
 }
 
 impl GroupsAggregator {
     pub(super) fn new(
         groups: usize,
         group_size: usize,
-        grouped_by: JsonPath,
+        grouped_by: Vec<JsonPath>,
         order: Option<Order>,
+        group_score: GroupScore,
     ) -> Self {
         Self {
             groups: HashMap::with_capacity(groups),
@@ -39,33 +43,43 @@ impl GroupsAggregator {
             group_best_scores: HashMap::with_capacity(groups),
             all_ids: HashSet::with_capacity(groups * group_size),
             order,
+            group_score,
         }
     }
 
     /// Adds a point to the group that corresponds based on the group_by field, assumes that the point has the group_by field
     fn add_point(&mut self, point: &ScoredPoint) -> Result<(), AggregatorError> {
-        // extract all values from the group_by field
-        let payload_values: Vec<_> = point
-            .payload
-            .as_ref()
-            .map(|p| {
-                p.get_value(&self.grouped_by)
+        let payload = point.payload.as_ref().ok_or(AggregatorError::KeyNotFound)?;
+
+        // extract all unique values from each group_by field
+        let keys_per_field = self
+            .grouped_by
+            .iter()
+            .map(|field| {
+                payload
+                    .get_value(field)
                     .into_iter()
                     .flat_map(|v| match v {
                         Value::Array(arr) => arr.iter().collect(),
                         _ => vec![v],
                     })
-                    .collect()
+                    .map(GroupId::try_from)
+                    .collect::<Result<Vec<GroupId>, ()>>()
+                    .map(|keys| keys.into_iter().unique().collect_vec())
             })
-            .ok_or(AggregatorError::KeyNotFound)?;
-
-        let group_keys = payload_values
-            .into_iter()
-            .map(GroupId::try_from)
-            .collect::<Result<Vec<GroupId>, ()>>()
+            .collect::<Result<Vec<_>, ()>>()
             .map_err(|_| AggregatorError::BadKeyType)?;
 
-        let unique_group_keys: Vec<_> = group_keys.into_iter().unique().collect();
+        // with several fields, the point is in the group of each combination of their values
+        let unique_group_keys: Vec<_> = if self.grouped_by.len() == 1 {
+            keys_per_field.into_iter().flatten().collect()
+        } else {
+            keys_per_field
+                .into_iter()
+                .multi_cartesian_product()
+                .map(GroupId::Composite)
+                .collect()
+        };
 
         for group_key in unique_group_keys {
             let group = self
@@ -129,6 +143,10 @@ impl GroupsAggregator {
 
     /// Return `max_groups` number of keys of the groups with the best score
     fn best_group_keys(&self) -> Vec<GroupId> {
+        if self.group_score != GroupScore::Max {
+            return self.best_group_keys_by_aggregated_score();
+        }
+
         let mut pairs: Vec<_> = self.group_best_scores.iter().collect();
 
         pairs.sort_unstable_by(|(_, score1), (_, score2)| match self.order {
@@ -144,6 +162,48 @@ impl GroupsAggregator {
             .collect()
     }
 
+    /// Return `max_groups` number of keys of the groups with the best score of their hits
+    fn best_group_keys_by_aggregated_score(&self) -> Vec<GroupId> {
+        let mut pairs: Vec<_> = self
+            .groups
+            .iter()
+            .map(|(key, hits)| (key, self.aggregated_score(hits)))
+            .collect();
+
+        pairs.sort_unstable_by(|(_, score1), (_, score2)| match self.order {
+            Some(Order::LargeBetter) => score2.total_cmp(score1),
+            Some(Order::SmallBetter) => score1.total_cmp(score2),
+            None => Ordering::Equal,
+        });
+
+        pairs
+            .into_iter()
+            .take(self.max_groups)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Score of a group, computed from the hits it would return
+    fn aggregated_score(&self, hits: &Hits) -> ScoreType {
+        let top_hits = self.top_hits(hits.values().cloned());
+        let scores = top_hits.iter().map(|hit| hit.score);
+
+        match self.group_score {
+            GroupScore::Max => scores.take(1).sum(),
+            GroupScore::Sum => scores.sum(),
+            GroupScore::Average => scores.sum::<ScoreType>() / top_hits.len().max(1) as ScoreType,
+        }
+    }
+
+    /// Best hits of a group, up to the max group size, sorted
+    fn top_hits(&self, hits: impl Iterator<Item = ScoredPoint>) -> Vec<ScoredPoint> {
+        match self.order {
+            Some(Order::LargeBetter) => peek_top_largest_iterable(hits, self.max_group_size),
+            Some(Order::SmallBetter) => peek_top_smallest_iterable(hits, self.max_group_size),
+            None => hits.take(self.max_group_size).collect(),
+        }
+    }
+
     /// Gets the keys of the groups that have less than the max group size
     pub(super) fn keys_of_unfilled_best_groups(&self) -> Vec<Value> {
         let best_group_keys: HashSet<_> = self.best_group_keys().into_iter().collect();
@@ -170,23 +230,14 @@ impl GroupsAggregator {
         &self.all_ids
     }
 
-    /// Returns the best groups sorted by their best hit. The hits are sorted too.
+    /// Returns the best groups sorted by their score. The hits are sorted too.
     pub(super) fn distill(mut self) -> Vec<Group> {
         let best_groups = self.best_group_keys();
         let mut groups = Vec::with_capacity(best_groups.len());
 
         for group_key in best_groups {
             let mut group = self.groups.remove(&group_key).unwrap();
-            let scored_points_iter = group.drain().map(|(_, hit)| hit);
-            let hits = match self.order {
-                Some(Order::LargeBetter) => {
-                    peek_top_largest_iterable(scored_points_iter, self.max_group_size)
-                }
-                Some(Order::SmallBetter) => {
-                    peek_top_smallest_iterable(scored_points_iter, self.max_group_size)
-                }
-                None => scored_points_iter.take(self.max_group_size).collect(),
-            };
+            let hits = self.top_hits(group.drain().map(|(_, hit)| hit));
             groups.push(Group {
                 hits,
                 key: group_key,
@@ -238,8 +289,13 @@ mod unit_tests {
             point(3, 0.75, json!("b")),
         ];
 
-        let mut aggregator =
-            GroupsAggregator::new(3, 2, "docId".parse().unwrap(), Some(Order::LargeBetter));
+        let mut aggregator = GroupsAggregator::new(
+            3,
+            2,
+            vec!["docId".parse().unwrap()],
+            Some(Order::LargeBetter),
+            GroupScore::Max,
+        );
         for point in &scored_points {
             aggregator.add_point(point).unwrap();
         }
@@ -285,8 +341,13 @@ mod unit_tests {
 
     #[test]
     fn it_adds_single_points() {
-        let mut aggregator =
-            GroupsAggregator::new(4, 3, "docId".parse().unwrap(), Some(Order::LargeBetter));
+        let mut aggregator = GroupsAggregator::new(
+            4,
+            3,
+            vec!["docId".parse().unwrap()],
+            Some(Order::LargeBetter),
+            GroupScore::Max,
+        );
 
         // cases
         #[rustfmt::skip]
@@ -389,8 +450,13 @@ mod unit_tests {
 
     #[test]
     fn test_aggregate_less_groups() {
-        let mut aggregator =
-            GroupsAggregator::new(3, 2, "docId".parse().unwrap(), Some(Order::LargeBetter));
+        let mut aggregator = GroupsAggregator::new(
+            3,
+            2,
+            vec!["docId".parse().unwrap()],
+            Some(Order::LargeBetter),
+            GroupScore::Max,
+        );
 
         // cases
         [
@@ -454,4 +520,99 @@ mod unit_tests {
             assert_eq!(expected_id_score, group_id_score);
         }
     }
+
+    #[test]
+    fn test_group_by_several_keys() {
+        let point = |idx: u64, score: ScoreType, author: Value, year: Value| ScoredPoint {
+            payload: Some(Payload::from(json!({ "author": author, "year": year }))),
+            ..empty_point(idx, score)
+        };
+
+        let mut aggregator = GroupsAggregator::new(
+            3,
+            2,
+            vec!["author".parse().unwrap(), "year".parse().unwrap()],
+            Some(Order::LargeBetter),
+            GroupScore::Max,
+        );
+
+        [
+            point(1, 0.9, json!("a"), json!(2020)),
+            point(2, 0.8, json!(["a", "b"]), json!(2020)), // to both authors
+            point(3, 0.7, json!("a"), json!([2020, 2021])), // to both years
+            point(4, 0.6, json!("b"), json!("2021")),
+        ]
+        .iter()
+        .for_each(|point| aggregator.add_point(point).unwrap());
+
+        // missing one of the keys
+        let missing_year = ScoredPoint {
+            payload: Some(Payload::from(json!({ "author": "a" }))),
+            ..empty_point(5, 1.0)
+        };
+        aggregator.add_point(&missing_year).unwrap();
+
+        let composite = |author: &str, year: Value| {
+            GroupId::Composite(vec![
+                GroupId::from(author),
+                GroupId::try_from(&year).unwrap(),
+            ])
+        };
+
+        assert_eq!(aggregator.keys_of_filled_groups(), vec![json!(["a", 2020])],);
+
+        let groups = aggregator.distill();
+        let keys_and_ids: Vec<_> = groups
+            .into_iter()
+            .map(|group| (group.key, group.hits.iter().map(|hit| hit.id).collect_vec()))
+            .collect();
+
+        assert_eq!(
+            keys_and_ids,
+            vec![
+                (composite("a", json!(2020)), vec![1.into(), 2.into()]),
+                (composite("b", json!(2020)), vec![2.into()]),
+                (composite("a", json!(2021)), vec![3.into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aggregated_group_scores() {
+        let points = [
+            point(1, 0.9, json!("a")),
+            point(2, 0.1, json!("a")),
+            point(3, 0.6, json!("b")),
+            point(4, 0.5, json!("b")),
+            point(5, 0.7, json!("c")),
+        ];
+
+        let best_groups = |group_score| {
+            let mut aggregator = GroupsAggregator::new(
+                3,
+                2,
+                vec!["docId".parse().unwrap()],
+                Some(Order::LargeBetter),
+                group_score,
+            );
+            aggregator.add_points(&points);
+            aggregator
+                .distill()
+                .into_iter()
+                .map(|group| group.key)
+                .collect_vec()
+        };
+
+        let [a, b, c] = ["a", "b", "c"].map(GroupId::from);
+
+        assert_eq!(
+            best_groups(GroupScore::Max),
+            vec![a.clone(), c.clone(), b.clone()]
+        );
+        assert_eq!(
+            best_groups(GroupScore::Sum),
+            vec![b.clone(), a.clone(), c.clone()]
+        );
+        assert_eq!(best_groups(GroupScore::Average), vec![c, b, a]);
+    }
 }
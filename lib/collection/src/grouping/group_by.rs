@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 use std::future::Future;
+use std::slice;
 use std::time::Duration;
 
 use api::rest::{BaseGroupRequest, SearchGroupsRequestInternal, SearchRequestInternal};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
+use segment::data_types::groups::GroupScore;
 use segment::json_path::JsonPath;
 use segment::types::{
     AnyVariants, Condition, FieldCondition, Filter, Match, ScoredPoint, WithPayloadInterface,
@@ -23,7 +25,8 @@ use crate::lookup::WithLookup;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    CollectionResult, PointGroup, RecommendGroupsRequestInternal, RecommendRequestInternal,
+    CollectionError, CollectionResult, PointGroup, RecommendGroupsRequestInternal,
+    RecommendRequestInternal,
 };
 use crate::operations::universal_query::collection_query::{
     CollectionQueryGroupsRequest, CollectionQueryRequest,
@@ -48,8 +51,11 @@ pub struct GroupRequest {
     /// Request to use (search or recommend)
     pub source: SourceRequest,
 
-    /// Path to the field to group by
-    pub group_by: JsonPath,
+    /// Paths to the fields to group by, groups of several fields have composite ids
+    pub group_by: Vec<JsonPath>,
+
+    /// How to compute the score of a group from the scores of its points
+    pub group_score: GroupScore,
 
     /// Limit of points to return per group
     pub group_size: usize,
@@ -74,7 +80,8 @@ impl GroupRequest {
         };
         Self {
             source,
-            group_by,
+            group_by: vec![group_by],
+            group_score: GroupScore::default(),
             group_size,
             limit,
             with_lookup: None,
@@ -93,6 +100,17 @@ impl GroupRequest {
         F: Fn(String) -> Fut,
         Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
     {
+        if self.group_by.is_empty() {
+            return Err(CollectionError::bad_request(
+                "At least one field to group by is required",
+            ));
+        }
+        if self.with_lookup.is_some() && self.group_by.len() > 1 {
+            return Err(CollectionError::bad_request(
+                "Lookups are not supported when grouping by several fields",
+            ));
+        }
+
         let query_search = match self.source {
             SourceRequest::Search(search_req) => ShardQueryRequest::from(search_req),
             SourceRequest::Recommend(recommend_req) => {
@@ -128,6 +146,7 @@ impl GroupRequest {
         Ok(QueryGroupRequest {
             source: query_search,
             group_by: self.group_by,
+            group_score: self.group_score,
             group_size: self.group_size,
             groups: self.limit,
             with_lookup: self.with_lookup,
@@ -136,9 +155,14 @@ impl GroupRequest {
 }
 
 impl QueryGroupRequest {
-    /// Make `group_by` field selector work with as `with_payload`.
-    fn group_by_to_payload_selector(group_by: &JsonPath) -> WithPayloadInterface {
-        WithPayloadInterface::Fields(vec![group_by.strip_wildcard_suffix()])
+    /// Make `group_by` field selectors work with as `with_payload`.
+    fn group_by_to_payload_selector(group_by: &[JsonPath]) -> WithPayloadInterface {
+        WithPayloadInterface::Fields(
+            group_by
+                .iter()
+                .map(JsonPath::strip_wildcard_suffix)
+                .collect(),
+        )
     }
 
     async fn r#do(
@@ -157,8 +181,16 @@ impl QueryGroupRequest {
             increase_limit_for_group(prefetch, self.group_size);
         });
 
-        let key_not_empty = Filter::new_must_not(Condition::IsEmpty(self.group_by.clone().into()));
-        request.filter = Some(request.filter.unwrap_or_default().merge(&key_not_empty));
+        let keys_not_empty = Filter {
+            must_not: Some(
+                self.group_by
+                    .iter()
+                    .map(|key| Condition::IsEmpty(key.clone().into()))
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        request.filter = Some(request.filter.unwrap_or_default().merge(&keys_not_empty));
 
         let with_group_by_payload = Self::group_by_to_payload_selector(&self.group_by);
 
@@ -190,6 +222,7 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             group_request:
                 BaseGroupRequest {
                     group_by,
+                    group_score,
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
@@ -209,7 +242,8 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
 
         GroupRequest {
             source: SourceRequest::Search(search),
-            group_by,
+            group_by: group_by.into(),
+            group_score: group_score.unwrap_or_default(),
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
@@ -233,6 +267,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             group_request:
                 BaseGroupRequest {
                     group_by,
+                    group_score,
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
@@ -256,7 +291,8 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
 
         GroupRequest {
             source: SourceRequest::Recommend(recommend),
-            group_by,
+            group_by: group_by.into(),
+            group_score: group_score.unwrap_or_default(),
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
@@ -277,6 +313,7 @@ impl From<CollectionQueryGroupsRequest> for GroupRequest {
             with_payload,
             lookup_from,
             group_by,
+            group_score,
             group_size,
             limit,
             with_lookup: with_lookup_interface,
@@ -299,6 +336,7 @@ impl From<CollectionQueryGroupsRequest> for GroupRequest {
         GroupRequest {
             source: SourceRequest::Query(collection_query_request),
             group_by,
+            group_score,
             group_size,
             limit,
             with_lookup: with_lookup_interface.map(Into::into),
@@ -324,6 +362,7 @@ pub async fn group_by(
         request.group_size,
         request.group_by.clone(),
         score_ordering,
+        request.group_score,
     );
 
     // Try to complete amount of groups
@@ -338,7 +377,7 @@ pub async fn group_by(
         // Construct filter to exclude already found groups
         let full_groups = aggregator.keys_of_filled_groups();
         if !full_groups.is_empty() {
-            let except_any = except_on(&request.group_by, &full_groups);
+            let except_any = except_groups(&request.group_by, &full_groups);
             if !except_any.is_empty() {
                 let exclude_groups = Filter {
                     must: Some(except_any),
@@ -402,7 +441,7 @@ pub async fn group_by(
 
             // Construct filter to only include unsatisfied groups
             let unsatisfied_groups = aggregator.keys_of_unfilled_best_groups();
-            let match_any = match_on(&request.group_by, &unsatisfied_groups);
+            let match_any = match_groups(&request.group_by, &unsatisfied_groups);
             if !match_any.is_empty() {
                 let include_groups = Filter {
                     must: Some(match_any),
@@ -492,6 +531,60 @@ pub async fn group_by(
     Ok(groups)
 }
 
+/// Creates conditions to exclude the groups with the given keys, if possible
+fn except_groups(group_by: &[JsonPath], keys: &[Value]) -> Vec<Condition> {
+    match group_by {
+        [path] => except_on(path, keys),
+        paths => {
+            let groups = match_composite_keys(paths, keys);
+            if groups.is_empty() {
+                return Vec::new();
+            }
+            vec![Condition::Filter(Filter {
+                must_not: Some(groups),
+                ..Default::default()
+            })]
+        }
+    }
+}
+
+/// Creates conditions to only include the groups with the given keys, if possible
+fn match_groups(group_by: &[JsonPath], keys: &[Value]) -> Vec<Condition> {
+    match group_by {
+        [path] => match_on(path, keys),
+        paths => {
+            let groups = match_composite_keys(paths, keys);
+            if groups.is_empty() {
+                return Vec::new();
+            }
+            vec![Condition::Filter(Filter {
+                should: Some(groups),
+                ..Default::default()
+            })]
+        }
+    }
+}
+
+/// Creates a condition per composite key, matching each of its values on the corresponding path
+fn match_composite_keys(paths: &[JsonPath], keys: &[Value]) -> Vec<Condition> {
+    keys.iter()
+        .filter_map(|key| {
+            let values = key.as_array()?;
+            let must: Vec<_> = paths
+                .iter()
+                .zip(values)
+                .flat_map(|(path, value)| match_on(path, slice::from_ref(value)))
+                .collect();
+            (must.len() == paths.len()).then(|| {
+                Condition::Filter(Filter {
+                    must: Some(must),
+                    ..Default::default()
+                })
+            })
+        })
+        .collect()
+}
+
 /// Uses the set of values to create Match::Except's, if possible
 fn except_on(path: &JsonPath, values: &[Value]) -> Vec<Condition> {
     values_to_any_variants(values)
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use segment::data_types::groups::GroupId;
+use segment::data_types::groups::{GroupId, GroupScore};
 use segment::json_path::JsonPath;
 use segment::types::{PointIdType, ScoredPoint};
 
@@ -49,8 +49,11 @@ pub struct QueryGroupRequest {
     /// Query request to use
     pub source: ShardQueryRequest,
 
-    /// Path to the field to group by
-    pub group_by: JsonPath,
+    /// Paths to the fields to group by, groups of several fields have composite ids
+    pub group_by: Vec<JsonPath>,
+
+    /// How to compute the score of a group from the scores of its points
+    pub group_score: GroupScore,
 
     /// Limit of points to return per group
     pub group_size: usize,
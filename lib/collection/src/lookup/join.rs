@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::time::Duration;
+
+use fnv::FnvBuildHasher;
+use indexmap::IndexSet;
+use segment::types::{
+    AnyVariants, Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Join, Match,
+    PayloadContainer, WithPayloadInterface, WithVector,
+};
+use serde_json::Value;
+use tokio::sync::RwLockReadGuard;
+
+use crate::collection::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, ScrollRequestInternal};
+
+/// Replaces the join conditions of the filter with conditions on the ids or keys
+/// of the related points, looked up in their collections.
+pub async fn resolve_joins<'a, F, Fut>(
+    filter: &mut Filter,
+    collection_by_name: F,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+) -> CollectionResult<()>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+{
+    for condition in join_conditions_mut(filter) {
+        let Condition::Join(join_condition) = &*condition else {
+            unreachable!("only join conditions are selected");
+        };
+        let join = &join_condition.join;
+
+        if let Some(related_filter) = &join.filter {
+            if has_joins(related_filter) {
+                return Err(CollectionError::bad_request(
+                    "Join conditions are not supported in the filter of a join",
+                ));
+            }
+        }
+
+        let collection = collection_by_name(join.collection.clone())
+            .await
+            .ok_or_else(|| CollectionError::NotFound {
+                what: format!("Collection {}", join.collection),
+            })?;
+
+        let resolved = resolve_join(join, &collection, read_consistency, timeout).await?;
+        *condition = resolved;
+    }
+
+    Ok(())
+}
+
+pub fn has_joins(filter: &Filter) -> bool {
+    !joined_collections(filter).is_empty()
+}
+
+/// Names of the collections referred to by the join conditions of the filter,
+/// not including joins in the filters of the joins themselves
+pub fn joined_collections(filter: &Filter) -> Vec<&str> {
+    let mut collections = Vec::new();
+    for condition in filter.iter_conditions() {
+        match condition {
+            Condition::Join(join) => collections.push(join.join.collection.as_str()),
+            Condition::Filter(filter) => collections.extend(joined_collections(filter)),
+            Condition::Nested(nested) => collections.extend(joined_collections(nested.filter())),
+            _ => {}
+        }
+    }
+    collections
+}
+
+fn join_conditions_mut(filter: &mut Filter) -> Vec<&mut Condition> {
+    let Filter {
+        should,
+        min_should,
+        must,
+        must_not,
+    } = filter;
+
+    let conditions = [must.as_mut(), must_not.as_mut(), should.as_mut()]
+        .into_iter()
+        .flatten()
+        .chain(
+            min_should
+                .as_mut()
+                .map(|min_should| &mut min_should.conditions),
+        )
+        .flatten();
+
+    let mut joins = Vec::new();
+    for condition in conditions {
+        if matches!(condition, Condition::Join(_)) {
+            joins.push(condition);
+            continue;
+        }
+        match condition {
+            Condition::Filter(filter) => joins.extend(join_conditions_mut(filter)),
+            Condition::Nested(nested) => {
+                joins.extend(join_conditions_mut(&mut nested.nested.filter));
+            }
+            _ => {}
+        }
+    }
+    joins
+}
+
+/// Looks up the related points of the join, and builds a condition matching their ids or keys
+async fn resolve_join(
+    join: &Join,
+    collection: &Collection,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+) -> CollectionResult<Condition> {
+    let limit = join.limit.unwrap_or(Join::DEFAULT_LIMIT);
+
+    let request = ScrollRequestInternal {
+        offset: None,
+        limit: Some(limit),
+        filter: join.filter.clone(),
+        with_payload: Some(match &join.related_key {
+            Some(related_key) => WithPayloadInterface::Fields(vec![related_key.clone()]),
+            None => WithPayloadInterface::Bool(false),
+        }),
+        with_vector: WithVector::Bool(false),
+        order_by: None,
+    };
+
+    let related = collection
+        .scroll_by(
+            request,
+            read_consistency,
+            &ShardSelectorInternal::All,
+            timeout,
+        )
+        .await?;
+
+    if related.next_page_offset.is_some() {
+        return Err(CollectionError::bad_request(format!(
+            "Join on collection {} matches more than {limit} points, \
+             use a more selective filter or a greater limit",
+            join.collection,
+        )));
+    }
+
+    let mut integers: IndexSet<i64, FnvBuildHasher> = IndexSet::default();
+    let mut strings: IndexSet<String, FnvBuildHasher> = IndexSet::default();
+
+    for point in related.points {
+        let Some(related_key) = &join.related_key else {
+            match point.id {
+                ExtendedPointId::NumId(id) => integers.extend(i64::try_from(id).ok()),
+                ExtendedPointId::Uuid(uuid) => {
+                    strings.insert(uuid.to_string());
+                }
+            }
+            continue;
+        };
+
+        let Some(payload) = &point.payload else {
+            continue;
+        };
+        let values = payload
+            .get_value(related_key)
+            .into_iter()
+            .flat_map(|value| match value {
+                Value::Array(values) => values.iter().collect(),
+                value => vec![value],
+            });
+        for value in values {
+            if let Some(integer) = value.as_i64() {
+                integers.insert(integer);
+            } else if let Some(string) = value.as_str() {
+                strings.insert(string.to_string());
+            }
+        }
+    }
+
+    let mut conditions: Vec<_> = [
+        (!integers.is_empty()).then_some(AnyVariants::Integers(integers)),
+        (!strings.is_empty()).then_some(AnyVariants::Strings(strings)),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|any_variants| {
+        Condition::Field(FieldCondition::new_match(
+            join.key.clone(),
+            Match::new_any(any_variants),
+        ))
+    })
+    .collect();
+
+    let resolved = match conditions.len() {
+        // No related points, so no point can match
+        0 => Condition::HasId(HasIdCondition::from(HashSet::new())),
+        1 => conditions.remove(0),
+        _ => Condition::Filter(Filter {
+            should: Some(conditions),
+            ..Default::default()
+        }),
+    };
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{JoinCondition, Nested, NestedCondition};
+
+    use super::*;
+
+    fn join(collection: &str, filter: Option<Filter>) -> Condition {
+        Condition::Join(JoinCondition {
+            join: Join {
+                key: "doc_id".parse().unwrap(),
+                collection: collection.to_string(),
+                filter,
+                related_key: None,
+                limit: None,
+            },
+        })
+    }
+
+    #[test]
+    fn test_select_join_conditions() {
+        let nested = Condition::Nested(NestedCondition::new(Nested {
+            key: "chunks".parse().unwrap(),
+            filter: Filter::new_must(join("nested", None)),
+        }));
+
+        let mut filter = Filter {
+            should: Some(vec![join("should", None)]),
+            must: Some(vec![Condition::Filter(Filter::new_must_not(join(
+                "inner", None,
+            )))]),
+            must_not: Some(vec![nested]),
+            ..Default::default()
+        };
+
+        assert!(has_joins(&filter));
+        assert!(!has_joins(&Filter::default()));
+        assert_eq!(
+            joined_collections(&filter),
+            vec!["inner", "nested", "should"]
+        );
+
+        let collections: Vec<_> = join_conditions_mut(&mut filter)
+            .into_iter()
+            .map(|condition| match condition {
+                Condition::Join(join) => join.join.collection.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(collections, vec!["inner", "nested", "should"]);
+
+        let in_related_filter =
+            Filter::new_must(join("docs", Some(Filter::new_must(join("other", None)))));
+        assert!(has_joins(&in_related_filter));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod join;
 pub mod types;
 
 use std::collections::HashMap;
@@ -74,6 +74,8 @@ impl From<GroupId> for PseudoId {
             GroupId::String(s) => Self::String(s),
             GroupId::NumberU64(n) => Self::NumberU64(n),
             GroupId::NumberI64(n) => Self::NumberI64(n),
+            // Lookups are not supported for composite ids, keep them distinct anyway
+            id @ GroupId::Composite(_) => Self::String(serde_json::Value::from(id).to_string()),
         }
     }
 }
@@ -5,7 +5,7 @@ use std::time::Duration;
 use api::conversions::json::{json_path_from_proto, payload_to_proto};
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist,
+    from_grpc_dist, group_by_from_proto, group_score_from_proto,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -1389,7 +1389,8 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             with_vector,
             score_threshold,
             group_request: BaseGroupRequest {
-                group_by: json_path_from_proto(&value.group_by)?,
+                group_by: group_by_from_proto(&value.group_by, &value.extra_group_by)?,
+                group_score: value.group_score.map(group_score_from_proto).transpose()?,
                 limit: value.limit,
                 group_size: value.group_size,
                 with_lookup: value.with_lookup.map(|l| l.try_into()).transpose()?,
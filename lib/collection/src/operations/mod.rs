@@ -21,7 +21,7 @@ pub mod verification;
 use std::collections::HashMap;
 
 use segment::json_path::JsonPath;
-use segment::types::{ExtendedPointId, PayloadFieldSchema, PointIdType};
+use segment::types::{ExtendedPointId, Filter, PayloadFieldSchema, PointIdType};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 
@@ -169,6 +169,31 @@ impl CollectionUpdateOperations {
         }
     }
 
+    /// Filter selecting the points to update, if the operation has one
+    pub fn filter(&self) -> Option<&Filter> {
+        match self {
+            Self::PointOperation(point_ops::PointOperations::DeletePointsByFilter(filter))
+            | Self::VectorOperation(vector_ops::VectorOperations::DeleteVectorsByFilter(
+                filter,
+                _,
+            ))
+            | Self::PayloadOperation(payload_ops::PayloadOps::ClearPayloadByFilter(filter)) => {
+                Some(filter)
+            }
+            Self::PayloadOperation(
+                payload_ops::PayloadOps::SetPayload(op)
+                | payload_ops::PayloadOps::OverwritePayload(op),
+            ) => op.filter.as_ref(),
+            Self::PayloadOperation(payload_ops::PayloadOps::DeletePayload(op)) => {
+                op.filter.as_ref()
+            }
+            Self::PointOperation(_)
+            | Self::VectorOperation(_)
+            | Self::PayloadOperation(_)
+            | Self::FieldIndexOperation(_) => None,
+        }
+    }
+
     pub fn retain_point_ids<F>(&mut self, filter: F)
     where
         F: Fn(&PointIdType) -> bool,
@@ -21,6 +21,7 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::bm25::Bm25Query;
 use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::{OrderBy, OrderValue};
 use segment::data_types::vectors::{
@@ -474,6 +475,11 @@ pub enum ScrollOrder {
     ById,
     ByField(OrderBy),
     Random,
+    /// Best BM25 relevance first, only points with at least one matching token
+    Bm25 {
+        query: Bm25Query,
+        score_threshold: Option<ScoreType>,
+    },
 }
 
 /// Scroll request, used as a part of query request
@@ -1199,7 +1205,13 @@ impl From<OperationError> for CollectionError {
                 description: "Conversion between multi and regular vectors failed".to_string(),
             },
             OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingGeoIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
             OperationError::MissingMapIndexForFacet { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingGeoIndexForFacet { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingRangeIndexForFacet { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingFullTextIndex { .. } => Self::bad_input(format!("{err}")),
+            OperationError::VariableTypeError { .. } => Self::bad_input(format!("{err}")),
+            OperationError::NonFiniteNumber { .. } => Self::bad_input(format!("{err}")),
         }
     }
 }
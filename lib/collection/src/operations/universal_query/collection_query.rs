@@ -3,10 +3,13 @@ use std::collections::HashSet;
 use api::rest::LookupLocation;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::data_types::bm25::Bm25Query;
+use segment::data_types::groups::GroupScore;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{
     NamedQuery, NamedVectorStruct, VectorInternal, VectorRef, DEFAULT_VECTOR_NAME,
 };
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::json_path::JsonPath;
 use segment::types::{
     Condition, ExtendedPointId, Filter, HasIdCondition, PointIdType, SearchParams,
@@ -14,8 +17,9 @@ use segment::types::{
 };
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 
+use super::formula::FormulaInternal;
 use super::shard_query::{
-    FusionInternal, SampleInternal, ScoringQuery, ShardPrefetch, ShardQueryRequest,
+    FusionInternal, MmrInternal, SampleInternal, ScoringQuery, ShardPrefetch, ShardQueryRequest,
 };
 use crate::common::fetch_vectors::ReferencedVectors;
 use crate::lookup::WithLookup;
@@ -72,7 +76,8 @@ pub struct CollectionQueryGroupsRequest {
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
     pub lookup_from: Option<LookupLocation>,
-    pub group_by: JsonPath,
+    pub group_by: Vec<JsonPath>,
+    pub group_score: GroupScore,
     pub group_size: usize,
     pub limit: usize,
     pub with_lookup: Option<WithLookup>,
@@ -91,6 +96,12 @@ pub enum Query {
 
     /// Sample points
     Sample(SampleInternal),
+
+    /// Score boosting via an arbitrary formula
+    Formula(FormulaInternal),
+
+    /// Rank by BM25 relevance of a full-text indexed field
+    Bm25(Bm25Query),
 }
 
 impl Query {
@@ -102,17 +113,16 @@ impl Query {
         using: String,
     ) -> CollectionResult<ScoringQuery> {
         let scoring_query = match self {
-            Query::Vector(vector_query) => {
-                let query_enum = vector_query
-                    // Homogenize the input into raw vectors
-                    .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)?
-                    // Turn into QueryEnum
-                    .into_query_enum(using)?;
-                ScoringQuery::Vector(query_enum)
-            }
+            Query::Vector(vector_query) => vector_query
+                // Homogenize the input into raw vectors
+                .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)?
+                // Turn into ScoringQuery
+                .into_scoring_query(using)?,
             Query::Fusion(fusion) => ScoringQuery::Fusion(fusion),
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
             Query::Sample(sample) => ScoringQuery::Sample(sample),
+            Query::Formula(formula) => ScoringQuery::Formula(ParsedFormula::try_from(formula)?),
+            Query::Bm25(bm25) => ScoringQuery::Bm25(bm25),
         };
 
         Ok(scoring_query)
@@ -140,6 +150,15 @@ pub enum VectorQuery<T> {
     RecommendBestScore(RecoQuery<T>),
     Discover(DiscoveryQuery<T>),
     Context(ContextQuery<T>),
+    Mmr(MmrQuery<T>),
+}
+
+/// Nearest neighbours to the vector, diversified with Maximal Marginal Relevance
+#[derive(Clone, Debug, PartialEq)]
+pub struct MmrQuery<T> {
+    pub vector: T,
+    /// Balance between relevance (1.0) and diversity (0.0)
+    pub lambda: f32,
 }
 
 impl<T> VectorQuery<T> {
@@ -151,6 +170,7 @@ impl<T> VectorQuery<T> {
             VectorQuery::RecommendBestScore(query) => Box::new(query.flat_iter()),
             VectorQuery::Discover(query) => Box::new(query.flat_iter()),
             VectorQuery::Context(query) => Box::new(query.flat_iter()),
+            VectorQuery::Mmr(query) => Box::new(std::iter::once(&query.vector)),
         }
     }
 }
@@ -250,6 +270,13 @@ impl VectorQuery<VectorInputInternal> {
 
                 Ok(VectorQuery::Context(ContextQuery { pairs }))
             }
+            VectorQuery::Mmr(MmrQuery { vector, lambda }) => {
+                let vector = ids_to_vectors
+                    .resolve_reference(lookup_collection, lookup_vector_name, vector)
+                    .ok_or_else(|| vector_not_found_error(lookup_vector_name))?;
+
+                Ok(VectorQuery::Mmr(MmrQuery { vector, lambda }))
+            }
         }
     }
 
@@ -291,7 +318,7 @@ fn vector_not_found_error(vector_name: &str) -> CollectionError {
 }
 
 impl VectorQuery<VectorInternal> {
-    fn into_query_enum(self, using: String) -> CollectionResult<QueryEnum> {
+    fn into_scoring_query(self, using: String) -> CollectionResult<ScoringQuery> {
         let query_enum = match self {
             VectorQuery::Nearest(vector) => {
                 QueryEnum::Nearest(NamedVectorStruct::new_from_vector(vector, using))
@@ -316,9 +343,16 @@ impl VectorQuery<VectorInternal> {
                 query: context,
                 using: Some(using),
             }),
+            VectorQuery::Mmr(MmrQuery { vector, lambda }) => {
+                return Ok(ScoringQuery::Mmr(MmrInternal {
+                    vector,
+                    using,
+                    lambda,
+                }));
+            }
         };
 
-        Ok(query_enum)
+        Ok(ScoringQuery::Vector(query_enum))
     }
 }
 
@@ -395,6 +429,12 @@ impl CollectionPrefetch {
             self.score_threshold,
         )?;
 
+        if let Some(Query::Vector(VectorQuery::Mmr(_))) = &self.query {
+            return Err(CollectionError::bad_request(
+                "MMR can only be used in the root query, as it diversifies the results of all shards.",
+            ));
+        }
+
         let lookup_vector_name = self.get_lookup_vector_name();
         let lookup_collection = self.get_lookup_collection().cloned();
         let using = self.using.clone();
@@ -578,13 +618,60 @@ impl CollectionQueryRequest {
             }
         }
 
+        // Check that formula queries have something to rescore
+        if let Some(Query::Formula(_)) = query {
+            if prefetch.is_empty() {
+                return Err(CollectionError::bad_request(
+                    "A formula query needs prefetches to rescore. Can't use a formula without prefetches.",
+                ));
+            }
+        }
+
+        // Check that MMR queries have candidates to diversify, and a valid lambda
+        if let Some(Query::Vector(VectorQuery::Mmr(mmr))) = query {
+            if prefetch.is_empty() {
+                return Err(CollectionError::bad_request(
+                    "An MMR query needs prefetches to select the candidates from. Can't use MMR without prefetches.",
+                ));
+            }
+            if !(0.0..=1.0).contains(&mmr.lambda) {
+                return Err(CollectionError::bad_request(format!(
+                    "MMR lambda must be in the [0, 1] range, got {}",
+                    mmr.lambda,
+                )));
+            }
+        }
+
+        // Check BM25 parameters
+        if let Some(Query::Bm25(bm25)) = query {
+            if bm25.text.is_empty() {
+                return Err(CollectionError::bad_request(
+                    "BM25 query text must not be empty",
+                ));
+            }
+            if !bm25.k1.is_finite() || bm25.k1 < 0.0 {
+                return Err(CollectionError::bad_request(format!(
+                    "BM25 k1 must be a finite non-negative number, got {}",
+                    bm25.k1,
+                )));
+            }
+            if !(0.0..=1.0).contains(&bm25.b) {
+                return Err(CollectionError::bad_request(format!(
+                    "BM25 b must be in the [0, 1] range, got {}",
+                    bm25.b,
+                )));
+            }
+        }
+
         // Check that fusion queries are not combined with a using vector name
-        if let Some(Query::Fusion(_)) = query {
+        if let Some(Query::Fusion(fusion)) = query {
             if using != DEFAULT_VECTOR_NAME {
                 return Err(CollectionError::bad_request(
                     "Fusion queries cannot be combined with the 'using' field.",
                 ));
             }
+
+            fusion.validate(prefetch.len())?;
         }
 
         Ok(())
@@ -593,14 +680,23 @@ impl CollectionQueryRequest {
 
 mod from_rest {
     use api::rest::schema as rest;
+    use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
 
     use super::*;
 
-    impl From<rest::Fusion> for FusionInternal {
-        fn from(value: rest::Fusion) -> Self {
-            match value {
-                rest::Fusion::Rrf => FusionInternal::Rrf,
-                rest::Fusion::Dbsf => FusionInternal::Dbsf,
+    impl From<rest::FusionQuery> for FusionInternal {
+        fn from(value: rest::FusionQuery) -> Self {
+            let rest::FusionQuery { fusion, k, weights } = value;
+            let weights = weights.unwrap_or_default();
+            match fusion {
+                rest::Fusion::Rrf => FusionInternal::Rrf {
+                    k: k.unwrap_or(DEFAULT_RRF_K),
+                    weights,
+                },
+                // `k` is rejected by the validation
+                rest::Fusion::Dbsf => FusionInternal::Dbsf { weights },
+                rest::Fusion::MinMax => FusionInternal::MinMax { weights },
+                rest::Fusion::ZScore => FusionInternal::ZScore { weights },
             }
         }
     }
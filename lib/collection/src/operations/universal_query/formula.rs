@@ -0,0 +1,733 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use api::grpc::qdrant as grpc;
+use api::rest::schema as rest;
+use common::types::ScoreType;
+use itertools::Itertools;
+use segment::index::query_optimization::rescore_formula::parsed_formula::{
+    DatetimeExpression, DecayKind, DecayParams, ParsedExpression, ParsedFormula, PreciseScore,
+    VariableId,
+};
+use segment::json_path::JsonPath;
+use segment::types::{Condition, DateTimePayloadType, Filter, GeoPoint};
+use serde_json::Value;
+use tonic::Status;
+
+use crate::lookup::join::has_joins;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Formula to rescore prefetched points with, as received from the API.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormulaInternal {
+    pub formula: ExpressionInternal,
+    pub defaults: HashMap<String, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionInternal {
+    Constant(f32),
+    Variable(String),
+    Condition(Box<Condition>),
+    Mult(Vec<ExpressionInternal>),
+    Sum(Vec<ExpressionInternal>),
+    Neg(Box<ExpressionInternal>),
+    Div {
+        left: Box<ExpressionInternal>,
+        right: Box<ExpressionInternal>,
+        by_zero_default: Option<ScoreType>,
+    },
+    Sqrt(Box<ExpressionInternal>),
+    Pow {
+        base: Box<ExpressionInternal>,
+        exponent: Box<ExpressionInternal>,
+    },
+    Exp(Box<ExpressionInternal>),
+    Log10(Box<ExpressionInternal>),
+    Ln(Box<ExpressionInternal>),
+    Abs(Box<ExpressionInternal>),
+    Datetime(String),
+    DatetimeKey(JsonPath),
+    GeoDistance {
+        origin: GeoPoint,
+        to: JsonPath,
+    },
+    Decay {
+        kind: DecayKind,
+        x: Box<ExpressionInternal>,
+        origin: Option<Box<ExpressionInternal>>,
+        scale: Option<f32>,
+        offset: Option<f32>,
+        decay: Option<f32>,
+    },
+}
+
+/// Variables and conditions collected while parsing an expression
+#[derive(Default)]
+struct ParseContext {
+    payload_vars: HashSet<JsonPath>,
+    datetime_vars: HashSet<JsonPath>,
+    geo_vars: HashSet<JsonPath>,
+    conditions: Vec<Filter>,
+}
+
+impl ExpressionInternal {
+    /// Converts the expression into its parsed form.
+    ///
+    /// Collects the variables and conditions used in the expression along the way.
+    fn parse(self, ctx: &mut ParseContext) -> CollectionResult<ParsedExpression> {
+        let expression = match self {
+            ExpressionInternal::Constant(constant) => {
+                ParsedExpression::Constant(PreciseScore::from(constant))
+            }
+            ExpressionInternal::Variable(variable) => {
+                let variable_id = parse_variable(&variable)?;
+                if let VariableId::Payload(key) = &variable_id {
+                    ctx.payload_vars.insert(key.clone());
+                }
+                ParsedExpression::Variable(variable_id)
+            }
+            ExpressionInternal::Condition(condition) => {
+                let condition = Filter::new_must(*condition);
+                // Only the filters of the request are resolved, not the conditions of formulas
+                if has_joins(&condition) {
+                    return Err(CollectionError::bad_input(
+                        "Join conditions are not supported in formulas",
+                    ));
+                }
+                let condition_idx = ctx.conditions.len();
+                ctx.conditions.push(condition);
+                ParsedExpression::Variable(VariableId::Condition(condition_idx))
+            }
+            ExpressionInternal::Mult(expressions) => ParsedExpression::Mult(
+                expressions
+                    .into_iter()
+                    .map(|expression| expression.parse(ctx))
+                    .try_collect()?,
+            ),
+            ExpressionInternal::Sum(expressions) => ParsedExpression::Sum(
+                expressions
+                    .into_iter()
+                    .map(|expression| expression.parse(ctx))
+                    .try_collect()?,
+            ),
+            ExpressionInternal::Neg(expression) => {
+                ParsedExpression::Neg(Box::new(expression.parse(ctx)?))
+            }
+            ExpressionInternal::Div {
+                left,
+                right,
+                by_zero_default,
+            } => ParsedExpression::new_div(
+                left.parse(ctx)?,
+                right.parse(ctx)?,
+                by_zero_default.map(PreciseScore::from),
+            ),
+            ExpressionInternal::Sqrt(expression) => {
+                ParsedExpression::Sqrt(Box::new(expression.parse(ctx)?))
+            }
+            ExpressionInternal::Pow { base, exponent } => {
+                ParsedExpression::new_pow(base.parse(ctx)?, exponent.parse(ctx)?)
+            }
+            ExpressionInternal::Exp(expression) => {
+                ParsedExpression::Exp(Box::new(expression.parse(ctx)?))
+            }
+            ExpressionInternal::Log10(expression) => {
+                ParsedExpression::Log10(Box::new(expression.parse(ctx)?))
+            }
+            ExpressionInternal::Ln(expression) => {
+                ParsedExpression::Ln(Box::new(expression.parse(ctx)?))
+            }
+            ExpressionInternal::Abs(expression) => {
+                ParsedExpression::Abs(Box::new(expression.parse(ctx)?))
+            }
+            ExpressionInternal::Datetime(datetime) => {
+                let datetime = DateTimePayloadType::from_str(&datetime).map_err(|_| {
+                    CollectionError::bad_input(format!(
+                        "Invalid formula: '{datetime}' is not in a supported date/time format, please use RFC 3339"
+                    ))
+                })?;
+                ParsedExpression::Datetime(DatetimeExpression::Constant(datetime))
+            }
+            ExpressionInternal::DatetimeKey(key) => {
+                ctx.datetime_vars.insert(key.clone());
+                ParsedExpression::Datetime(DatetimeExpression::PayloadVariable(key))
+            }
+            ExpressionInternal::GeoDistance { origin, to } => {
+                ctx.geo_vars.insert(to.clone());
+                ParsedExpression::GeoDistance { origin, key: to }
+            }
+            ExpressionInternal::Decay {
+                kind,
+                x,
+                origin,
+                scale,
+                offset,
+                decay,
+            } => {
+                let params = DecayParams {
+                    scale: scale.map_or(DecayParams::DEFAULT_SCALE, PreciseScore::from),
+                    offset: offset.map_or(DecayParams::DEFAULT_OFFSET, PreciseScore::from),
+                    decay: decay.map_or(DecayParams::DEFAULT_DECAY, PreciseScore::from),
+                };
+                params
+                    .validate()
+                    .map_err(|err| CollectionError::bad_input(format!("Invalid formula: {err}")))?;
+
+                ParsedExpression::Decay {
+                    kind,
+                    x: Box::new(x.parse(ctx)?),
+                    origin: origin
+                        .map(|origin| origin.parse(ctx).map(Box::new))
+                        .transpose()?,
+                    params,
+                }
+            }
+        };
+
+        Ok(expression)
+    }
+}
+
+fn parse_variable(variable: &str) -> CollectionResult<VariableId> {
+    variable
+        .parse()
+        .map_err(|err: String| CollectionError::bad_input(format!("Invalid formula: {err}")))
+}
+
+impl TryFrom<FormulaInternal> for ParsedFormula {
+    type Error = CollectionError;
+
+    fn try_from(value: FormulaInternal) -> Result<Self, Self::Error> {
+        let FormulaInternal { formula, defaults } = value;
+
+        let mut ctx = ParseContext::default();
+        let formula = formula.parse(&mut ctx)?;
+
+        let ParseContext {
+            payload_vars,
+            datetime_vars,
+            geo_vars,
+            conditions,
+        } = ctx;
+
+        let defaults = defaults
+            .into_iter()
+            .map(|(variable, value)| Ok((parse_variable(&variable)?, value)))
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(ParsedFormula {
+            payload_vars,
+            datetime_vars,
+            geo_vars,
+            conditions,
+            defaults,
+            formula,
+        })
+    }
+}
+
+impl From<rest::FormulaQuery> for FormulaInternal {
+    fn from(value: rest::FormulaQuery) -> Self {
+        let rest::FormulaQuery { formula, defaults } = value;
+
+        FormulaInternal {
+            formula: ExpressionInternal::from(formula),
+            defaults,
+        }
+    }
+}
+
+impl From<rest::Expression> for ExpressionInternal {
+    fn from(value: rest::Expression) -> Self {
+        let from_boxed = |expression: Box<rest::Expression>| Box::new(Self::from(*expression));
+
+        match value {
+            rest::Expression::Constant(constant) => ExpressionInternal::Constant(constant),
+            rest::Expression::Variable(variable) => ExpressionInternal::Variable(variable),
+            rest::Expression::Condition(condition) => ExpressionInternal::Condition(condition),
+            rest::Expression::Mult(rest::MultExpression { mult }) => {
+                ExpressionInternal::Mult(mult.into_iter().map(Self::from).collect())
+            }
+            rest::Expression::Sum(rest::SumExpression { sum }) => {
+                ExpressionInternal::Sum(sum.into_iter().map(Self::from).collect())
+            }
+            rest::Expression::Neg(rest::NegExpression { neg }) => {
+                ExpressionInternal::Neg(from_boxed(neg))
+            }
+            rest::Expression::Abs(rest::AbsExpression { abs }) => {
+                ExpressionInternal::Abs(from_boxed(abs))
+            }
+            rest::Expression::Div(rest::DivExpression { div }) => {
+                let rest::DivParams {
+                    left,
+                    right,
+                    by_zero_default,
+                } = div;
+                ExpressionInternal::Div {
+                    left: from_boxed(left),
+                    right: from_boxed(right),
+                    by_zero_default,
+                }
+            }
+            rest::Expression::Sqrt(rest::SqrtExpression { sqrt }) => {
+                ExpressionInternal::Sqrt(from_boxed(sqrt))
+            }
+            rest::Expression::Pow(rest::PowExpression { pow }) => {
+                let rest::PowParams { base, exponent } = pow;
+                ExpressionInternal::Pow {
+                    base: from_boxed(base),
+                    exponent: from_boxed(exponent),
+                }
+            }
+            rest::Expression::Exp(rest::ExpExpression { exp }) => {
+                ExpressionInternal::Exp(from_boxed(exp))
+            }
+            rest::Expression::Log10(rest::Log10Expression { log10 }) => {
+                ExpressionInternal::Log10(from_boxed(log10))
+            }
+            rest::Expression::Ln(rest::LnExpression { ln }) => {
+                ExpressionInternal::Ln(from_boxed(ln))
+            }
+            rest::Expression::Datetime(rest::DatetimeExpression { datetime }) => {
+                ExpressionInternal::Datetime(datetime)
+            }
+            rest::Expression::DatetimeKey(rest::DatetimeKeyExpression { datetime_key }) => {
+                ExpressionInternal::DatetimeKey(datetime_key)
+            }
+            rest::Expression::GeoDistance(rest::GeoDistance { geo_distance }) => {
+                let rest::GeoDistanceParams { origin, to } = geo_distance;
+                ExpressionInternal::GeoDistance { origin, to }
+            }
+            rest::Expression::LinDecay(rest::LinDecayExpression { lin_decay }) => {
+                Self::from_rest_decay(DecayKind::Lin, lin_decay)
+            }
+            rest::Expression::ExpDecay(rest::ExpDecayExpression { exp_decay }) => {
+                Self::from_rest_decay(DecayKind::Exp, exp_decay)
+            }
+            rest::Expression::GaussDecay(rest::GaussDecayExpression { gauss_decay }) => {
+                Self::from_rest_decay(DecayKind::Gauss, gauss_decay)
+            }
+        }
+    }
+}
+
+impl ExpressionInternal {
+    fn from_rest_decay(kind: DecayKind, params: rest::DecayParamsExpression) -> Self {
+        let rest::DecayParamsExpression {
+            x,
+            origin,
+            scale,
+            offset,
+            decay,
+        } = params;
+
+        ExpressionInternal::Decay {
+            kind,
+            x: Box::new(Self::from(*x)),
+            origin: origin.map(|origin| Box::new(Self::from(*origin))),
+            scale,
+            offset,
+            decay,
+        }
+    }
+
+    fn try_from_grpc_decay(
+        kind: DecayKind,
+        params: grpc::DecayParamsExpression,
+    ) -> Result<Self, Status> {
+        let grpc::DecayParamsExpression {
+            x,
+            origin,
+            scale,
+            offset,
+            decay,
+        } = params;
+
+        let x = x.ok_or_else(|| Status::invalid_argument("missing field: x"))?;
+
+        Ok(ExpressionInternal::Decay {
+            kind,
+            x: Box::new(Self::try_from(*x)?),
+            origin: origin
+                .map(|origin| Self::try_from(*origin).map(Box::new))
+                .transpose()?,
+            scale,
+            offset,
+            decay,
+        })
+    }
+}
+
+impl TryFrom<grpc::Formula> for FormulaInternal {
+    type Error = Status;
+
+    fn try_from(value: grpc::Formula) -> Result<Self, Self::Error> {
+        let grpc::Formula {
+            expression,
+            defaults,
+        } = value;
+
+        let expression =
+            expression.ok_or_else(|| Status::invalid_argument("missing field: expression"))?;
+
+        Ok(FormulaInternal {
+            formula: ExpressionInternal::try_from(expression)?,
+            defaults: api::conversions::json::proto_dict_to_json(defaults)?,
+        })
+    }
+}
+
+impl TryFrom<grpc::Expression> for ExpressionInternal {
+    type Error = Status;
+
+    fn try_from(value: grpc::Expression) -> Result<Self, Self::Error> {
+        use grpc::expression::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("missing field: variant"))?;
+
+        let try_boxed = |expression: Option<Box<grpc::Expression>>, field: &str| {
+            let expression = expression
+                .ok_or_else(|| Status::invalid_argument(format!("missing field: {field}")))?;
+            Self::try_from(*expression).map(Box::new)
+        };
+
+        let expression = match variant {
+            Variant::Constant(constant) => ExpressionInternal::Constant(constant),
+            Variant::Variable(variable) => ExpressionInternal::Variable(variable),
+            Variant::Condition(condition) => {
+                let condition = Condition::try_from(condition)?;
+                ExpressionInternal::Condition(Box::new(condition))
+            }
+            Variant::Mult(grpc::MultExpression { mult }) => {
+                ExpressionInternal::Mult(mult.into_iter().map(Self::try_from).try_collect()?)
+            }
+            Variant::Sum(grpc::SumExpression { sum }) => {
+                ExpressionInternal::Sum(sum.into_iter().map(Self::try_from).try_collect()?)
+            }
+            Variant::Div(div) => {
+                let grpc::DivExpression {
+                    left,
+                    right,
+                    by_zero_default,
+                } = *div;
+                ExpressionInternal::Div {
+                    left: try_boxed(left, "left")?,
+                    right: try_boxed(right, "right")?,
+                    by_zero_default,
+                }
+            }
+            Variant::Neg(expression) => {
+                ExpressionInternal::Neg(try_boxed(Some(expression), "neg")?)
+            }
+            Variant::Abs(expression) => {
+                ExpressionInternal::Abs(try_boxed(Some(expression), "abs")?)
+            }
+            Variant::Sqrt(expression) => {
+                ExpressionInternal::Sqrt(try_boxed(Some(expression), "sqrt")?)
+            }
+            Variant::Pow(pow) => {
+                let grpc::PowExpression { base, exponent } = *pow;
+                ExpressionInternal::Pow {
+                    base: try_boxed(base, "base")?,
+                    exponent: try_boxed(exponent, "exponent")?,
+                }
+            }
+            Variant::Exp(expression) => {
+                ExpressionInternal::Exp(try_boxed(Some(expression), "exp")?)
+            }
+            Variant::Log10(expression) => {
+                ExpressionInternal::Log10(try_boxed(Some(expression), "log10")?)
+            }
+            Variant::Ln(expression) => ExpressionInternal::Ln(try_boxed(Some(expression), "ln")?),
+            Variant::Datetime(datetime) => ExpressionInternal::Datetime(datetime),
+            Variant::DatetimeKey(key) => {
+                ExpressionInternal::DatetimeKey(api::conversions::json::json_path_from_proto(&key)?)
+            }
+            Variant::GeoDistance(grpc::GeoDistance { origin, to }) => {
+                let origin =
+                    origin.ok_or_else(|| Status::invalid_argument("missing field: origin"))?;
+                ExpressionInternal::GeoDistance {
+                    origin: GeoPoint::from(origin),
+                    to: api::conversions::json::json_path_from_proto(&to)?,
+                }
+            }
+            Variant::LinDecay(decay) => Self::try_from_grpc_decay(DecayKind::Lin, *decay)?,
+            Variant::ExpDecay(decay) => Self::try_from_grpc_decay(DecayKind::Exp, *decay)?,
+            Variant::GaussDecay(decay) => Self::try_from_grpc_decay(DecayKind::Gauss, *decay)?,
+        };
+
+        Ok(expression)
+    }
+}
+
+// Conversions for the internal service. Conditions are sent as single-condition filters.
+
+impl From<ParsedFormula> for grpc::Formula {
+    fn from(value: ParsedFormula) -> Self {
+        let ParsedFormula {
+            payload_vars: _,
+            datetime_vars: _,
+            geo_vars: _,
+            conditions,
+            defaults,
+            formula,
+        } = value;
+
+        grpc::Formula {
+            expression: Some(expression_to_grpc(formula, &conditions)),
+            defaults: defaults
+                .into_iter()
+                .map(|(variable, value)| {
+                    (
+                        variable.to_string(),
+                        api::conversions::json::json_to_proto(value),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+fn expression_to_grpc(expression: ParsedExpression, conditions: &[Filter]) -> grpc::Expression {
+    use grpc::expression::Variant;
+
+    let to_boxed =
+        |expression: Box<ParsedExpression>| Box::new(expression_to_grpc(*expression, conditions));
+
+    let variant = match expression {
+        ParsedExpression::Constant(constant) => Variant::Constant(constant as f32),
+        ParsedExpression::Variable(VariableId::Condition(condition_idx)) => {
+            // Condition indices are only assigned during parsing, so they are always valid
+            let filter = conditions[condition_idx].clone();
+            Variant::Condition(grpc::Condition::from(Condition::Filter(filter)))
+        }
+        ParsedExpression::Variable(variable) => Variant::Variable(variable.to_string()),
+        ParsedExpression::Mult(expressions) => Variant::Mult(grpc::MultExpression {
+            mult: expressions
+                .into_iter()
+                .map(|expression| expression_to_grpc(expression, conditions))
+                .collect(),
+        }),
+        ParsedExpression::Sum(expressions) => Variant::Sum(grpc::SumExpression {
+            sum: expressions
+                .into_iter()
+                .map(|expression| expression_to_grpc(expression, conditions))
+                .collect(),
+        }),
+        ParsedExpression::Neg(expression) => Variant::Neg(to_boxed(expression)),
+        ParsedExpression::Div {
+            left,
+            right,
+            by_zero_default,
+        } => Variant::Div(Box::new(grpc::DivExpression {
+            left: Some(to_boxed(left)),
+            right: Some(to_boxed(right)),
+            by_zero_default: by_zero_default.map(|default| default as f32),
+        })),
+        ParsedExpression::Sqrt(expression) => Variant::Sqrt(to_boxed(expression)),
+        ParsedExpression::Pow { base, exponent } => Variant::Pow(Box::new(grpc::PowExpression {
+            base: Some(to_boxed(base)),
+            exponent: Some(to_boxed(exponent)),
+        })),
+        ParsedExpression::Exp(expression) => Variant::Exp(to_boxed(expression)),
+        ParsedExpression::Log10(expression) => Variant::Log10(to_boxed(expression)),
+        ParsedExpression::Ln(expression) => Variant::Ln(to_boxed(expression)),
+        ParsedExpression::Abs(expression) => Variant::Abs(to_boxed(expression)),
+        ParsedExpression::Datetime(DatetimeExpression::Constant(datetime)) => {
+            Variant::Datetime(datetime.0.to_rfc3339())
+        }
+        ParsedExpression::Datetime(DatetimeExpression::PayloadVariable(key)) => {
+            Variant::DatetimeKey(key.to_string())
+        }
+        ParsedExpression::GeoDistance { origin, key } => Variant::GeoDistance(grpc::GeoDistance {
+            origin: Some(grpc::GeoPoint::from(origin)),
+            to: key.to_string(),
+        }),
+        ParsedExpression::Decay {
+            kind,
+            x,
+            origin,
+            params,
+        } => {
+            let DecayParams {
+                scale,
+                offset,
+                decay,
+            } = params;
+            let decay = Box::new(grpc::DecayParamsExpression {
+                x: Some(to_boxed(x)),
+                origin: origin.map(to_boxed),
+                scale: Some(scale as f32),
+                offset: Some(offset as f32),
+                decay: Some(decay as f32),
+            });
+            match kind {
+                DecayKind::Lin => Variant::LinDecay(decay),
+                DecayKind::Exp => Variant::ExpDecay(decay),
+                DecayKind::Gauss => Variant::GaussDecay(decay),
+            }
+        }
+    };
+
+    grpc::Expression {
+        variant: Some(variant),
+    }
+}
+
+impl TryFrom<grpc::Formula> for ParsedFormula {
+    type Error = Status;
+
+    fn try_from(value: grpc::Formula) -> Result<Self, Self::Error> {
+        let formula = FormulaInternal::try_from(value)?;
+        ParsedFormula::try_from(formula).map_err(|err| Status::invalid_argument(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::{FieldCondition, Join, JoinCondition, Match, ValueVariants};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_formula() {
+        let condition = Condition::Field(FieldCondition::new_match(
+            "color".try_into().unwrap(),
+            Match::new_value(ValueVariants::String("red".to_string())),
+        ));
+
+        // $score[1] + price * (color == red)
+        let formula = FormulaInternal {
+            formula: ExpressionInternal::Sum(vec![
+                ExpressionInternal::Variable("$score[1]".to_string()),
+                ExpressionInternal::Mult(vec![
+                    ExpressionInternal::Variable("price".to_string()),
+                    ExpressionInternal::Condition(Box::new(condition.clone())),
+                ]),
+            ]),
+            defaults: HashMap::from([("price".to_string(), json!(1.5))]),
+        };
+
+        let parsed = ParsedFormula::try_from(formula).unwrap();
+
+        let price = JsonPath::try_from("price").unwrap();
+        assert_eq!(parsed.payload_vars, HashSet::from([price.clone()]));
+        assert_eq!(parsed.conditions, vec![Filter::new_must(condition)]);
+        assert_eq!(
+            parsed.defaults,
+            HashMap::from([(VariableId::Payload(price.clone()), json!(1.5))]),
+        );
+        assert_eq!(
+            parsed.formula,
+            ParsedExpression::Sum(vec![
+                ParsedExpression::Variable(VariableId::Score(1)),
+                ParsedExpression::Mult(vec![
+                    ParsedExpression::Variable(VariableId::Payload(price)),
+                    ParsedExpression::Variable(VariableId::Condition(0)),
+                ]),
+            ]),
+        );
+
+        // Round trip through the internal service representation
+        let grpc_formula = grpc::Formula::from(parsed.clone());
+        let round_trip = ParsedFormula::try_from(grpc_formula).unwrap();
+        assert_eq!(round_trip.payload_vars, parsed.payload_vars);
+        assert_eq!(round_trip.defaults, parsed.defaults);
+        assert_eq!(round_trip.formula, parsed.formula);
+    }
+
+    #[test]
+    fn test_reject_join_in_formula() {
+        let join = Condition::Join(JoinCondition {
+            join: Join {
+                key: "doc_id".try_into().unwrap(),
+                collection: "docs".to_string(),
+                filter: None,
+                related_key: None,
+                limit: None,
+            },
+        });
+
+        let formula = FormulaInternal {
+            formula: ExpressionInternal::Condition(Box::new(Condition::Filter(
+                Filter::new_must_not(join),
+            ))),
+            defaults: HashMap::new(),
+        };
+
+        assert!(ParsedFormula::try_from(formula).is_err());
+    }
+
+    #[test]
+    fn test_parse_decay() {
+        let created_at = JsonPath::try_from("created_at").unwrap();
+        let location = JsonPath::try_from("location").unwrap();
+
+        // gauss_decay(datetime_key(created_at), origin: datetime(...)) * exp_decay(geo_distance)
+        let formula = FormulaInternal {
+            formula: ExpressionInternal::Mult(vec![
+                ExpressionInternal::Decay {
+                    kind: DecayKind::Gauss,
+                    x: Box::new(ExpressionInternal::DatetimeKey(created_at.clone())),
+                    origin: Some(Box::new(ExpressionInternal::Datetime(
+                        "2025-01-01T00:00:00Z".to_string(),
+                    ))),
+                    scale: Some(86400.0),
+                    offset: None,
+                    decay: None,
+                },
+                ExpressionInternal::Decay {
+                    kind: DecayKind::Exp,
+                    x: Box::new(ExpressionInternal::GeoDistance {
+                        origin: GeoPoint { lon: 0.0, lat: 0.0 },
+                        to: location.clone(),
+                    }),
+                    origin: None,
+                    scale: Some(1000.0),
+                    offset: Some(100.0),
+                    decay: Some(0.25),
+                },
+            ]),
+            defaults: HashMap::new(),
+        };
+
+        let parsed = ParsedFormula::try_from(formula.clone()).unwrap();
+        assert!(parsed.payload_vars.is_empty());
+        assert_eq!(parsed.datetime_vars, HashSet::from([created_at]));
+        assert_eq!(parsed.geo_vars, HashSet::from([location]));
+
+        // Round trip through the internal service representation
+        let grpc_formula = grpc::Formula::from(parsed.clone());
+        let round_trip = ParsedFormula::try_from(grpc_formula).unwrap();
+        assert_eq!(round_trip, parsed);
+
+        // Invalid decay params
+        let mut invalid = formula;
+        if let ExpressionInternal::Mult(expressions) = &mut invalid.formula {
+            if let ExpressionInternal::Decay { decay, .. } = &mut expressions[1] {
+                *decay = Some(1.5);
+            }
+        }
+        assert!(ParsedFormula::try_from(invalid).is_err());
+
+        // Invalid datetime
+        let invalid = FormulaInternal {
+            formula: ExpressionInternal::Datetime("yesterday".to_string()),
+            defaults: HashMap::new(),
+        };
+        assert!(ParsedFormula::try_from(invalid).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_variable() {
+        let formula = FormulaInternal {
+            formula: ExpressionInternal::Variable("$score[x]".to_string()),
+            defaults: HashMap::new(),
+        };
+        assert!(ParsedFormula::try_from(formula).is_err());
+    }
+}
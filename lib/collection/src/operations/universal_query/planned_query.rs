@@ -170,6 +170,16 @@ impl PlannedQuery {
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
+                Some(ScoringQuery::Mmr(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply MMR without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
@@ -200,6 +210,24 @@ impl PlannedQuery {
 
                     vec![Source::ScrollsIdx(idx)]
                 }
+                Some(ScoringQuery::Bm25(query)) => {
+                    // Everything should come from 1 scroll
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::Bm25 {
+                            query,
+                            score_threshold,
+                        },
+                        limit,
+                        filter,
+                        with_vector,
+                        with_payload,
+                    };
+
+                    let idx = self.scrolls.len();
+                    self.scrolls.push(scroll);
+
+                    vec![Source::ScrollsIdx(idx)]
+                }
                 None => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
@@ -315,6 +343,16 @@ fn recurse_prefetches(
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
+                Some(ScoringQuery::Mmr(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply MMR without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     let scroll = QueryScrollRequestInternal {
                         scroll_order: ScrollOrder::ByField(order_by),
@@ -343,6 +381,23 @@ fn recurse_prefetches(
 
                     Source::ScrollsIdx(idx)
                 }
+                Some(ScoringQuery::Bm25(query)) => {
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::Bm25 {
+                            query,
+                            score_threshold,
+                        },
+                        filter,
+                        with_vector: with_vector.clone(),
+                        with_payload: with_payload.clone(),
+                        limit,
+                    };
+
+                    let idx = scrolls.len();
+                    scrolls.push(scroll);
+
+                    Source::ScrollsIdx(idx)
+                }
                 None => {
                     let scroll = QueryScrollRequestInternal {
                         scroll_order: Default::default(),
@@ -382,6 +437,7 @@ mod tests {
 
     use std::collections::HashSet;
 
+    use segment::data_types::bm25::Bm25Query;
     use segment::data_types::vectors::{
         MultiDenseVectorInternal, NamedVectorStruct, VectorInternal,
     };
@@ -613,7 +669,7 @@ mod tests {
                     score_threshold: None,
                 },
             ],
-            query: Some(ScoringQuery::Fusion(FusionInternal::Rrf)),
+            query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
             filter: Some(filter_outer.clone()),
             score_threshold: None,
             limit: 50,
@@ -670,7 +726,7 @@ mod tests {
     fn test_try_from_rrf_without_source() {
         let request = ShardQueryRequest {
             prefetches: vec![],
-            query: Some(ScoringQuery::Fusion(FusionInternal::Rrf)),
+            query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
             filter: Some(Filter::default()),
             score_threshold: None,
             limit: 50,
@@ -685,6 +741,76 @@ mod tests {
         assert!(planned_query.is_err())
     }
 
+    #[test]
+    fn test_try_from_bm25_hybrid_query() {
+        let dummy_vector = vec![1.0, 2.0, 3.0];
+        let bm25 = Bm25Query {
+            key: JsonPath::new("description"),
+            text: "rust vector database".to_string(),
+            k1: Bm25Query::DEFAULT_K1,
+            b: Bm25Query::DEFAULT_B,
+        };
+
+        let request = ShardQueryRequest {
+            prefetches: vec![
+                ShardPrefetch {
+                    prefetches: Vec::new(),
+                    query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                        NamedVectorStruct::new_from_vector(
+                            VectorInternal::Dense(dummy_vector.clone()),
+                            "dense",
+                        ),
+                    ))),
+                    limit: 100,
+                    params: None,
+                    filter: None,
+                    score_threshold: None,
+                },
+                ShardPrefetch {
+                    prefetches: Vec::new(),
+                    query: Some(ScoringQuery::Bm25(bm25.clone())),
+                    limit: 100,
+                    params: None,
+                    filter: None,
+                    score_threshold: Some(0.5),
+                },
+            ],
+            query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
+            filter: None,
+            score_threshold: None,
+            limit: 50,
+            offset: 0,
+            params: None,
+            with_payload: WithPayloadInterface::Bool(false),
+            with_vector: WithVector::Bool(true),
+        };
+
+        let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+        assert_eq!(planned_query.searches.len(), 1);
+        assert_eq!(
+            planned_query.scrolls,
+            vec![QueryScrollRequestInternal {
+                scroll_order: ScrollOrder::Bm25 {
+                    query: bm25,
+                    score_threshold: Some(0.5),
+                },
+                filter: None,
+                limit: 100,
+                with_payload: WithPayloadInterface::Bool(false),
+                with_vector: WithVector::Bool(true),
+            }]
+        );
+
+        assert_eq!(
+            planned_query.root_plans,
+            vec![MergePlan {
+                sources: vec![Source::SearchesIdx(0), Source::ScrollsIdx(0)],
+                rescore_params: None
+            }]
+        );
+    }
+
     #[test]
     fn test_base_params_mapping_in_try_from() {
         let dummy_vector = vec![1.0, 2.0, 3.0];
@@ -713,7 +839,7 @@ mod tests {
                 filter: dummy_filter.clone(),
                 score_threshold: Some(0.1),
             }],
-            query: Some(ScoringQuery::Fusion(FusionInternal::Rrf)),
+            query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
             filter: Some(Filter::default()),
             score_threshold: Some(0.666),
             limit: 50,
@@ -934,7 +1060,7 @@ mod tests {
                 prefetches: vec![
                     ShardPrefetch {
                         prefetches: vec![dummy_core_prefetch(30), dummy_core_prefetch(40)],
-                        query: Some(ScoringQuery::Fusion(FusionInternal::Rrf)),
+                        query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
                         filter: None,
                         params: None,
                         score_threshold: None,
@@ -942,7 +1068,7 @@ mod tests {
                     },
                     dummy_scroll_prefetch(50),
                 ],
-                query: Some(ScoringQuery::Fusion(FusionInternal::Rrf)),
+                query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
                 filter: None,
                 score_threshold: None,
                 limit: 10,
@@ -974,7 +1100,7 @@ mod tests {
                         Source::Prefetch(Box::from(MergePlan {
                             sources: vec![Source::SearchesIdx(1), Source::SearchesIdx(2),],
                             rescore_params: Some(RescoreParams {
-                                rescore: ScoringQuery::Fusion(FusionInternal::Rrf),
+                                rescore: ScoringQuery::Fusion(FusionInternal::rrf()),
                                 limit: 10,
                                 score_threshold: None,
                                 with_vector: WithVector::Bool(true),
@@ -1002,4 +1128,58 @@ mod tests {
         assert_eq!(planned_query.scrolls[0].limit, 20);
         assert_eq!(planned_query.scrolls[1].limit, 50);
     }
+
+    #[test]
+    fn test_validate_nested_fusion() {
+        let dummy_prefetch = || ShardPrefetch {
+            prefetches: Default::default(),
+            query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                NamedVectorStruct::new_from_vector(VectorInternal::Dense(vec![1.0, 2.0]), "full"),
+            ))),
+            limit: 100,
+            params: None,
+            filter: None,
+            score_threshold: None,
+        };
+
+        let request_with_nested_fusion = |fusion: FusionInternal| ShardQueryRequest {
+            prefetches: vec![ShardPrefetch {
+                prefetches: vec![dummy_prefetch(), dummy_prefetch()],
+                query: Some(ScoringQuery::Fusion(fusion)),
+                limit: 100,
+                params: None,
+                filter: None,
+                score_threshold: None,
+            }],
+            query: Some(ScoringQuery::Fusion(FusionInternal::rrf())),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let valid = request_with_nested_fusion(FusionInternal::Rrf {
+            k: 60,
+            weights: vec![1.0, 0.5],
+        });
+        assert!(valid.validate().is_ok());
+
+        let zero_k = request_with_nested_fusion(FusionInternal::Rrf {
+            k: 0,
+            weights: vec![],
+        });
+        assert!(zero_k.validate().is_err());
+
+        let missing_weights =
+            request_with_nested_fusion(FusionInternal::Dbsf { weights: vec![1.0] });
+        assert!(missing_weights.validate().is_err());
+
+        let negative_weights = request_with_nested_fusion(FusionInternal::MinMax {
+            weights: vec![1.0, -1.0],
+        });
+        assert!(negative_weights.validate().is_err());
+    }
 }
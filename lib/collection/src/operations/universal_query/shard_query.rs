@@ -1,17 +1,20 @@
 use api::grpc::qdrant as grpc;
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
+use segment::data_types::bm25::Bm25Query;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{
     NamedQuery, NamedVectorStruct, VectorInternal, DEFAULT_VECTOR_NAME,
 };
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
 use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
 use tonic::Status;
 
 use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionError, CollectionResult};
 
 /// Internal response type for a universal query request.
 ///
@@ -48,9 +51,91 @@ impl ShardQueryRequest {
 #[derive(Debug, Clone, PartialEq)]
 pub enum FusionInternal {
     /// Reciprocal Rank Fusion
-    Rrf,
+    Rrf {
+        /// Constant `k` of the RRF formula
+        k: usize,
+        /// Weights of the prefetches, empty means equal weights
+        weights: Vec<f32>,
+    },
     /// Distribution-based score fusion
-    Dbsf,
+    Dbsf {
+        /// Weights of the prefetches, empty means equal weights
+        weights: Vec<f32>,
+    },
+    /// Weighted sum of the min-max normalized scores
+    MinMax {
+        /// Weights of the prefetches, empty means equal weights
+        weights: Vec<f32>,
+    },
+    /// Weighted sum of the z-score normalized scores
+    ZScore {
+        /// Weights of the prefetches, empty means equal weights
+        weights: Vec<f32>,
+    },
+}
+
+impl FusionInternal {
+    /// Reciprocal Rank Fusion with the default parameters
+    pub fn rrf() -> Self {
+        Self::Rrf {
+            k: DEFAULT_RRF_K,
+            weights: Vec::new(),
+        }
+    }
+
+    /// Distribution-based score fusion with the default parameters
+    pub fn dbsf() -> Self {
+        Self::Dbsf {
+            weights: Vec::new(),
+        }
+    }
+
+    pub fn weights(&self) -> &[f32] {
+        match self {
+            Self::Rrf { k: _, weights }
+            | Self::Dbsf { weights }
+            | Self::MinMax { weights }
+            | Self::ZScore { weights } => weights,
+        }
+    }
+
+    /// Check the fusion parameters against the number of prefetches it merges
+    pub fn validate(&self, prefetches: usize) -> CollectionResult<()> {
+        if let Self::Rrf { k: 0, .. } = self {
+            return Err(CollectionError::bad_request(
+                "RRF fusion k must be greater than 0",
+            ));
+        }
+
+        let weights = self.weights();
+        if !weights.is_empty() && weights.len() != prefetches {
+            return Err(CollectionError::bad_request(format!(
+                "Fusion weights must be specified for each prefetch, got {} weights for {} prefetches",
+                weights.len(),
+                prefetches,
+            )));
+        }
+        if weights
+            .iter()
+            .any(|weight| !weight.is_finite() || *weight < 0.0)
+        {
+            return Err(CollectionError::bad_request(
+                "Fusion weights must be finite non-negative numbers",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether the fusion can be represented without parameters, as the plain `Fusion` enum
+    fn has_default_params(&self) -> bool {
+        match self {
+            Self::Rrf { k, weights } => *k == DEFAULT_RRF_K && weights.is_empty(),
+            Self::Dbsf { weights } | Self::MinMax { weights } | Self::ZScore { weights } => {
+                weights.is_empty()
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -58,6 +143,24 @@ pub enum SampleInternal {
     Random,
 }
 
+/// Maximal Marginal Relevance diversification of the prefetched points.
+///
+/// Shards only score the candidates against the vector,
+/// the diversification happens at collection level once the candidates from all shards are merged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MmrInternal {
+    /// Vector to measure the relevance against
+    pub vector: VectorInternal,
+    /// Name of the vector to measure relevance and diversity with
+    pub using: String,
+    /// Balance between relevance (1.0) and diversity (0.0)
+    pub lambda: f32,
+}
+
+impl MmrInternal {
+    pub const DEFAULT_LAMBDA: f32 = 0.5;
+}
+
 /// Same as `Query`, but with the resolved vector references.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScoringQuery {
@@ -72,16 +175,32 @@ pub enum ScoringQuery {
 
     /// Sample points
     Sample(SampleInternal),
+
+    /// Score boosting via an arbitrary formula
+    Formula(ParsedFormula),
+
+    /// Maximal Marginal Relevance diversification
+    Mmr(MmrInternal),
+
+    /// Rank by BM25 relevance of a full-text indexed field
+    Bm25(Bm25Query),
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
             Self::Fusion(fusion) => match fusion {
-                FusionInternal::Rrf => true,
-                FusionInternal::Dbsf => true,
+                FusionInternal::Rrf { .. } => true,
+                FusionInternal::Dbsf { .. } => true,
+                FusionInternal::MinMax { .. } => true,
+                FusionInternal::ZScore { .. } => true,
             },
-            Self::Vector(_) | Self::OrderBy(_) | Self::Sample(_) => false,
+            Self::Vector(_)
+            | Self::OrderBy(_)
+            | Self::Sample(_)
+            | Self::Formula(_)
+            | Self::Mmr(_)
+            | Self::Bm25(_) => false,
         }
     }
 
@@ -89,6 +208,7 @@ impl ScoringQuery {
     pub fn get_vector_name(&self) -> Option<&str> {
         match self {
             Self::Vector(query) => Some(query.get_vector_name()),
+            Self::Mmr(mmr) => Some(&mmr.using),
             _ => None,
         }
     }
@@ -112,11 +232,19 @@ impl ScoringQuery {
                     }
                 }
                 ScoringQuery::Fusion(fusion) => match fusion {
-                    FusionInternal::Rrf | FusionInternal::Dbsf => Some(Order::LargeBetter),
+                    FusionInternal::Rrf { .. }
+                    | FusionInternal::Dbsf { .. }
+                    | FusionInternal::MinMax { .. }
+                    | FusionInternal::ZScore { .. } => Some(Order::LargeBetter),
                 },
                 ScoringQuery::OrderBy(order_by) => Some(Order::from(order_by.direction())),
                 // Random sample does not require ordering
                 ScoringQuery::Sample(SampleInternal::Random) => None,
+                ScoringQuery::Formula(_) => Some(Order::LargeBetter),
+                ScoringQuery::Bm25(_) => Some(Order::LargeBetter),
+                ScoringQuery::Mmr(mmr) => {
+                    Some(collection_params.get_distance(&mmr.using)?.distance_order())
+                }
             },
             None => {
                 // Order by ID
@@ -138,6 +266,14 @@ pub struct ShardPrefetch {
 }
 
 impl ShardPrefetch {
+    /// Check the fusion parameters of this prefetch and all the nested ones
+    fn validate(&self) -> CollectionResult<()> {
+        if let Some(ScoringQuery::Fusion(fusion)) = &self.query {
+            fusion.validate(self.prefetches.len())?;
+        }
+        self.prefetches.iter().try_for_each(ShardPrefetch::validate)
+    }
+
     pub fn depth(&self) -> usize {
         let mut depth = 1;
         for prefetch in &self.prefetches {
@@ -148,6 +284,14 @@ impl ShardPrefetch {
 }
 
 impl ShardQueryRequest {
+    /// Check the fusion parameters of the query and all the nested prefetches
+    pub fn validate(&self) -> CollectionResult<()> {
+        if let Some(ScoringQuery::Fusion(fusion)) = &self.query {
+            fusion.validate(self.prefetches.len())?;
+        }
+        self.prefetches.iter().try_for_each(ShardPrefetch::validate)
+    }
+
     pub fn filter_refs(&self) -> Vec<Option<&Filter>> {
         let mut filters = vec![];
         filters.push(self.filter.as_ref());
@@ -213,6 +357,10 @@ impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
                 .unwrap_or(WithPayloadInterface::Bool(true)),
         };
 
+        request
+            .validate()
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
         Ok(request)
     }
 }
@@ -324,17 +472,71 @@ impl TryFrom<i32> for SampleInternal {
 impl From<api::grpc::qdrant::Fusion> for FusionInternal {
     fn from(fusion: api::grpc::qdrant::Fusion) -> Self {
         match fusion {
-            api::grpc::qdrant::Fusion::Rrf => FusionInternal::Rrf,
-            api::grpc::qdrant::Fusion::Dbsf => FusionInternal::Dbsf,
+            api::grpc::qdrant::Fusion::Rrf => FusionInternal::rrf(),
+            api::grpc::qdrant::Fusion::Dbsf => FusionInternal::dbsf(),
+            api::grpc::qdrant::Fusion::MinMax => FusionInternal::MinMax {
+                weights: Vec::new(),
+            },
+            api::grpc::qdrant::Fusion::ZScore => FusionInternal::ZScore {
+                weights: Vec::new(),
+            },
         }
     }
 }
 
-impl From<FusionInternal> for api::grpc::qdrant::Fusion {
+impl From<&FusionInternal> for api::grpc::qdrant::Fusion {
+    fn from(fusion: &FusionInternal) -> Self {
+        match fusion {
+            FusionInternal::Rrf { .. } => api::grpc::qdrant::Fusion::Rrf,
+            FusionInternal::Dbsf { .. } => api::grpc::qdrant::Fusion::Dbsf,
+            FusionInternal::MinMax { .. } => api::grpc::qdrant::Fusion::MinMax,
+            FusionInternal::ZScore { .. } => api::grpc::qdrant::Fusion::ZScore,
+        }
+    }
+}
+
+impl TryFrom<grpc::FusionQuery> for FusionInternal {
+    type Error = Status;
+
+    fn try_from(fusion: grpc::FusionQuery) -> Result<Self, Self::Error> {
+        let grpc::FusionQuery { fusion, k, weights } = fusion;
+
+        let fusion = FusionInternal::try_from(fusion)?;
+
+        if k.is_some() && !matches!(fusion, FusionInternal::Rrf { .. }) {
+            return Err(Status::invalid_argument(
+                "k is only applicable to RRF fusion",
+            ));
+        }
+
+        Ok(match fusion {
+            FusionInternal::Rrf { .. } => FusionInternal::Rrf {
+                k: k.map_or(DEFAULT_RRF_K, |k| k as usize),
+                weights,
+            },
+            FusionInternal::Dbsf { .. } => FusionInternal::Dbsf { weights },
+            FusionInternal::MinMax { .. } => FusionInternal::MinMax { weights },
+            FusionInternal::ZScore { .. } => FusionInternal::ZScore { weights },
+        })
+    }
+}
+
+impl From<FusionInternal> for grpc::FusionQuery {
     fn from(fusion: FusionInternal) -> Self {
+        let fusion_method = api::grpc::qdrant::Fusion::from(&fusion) as i32;
         match fusion {
-            FusionInternal::Rrf => api::grpc::qdrant::Fusion::Rrf,
-            FusionInternal::Dbsf => api::grpc::qdrant::Fusion::Dbsf,
+            FusionInternal::Rrf { k, weights } => Self {
+                fusion: fusion_method,
+                k: Some(k as u32),
+                weights,
+            },
+            FusionInternal::Dbsf { weights }
+            | FusionInternal::MinMax { weights }
+            | FusionInternal::ZScore { weights } => Self {
+                fusion: fusion_method,
+                k: None,
+                weights,
+            },
         }
     }
 }
@@ -370,12 +572,39 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::Fusion(fusion) => {
                 ScoringQuery::Fusion(FusionInternal::try_from(fusion)?)
             }
+            grpc::query_shard_points::query::Score::FusionQuery(fusion) => {
+                ScoringQuery::Fusion(FusionInternal::try_from(fusion)?)
+            }
+            grpc::query_shard_points::query::Score::MinMaxFusion(fusion) => {
+                let grpc::query_shard_points::NormalizedFusion { weights } = fusion;
+                ScoringQuery::Fusion(FusionInternal::MinMax { weights })
+            }
+            grpc::query_shard_points::query::Score::ZScoreFusion(fusion) => {
+                let grpc::query_shard_points::NormalizedFusion { weights } = fusion;
+                ScoringQuery::Fusion(FusionInternal::ZScore { weights })
+            }
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
             grpc::query_shard_points::query::Score::Sample(sample) => {
                 ScoringQuery::Sample(SampleInternal::try_from(sample)?)
             }
+            grpc::query_shard_points::query::Score::Formula(formula) => {
+                ScoringQuery::Formula(ParsedFormula::try_from(formula)?)
+            }
+            grpc::query_shard_points::query::Score::Bm25(bm25) => {
+                ScoringQuery::Bm25(Bm25Query::try_from(bm25)?)
+            }
+            grpc::query_shard_points::query::Score::Mmr(mmr) => {
+                let grpc::query_shard_points::Mmr { vector, lambda } = mmr;
+                let vector =
+                    vector.ok_or_else(|| Status::invalid_argument("missing field: vector"))?;
+                ScoringQuery::Mmr(MmrInternal {
+                    vector: VectorInternal::try_from(vector)?,
+                    using: using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string()),
+                    lambda,
+                })
+            }
         };
 
         Ok(scoring_query)
@@ -413,8 +642,25 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::Vector(query) => Self {
                 score: Some(Score::Vector(grpc::RawQuery::from(query))),
             },
+            // Normalized fusions have dedicated fields, so that older peers reject them
+            ScoringQuery::Fusion(FusionInternal::MinMax { weights }) => Self {
+                score: Some(Score::MinMaxFusion(
+                    grpc::query_shard_points::NormalizedFusion { weights },
+                )),
+            },
+            ScoringQuery::Fusion(FusionInternal::ZScore { weights }) => Self {
+                score: Some(Score::ZScoreFusion(
+                    grpc::query_shard_points::NormalizedFusion { weights },
+                )),
+            },
+            // Keep sending fusions without parameters in the old format, for compatibility
+            ScoringQuery::Fusion(fusion) if fusion.has_default_params() => Self {
+                score: Some(Score::Fusion(
+                    api::grpc::qdrant::Fusion::from(&fusion) as i32
+                )),
+            },
             ScoringQuery::Fusion(fusion) => Self {
-                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::from(fusion) as i32)),
+                score: Some(Score::FusionQuery(grpc::FusionQuery::from(fusion))),
             },
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
@@ -422,6 +668,26 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::Sample(sample) => Self {
                 score: Some(Score::Sample(api::grpc::qdrant::Sample::from(sample) as i32)),
             },
+            ScoringQuery::Formula(formula) => Self {
+                score: Some(Score::Formula(grpc::Formula::from(formula))),
+            },
+            ScoringQuery::Bm25(bm25) => Self {
+                score: Some(Score::Bm25(grpc::Bm25::from(bm25))),
+            },
+            ScoringQuery::Mmr(mmr) => {
+                // `using` is sent as part of the request
+                let MmrInternal {
+                    vector,
+                    using: _,
+                    lambda,
+                } = mmr;
+                Self {
+                    score: Some(Score::Mmr(grpc::query_shard_points::Mmr {
+                        vector: Some(grpc::RawVector::from(vector)),
+                        lambda,
+                    })),
+                }
+            }
         }
     }
 }
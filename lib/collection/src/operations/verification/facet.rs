@@ -28,7 +28,11 @@ impl StrictModeVerification for FacetRequestInternal {
 
 impl StrictModeVerification for FacetParams {
     fn query_limit(&self) -> Option<usize> {
-        Some(self.limit)
+        // Each value of a level can have its own top values of the next level
+        self.limits()
+            .into_iter()
+            .try_fold(1usize, |product, limit| product.checked_mul(limit))
+            .or(Some(usize::MAX))
     }
 
     fn indexed_filter_read(&self) -> Option<&Filter> {
@@ -1,3 +1,4 @@
+mod aggregate;
 mod count;
 mod discovery;
 mod facet;
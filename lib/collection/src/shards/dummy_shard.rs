@@ -5,6 +5,9 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::tar_ext;
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
@@ -141,4 +144,22 @@ impl ShardOperation for DummyShard {
     ) -> CollectionResult<FacetResponse> {
         self.dummy()
     }
+
+    async fn aggregate(
+        &self,
+        _: Arc<AggregateParams>,
+        _search_runtime_handle: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        self.dummy()
+    }
+
+    async fn distinct_count(
+        &self,
+        _: Arc<DistinctCountParams>,
+        _search_runtime_handle: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        self.dummy()
+    }
 }
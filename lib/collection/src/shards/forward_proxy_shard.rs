@@ -6,6 +6,9 @@ use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::tar_ext;
 use common::types::TelemetryDetail;
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
@@ -424,4 +427,28 @@ impl ShardOperation for ForwardProxyShard {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .aggregate(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn distinct_count(
+        &self,
+        request: Arc<DistinctCountParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .distinct_count(request, search_runtime_handle, timeout)
+            .await
+    }
 }
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::try_join_all;
+use itertools::process_results;
+use segment::common::hyperloglog::HyperLogLog;
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
+use segment::data_types::facets::FacetParams;
+use segment::types::{Condition, Filter, IsEmptyCondition, PayloadField};
+use tokio::runtime::Handle;
+use tokio::time::error::Elapsed;
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::common::stopping_guard::StoppingGuard;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+impl LocalShard {
+    /// Aggregates the values of all segments for the given request.
+    ///
+    /// Outdated versions of points are deleted from their segments on copy-on-write, like for
+    /// facets, so each point is only aggregated once.
+    pub async fn aggregate_segments(
+        &self,
+        request: Arc<AggregateParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let stopping_guard = StoppingGuard::new();
+
+        let spawn_read = |segment: LockedSegment| {
+            let request = Arc::clone(&request);
+            let is_stopped = stopping_guard.get_is_stopped();
+
+            search_runtime_handle.spawn_blocking(move || {
+                let get_segment = segment.get();
+                let read_segment = get_segment.read();
+
+                read_segment.aggregate(&request, &is_stopped)
+            })
+        };
+
+        let all_reads = {
+            let segments_lock = self.segments().read();
+
+            tokio::time::timeout(
+                timeout,
+                try_join_all(
+                    segments_lock
+                        .non_appendable_then_appendable_segments()
+                        .map(spawn_read),
+                ),
+            )
+        }
+        .await
+        .map_err(|_: Elapsed| {
+            CollectionError::timeout(timeout.as_secs() as usize, "aggregate")
+        })??;
+
+        let merged = process_results(all_reads, |reads| {
+            reads.fold(PartialAggregate::default(), |mut acc, aggregate| {
+                acc.merge(aggregate);
+                acc
+            })
+        })?;
+
+        Ok(merged)
+    }
+
+    /// Sketches the distinct values of all segments for the given request.
+    pub async fn approx_distinct_values(
+        &self,
+        request: Arc<DistinctCountParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let stopping_guard = StoppingGuard::new();
+
+        let spawn_read = |segment: LockedSegment| {
+            let request = Arc::clone(&request);
+            let is_stopped = stopping_guard.get_is_stopped();
+
+            search_runtime_handle.spawn_blocking(move || {
+                let get_segment = segment.get();
+                let read_segment = get_segment.read();
+
+                read_segment.distinct_count(&request, &is_stopped)
+            })
+        };
+
+        let all_reads = {
+            let segments_lock = self.segments().read();
+
+            tokio::time::timeout(
+                timeout,
+                try_join_all(
+                    segments_lock
+                        .non_appendable_then_appendable_segments()
+                        .map(spawn_read),
+                ),
+            )
+        }
+        .await
+        .map_err(|_: Elapsed| {
+            CollectionError::timeout(timeout.as_secs() as usize, "distinct_count")
+        })??;
+
+        let merged = process_results(all_reads, |reads| {
+            reads.fold(HyperLogLog::default(), |mut acc, sketch| {
+                acc.merge(sketch);
+                acc
+            })
+        })?;
+
+        Ok(PartialDistinctCount::Sketch(merged))
+    }
+
+    /// Collects the distinct values having at least one matching point, counted exactly.
+    pub async fn exact_distinct_values(
+        &self,
+        request: Arc<DistinctCountParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        let DistinctCountParams {
+            key,
+            filter,
+            exact: _,
+        } = request.as_ref();
+
+        // Values are read from the points, so that values of deleted points are not counted
+        let has_values = Filter::new_must_not(Condition::IsEmpty(IsEmptyCondition {
+            is_empty: PayloadField { key: key.clone() },
+        }));
+
+        // Only the key and the filter are used to collect the values
+        let facet_request = FacetParams {
+            key: key.clone(),
+            limit: 0,
+            filter: Filter::merge_opts(filter.clone(), Some(has_values)),
+            exact: true,
+            geo_grid: None,
+            histogram: None,
+            pivot: vec![],
+        };
+
+        let values = self
+            .unique_values(Arc::new(facet_request), search_runtime_handle, timeout)
+            .await?;
+
+        Ok(PartialDistinctCount::from_values(values))
+    }
+}
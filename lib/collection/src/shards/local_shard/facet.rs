@@ -6,7 +6,7 @@ use futures::future;
 use futures::future::try_join_all;
 use itertools::{process_results, Itertools};
 use segment::data_types::facets::{FacetParams, FacetValue, FacetValueHit};
-use segment::types::{Condition, FieldCondition, Filter, Match};
+use segment::types::Filter;
 use tokio::runtime::Handle;
 use tokio::time::error::Elapsed;
 
@@ -57,7 +57,7 @@ impl LocalShard {
         let merged_hits = process_results(all_reads, |reads| {
             reads.reduce(|mut acc, map| {
                 map.into_iter()
-                    .for_each(|(value, count)| *acc.entry(value).or_insert(0) += count);
+                    .for_each(|(value, count)| acc.entry(value).or_default().merge(count));
                 acc
             })
         })?;
@@ -68,11 +68,8 @@ impl LocalShard {
         // We need all values to be able to aggregate correctly across segments
         let top_hits = merged_hits
             .map(|map| {
-                map.iter()
-                    .map(|(value, count)| FacetValueHit {
-                        value: value.to_owned(),
-                        count: *count,
-                    })
+                map.into_iter()
+                    .map(|(value, count)| count.into_hit(value))
                     .collect_vec()
             })
             .unwrap_or_default();
@@ -97,16 +94,32 @@ impl LocalShard {
         let instant = std::time::Instant::now();
 
         // Get unique values for the field
-        let unique_values = self
-            .unique_values(Arc::clone(&request), search_runtime_handle, timeout)
-            .await?;
+        let unique_values = if request.histogram.is_some() || !request.pivot.is_empty() {
+            // Buckets and combinations are not stored in the index, take the ones having points in any segment
+            self.approx_facet(Arc::clone(&request), search_runtime_handle, Some(timeout))
+                .await?
+                .into_iter()
+                .map(|hit| hit.value)
+                .collect::<BTreeSet<_>>()
+        } else {
+            self.unique_values(Arc::clone(&request), search_runtime_handle, timeout)
+                .await?
+        };
+
+        // Each combination is counted with its own read, so their number is bounded
+        if !request.pivot.is_empty()
+            && unique_values.len() > FacetParams::MAX_EXACT_PIVOT_COMBINATIONS
+        {
+            return Err(CollectionError::bad_request(format!(
+                "Exact pivot facet has more than {} combinations of values, use a more selective filter or an approximate count",
+                FacetParams::MAX_EXACT_PIVOT_COMBINATIONS,
+            )));
+        }
 
         // Make an exact count for each value
+        let keys = request.keys();
         let hits_futures = unique_values.into_iter().map(|value| {
-            let match_value = Filter::new_must(Condition::Field(FieldCondition::new_match(
-                request.key.clone(),
-                Match::new_value(From::from(value.clone())),
-            )));
+            let match_value = value.to_filter(&keys);
 
             let filter = Filter::merge_opts(request.filter.clone(), Some(match_value));
 
@@ -115,7 +128,11 @@ impl LocalShard {
                     .read_filtered(filter.as_ref(), search_runtime_handle)
                     .await?
                     .len();
-                CollectionResult::Ok(FacetValueHit { value, count })
+                CollectionResult::Ok(FacetValueHit {
+                    value,
+                    count,
+                    centroid: None,
+                })
             }
         });
 
@@ -129,7 +146,7 @@ impl LocalShard {
         Ok(hits)
     }
 
-    async fn unique_values(
+    pub(super) async fn unique_values(
         &self,
         request: Arc<FacetParams>,
         handle: &Handle,
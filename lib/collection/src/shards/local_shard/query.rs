@@ -9,12 +9,16 @@ use futures::FutureExt;
 use parking_lot::Mutex;
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::common::score_fusion::{score_fusion, ScoreFusion};
+use segment::data_types::vectors::NamedVectorStruct;
+use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
 use tokio::runtime::Handle;
 use tokio::time::error::Elapsed;
 
 use super::LocalShard;
+use crate::collection::mmr;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
     QueryScrollRequestInternal, ScrollOrder,
@@ -23,7 +27,7 @@ use crate::operations::universal_query::planned_query::{
     MergePlan, PlannedQuery, RescoreParams, Source,
 };
 use crate::operations::universal_query::shard_query::{
-    FusionInternal, SampleInternal, ScoringQuery, ShardQueryResponse,
+    FusionInternal, MmrInternal, SampleInternal, ScoringQuery, ShardQueryResponse,
 };
 
 pub enum FetchedSource {
@@ -336,6 +340,88 @@ impl LocalShard {
                     })
                 }
             },
+            ScoringQuery::Mmr(MmrInternal {
+                vector,
+                using,
+                lambda: _,
+            }) => {
+                // Score all the candidates against the vector, diversification happens at collection level
+                let candidates_limit = sources.iter().map(Vec::len).sum();
+                let filter = filter_with_sources_ids(sources.into_iter());
+
+                let search_request = CoreSearchRequest {
+                    query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                        vector,
+                        using.clone(),
+                    )),
+                    filter: Some(filter),
+                    params,
+                    limit: candidates_limit,
+                    offset: 0,
+                    with_payload: Some(with_payload),
+                    with_vector: Some(mmr::with_mmr_vector(with_vector, using)),
+                    score_threshold,
+                };
+                let rescoring_core_search_request = CoreSearchRequestBatch {
+                    searches: vec![search_request],
+                };
+
+                self.do_search(
+                    Arc::new(rescoring_core_search_request),
+                    search_runtime_handle,
+                    Some(timeout),
+                    hw_counter_acc,
+                )
+                .await?
+                // One search request is sent. We expect only one result
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with MMR query didn't return expected batch of results",
+                    )
+                })
+            }
+            ScoringQuery::Bm25(query) => {
+                // create single scroll request for rescoring query
+                let filter = filter_with_sources_ids(sources.into_iter());
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload,
+                    with_vector,
+                    scroll_order: ScrollOrder::Bm25 {
+                        query,
+                        score_threshold,
+                    },
+                };
+
+                self.query_scroll_batch(
+                    Arc::new(vec![scroll_request]),
+                    search_runtime_handle,
+                    timeout,
+                )
+                .await?
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with BM25 query didn't return expected batch of results",
+                    )
+                })
+            }
+            ScoringQuery::Formula(formula) => {
+                self.formula_rescore(
+                    sources,
+                    formula,
+                    score_threshold,
+                    limit,
+                    with_payload,
+                    with_vector,
+                    search_runtime_handle,
+                    timeout,
+                )
+                .await
+            }
         }
     }
 
@@ -351,8 +437,28 @@ impl LocalShard {
         timeout: Duration,
     ) -> Result<Vec<ScoredPoint>, CollectionError> {
         let fused = match fusion {
-            FusionInternal::Rrf => rrf_scoring(sources),
-            FusionInternal::Dbsf => score_fusion(sources, ScoreFusion::dbsf()),
+            FusionInternal::Rrf { k, weights } => rrf_scoring(sources, k, &weights),
+            FusionInternal::Dbsf { weights } => score_fusion(
+                sources,
+                ScoreFusion {
+                    weights,
+                    ..ScoreFusion::dbsf()
+                },
+            ),
+            FusionInternal::MinMax { weights } => score_fusion(
+                sources,
+                ScoreFusion {
+                    weights,
+                    ..ScoreFusion::min_max()
+                },
+            ),
+            FusionInternal::ZScore { weights } => score_fusion(
+                sources,
+                ScoreFusion {
+                    weights,
+                    ..ScoreFusion::z_score()
+                },
+            ),
         };
 
         let top_fused: Vec<_> = if let Some(score_threshold) = score_threshold {
@@ -371,6 +477,46 @@ impl LocalShard {
 
         Ok(filled_top_fused)
     }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn formula_rescore(
+        &self,
+        sources: Vec<Vec<ScoredPoint>>,
+        formula: ParsedFormula,
+        score_threshold: Option<f32>,
+        limit: usize,
+        with_payload: WithPayloadInterface,
+        with_vector: WithVector,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+    ) -> Result<Vec<ScoredPoint>, CollectionError> {
+        let rescored = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::rescore_with_formula(
+                self.segments.clone(),
+                formula,
+                sources,
+                limit,
+                search_runtime_handle,
+            ),
+        )
+        .await
+        .map_err(|_: Elapsed| CollectionError::timeout(timeout.as_secs() as usize, "rescore"))??;
+
+        let top_rescored = match score_threshold {
+            Some(score_threshold) => rescored
+                .into_iter()
+                .take_while(|point| point.score >= score_threshold)
+                .collect(),
+            None => rescored,
+        };
+
+        let filled_top_rescored = self
+            .fill_with_payload_or_vectors(top_rescored, with_payload, with_vector, timeout)
+            .await?;
+
+        Ok(filled_top_rescored)
+    }
 }
 
 /// Extracts point ids from sources, and creates a filter to only include those ids.
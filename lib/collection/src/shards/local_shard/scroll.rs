@@ -2,12 +2,14 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use common::types::ScoreType;
 use futures::future::try_join_all;
 use itertools::Itertools as _;
 use rand::distributions::WeightedIndex;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
+use segment::data_types::bm25::Bm25Query;
+use segment::data_types::order_by::{OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -141,6 +143,22 @@ impl LocalShard {
                     })
                     .collect()
             }
+            ScrollOrder::Bm25 {
+                query,
+                score_threshold,
+            } => {
+                self.scroll_by_bm25(
+                    limit,
+                    with_payload,
+                    with_vector,
+                    filter.as_ref(),
+                    search_runtime_handle,
+                    query,
+                    *score_threshold,
+                    timeout,
+                )
+                .await?
+            }
         };
 
         Ok(point_results)
@@ -275,10 +293,7 @@ impl LocalShard {
 
         let (values, point_ids): (Vec<_>, Vec<_>) = all_reads
             .into_iter()
-            .kmerge_by(|a, b| match order_by.direction() {
-                Direction::Asc => a <= b,
-                Direction::Desc => a >= b,
-            })
+            .kmerge_by(|a, b| order_by.cmp_entries(a, b).is_le())
             .dedup()
             .take(limit)
             .unzip();
@@ -425,4 +440,77 @@ impl LocalShard {
 
         Ok(records_map.into_values().collect())
     }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn scroll_by_bm25(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        query: &Bm25Query,
+        score_threshold: Option<ScoreType>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let start = Instant::now();
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+        let segments = self.segments.clone();
+
+        let ranked = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::search_bm25(
+                segments.clone(),
+                query.clone(),
+                filter.cloned(),
+                limit,
+                search_runtime_handle,
+            ),
+        )
+        .await
+        .map_err(|_: Elapsed| {
+            CollectionError::timeout(timeout.as_secs() as usize, "scroll_by_bm25")
+        })??;
+
+        let ranked = match score_threshold {
+            Some(score_threshold) => ranked
+                .into_iter()
+                .take_while(|point| point.score >= score_threshold)
+                .collect(),
+            None => ranked,
+        };
+
+        let point_ids = ranked.iter().map(|point| point.id).collect_vec();
+
+        let with_payload = WithPayload::from(with_payload_interface);
+        // update timeout
+        let timeout = timeout.saturating_sub(start.elapsed());
+        let mut records_map = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::retrieve(
+                segments,
+                &point_ids,
+                &with_payload,
+                with_vector,
+                search_runtime_handle,
+            ),
+        )
+        .await
+        .map_err(|_: Elapsed| CollectionError::timeout(timeout.as_secs() as usize, "retrieve"))??;
+
+        let ranked_points = ranked
+            .into_iter()
+            .filter_map(|point| {
+                let record = records_map.remove(&point.id)?;
+                Some(ScoredPoint {
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    ..point
+                })
+            })
+            .collect();
+
+        Ok(ranked_points)
+    }
 }
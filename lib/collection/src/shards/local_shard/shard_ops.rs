@@ -3,6 +3,9 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
@@ -153,8 +156,10 @@ impl ShardOperation for LocalShard {
                 records.iter_mut().zip(values).for_each(|(record, value)| {
                     // TODO(1.11): stop inserting the value in the payload, only use the order_value
                     // Add order_by value to the payload. It will be removed in the next step, after crossing the shard boundary.
-                    let new_payload =
-                        OrderBy::insert_order_value_in_payload(record.payload.take(), value);
+                    let new_payload = OrderBy::insert_order_value_in_payload(
+                        record.payload.take(),
+                        value.clone(),
+                    );
 
                     record.payload = Some(new_payload);
                     record.order_value = Some(value);
@@ -279,4 +284,33 @@ impl ShardOperation for LocalShard {
         };
         Ok(FacetResponse { hits })
     }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        // Check read rate limiter before proceeding
+        self.check_read_rate_limiter()?;
+        self.aggregate_segments(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn distinct_count(
+        &self,
+        request: Arc<DistinctCountParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        // Check read rate limiter before proceeding
+        self.check_read_rate_limiter()?;
+        if request.exact {
+            self.exact_distinct_values(request, search_runtime_handle, timeout)
+                .await
+        } else {
+            self.approx_distinct_values(request, search_runtime_handle, timeout)
+                .await
+        }
+    }
 }
@@ -8,6 +8,9 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::tar_ext;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
@@ -326,6 +329,30 @@ impl ShardOperation for QueueProxyShard {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .aggregate(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn distinct_count(
+        &self,
+        request: Arc<DistinctCountParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .distinct_count(request, search_runtime_handle, timeout)
+            .await
+    }
 }
 
 // Safe guard in debug mode to ensure that `finalize()` is called before dropping
@@ -647,6 +674,30 @@ impl ShardOperation for Inner {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .aggregate(request, search_runtime_handle, timeout)
+            .await
+    }
+
+    async fn distinct_count(
+        &self,
+        request: Arc<DistinctCountParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .distinct_count(request, search_runtime_handle, timeout)
+            .await
+    }
 }
 
 /// Transfer batch of operations without retries
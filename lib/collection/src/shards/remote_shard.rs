@@ -9,12 +9,13 @@ use api::grpc::qdrant::qdrant_client::QdrantClient;
 use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
-    CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints,
-    GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
-    InitiateShardTransferRequest, QueryBatchPointsInternal, QueryShardPoints,
-    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
-    ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    AggregatePointsInternal, CollectionOperationResponse, CoreSearchBatchPointsInternal,
+    CountPoints, CountPointsInternal, DistinctCountPointsInternal, FacetCountsInternal,
+    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
+    GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
+    QueryBatchPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest,
+    RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation,
+    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
@@ -25,6 +26,9 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse, FacetValueHit};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
@@ -973,6 +977,9 @@ impl ShardOperation for RemoteShard {
             limit,
             filter,
             exact,
+            geo_grid,
+            histogram,
+            pivot,
         } = request.as_ref();
 
         let response = self
@@ -985,6 +992,9 @@ impl ShardOperation for RemoteShard {
                     exact: *exact,
                     shard_id: self.id,
                     timeout: processed_timeout.map(|t| t.as_secs()),
+                    geo_grid: geo_grid.clone().map(From::from),
+                    histogram: histogram.clone().map(From::from),
+                    pivot: pivot.iter().cloned().map(From::from).collect(),
                 };
 
                 let mut request = tonic::Request::new(request.clone());
@@ -1010,4 +1020,94 @@ impl ShardOperation for RemoteShard {
 
         Ok(result)
     }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregateParams>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialAggregate> {
+        let processed_timeout = Self::process_read_timeout(timeout, "aggregate")?;
+        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
+        timer.set_success(false);
+
+        let AggregateParams {
+            key,
+            filter,
+            percentiles: _,
+        } = request.as_ref();
+
+        let response = self
+            .with_points_client(|mut client| async move {
+                let request = &AggregatePointsInternal {
+                    collection_name: self.collection_id.clone(),
+                    key: key.to_string(),
+                    filter: filter.clone().map(api::grpc::qdrant::Filter::from),
+                    shard_id: self.id,
+                    timeout: processed_timeout.map(|t| t.as_secs()),
+                };
+
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.aggregate(request).await
+            })
+            .await?
+            .into_inner();
+
+        let result = response.result.map(PartialAggregate::from).ok_or_else(|| {
+            CollectionError::service_error("Unexpected empty PartialAggregateInternal")
+        })?;
+
+        timer.set_success(true);
+
+        Ok(result)
+    }
+
+    async fn distinct_count(
+        &self,
+        request: Arc<DistinctCountParams>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<PartialDistinctCount> {
+        let processed_timeout = Self::process_read_timeout(timeout, "distinct_count")?;
+        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
+        timer.set_success(false);
+
+        let DistinctCountParams { key, filter, exact } = request.as_ref();
+
+        let response = self
+            .with_points_client(|mut client| async move {
+                let request = &DistinctCountPointsInternal {
+                    collection_name: self.collection_id.clone(),
+                    key: key.to_string(),
+                    filter: filter.clone().map(api::grpc::qdrant::Filter::from),
+                    exact: *exact,
+                    shard_id: self.id,
+                    timeout: processed_timeout.map(|t| t.as_secs()),
+                };
+
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.distinct_count(request).await
+            })
+            .await?
+            .into_inner();
+
+        let result = response.result.ok_or_else(|| {
+            CollectionError::service_error("Unexpected empty PartialDistinctCountInternal")
+        })?;
+        let result = PartialDistinctCount::try_from(result)?;
+
+        timer.set_success(true);
+
+        Ok(result)
+    }
 }
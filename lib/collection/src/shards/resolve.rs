@@ -4,6 +4,7 @@ use std::iter::Peekable;
 use std::rc::Rc;
 
 use itertools::Itertools;
+use segment::data_types::aggregations::{PartialAggregate, PartialDistinctCount};
 use segment::data_types::facets::{FacetResponse, FacetValue};
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
@@ -132,6 +133,34 @@ impl Resolve for FacetResponse {
     }
 }
 
+impl Resolve for PartialAggregate {
+    /// Pick the aggregate of one replica, by number of values like the CountResult implementation
+    fn resolve(mut responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        responses.sort_unstable_by_key(|aggregate| aggregate.count);
+
+        let position = match condition {
+            ResolveCondition::All => 0,
+            ResolveCondition::Majority => responses.len() / 2,
+        };
+
+        responses.into_iter().nth(position).unwrap_or_default()
+    }
+}
+
+impl Resolve for PartialDistinctCount {
+    /// Pick the values of one replica, by number of values like the CountResult implementation
+    fn resolve(mut responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        responses.sort_by_cached_key(PartialDistinctCount::count);
+
+        let position = match condition {
+            ResolveCondition::All => 0,
+            ResolveCondition::Majority => responses.len() / 2,
+        };
+
+        responses.into_iter().nth(position).unwrap_or_default()
+    }
+}
+
 impl Resolve for Vec<RecordInternal> {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         Resolver::resolve(records, |record| record.id, record_eq, condition)
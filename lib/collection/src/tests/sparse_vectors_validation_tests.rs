@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use api::rest::{
-    BaseGroupRequest, Batch, BatchVectorStruct, PointStruct, PointVectors, PointsList,
-    SearchGroupsRequestInternal, SearchRequestInternal, Vector, VectorStruct,
+    BaseGroupRequest, Batch, BatchVectorStruct, GroupByInterface, PointStruct, PointVectors,
+    PointsList, SearchGroupsRequestInternal, SearchRequestInternal, Vector, VectorStruct,
 };
 use sparse::common::sparse_vector::SparseVector;
 use validator::Validate;
@@ -97,7 +97,8 @@ fn validate_error_sparse_vector_search_groups_request_internal() {
         with_vector: None,
         score_threshold: None,
         group_request: BaseGroupRequest {
-            group_by: "sparse".parse().unwrap(),
+            group_by: GroupByInterface::Field("sparse".parse().unwrap()),
+            group_score: None,
             group_size: 5,
             limit: 5,
             with_lookup: None,
@@ -17,7 +17,9 @@ use collection::recommendations::recommend_by;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::{
+    Direction, OrderBy, OrderByKey, OrderCursor, OrderValue, StartFrom,
+};
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
     Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
@@ -572,6 +574,9 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        then_by: vec![],
+                        start_after: None,
+                        distance_from: None,
                     })),
                 },
                 None,
@@ -603,6 +608,9 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        then_by: vec![],
+                        start_after: None,
+                        distance_from: None,
                     })),
                 },
                 None,
@@ -643,6 +651,9 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        then_by: vec![],
+                        start_after: None,
+                        distance_from: None,
                     })),
                 },
                 None,
@@ -682,6 +693,9 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        then_by: vec![],
+                        start_after: None,
+                        distance_from: None,
                     })),
                 },
                 None,
@@ -742,6 +756,231 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .all(|&x| x == 2));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ordered_read_by_keyword_api() {
+    test_ordered_scroll_by_keyword_with_shards(1).await;
+    test_ordered_scroll_by_keyword_with_shards(N_SHARDS).await;
+}
+
+async fn test_ordered_scroll_by_keyword_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const TITLE_KEY: &str = "title";
+
+    let titles = ["delta", "alpha", "echo", "charlie", "bravo", "foxtrot"];
+
+    let batch = BatchPersisted {
+        ids: (0..titles.len() as u64).map(|x| x.into()).collect_vec(),
+        vectors: BatchVectorStructPersisted::Single(vec![vec![1.0, 0.0, 0.0, 0.0]; titles.len()]),
+        payloads: Some(
+            titles
+                .iter()
+                .map(|title| {
+                    let mut payload_map = Map::new();
+                    payload_map.insert(TITLE_KEY.to_string(), (*title).into());
+                    Some(Payload(payload_map))
+                })
+                .collect(),
+        ),
+    };
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::from(batch),
+    ));
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            TITLE_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let scroll = |direction: Direction, start_from: Option<&str>| {
+        collection.scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(3),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: false.into(),
+                order_by: Some(OrderByInterface::Struct(OrderBy {
+                    key: TITLE_KEY.parse().unwrap(),
+                    direction: Some(direction),
+                    start_from: start_from.map(|s| StartFrom::Keyword(s.to_string())),
+                    then_by: vec![],
+                    start_after: None,
+                    distance_from: None,
+                })),
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+    };
+
+    let order_values = |points: &[api::rest::Record]| {
+        points
+            .iter()
+            .map(|point| point.order_value.clone().unwrap())
+            .collect_vec()
+    };
+    let keywords = |keywords: &[&str]| {
+        keywords
+            .iter()
+            .map(|keyword| OrderValue::Keyword(keyword.to_string()))
+            .collect_vec()
+    };
+
+    let result = scroll(Direction::Asc, None).await.unwrap();
+    assert_eq!(
+        order_values(&result.points),
+        keywords(&["alpha", "bravo", "charlie"])
+    );
+
+    let result = scroll(Direction::Asc, Some("charlie")).await.unwrap();
+    assert_eq!(
+        order_values(&result.points),
+        keywords(&["charlie", "delta", "echo"])
+    );
+
+    let result = scroll(Direction::Desc, None).await.unwrap();
+    assert_eq!(
+        order_values(&result.points),
+        keywords(&["foxtrot", "echo", "delta"])
+    );
+
+    let result = scroll(Direction::Desc, Some("c")).await.unwrap();
+    assert_eq!(order_values(&result.points), keywords(&["bravo", "alpha"]));
+
+    // Keywords which look like datetimes are still keywords for a keyword index
+    let result = scroll(Direction::Asc, Some("2024-01-01")).await.unwrap();
+    assert_eq!(
+        order_values(&result.points),
+        keywords(&["alpha", "bravo", "charlie"])
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ordered_read_by_multiple_keys_api() {
+    test_ordered_scroll_by_multiple_keys_with_shards(1).await;
+    test_ordered_scroll_by_multiple_keys_with_shards(N_SHARDS).await;
+}
+
+async fn test_ordered_scroll_by_multiple_keys_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const GROUP_KEY: &str = "group";
+    const TITLE_KEY: &str = "title";
+
+    let groups = [1, 2, 1, 2, 1, 2, 1, 2];
+    let titles = [
+        "delta", "alpha", "echo", "charlie", "bravo", "foxtrot", "alpha", "alpha",
+    ];
+
+    let batch = BatchPersisted {
+        ids: (0..titles.len() as u64).map(|x| x.into()).collect_vec(),
+        vectors: BatchVectorStructPersisted::Single(vec![vec![1.0, 0.0, 0.0, 0.0]; titles.len()]),
+        payloads: Some(
+            groups
+                .iter()
+                .zip(titles)
+                .map(|(group, title)| {
+                    let mut payload_map = Map::new();
+                    payload_map.insert(GROUP_KEY.to_string(), (*group).into());
+                    payload_map.insert(TITLE_KEY.to_string(), title.into());
+                    Some(Payload(payload_map))
+                })
+                .collect(),
+        ),
+    };
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::from(batch),
+    ));
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    for (key, schema) in [
+        (GROUP_KEY, PayloadSchemaType::Integer),
+        (TITLE_KEY, PayloadSchemaType::Keyword),
+    ] {
+        collection
+            .create_payload_index_with_wait(
+                key.parse().unwrap(),
+                PayloadFieldSchema::FieldType(schema),
+                true,
+            )
+            .await
+            .unwrap();
+    }
+
+    let scroll = |start_after: Option<OrderCursor>| {
+        collection.scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(3),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: false.into(),
+                order_by: Some(OrderByInterface::Struct(OrderBy {
+                    key: GROUP_KEY.parse().unwrap(),
+                    direction: Some(Direction::Desc),
+                    start_from: None,
+                    then_by: vec![OrderByKey {
+                        key: TITLE_KEY.parse().unwrap(),
+                        direction: Some(Direction::Asc),
+                    }],
+                    start_after,
+                    distance_from: None,
+                })),
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+    };
+
+    let first_page = scroll(None).await.unwrap();
+    assert_eq!(
+        first_page.points[0].order_value,
+        Some(OrderValue::Composite(vec![
+            OrderValue::Int(2),
+            OrderValue::Keyword("alpha".to_string()),
+        ])),
+    );
+
+    // Page through all points with the cursor of the last point
+    let mut ids = Vec::new();
+    let mut start_after = None;
+    loop {
+        let page = scroll(start_after.take()).await.unwrap();
+        let Some(last) = page.points.last() else {
+            break;
+        };
+        start_after = Some(OrderCursor {
+            values: last.order_value.as_ref().unwrap().components().to_vec(),
+            id: last.id,
+        });
+        ids.extend(page.points.iter().map(|point| point.id));
+    }
+
+    // Group descending, then title ascending, then id ascending
+    let expected: Vec<PointIdType> = [1u64, 7, 3, 5, 6, 4, 0, 2].map(PointIdType::from).into();
+    assert_eq!(ids, expected);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
@@ -0,0 +1,122 @@
+use collection::collection::Collection;
+use collection::lookup::join::resolve_joins;
+use collection::operations::point_ops::{
+    BatchPersisted, BatchVectorStructPersisted, PointInsertOperationsInternal, PointOperations,
+    WriteOrdering,
+};
+use collection::operations::CollectionUpdateOperations;
+use itertools::Itertools;
+use segment::types::{
+    AnyVariants, Condition, FieldCondition, Filter, HasIdCondition, Join, JoinCondition, Match,
+    Payload, PointIdType,
+};
+use serde_json::json;
+use tempfile::Builder;
+use tokio::sync::RwLock;
+
+use crate::common::simple_collection_fixture;
+
+async fn setup(collection_dir: &std::path::Path) -> RwLock<Collection> {
+    let collection = simple_collection_fixture(collection_dir, 1).await;
+
+    let ids = (0..10).map(PointIdType::from).collect_vec();
+    let vectors = ids.iter().map(|_| vec![1.0, 0.0, 0.0, 0.0]).collect_vec();
+    let payloads = (0..10)
+        .map(|i| {
+            Some(Payload::from(json!({
+                "group": i % 2,
+                "author": format!("author_{}", i % 3),
+            })))
+        })
+        .collect_vec();
+
+    let batch = BatchPersisted {
+        ids,
+        vectors: BatchVectorStructPersisted::Single(vectors),
+        payloads: Some(payloads),
+    };
+
+    let upsert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::from(batch),
+    ));
+
+    collection
+        .update_from_client_simple(upsert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    RwLock::new(collection)
+}
+
+fn join_filter(group: i64, related_key: Option<&str>, limit: Option<usize>) -> Filter {
+    Filter::new_must(Condition::Join(JoinCondition {
+        join: Join {
+            key: "doc_id".parse().unwrap(),
+            collection: "test".to_string(),
+            filter: Some(Filter::new_must(Condition::Field(
+                FieldCondition::new_match("group".parse().unwrap(), group.into()),
+            ))),
+            related_key: related_key.map(|key| key.parse().unwrap()),
+            limit,
+        },
+    }))
+}
+
+fn match_any(any: AnyVariants) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_match(
+        "doc_id".parse().unwrap(),
+        Match::new_any(any),
+    )))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resolve_join() {
+    let collection_dir = Builder::new().prefix("storage").tempdir().unwrap();
+    let collection = setup(collection_dir.path()).await;
+
+    let collection = &collection;
+    let collection_by_name = move |_: String| async move { Some(collection.read().await) };
+
+    // Ids of the related points
+    let mut filter = join_filter(0, None, None);
+    resolve_joins(&mut filter, collection_by_name, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        filter,
+        match_any(AnyVariants::Integers([0, 2, 4, 6, 8].into_iter().collect())),
+    );
+
+    // Values of a payload key of the related points
+    let mut filter = join_filter(0, Some("author"), None);
+    resolve_joins(&mut filter, collection_by_name, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        filter,
+        match_any(AnyVariants::Strings(
+            ["author_0", "author_1", "author_2"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        )),
+    );
+
+    // No related points, nothing matches
+    let mut filter = join_filter(5, None, None);
+    resolve_joins(&mut filter, collection_by_name, None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        filter,
+        Filter::new_must(Condition::HasId(HasIdCondition::from(
+            std::collections::HashSet::new()
+        ))),
+    );
+
+    // More related points than the limit
+    let mut filter = join_filter(0, None, Some(2));
+    assert!(resolve_joins(&mut filter, collection_by_name, None, None)
+        .await
+        .is_err());
+}
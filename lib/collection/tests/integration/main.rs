@@ -3,6 +3,7 @@ mod collection_test;
 mod common;
 mod distance_matrix_test;
 mod grouping_test;
+mod join_test;
 mod lookup_test;
 mod multi_vec_test;
 mod pagination_test;
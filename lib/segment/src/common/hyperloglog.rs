@@ -0,0 +1,150 @@
+//! Mergeable sketch of a set of values, used to approximate the number of distinct values.
+
+use std::hash::{Hash, Hasher};
+
+use seahash::SeaHasher;
+
+/// Number of bits of the hash used to select a register.
+/// Gives 16384 registers, with a standard error of about 0.8%.
+const PRECISION: u32 = 14;
+
+const REGISTERS: usize = 1 << PRECISION;
+
+/// HyperLogLog, as described by Flajolet et al., with linear counting for small cardinalities.
+///
+/// Values are hashed with a fixed-key hasher, so sketches built on different peers can be merged.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HyperLogLog {
+    /// Highest rank seen per register, empty until the first value is added
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// Restore a sketch from its registers, as returned by [`HyperLogLog::registers`].
+    pub fn from_registers(registers: Vec<u8>) -> Option<Self> {
+        if !registers.is_empty() && registers.len() != REGISTERS {
+            return None;
+        }
+        Some(Self { registers })
+    }
+
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty()
+    }
+
+    pub fn add<T: Hash + ?Sized>(&mut self, value: &T) {
+        let mut hasher = SeaHasher::new();
+        value.hash(&mut hasher);
+        self.add_hash(hasher.finish());
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        if self.registers.is_empty() {
+            self.registers = vec![0; REGISTERS];
+        }
+
+        let index = (hash >> (u64::BITS - PRECISION)) as usize;
+        // Mark the end of the remaining bits, so the rank is bounded when they are all zeros
+        let remaining = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = remaining.leading_zeros() as u8 + 1;
+
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+    }
+
+    pub fn merge(&mut self, other: HyperLogLog) {
+        if other.registers.is_empty() {
+            return;
+        }
+        if self.registers.is_empty() {
+            self.registers = other.registers;
+            return;
+        }
+
+        self.registers
+            .iter_mut()
+            .zip(other.registers)
+            .for_each(|(register, other)| *register = (*register).max(other));
+    }
+
+    /// Approximate number of distinct values added to the sketch.
+    pub fn estimate(&self) -> usize {
+        if self.registers.is_empty() {
+            return 0;
+        }
+
+        let registers = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / registers);
+
+        let (sum, zeros) = self
+            .registers
+            .iter()
+            .fold((0.0, 0usize), |(sum, zeros), &rank| {
+                (
+                    sum + 2f64.powi(-i32::from(rank)),
+                    zeros + usize::from(rank == 0),
+                )
+            });
+
+        let raw_estimate = alpha * registers * registers / sum;
+
+        // The raw estimate is biased for small cardinalities, count the empty registers instead
+        let estimate = if raw_estimate <= 2.5 * registers && zeros > 0 {
+            registers * (registers / zeros as f64).ln()
+        } else {
+            raw_estimate
+        };
+
+        estimate.round() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(estimate: usize, expected: usize) {
+        let error = (estimate as f64 - expected as f64).abs() / expected as f64;
+        assert!(error < 0.03, "estimate {estimate} vs {expected}");
+    }
+
+    #[test]
+    fn test_estimate_distinct_values() {
+        let mut sketch = HyperLogLog::default();
+        assert_eq!(sketch.estimate(), 0);
+
+        for cardinality in [10, 1_000, 100_000] {
+            let mut sketch = HyperLogLog::default();
+            // Repeated values should not change the estimate
+            for _ in 0..3 {
+                (0..cardinality).for_each(|value: u64| sketch.add(&value));
+            }
+            assert_close(sketch.estimate(), cardinality as usize);
+        }
+
+        sketch.add("keyword");
+        sketch.add("keyword");
+        assert_eq!(sketch.estimate(), 1);
+    }
+
+    #[test]
+    fn test_merge_sketches() {
+        let mut left = HyperLogLog::default();
+        let mut right = HyperLogLog::default();
+
+        (0..30_000).for_each(|value: u64| left.add(&value));
+        (20_000..50_000).for_each(|value: u64| right.add(&value));
+
+        left.merge(right);
+        left.merge(HyperLogLog::default());
+        assert_close(left.estimate(), 50_000);
+
+        let restored = HyperLogLog::from_registers(left.registers().to_vec()).unwrap();
+        assert_eq!(restored, left);
+        assert!(HyperLogLog::from_registers(vec![0; 10]).is_none());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod anonymize;
 pub mod error_logging;
+pub mod hyperloglog;
 pub mod macros;
 pub mod mmap_bitslice_buffered_update_wrapper;
 pub mod mmap_slice_buffered_update_wrapper;
@@ -10,6 +11,7 @@ pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_buffered_update_wrapper;
 pub mod rocksdb_wrapper;
 pub mod score_fusion;
+pub mod tdigest;
 pub mod utils;
 pub mod validate_snapshot_archive;
 pub mod vector_utils;
@@ -56,8 +56,24 @@ pub enum OperationError {
     WrongMulti,
     #[error("No range index for `order_by` key: `{key}`. Please create one to use `order_by`. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Range conditions")]
     MissingRangeIndexForOrderBy { key: String },
+    #[error("No geo index for `order_by` key: `{key}`. Please create one to order by distance from a point")]
+    MissingGeoIndexForOrderBy { key: String },
     #[error("No appropriate index for faceting: `{key}`. Please create one to facet on this field. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Match conditions")]
     MissingMapIndexForFacet { key: String },
+    #[error("No geo index for geo grid facet key: `{key}`. Please create one to count points per geo-hash cell")]
+    MissingGeoIndexForFacet { key: String },
+    #[error("No range index for histogram facet key: `{key}`. Please create one to count points per bucket. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Range conditions")]
+    MissingRangeIndexForFacet { key: String },
+    #[error("No full-text index for `bm25` key: `{key}`. Please create one to rank points by text relevance. Check https://qdrant.tech/documentation/concepts/indexing/#full-text-index")]
+    MissingFullTextIndex { key: String },
+    #[error("Expected {expected_type} value for {field_name} in the payload and/or in the formula defaults. Error: {description}")]
+    VariableTypeError {
+        field_name: String,
+        expected_type: String,
+        description: String,
+    },
+    #[error("The expression {expression} produced a non-finite number")]
+    NonFiniteNumber { expression: String },
 }
 
 impl OperationError {
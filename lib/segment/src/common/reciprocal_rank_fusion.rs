@@ -2,33 +2,42 @@
 //! See https://plg.uwaterloo.ca/~gvcormac/cormacksigir09-rrf.pdf
 
 use std::collections::hash_map::Entry;
+use std::iter;
 
 use ahash::{HashMap, HashMapExt};
 use ordered_float::OrderedFloat;
 
 use crate::types::{ExtendedPointId, ScoredPoint};
 
-/// Mitigates the impact of high rankings by outlier systems
-const RFF_RANKING_K: f32 = 2.0;
+/// Default `k` constant, mitigates the impact of high rankings by outlier systems
+pub const DEFAULT_RRF_K: usize = 2;
 
 /// Compute the RRF score for a given position.
-fn position_score(position: usize) -> f32 {
-    1.0 / (position as f32 + RFF_RANKING_K)
+fn position_score(position: usize, k: usize) -> f32 {
+    1.0 / (position as f32 + k as f32)
 }
 
 /// Compute RRF scores for multiple results from different sources.
 /// Each response can have a different length.
 /// The input scores are irrelevant, only the order matters.
 ///
+/// The score of each source is multiplied by its weight, sources without a weight have a weight of 1.0.
+///
 /// The output is a single sorted list of ScoredPoint.
 /// Does not break ties.
-pub fn rrf_scoring(responses: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+pub fn rrf_scoring(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    k: usize,
+    weights: &[f32],
+) -> Vec<ScoredPoint> {
     // track scored points by id
     let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
 
-    for response in responses {
+    let weights = weights.iter().copied().chain(iter::repeat(1.0));
+
+    for (response, weight) in responses.into_iter().zip(weights) {
         for (pos, mut point) in response.into_iter().enumerate() {
-            let rrf_score = position_score(pos);
+            let rrf_score = weight * position_score(pos, k);
             match points_by_id.entry(point.id) {
                 Entry::Occupied(mut entry) => {
                     // accumulate score
@@ -72,14 +81,14 @@ mod tests {
     #[test]
     fn test_rrf_scoring_empty() {
         let responses = vec![];
-        let scored_points = rrf_scoring(responses);
+        let scored_points = rrf_scoring(responses, DEFAULT_RRF_K, &[]);
         assert_eq!(scored_points.len(), 0);
     }
 
     #[test]
     fn test_rrf_scoring_one() {
         let responses = vec![vec![make_scored_point(1, 0.9)]];
-        let scored_points = rrf_scoring(responses);
+        let scored_points = rrf_scoring(responses, DEFAULT_RRF_K, &[]);
         assert_eq!(scored_points.len(), 1);
         assert_eq!(scored_points[0].id, 1.into());
         assert_eq!(scored_points[0].score, 0.5); // 1 / (0 + 2)
@@ -102,7 +111,7 @@ mod tests {
         ];
 
         // top 10
-        let scored_points = rrf_scoring(responses);
+        let scored_points = rrf_scoring(responses, DEFAULT_RRF_K, &[]);
         assert_eq!(scored_points.len(), 4);
         // assert that the list is sorted
         assert!(scored_points.windows(2).all(|w| w[0].score >= w[1].score));
@@ -120,4 +129,39 @@ mod tests {
         assert_eq!(scored_points[3].id, 5.into());
         assert_eq!(scored_points[3].score, 0.5);
     }
+
+    #[test]
+    fn test_rrf_scoring_with_k() {
+        let responses = vec![vec![make_scored_point(1, 0.9), make_scored_point(2, 0.8)]];
+
+        let scored_points = rrf_scoring(responses, 60, &[]);
+        assert_eq!(scored_points[0].id, 1.into());
+        assert_eq!(scored_points[0].score, 1.0 / 60.0);
+        assert_eq!(scored_points[1].id, 2.into());
+        assert_eq!(scored_points[1].score, 1.0 / 61.0);
+    }
+
+    #[test]
+    fn test_rrf_scoring_weighted() {
+        let responses = vec![
+            vec![make_scored_point(1, 0.9), make_scored_point(2, 0.8)],
+            vec![make_scored_point(2, 0.7), make_scored_point(1, 0.6)],
+        ];
+
+        // Equal weights make a tie
+        let scored_points = rrf_scoring(responses.clone(), DEFAULT_RRF_K, &[]);
+        assert_eq!(scored_points[0].score, scored_points[1].score);
+
+        // Second source is more important
+        let scored_points = rrf_scoring(responses.clone(), DEFAULT_RRF_K, &[1.0, 3.0]);
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 1.0 / 3.0 + 3.0 / 2.0);
+        assert_eq!(scored_points[1].id, 1.into());
+        assert_eq!(scored_points[1].score, 1.0 / 2.0 + 3.0 / 3.0);
+
+        // Missing weights default to 1.0
+        let scored_points = rrf_scoring(responses, DEFAULT_RRF_K, &[0.5]);
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 0.5 / 3.0 + 1.0 / 2.0);
+    }
 }
@@ -28,6 +28,26 @@ impl ScoreFusion {
             order: Order::LargeBetter,
         }
     }
+
+    /// Params for the weighted sum of min-max normalized scores
+    pub fn min_max() -> Self {
+        Self {
+            method: Aggregation::Sum,
+            norm: Normalization::MinMax,
+            weights: vec![],
+            order: Order::LargeBetter,
+        }
+    }
+
+    /// Params for the weighted sum of z-score normalized scores
+    pub fn z_score() -> Self {
+        Self {
+            method: Aggregation::Sum,
+            norm: Normalization::ZScore,
+            weights: vec![],
+            order: Order::LargeBetter,
+        }
+    }
 }
 
 /// Defines how to combine the scores of the same point in different lists
@@ -41,6 +61,8 @@ pub enum Normalization {
     MinMax,
     /// Uses the 3rd standard deviation as extremes
     Distr,
+    /// Number of standard deviations from the mean
+    ZScore,
 }
 
 pub fn score_fusion(
@@ -62,6 +84,7 @@ pub fn score_fusion(
         .map(|points| match norm {
             Normalization::MinMax => min_max_norm(points),
             Normalization::Distr => distr_norm(points),
+            Normalization::ZScore => z_score_norm(points),
         })
         // weight each list of points
         .zip(weights)
@@ -163,6 +186,29 @@ pub fn distr_norm(mut points: Vec<ScoredPoint>) -> Vec<ScoredPoint> {
     norm(points, min, max)
 }
 
+/// Replaces the scores of the given points with the number of standard deviations they are from the mean.
+pub fn z_score_norm(mut points: Vec<ScoredPoint>) -> Vec<ScoredPoint> {
+    if points.len() < 2 {
+        points.iter_mut().for_each(|p| p.score = 0.0);
+        return points;
+    }
+
+    let (mean, variance) = welfords_mean_variance(&points);
+    let std_dev = variance.sqrt();
+
+    // Protect against division by zero
+    if std_dev == 0.0 {
+        points.iter_mut().for_each(|p| p.score = 0.0);
+        return points;
+    }
+
+    points.iter_mut().for_each(|p| {
+        p.score = (p.score - mean) / std_dev;
+    });
+
+    points
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -185,6 +231,70 @@ mod tests {
         assert!((a - b).abs() < 1e-2, "{a} is not close to {b}");
     }
 
+    #[test]
+    fn test_weighted_score_fusion() {
+        let results = vec![
+            vec![point(1, 10.0), point(2, 5.0), point(3, 0.0)],
+            vec![point(3, 0.9), point(2, 0.5), point(1, 0.1)],
+        ];
+
+        let fused = score_fusion(results.clone(), ScoreFusion::dbsf());
+        assert_close(fused[0].score, fused[1].score);
+        assert_close(fused[1].score, fused[2].score);
+
+        let fused = score_fusion(
+            results,
+            ScoreFusion {
+                weights: vec![1.0, 2.0],
+                ..ScoreFusion::dbsf()
+            },
+        );
+        assert_eq!(fused[0].id, PointIdType::NumId(3));
+        assert_eq!(fused[2].id, PointIdType::NumId(1));
+        assert!(fused.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_min_max_score_fusion() {
+        // Same ranking, but the second list has a much sharper gap between the first two points
+        let results = vec![
+            vec![point(1, 0.9), point(2, 0.8), point(3, 0.7)],
+            vec![point(2, 20.0), point(1, 1.5), point(3, 1.0)],
+        ];
+
+        let fused = score_fusion(results.clone(), ScoreFusion::min_max());
+        assert_eq!(fused[0].id, PointIdType::NumId(2));
+        assert_close(fused[0].score, 1.5);
+        assert_eq!(fused[1].id, PointIdType::NumId(1));
+        assert_close(fused[1].score, 1.0 + 0.5 / 19.0);
+        assert_eq!(fused[2].id, PointIdType::NumId(3));
+        assert_close(fused[2].score, 0.0);
+
+        let fused = score_fusion(
+            results,
+            ScoreFusion {
+                weights: vec![2.0, 0.5],
+                ..ScoreFusion::min_max()
+            },
+        );
+        assert_eq!(fused[0].id, PointIdType::NumId(1));
+        assert_close(fused[0].score, 2.0 + 0.25 / 19.0);
+    }
+
+    #[test]
+    fn test_z_score_norm() {
+        let points = z_score_norm(vec![point(1, 3.0), point(2, 2.0), point(3, 1.0)]);
+        let scores = points.iter().map(|p| p.score).collect_vec();
+        assert_eq!(scores, vec![1.0, 0.0, -1.0]);
+
+        // Same scores do not divide by zero
+        let points = z_score_norm(vec![point(1, 2.0), point(2, 2.0)]);
+        assert!(points.iter().all(|p| p.score == 0.0));
+
+        let points = z_score_norm(vec![point(1, 2.0)]);
+        assert_eq!(points[0].score, 0.0);
+    }
+
     proptest! {
         #[test]
         fn welford_calc_vs_naive(scores in prop::collection::vec(-100.0..100.0f32, 2..1000)) {
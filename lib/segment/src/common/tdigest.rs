@@ -0,0 +1,183 @@
+//! Mergeable sketch of a distribution of values, used to approximate percentiles.
+
+/// Controls the size of the digest. It keeps in the order of `COMPRESSION` centroids,
+/// which gives a relative rank error below 1% for the middle percentiles,
+/// and better for the extreme ones.
+const COMPRESSION: f64 = 100.0;
+
+/// Number of single values accepted before compressing, relative to the compression.
+const BUFFER_FACTOR: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Centroid {
+    pub mean: f64,
+    pub weight: f64,
+}
+
+/// Merging t-digest, as described by Dunning & Ertl.
+///
+/// Values are buffered as centroids of weight 1, and adjacent centroids are merged
+/// as long as they stay small enough for their position in the distribution.
+/// Centroids near the tails are kept small, so extreme percentiles remain accurate.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TDigest {
+    centroids: Vec<Centroid>,
+}
+
+impl TDigest {
+    pub fn from_centroids(centroids: Vec<Centroid>) -> Self {
+        let mut digest = Self { centroids };
+        digest.compress();
+        digest
+    }
+
+    pub fn centroids(&self) -> &[Centroid] {
+        &self.centroids
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.centroids.is_empty()
+    }
+
+    pub fn add(&mut self, value: f64) {
+        self.centroids.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+        self.compress_if_full();
+    }
+
+    pub fn merge(&mut self, other: TDigest) {
+        self.centroids.extend(other.centroids);
+        self.compress_if_full();
+    }
+
+    fn compress_if_full(&mut self) {
+        if self.centroids.len() > BUFFER_FACTOR * COMPRESSION as usize {
+            self.compress();
+        }
+    }
+
+    pub fn compress(&mut self) {
+        if self.centroids.len() <= 1 {
+            return;
+        }
+
+        self.centroids
+            .sort_unstable_by(|left, right| left.mean.total_cmp(&right.mean));
+
+        let total_weight: f64 = self.centroids.iter().map(|centroid| centroid.weight).sum();
+
+        let mut compressed: Vec<Centroid> = Vec::with_capacity(COMPRESSION as usize * 2);
+        // Weight of the centroids before the last compressed one
+        let mut weight_before = 0.0;
+
+        for centroid in self.centroids.drain(..) {
+            if let Some(last) = compressed.last_mut() {
+                let merged_weight = last.weight + centroid.weight;
+                let quantile = (weight_before + merged_weight / 2.0) / total_weight;
+                let max_weight = 4.0 * total_weight * quantile * (1.0 - quantile) / COMPRESSION;
+
+                if merged_weight <= max_weight {
+                    last.mean += (centroid.mean - last.mean) * centroid.weight / merged_weight;
+                    last.weight = merged_weight;
+                    continue;
+                }
+
+                weight_before += last.weight;
+            }
+            compressed.push(centroid);
+        }
+
+        self.centroids = compressed;
+    }
+
+    /// Approximate value at the given quantile, between 0 and 1.
+    pub fn quantile(&self, quantile: f64) -> Option<f64> {
+        let mut centroids = self.centroids.clone();
+        centroids.sort_unstable_by(|left, right| left.mean.total_cmp(&right.mean));
+
+        let total_weight: f64 = centroids.iter().map(|centroid| centroid.weight).sum();
+        let rank = quantile.clamp(0.0, 1.0) * total_weight;
+
+        // Interpolate between the centers of the two centroids around the rank
+        let mut weight_before = 0.0;
+        let mut previous: Option<(f64, &Centroid)> = None;
+        for centroid in &centroids {
+            let center = weight_before + centroid.weight / 2.0;
+            if rank < center {
+                let Some((previous_center, previous)) = previous else {
+                    return Some(centroid.mean);
+                };
+                let fraction = (rank - previous_center) / (center - previous_center);
+                return Some(previous.mean + fraction * (centroid.mean - previous.mean));
+            }
+            previous = Some((center, centroid));
+            weight_before += centroid.weight;
+        }
+
+        centroids.last().map(|centroid| centroid.mean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_quantiles_of_uniform_values() {
+        let mut values = (0..10_000).map(f64::from).collect::<Vec<_>>();
+        values.shuffle(&mut StdRng::seed_from_u64(42));
+
+        let mut digest = TDigest::default();
+        values.iter().for_each(|&value| digest.add(value));
+        digest.compress();
+
+        assert!(digest.centroids().len() < 2 * COMPRESSION as usize);
+
+        for quantile in [0.0, 0.01, 0.25, 0.5, 0.9, 0.99, 1.0] {
+            let approx = digest.quantile(quantile).unwrap();
+            let expected = quantile * 9_999.0;
+            assert!(
+                (approx - expected).abs() <= 100.0,
+                "quantile {quantile}: {approx} vs {expected}",
+            );
+        }
+
+        assert_eq!(digest.quantile(0.0), Some(0.0));
+        assert_eq!(digest.quantile(1.0), Some(9_999.0));
+    }
+
+    #[test]
+    fn test_merge_digests() {
+        let mut left = TDigest::default();
+        let mut right = TDigest::default();
+        let mut both = TDigest::default();
+
+        for value in 0..5_000 {
+            let value = f64::from(value);
+            left.add(value);
+            both.add(value);
+        }
+        for value in 5_000..20_000 {
+            let value = f64::from(value);
+            right.add(value);
+            both.add(value);
+        }
+
+        left.merge(right);
+        left.compress();
+
+        for quantile in [0.1, 0.5, 0.9] {
+            let merged = left.quantile(quantile).unwrap();
+            let single = both.quantile(quantile).unwrap();
+            assert!((merged - single).abs() <= 200.0);
+        }
+
+        assert_eq!(TDigest::default().quantile(0.5), None);
+    }
+}
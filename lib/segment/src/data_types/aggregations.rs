@@ -0,0 +1,331 @@
+use std::collections::BTreeSet;
+
+use chrono::DateTime;
+use common::validation::validate_range_generic;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::common::hyperloglog::HyperLogLog;
+use crate::common::tdigest::TDigest;
+use crate::data_types::facets::FacetValue;
+use crate::json_path::JsonPath;
+use crate::types::{DateTimePayloadType, Filter, FloatPayloadType};
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct AggregateParams {
+    pub key: JsonPath,
+    pub filter: Option<Filter>,
+    /// Percentiles to approximate, from 0 to 100
+    #[serde(default)]
+    #[validate(custom(function = "validate_percentiles"))]
+    pub percentiles: Vec<FloatPayloadType>,
+}
+
+/// Validate all the percentiles are numbers in `[0.0, 100.0]`.
+pub fn validate_percentiles(percentiles: &[FloatPayloadType]) -> Result<(), ValidationError> {
+    percentiles.iter().try_for_each(|percentile| {
+        if percentile.is_nan() {
+            return Err(ValidationError::new("nan"));
+        }
+        validate_range_generic(percentile, Some(&0.0), Some(&100.0))
+    })
+}
+
+/// Aggregation of the values of a part of the points, which can be merged with other parts.
+///
+/// Datetimes are aggregated as timestamps in microseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialAggregate {
+    /// Number of aggregated values, each value of multi-valued fields is counted
+    pub count: usize,
+    pub sum: FloatPayloadType,
+    pub min: Option<FloatPayloadType>,
+    pub max: Option<FloatPayloadType>,
+    /// Whether the values come from a datetime index
+    pub is_datetime: bool,
+    pub digest: TDigest,
+}
+
+impl PartialAggregate {
+    pub fn new(is_datetime: bool) -> Self {
+        Self {
+            is_datetime,
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, value: FloatPayloadType) {
+        if !value.is_finite() {
+            return;
+        }
+        self.count += 1;
+        self.sum += value;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self.digest.add(value);
+    }
+
+    pub fn merge(&mut self, other: PartialAggregate) {
+        let PartialAggregate {
+            count,
+            sum,
+            min,
+            max,
+            is_datetime,
+            digest,
+        } = other;
+
+        self.count += count;
+        self.sum += sum;
+        self.min = match (self.min, min) {
+            (Some(left), Some(right)) => Some(left.min(right)),
+            (left, right) => left.or(right),
+        };
+        self.max = match (self.max, max) {
+            (Some(left), Some(right)) => Some(left.max(right)),
+            (left, right) => left.or(right),
+        };
+        self.is_datetime |= is_datetime;
+        self.digest.merge(digest);
+    }
+
+    /// Compute the final statistics, with the requested percentiles in the same order
+    pub fn finalize(mut self, percentiles: &[FloatPayloadType]) -> AggregateResponse {
+        self.digest.compress();
+
+        let to_value = |value: FloatPayloadType| {
+            if self.is_datetime {
+                DateTime::from_timestamp_micros(value.round() as i64)
+                    .map(|date_time| AggregateValue::Datetime(DateTimePayloadType::from(date_time)))
+            } else {
+                Some(AggregateValue::Number(value))
+            }
+        };
+
+        let avg = (self.count > 0).then(|| self.sum / self.count as FloatPayloadType);
+
+        let percentiles = percentiles
+            .iter()
+            .filter_map(|&percentile| {
+                let value = self.digest.quantile(percentile / 100.0)?;
+                // The digest interpolates, but never beyond the actual bounds
+                let value = value.clamp(self.min?, self.max?);
+                Some(PercentileValue {
+                    percentile,
+                    value: to_value(value)?,
+                })
+            })
+            .collect();
+
+        AggregateResponse {
+            count: self.count,
+            sum: (!self.is_datetime && self.count > 0).then_some(self.sum),
+            min: self.min.and_then(to_value),
+            max: self.max.and_then(to_value),
+            avg: avg.and_then(to_value),
+            percentiles,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AggregateValue {
+    Number(FloatPayloadType),
+    Datetime(DateTimePayloadType),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PercentileValue {
+    pub percentile: FloatPayloadType,
+    pub value: AggregateValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregateResponse {
+    pub count: usize,
+    /// Not defined for datetimes
+    pub sum: Option<FloatPayloadType>,
+    pub min: Option<AggregateValue>,
+    pub max: Option<AggregateValue>,
+    pub avg: Option<AggregateValue>,
+    pub percentiles: Vec<PercentileValue>,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct DistinctCountParams {
+    pub key: JsonPath,
+    pub filter: Option<Filter>,
+    /// Count the values exactly instead of approximating, slower on fields with many values.
+    /// Above [`PartialDistinctCount::MAX_EXACT_VALUES`] distinct values, the count is approximated.
+    #[serde(default)]
+    pub exact: bool,
+}
+
+impl DistinctCountParams {
+    pub const DEFAULT_EXACT: bool = false;
+}
+
+/// Distinct values of a part of the points, which can be merged with other parts.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PartialDistinctCount {
+    Exact(BTreeSet<FacetValue>),
+    Sketch(HyperLogLog),
+}
+
+impl Default for PartialDistinctCount {
+    fn default() -> Self {
+        PartialDistinctCount::Sketch(HyperLogLog::default())
+    }
+}
+
+impl PartialDistinctCount {
+    /// Number of distinct values kept exactly, more values are sketched
+    pub const MAX_EXACT_VALUES: usize = 100_000;
+
+    /// Keeps the values exactly, unless there are too many of them
+    pub fn from_values(values: BTreeSet<FacetValue>) -> Self {
+        if values.len() <= Self::MAX_EXACT_VALUES {
+            return PartialDistinctCount::Exact(values);
+        }
+        let mut sketch = HyperLogLog::default();
+        values.iter().for_each(|value| sketch.add(value));
+        PartialDistinctCount::Sketch(sketch)
+    }
+
+    pub fn merge(&mut self, other: PartialDistinctCount) {
+        let merged = match (std::mem::take(self), other) {
+            // An empty sketch is the default, it does not force the other part to be approximated
+            (PartialDistinctCount::Sketch(sketch), other) if sketch.is_empty() => other,
+            (this, PartialDistinctCount::Sketch(sketch)) if sketch.is_empty() => this,
+            (PartialDistinctCount::Exact(mut values), PartialDistinctCount::Exact(other)) => {
+                values.extend(other);
+                PartialDistinctCount::from_values(values)
+            }
+            (PartialDistinctCount::Sketch(mut sketch), PartialDistinctCount::Sketch(other)) => {
+                sketch.merge(other);
+                PartialDistinctCount::Sketch(sketch)
+            }
+            (PartialDistinctCount::Sketch(mut sketch), PartialDistinctCount::Exact(values))
+            | (PartialDistinctCount::Exact(values), PartialDistinctCount::Sketch(mut sketch)) => {
+                values.iter().for_each(|value| sketch.add(value));
+                PartialDistinctCount::Sketch(sketch)
+            }
+        };
+        *self = merged;
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            PartialDistinctCount::Exact(values) => values.len(),
+            PartialDistinctCount::Sketch(sketch) => sketch.estimate(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DistinctCountResponse {
+    pub count: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_partial_aggregates() {
+        let mut left = PartialAggregate::new(false);
+        let mut right = PartialAggregate::new(false);
+        (1..=50).for_each(|value| left.add(f64::from(value)));
+        (51..=100).for_each(|value| right.add(f64::from(value)));
+        right.add(f64::NAN);
+
+        left.merge(right);
+        left.merge(PartialAggregate::new(false));
+
+        let response = left.finalize(&[0.0, 50.0, 100.0]);
+
+        assert_eq!(response.count, 100);
+        assert_eq!(response.sum, Some(5050.0));
+        assert_eq!(response.min, Some(AggregateValue::Number(1.0)));
+        assert_eq!(response.max, Some(AggregateValue::Number(100.0)));
+        assert_eq!(response.avg, Some(AggregateValue::Number(50.5)));
+
+        let percentiles = response
+            .percentiles
+            .iter()
+            .map(|percentile| match percentile.value {
+                AggregateValue::Number(value) => value,
+                AggregateValue::Datetime(_) => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(percentiles[0], 1.0);
+        assert!((percentiles[1] - 50.5).abs() <= 1.0);
+        assert_eq!(percentiles[2], 100.0);
+    }
+
+    #[test]
+    fn test_finalize_datetimes() {
+        let first = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap();
+        let last = DateTime::parse_from_rfc3339("2024-01-03T00:00:00Z").unwrap();
+
+        let mut aggregate = PartialAggregate::new(true);
+        aggregate.add(first.timestamp_micros() as f64);
+        aggregate.add(last.timestamp_micros() as f64);
+
+        let response = aggregate.finalize(&[]);
+
+        let middle = DateTime::parse_from_rfc3339("2024-01-02T00:00:00Z").unwrap();
+        assert_eq!(response.count, 2);
+        assert_eq!(response.sum, None);
+        assert_eq!(
+            response.avg,
+            Some(AggregateValue::Datetime(DateTimePayloadType::from(
+                middle.with_timezone(&chrono::Utc)
+            )))
+        );
+        assert!(response.percentiles.is_empty());
+
+        let empty = PartialAggregate::new(true).finalize(&[50.0]);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.min, None);
+        assert_eq!(empty.avg, None);
+        assert!(empty.percentiles.is_empty());
+    }
+
+    #[test]
+    fn test_validate_percentiles() {
+        assert!(validate_percentiles(&[0.0, 50.0, 100.0]).is_ok());
+        assert!(validate_percentiles(&[-1.0]).is_err());
+        assert!(validate_percentiles(&[100.5]).is_err());
+        assert!(validate_percentiles(&[50.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_merge_partial_distinct_counts() {
+        let values =
+            |range: std::ops::Range<i64>| range.map(FacetValue::Int).collect::<BTreeSet<_>>();
+
+        let mut exact = PartialDistinctCount::default();
+        exact.merge(PartialDistinctCount::Exact(values(0..10)));
+        exact.merge(PartialDistinctCount::Exact(values(5..20)));
+        exact.merge(PartialDistinctCount::default());
+        assert!(matches!(exact, PartialDistinctCount::Exact(_)));
+        assert_eq!(exact.count(), 20);
+
+        // Sketches and exact values of the same field can be merged
+        let mut sketch = HyperLogLog::default();
+        (20..30).for_each(|value| sketch.add(&FacetValue::Int(value)));
+        exact.merge(PartialDistinctCount::Sketch(sketch));
+        exact.merge(PartialDistinctCount::Exact(values(0..5)));
+        assert!(matches!(exact, PartialDistinctCount::Sketch(_)));
+        assert_eq!(exact.count(), 30);
+
+        // Too many exact values are sketched
+        let max = PartialDistinctCount::MAX_EXACT_VALUES as i64;
+        let mut capped = PartialDistinctCount::from_values(values(0..max));
+        assert!(matches!(capped, PartialDistinctCount::Exact(_)));
+        capped.merge(PartialDistinctCount::Exact(values(max..max + 1)));
+        assert!(matches!(capped, PartialDistinctCount::Sketch(_)));
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use common::types::ScoreType;
+
+use crate::json_path::JsonPath;
+
+/// Ranks points by the BM25 relevance of a full-text indexed field to the query text
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bm25Query {
+    /// Payload key with a full-text index
+    pub key: JsonPath,
+    /// Text to rank the points by
+    pub text: String,
+    /// Term frequency saturation
+    pub k1: f32,
+    /// Document length normalization, from 0.0 (none) to 1.0 (full)
+    pub b: f32,
+}
+
+impl Bm25Query {
+    pub const DEFAULT_K1: f32 = 1.2;
+    pub const DEFAULT_B: f32 = 0.75;
+
+    /// Contribution of a query token found in a document of `doc_len` tokens.
+    ///
+    /// The index does not store term frequencies, so each token of a document counts once.
+    pub fn token_score(&self, idf: ScoreType, doc_len: usize, avg_doc_len: f32) -> ScoreType {
+        let Self { k1, b, .. } = *self;
+        let length_norm = 1.0 - b + b * doc_len as f32 / avg_doc_len;
+        idf * (k1 + 1.0) / (1.0 + k1 * length_norm)
+    }
+}
+
+/// Statistics of the corpus and of the query tokens.
+///
+/// Collected from all the segments before scoring, so that the scores of different segments are comparable.
+/// The statistics are not shared between shards, so scores of different shards are not comparable.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bm25Stats {
+    /// Number of indexed documents
+    pub docs_count: usize,
+    /// Total number of tokens in the indexed documents
+    pub tokens_count: usize,
+    /// Number of documents containing each query token
+    pub docs_with_token: HashMap<String, usize>,
+}
+
+impl Bm25Stats {
+    pub fn merge(&mut self, other: Bm25Stats) {
+        let Bm25Stats {
+            docs_count,
+            tokens_count,
+            docs_with_token,
+        } = other;
+
+        self.docs_count += docs_count;
+        self.tokens_count += tokens_count;
+        for (token, count) in docs_with_token {
+            *self.docs_with_token.entry(token).or_default() += count;
+        }
+    }
+
+    pub fn avg_doc_len(&self) -> f32 {
+        if self.docs_count == 0 || self.tokens_count == 0 {
+            return 1.0;
+        }
+        self.tokens_count as f32 / self.docs_count as f32
+    }
+
+    /// Inverse document frequency of a query token, never negative
+    pub fn idf(&self, token: &str) -> ScoreType {
+        let docs_count = self.docs_count as f32;
+        let docs_with_token = self.docs_with_token.get(token).copied().unwrap_or(0) as f32;
+        (1.0 + (docs_count - docs_with_token + 0.5) / (docs_with_token + 0.5)).ln()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_query() -> Bm25Query {
+        Bm25Query {
+            key: JsonPath::new("text"),
+            text: "hello world".to_string(),
+            k1: Bm25Query::DEFAULT_K1,
+            b: Bm25Query::DEFAULT_B,
+        }
+    }
+
+    #[test]
+    fn test_bm25_stats_merge() {
+        let mut stats = Bm25Stats {
+            docs_count: 2,
+            tokens_count: 10,
+            docs_with_token: HashMap::from([("hello".to_string(), 1)]),
+        };
+        stats.merge(Bm25Stats {
+            docs_count: 3,
+            tokens_count: 5,
+            docs_with_token: HashMap::from([("hello".to_string(), 2), ("world".to_string(), 1)]),
+        });
+
+        assert_eq!(stats.docs_count, 5);
+        assert_eq!(stats.tokens_count, 15);
+        assert_eq!(stats.docs_with_token["hello"], 3);
+        assert_eq!(stats.docs_with_token["world"], 1);
+        assert_eq!(stats.avg_doc_len(), 3.0);
+    }
+
+    #[test]
+    fn test_bm25_idf() {
+        let stats = Bm25Stats {
+            docs_count: 100,
+            tokens_count: 1000,
+            docs_with_token: HashMap::from([("common".to_string(), 100), ("rare".to_string(), 1)]),
+        };
+
+        // Rare tokens are worth more, but even the tokens present everywhere are not negative
+        assert!(stats.idf("rare") > stats.idf("common"));
+        assert!(stats.idf("common") > 0.0);
+        assert!(stats.idf("unknown") > stats.idf("rare"));
+    }
+
+    #[test]
+    fn test_bm25_token_score() {
+        let query = make_query();
+
+        // Shorter documents score higher
+        let short = query.token_score(1.0, 5, 10.0);
+        let long = query.token_score(1.0, 20, 10.0);
+        assert!(short > long);
+
+        // Without length normalization, the score is the idf
+        let query = Bm25Query { b: 0.0, ..query };
+        assert_eq!(query.token_score(2.0, 5, 10.0), 2.0);
+        assert_eq!(query.token_score(2.0, 20, 10.0), 2.0);
+    }
+}
@@ -1,13 +1,23 @@
 use std::cmp::Reverse;
 use std::hash::Hash;
 
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate};
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::index::field_index::geo_hash::{geo_hash_to_box, GeoHash};
 use crate::json_path::JsonPath;
-use crate::types::{Filter, IntPayloadType, UuidIntType, ValueVariants};
+use crate::types::{
+    Condition, DateTimePayloadType, FieldCondition, Filter, FloatPayloadType, GeoPoint,
+    IntPayloadType, Match, Range, UuidIntType, ValueVariants,
+};
+
+const MICROS_PER_SECOND: FloatPayloadType = 1_000_000.0;
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Validate)]
 pub struct FacetParams {
@@ -18,13 +28,286 @@ pub struct FacetParams {
     pub filter: Option<Filter>,
     #[serde(default)]
     pub exact: bool,
+    /// Count points per geo-hash cell of a geo field, instead of per value
+    #[serde(default)]
+    #[validate(nested)]
+    pub geo_grid: Option<GeoGridParams>,
+    /// Count points per bucket of a numeric or datetime field, instead of per value
+    #[serde(default)]
+    pub histogram: Option<HistogramParams>,
+    /// Count combinations of the values of the key and of these keys, instead of single values.
+    /// Values of the facet are then tuples, with one value per key. At most 3 keys.
+    #[serde(default)]
+    #[validate(nested, length(max = 3))]
+    pub pivot: Vec<FacetPivotParams>,
 }
 
 impl FacetParams {
     pub const DEFAULT_LIMIT: usize = 10;
     pub const DEFAULT_EXACT: bool = false;
+    /// Max number of combinations of a pivot facet which are counted exactly
+    pub const MAX_EXACT_PIVOT_COMBINATIONS: usize = 10_000;
+
+    /// Keys of each level of the facet, the key of the request first
+    pub fn keys(&self) -> Vec<JsonPath> {
+        std::iter::once(&self.key)
+            .chain(self.pivot.iter().map(|level| &level.key))
+            .cloned()
+            .collect()
+    }
+
+    /// Max number of values of each level of the facet
+    pub fn limits(&self) -> Vec<usize> {
+        std::iter::once(self.limit)
+            .chain(self.pivot.iter().map(|level| level.limit))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct FacetPivotParams {
+    pub key: JsonPath,
+    /// Max number of values of this key, for each value of the previous keys
+    #[validate(range(min = 1))]
+    pub limit: usize,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct GeoGridParams {
+    /// Length of the geo-hash of the cells, from 1 (cells of ~5000km) to 12 (cells of ~4cm)
+    #[validate(range(min = 1, max = 12))]
+    pub precision: usize,
+    /// Whether to return the centroid of the points in each cell
+    #[serde(default)]
+    pub with_centroid: bool,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistogramParams {
+    /// Buckets of a fixed width, aligned on zero. For datetime fields the width is in seconds.
+    Interval(FloatPayloadType),
+    /// Calendar buckets of a datetime field, in UTC
+    CalendarInterval(CalendarInterval),
+    /// Explicit buckets, a point is counted in all buckets containing one of its values
+    Ranges(Vec<HistogramRange>),
+}
+
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarInterval {
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+}
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq)]
+pub struct HistogramRange {
+    /// Inclusive lower bound, unbounded if not set
+    pub from: Option<HistogramBound>,
+    /// Exclusive upper bound, unbounded if not set
+    pub to: Option<HistogramBound>,
+}
+
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum HistogramBound {
+    /// For datetime fields, number of seconds since the epoch
+    Float(FloatPayloadType),
+    Datetime(DateTimePayloadType),
+}
+
+impl HistogramBound {
+    fn to_number(self) -> OperationResult<FloatPayloadType> {
+        match self {
+            HistogramBound::Float(value) => Ok(value),
+            HistogramBound::Datetime(_) => Err(OperationError::ValidationError {
+                description: "Datetime bounds are only supported for datetime fields".to_string(),
+            }),
+        }
+    }
+
+    fn to_timestamp(self) -> IntPayloadType {
+        match self {
+            HistogramBound::Float(seconds) => (seconds * MICROS_PER_SECOND) as IntPayloadType,
+            HistogramBound::Datetime(datetime) => datetime.timestamp(),
+        }
+    }
+}
+
+impl HistogramParams {
+    /// Resolve the buckets for the type of the field
+    pub fn resolve(&self, is_datetime: bool) -> OperationResult<HistogramBuckets> {
+        match self {
+            HistogramParams::Interval(interval) => {
+                if !interval.is_finite() || *interval <= 0.0 {
+                    return Err(OperationError::ValidationError {
+                        description: format!("Histogram interval must be positive, got {interval}"),
+                    });
+                }
+                if is_datetime {
+                    let interval = (interval * MICROS_PER_SECOND) as IntPayloadType;
+                    Ok(HistogramBuckets::DatetimeInterval(interval.max(1)))
+                } else {
+                    Ok(HistogramBuckets::Interval(*interval))
+                }
+            }
+            HistogramParams::CalendarInterval(interval) => {
+                if !is_datetime {
+                    return Err(OperationError::ValidationError {
+                        description: "Calendar intervals are only supported for datetime fields"
+                            .to_string(),
+                    });
+                }
+                Ok(HistogramBuckets::Calendar(*interval))
+            }
+            HistogramParams::Ranges(ranges) => {
+                if is_datetime {
+                    let ranges = ranges
+                        .iter()
+                        .map(|range| FacetRange {
+                            from: range.from.map(HistogramBound::to_timestamp),
+                            to: range.to.map(HistogramBound::to_timestamp),
+                        })
+                        .collect();
+                    Ok(HistogramBuckets::DatetimeRanges(ranges))
+                } else {
+                    let ranges = ranges
+                        .iter()
+                        .map(|range| {
+                            Ok(FacetRange {
+                                from: range.from.map(HistogramBound::to_number).transpose()?,
+                                to: range.to.map(HistogramBound::to_number).transpose()?,
+                            }
+                            .map(OrderedFloat))
+                        })
+                        .collect::<OperationResult<_>>()?;
+                    Ok(HistogramBuckets::Ranges(ranges))
+                }
+            }
+        }
+    }
+}
+
+impl CalendarInterval {
+    /// Calendar bucket containing a timestamp in microseconds
+    fn bucket(self, timestamp: IntPayloadType) -> Option<FacetRange<IntPayloadType>> {
+        let date = DateTime::from_timestamp_micros(timestamp)?.date_naive();
+        let (from, to) = match self {
+            CalendarInterval::Day => (date, date.succ_opt()?),
+            CalendarInterval::Week => {
+                let days_from_monday = u64::from(date.weekday().num_days_from_monday());
+                let from = date.checked_sub_days(Days::new(days_from_monday))?;
+                (from, from.checked_add_days(Days::new(7))?)
+            }
+            CalendarInterval::Month => {
+                let from = date.with_day(1)?;
+                (from, from.checked_add_months(Months::new(1))?)
+            }
+        };
+        let start_of_day =
+            |date: NaiveDate| Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp_micros());
+        Some(FacetRange {
+            from: Some(start_of_day(from)?),
+            to: Some(start_of_day(to)?),
+        })
+    }
+}
+
+/// Buckets of a histogram facet, resolved for the type of the field
+#[derive(Clone, Debug)]
+pub enum HistogramBuckets {
+    Interval(FloatPayloadType),
+    /// Width in microseconds
+    DatetimeInterval(IntPayloadType),
+    Calendar(CalendarInterval),
+    Ranges(Vec<FacetRange<OrderedFloat<FloatPayloadType>>>),
+    /// Bounds in microseconds
+    DatetimeRanges(Vec<FacetRange<IntPayloadType>>),
+}
+
+impl HistogramBuckets {
+    /// Buckets containing a value of a numeric field
+    pub fn number_buckets(&self, value: FloatPayloadType) -> Vec<FacetValue> {
+        match self {
+            HistogramBuckets::Interval(interval) => {
+                let from = (value / interval).floor() * interval;
+                vec![FacetValue::FloatRange(FacetRange {
+                    from: Some(OrderedFloat(from)),
+                    to: Some(OrderedFloat(from + interval)),
+                })]
+            }
+            HistogramBuckets::Ranges(ranges) => ranges
+                .iter()
+                .filter(|range| range.contains(&OrderedFloat(value)))
+                .map(|range| FacetValue::FloatRange(*range))
+                .collect(),
+            HistogramBuckets::DatetimeInterval(_)
+            | HistogramBuckets::Calendar(_)
+            | HistogramBuckets::DatetimeRanges(_) => vec![],
+        }
+    }
+
+    /// Buckets containing a value of a datetime field, as a timestamp in microseconds
+    pub fn datetime_buckets(&self, timestamp: IntPayloadType) -> Vec<FacetValue> {
+        match self {
+            HistogramBuckets::DatetimeInterval(interval) => {
+                let from = timestamp.div_euclid(*interval) * interval;
+                vec![FacetValue::DatetimeRange(FacetRange {
+                    from: Some(from),
+                    to: Some(from + interval),
+                })]
+            }
+            HistogramBuckets::Calendar(interval) => interval
+                .bucket(timestamp)
+                .map(FacetValue::DatetimeRange)
+                .into_iter()
+                .collect(),
+            HistogramBuckets::DatetimeRanges(ranges) => ranges
+                .iter()
+                .filter(|range| range.contains(&timestamp))
+                .map(|range| FacetValue::DatetimeRange(*range))
+                .collect(),
+            HistogramBuckets::Interval(_) | HistogramBuckets::Ranges(_) => vec![],
+        }
+    }
+
+    /// Whether each value is in exactly one bucket, and buckets follow the order of the values
+    pub fn is_sequential(&self) -> bool {
+        match self {
+            HistogramBuckets::Interval(_)
+            | HistogramBuckets::DatetimeInterval(_)
+            | HistogramBuckets::Calendar(_) => true,
+            HistogramBuckets::Ranges(_) | HistogramBuckets::DatetimeRanges(_) => false,
+        }
+    }
+}
+
+/// Bucket of a histogram facet, `from` is inclusive and `to` is exclusive. Unset bounds are unbounded.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct FacetRange<T> {
+    pub from: Option<T>,
+    pub to: Option<T>,
+}
+
+impl<T: Ord> FacetRange<T> {
+    pub fn contains(&self, value: &T) -> bool {
+        self.from.as_ref().map_or(true, |from| value >= from)
+            && self.to.as_ref().map_or(true, |to| value < to)
+    }
 }
 
+impl<T> FacetRange<T> {
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> FacetRange<U> {
+        FacetRange {
+            from: self.from.map(&f),
+            to: self.to.map(&f),
+        }
+    }
+}
+
+/// Variants match the first ones of [`FacetValue`], so a value hashes the same in both forms.
 #[derive(Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum FacetValueRef<'a> {
     Keyword(&'a str),
@@ -50,9 +333,12 @@ pub enum FacetValue {
     Int(IntPayloadType),
     Uuid(UuidIntType),
     Bool(bool),
-    // other types to add?
-    // Bool(bool),
-    // FloatRange(FloatRange),
+    GeoHash(String),
+    FloatRange(FacetRange<OrderedFloat<FloatPayloadType>>),
+    /// Bounds in microseconds
+    DatetimeRange(FacetRange<IntPayloadType>),
+    /// Combination of values of a pivot facet, one per key
+    Tuple(Vec<FacetValue>),
 }
 
 pub trait FacetValueTrait: Clone + PartialEq + Eq + Hash + Ord {}
@@ -62,10 +348,59 @@ impl FacetValueTrait for FacetValueRef<'_> {}
 
 pub type FacetValueHit = FacetHit<FacetValue>;
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug)]
 pub struct FacetHit<T: FacetValueTrait> {
     pub value: T,
     pub count: usize,
+    /// Mean location of the counted points, only for geo grid cells
+    pub centroid: Option<GeoPoint>,
+}
+
+// Centroids are never NaN
+impl<T: FacetValueTrait> Eq for FacetHit<T> {}
+
+/// Number of points with a facet value, aggregated across segments and shards
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FacetCount {
+    pub count: usize,
+    pub centroid: Option<GeoPoint>,
+}
+
+impl FacetCount {
+    /// Add the points of another count of the same value.
+    /// Centroids are weighted by the number of points on each side.
+    pub fn merge(&mut self, other: FacetCount) {
+        self.centroid = match (self.centroid.take(), other.centroid) {
+            (Some(left), Some(right)) => {
+                let total = (self.count + other.count) as f64;
+                let left_weight = self.count as f64 / total;
+                let right_weight = other.count as f64 / total;
+                Some(GeoPoint {
+                    lon: left.lon * left_weight + right.lon * right_weight,
+                    lat: left.lat * left_weight + right.lat * right_weight,
+                })
+            }
+            (left, right) => left.or(right),
+        };
+        self.count += other.count;
+    }
+
+    pub fn into_hit(self, value: FacetValue) -> FacetValueHit {
+        FacetValueHit {
+            value,
+            count: self.count,
+            centroid: self.centroid,
+        }
+    }
+}
+
+impl From<usize> for FacetCount {
+    fn from(count: usize) -> Self {
+        Self {
+            count,
+            centroid: None,
+        }
+    }
 }
 
 pub struct FacetResponse {
@@ -87,13 +422,255 @@ impl<T: FacetValueTrait> PartialOrd for FacetHit<T> {
     }
 }
 
-impl From<FacetValue> for ValueVariants {
-    fn from(value: FacetValue) -> Self {
-        match value {
-            FacetValue::Keyword(s) => ValueVariants::String(s),
-            FacetValue::Int(i) => ValueVariants::Integer(i),
-            FacetValue::Uuid(uuid) => ValueVariants::String(Uuid::from_u128(uuid).to_string()),
-            FacetValue::Bool(b) => ValueVariants::Bool(b),
+impl FacetValue {
+    /// Filter on the facet keys, matching the points counted for this value.
+    /// Tuples of pivot facets match each of their values on the key at the same level.
+    pub fn to_filter(&self, keys: &[JsonPath]) -> Filter {
+        let conditions = match self {
+            FacetValue::Tuple(values) => values
+                .iter()
+                .zip(keys)
+                .filter_map(|(value, key)| value.to_condition(key.clone()))
+                .collect_vec(),
+            value => keys
+                .first()
+                .and_then(|key| value.to_condition(key.clone()))
+                .into_iter()
+                .collect_vec(),
+        };
+
+        Filter {
+            must: Some(conditions.into_iter().map(Condition::Field).collect()),
+            ..Default::default()
         }
     }
+
+    /// Condition on a single key, not defined for tuples
+    fn to_condition(&self, key: JsonPath) -> Option<FieldCondition> {
+        let match_value = |value| FieldCondition::new_match(key.clone(), Match::new_value(value));
+        let condition = match self {
+            FacetValue::Keyword(s) => match_value(ValueVariants::String(s.clone())),
+            FacetValue::Int(i) => match_value(ValueVariants::Integer(*i)),
+            FacetValue::Uuid(uuid) => {
+                match_value(ValueVariants::String(Uuid::from_u128(*uuid).to_string()))
+            }
+            FacetValue::Bool(b) => match_value(ValueVariants::Bool(*b)),
+            FacetValue::GeoHash(hash) => match GeoHash::new(hash) {
+                Ok(cell) => FieldCondition::new_geo_bounding_box(key, geo_hash_to_box(cell)),
+                Err(_) => match_value(ValueVariants::String(hash.clone())),
+            },
+            FacetValue::FloatRange(range) => FieldCondition::new_range(
+                key,
+                Range {
+                    gte: range.from.map(|from| from.0),
+                    lt: range.to.map(|to| to.0),
+                    ..Default::default()
+                },
+            ),
+            FacetValue::DatetimeRange(range) => {
+                let datetime = |timestamp| {
+                    DateTime::from_timestamp_micros(timestamp).map(DateTimePayloadType::from)
+                };
+                FieldCondition::new_datetime_range(
+                    key,
+                    Range {
+                        lt: range.to.and_then(datetime),
+                        gt: None,
+                        gte: range.from.and_then(datetime),
+                        lte: None,
+                    },
+                )
+            }
+            FacetValue::Tuple(_) => return None,
+        };
+        Some(condition)
+    }
+}
+
+/// Keep the top values of each level of a pivot facet, within each value of the previous levels.
+///
+/// Values of a level are ranked by the total count of their combinations,
+/// and the hits are ordered level by level.
+pub fn top_pivot_hits(hits: Vec<FacetValueHit>, limits: &[usize]) -> Vec<FacetValueHit> {
+    top_pivot_level_hits(hits, limits, 0)
+}
+
+fn top_pivot_level_hits(
+    hits: Vec<FacetValueHit>,
+    limits: &[usize],
+    level: usize,
+) -> Vec<FacetValueHit> {
+    let Some(&limit) = limits.get(level) else {
+        return hits;
+    };
+
+    let level_value = |hit: &FacetValueHit| match &hit.value {
+        FacetValue::Tuple(values) => values.get(level).cloned(),
+        _ => None,
+    };
+
+    hits.into_iter()
+        .into_group_map_by(level_value)
+        .into_iter()
+        .filter_map(|(value, hits)| {
+            let total = FacetHit {
+                value: value?,
+                count: hits.iter().map(|hit| hit.count).sum(),
+                centroid: None,
+            };
+            Some((total, hits))
+        })
+        .k_largest_by(limit, |(left, _), (right, _)| left.cmp(right))
+        .flat_map(|(_, hits)| top_pivot_level_hits(hits, limits, level + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_facet_count_centroids() {
+        let mut count = FacetCount {
+            count: 1,
+            centroid: Some(GeoPoint { lon: 0.0, lat: 0.0 }),
+        };
+        count.merge(FacetCount {
+            count: 3,
+            centroid: Some(GeoPoint { lon: 4.0, lat: 8.0 }),
+        });
+        count.merge(FacetCount::default());
+
+        assert_eq!(count.count, 4);
+        assert_eq!(count.centroid, Some(GeoPoint { lon: 3.0, lat: 6.0 }));
+    }
+
+    fn timestamp(datetime: &str) -> IntPayloadType {
+        datetime.parse::<DateTimePayloadType>().unwrap().timestamp()
+    }
+
+    #[test]
+    fn test_calendar_buckets() {
+        let value = timestamp("2024-02-29T13:45:00Z");
+        let bucket = |interval| {
+            let buckets = HistogramParams::CalendarInterval(interval)
+                .resolve(true)
+                .unwrap()
+                .datetime_buckets(value);
+            assert_eq!(buckets.len(), 1);
+            buckets[0].clone()
+        };
+        let range = |from, to| {
+            FacetValue::DatetimeRange(FacetRange {
+                from: Some(timestamp(from)),
+                to: Some(timestamp(to)),
+            })
+        };
+
+        assert_eq!(
+            bucket(CalendarInterval::Day),
+            range("2024-02-29T00:00:00Z", "2024-03-01T00:00:00Z"),
+        );
+        assert_eq!(
+            bucket(CalendarInterval::Week),
+            range("2024-02-26T00:00:00Z", "2024-03-04T00:00:00Z"),
+        );
+        assert_eq!(
+            bucket(CalendarInterval::Month),
+            range("2024-02-01T00:00:00Z", "2024-03-01T00:00:00Z"),
+        );
+
+        // Calendar intervals only make sense for datetimes
+        assert!(HistogramParams::CalendarInterval(CalendarInterval::Day)
+            .resolve(false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_number_buckets() {
+        let buckets = HistogramParams::Interval(10.0).resolve(false).unwrap();
+        let range = |from: f64, to: f64| {
+            FacetValue::FloatRange(FacetRange {
+                from: Some(OrderedFloat(from)),
+                to: Some(OrderedFloat(to)),
+            })
+        };
+        assert_eq!(buckets.number_buckets(25.0), vec![range(20.0, 30.0)]);
+        assert_eq!(buckets.number_buckets(-5.0), vec![range(-10.0, 0.0)]);
+
+        let buckets = HistogramParams::Ranges(vec![
+            HistogramRange {
+                from: None,
+                to: Some(HistogramBound::Float(10.0)),
+            },
+            HistogramRange {
+                from: Some(HistogramBound::Float(5.0)),
+                to: None,
+            },
+        ])
+        .resolve(false)
+        .unwrap();
+        assert_eq!(buckets.number_buckets(10.0).len(), 1);
+        assert_eq!(buckets.number_buckets(7.0).len(), 2);
+        assert!(HistogramParams::Interval(0.0).resolve(false).is_err());
+    }
+
+    #[test]
+    fn test_top_pivot_hits() {
+        let hit = |category: &str, brand: &str, count| FacetValueHit {
+            value: FacetValue::Tuple(vec![
+                FacetValue::Keyword(category.to_string()),
+                FacetValue::Keyword(brand.to_string()),
+            ]),
+            count,
+            centroid: None,
+        };
+
+        let hits = vec![
+            hit("shoes", "acme", 5),
+            hit("shoes", "globex", 4),
+            hit("shoes", "initech", 1),
+            hit("hats", "acme", 20),
+            hit("bags", "acme", 3),
+            hit("bags", "globex", 3),
+            hit("bags", "initech", 3),
+        ];
+
+        // Categories are ranked by their total count, brands within each category
+        let top = top_pivot_hits(hits, &[2, 2]);
+        assert_eq!(
+            top,
+            vec![
+                hit("hats", "acme", 20),
+                hit("shoes", "acme", 5),
+                hit("shoes", "globex", 4),
+            ],
+        );
+
+        let filter = top[1]
+            .value
+            .to_filter(&[JsonPath::new("category"), JsonPath::new("brand")]);
+        assert_eq!(filter.must.map(|must| must.len()), Some(2));
+    }
+
+    #[test]
+    fn test_pivot_levels() {
+        let params = |levels: usize| FacetParams {
+            key: JsonPath::new("category"),
+            limit: 10,
+            filter: None,
+            exact: false,
+            geo_grid: None,
+            histogram: None,
+            pivot: (0..levels)
+                .map(|level| FacetPivotParams {
+                    key: JsonPath::new(&format!("level_{level}")),
+                    limit: 10,
+                })
+                .collect(),
+        };
+
+        assert!(params(3).validate().is_ok());
+        assert!(params(4).validate().is_err());
+    }
 }
@@ -9,6 +9,8 @@ pub enum GroupId {
     String(String),
     NumberU64(u64),
     NumberI64(i64),
+    /// Values of each of the keys, when grouping by several keys
+    Composite(Vec<GroupId>),
 }
 
 impl From<u64> for GroupId {
@@ -41,6 +43,9 @@ impl From<GroupId> for serde_json::Value {
             GroupId::String(s) => serde_json::Value::String(s),
             GroupId::NumberU64(n) => json!(n),
             GroupId::NumberI64(n) => json!(n),
+            GroupId::Composite(ids) => {
+                serde_json::Value::Array(ids.into_iter().map(serde_json::Value::from).collect())
+            }
         }
     }
 }
@@ -71,7 +76,20 @@ impl GroupId {
         match self {
             GroupId::NumberI64(id) => u64::try_from(*id).ok(),
             GroupId::NumberU64(id) => Some(*id),
-            GroupId::String(_) => None,
+            GroupId::String(_) | GroupId::Composite(_) => None,
         }
     }
 }
+
+/// How the score of a group is computed from the scores of its hits
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupScore {
+    /// Score of the best hit
+    #[default]
+    Max,
+    /// Sum of the scores of the returned hits
+    Sum,
+    /// Average of the scores of the returned hits
+    Average,
+}
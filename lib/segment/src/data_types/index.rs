@@ -1,5 +1,11 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+use unicode_normalization::UnicodeNormalization;
+use validator::{Validate, ValidationError};
 
 // Keyword
 
@@ -23,6 +29,40 @@ pub struct KeywordIndexParams {
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+
+    /// Normalization applied to indexed keywords and to matched values. Default: none, keywords are matched exactly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalizer: Option<KeywordNormalizer>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeywordNormalizer {
+    /// Lowercase keywords
+    Lowercase,
+    /// Apply Unicode NFKC normalization, then lowercase keywords
+    LowercaseNfkc,
+}
+
+impl KeywordNormalizer {
+    pub fn normalize<'a>(&self, keyword: &'a str) -> Cow<'a, str> {
+        match self {
+            KeywordNormalizer::Lowercase => {
+                if keyword
+                    .chars()
+                    .flat_map(char::to_lowercase)
+                    .eq(keyword.chars())
+                {
+                    Cow::Borrowed(keyword)
+                } else {
+                    Cow::Owned(keyword.to_lowercase())
+                }
+            }
+            KeywordNormalizer::LowercaseNfkc => {
+                Cow::Owned(keyword.nfkc().collect::<String>().to_lowercase())
+            }
+        }
+    }
 }
 
 // Integer
@@ -123,6 +163,22 @@ pub struct GeoIndexParams {
     pub on_disk: Option<bool>,
 }
 
+// Geo shape
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoShapeIndexType {
+    #[default]
+    GeoShape,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoShapeIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: GeoShapeIndexType,
+}
+
 // Text
 
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
@@ -140,10 +196,92 @@ pub enum TokenizerType {
     #[default]
     Word,
     Multilingual,
+    /// Character n-grams of the whole text, for substring matching
+    Ngram,
+}
+
+/// Languages with predefined stopwords and stemmers
+#[derive(
+    Debug,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    Clone,
+    Copy,
+    PartialEq,
+    Hash,
+    Eq,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Language {
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Russian,
+    Spanish,
+    Swedish,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StopwordsInterface {
+    /// Predefined stopwords of a language
+    Language(Language),
+    Set(StopwordsSet),
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
 #[serde(rename_all = "snake_case")]
+pub struct StopwordsSet {
+    /// Languages of the predefined stopwords to use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub languages: Option<BTreeSet<Language>>,
+
+    /// Custom stopwords
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom: Option<BTreeSet<String>>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnowballType {
+    #[default]
+    Snowball,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SnowballParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: SnowballType,
+
+    /// Language of the stemmer
+    pub language: Language,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StemmingAlgorithm {
+    Snowball(SnowballParams),
+}
+
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Hash, Eq,
+)]
+#[validate(schema(function = "validate_ngram_range"))]
+#[serde(rename_all = "snake_case")]
 pub struct TextIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: TextIndexType,
@@ -159,15 +297,67 @@ pub struct TextIndexParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_token_len: Option<usize>,
 
+    /// Minimal size of the n-grams, only for the `ngram` tokenizer. Default: 3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10))]
+    pub min_ngram: Option<usize>,
+
+    /// Maximal size of the n-grams, only for the `ngram` tokenizer. Default: 3.
+    /// Must not be less than `min_ngram`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 10))]
+    pub max_ngram: Option<usize>,
+
     /// If true, lowercase all tokens. Default: true.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub lowercase: Option<bool>,
 
+    /// If true, store token positions to support phrase matching. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phrase_matching: Option<bool>,
+
+    /// Tokens to skip, from predefined languages and/or a custom set.
+    /// Compared to the tokens after lowercasing. Default: none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopwords: Option<StopwordsInterface>,
+
+    /// Algorithm to reduce the tokens to their stem, like "running" to "run". Default: none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stemmer: Option<StemmingAlgorithm>,
+
+    /// If true, fold the accented characters to ASCII, like "café" to "cafe". Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii_folding: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
 }
 
+impl TextIndexParams {
+    pub const DEFAULT_NGRAM: usize = 3;
+
+    /// Minimal and maximal sizes of the n-grams
+    pub fn ngram_range(&self) -> (usize, usize) {
+        let min_ngram = self.min_ngram.unwrap_or(Self::DEFAULT_NGRAM);
+        let max_ngram = self.max_ngram.unwrap_or(Self::DEFAULT_NGRAM);
+        (min_ngram, max_ngram)
+    }
+}
+
+fn validate_ngram_range(params: &TextIndexParams) -> Result<(), ValidationError> {
+    let (min_ngram, max_ngram) = params.ngram_range();
+    if min_ngram > max_ngram {
+        let mut error = ValidationError::new("invalid_ngram_range");
+        error.message.replace(
+            format!("min_ngram ({min_ngram}) must not be greater than max_ngram ({max_ngram})")
+                .into(),
+        );
+        return Err(error);
+    }
+    Ok(())
+}
+
 // Bool
 
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::Mutex;
+use regex::{Regex, RegexBuilder};
+use validator::{ValidationError, ValidationErrors};
+
+use crate::types::{Match, MatchPrefix, MatchRegex, MatchWildcard};
+
+/// Compiled regexes are reused between the checks of the same condition
+const REGEX_CACHE_SIZE: usize = 256;
+
+/// Maximal size of a compiled regex, in bytes, so that a pattern can't exhaust the memory
+const REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Pattern of a prefix, wildcard or regex match over keywords
+#[derive(Debug, Clone)]
+pub enum KeywordPattern {
+    Prefix(String),
+    /// Wildcards are compiled to regexes too
+    Regex(Arc<Regex>),
+}
+
+impl KeywordPattern {
+    /// Pattern of the match condition, `None` if it is not a pattern match
+    pub fn from_match(condition: &Match) -> Option<Result<Self, regex::Error>> {
+        match condition {
+            Match::Prefix(MatchPrefix { prefix }) => Some(Ok(Self::Prefix(prefix.clone()))),
+            Match::Wildcard(MatchWildcard { wildcard }) => {
+                Some(compile_cached(&wildcard_to_regex(wildcard)).map(Self::Regex))
+            }
+            Match::Regex(MatchRegex { regex }) => {
+                Some(compile_cached(&anchored_regex(regex)).map(Self::Regex))
+            }
+            Match::Value(_)
+            | Match::Text(_)
+            | Match::Phrase(_)
+            | Match::Fuzzy(_)
+            | Match::Any(_)
+            | Match::All(_)
+            | Match::Except(_) => None,
+        }
+    }
+
+    pub fn is_match(&self, keyword: &str) -> bool {
+        match self {
+            Self::Prefix(prefix) => keyword.starts_with(prefix.as_str()),
+            Self::Regex(regex) => regex.is_match(keyword),
+        }
+    }
+}
+
+/// Rejects the wildcard and regex patterns which can't be compiled
+pub fn validate_keyword_pattern(condition: &Match) -> Result<(), ValidationErrors> {
+    let Some(Err(err)) = KeywordPattern::from_match(condition) else {
+        return Ok(());
+    };
+
+    let mut error = ValidationError::new("invalid_regex");
+    error
+        .message
+        .replace(format!("Invalid pattern: {err}").into());
+    let mut errors = ValidationErrors::new();
+    errors.add("match", error);
+    Err(errors)
+}
+
+/// The whole keyword must match the regex
+fn anchored_regex(regex: &str) -> String {
+    format!("^(?:{regex})$")
+}
+
+/// `*` matches any sequence of characters, `?` matches a single character
+fn wildcard_to_regex(wildcard: &str) -> String {
+    let mut regex = String::with_capacity(wildcard.len() + 2);
+    regex.push('^');
+    let mut literal = [0; 4];
+    for c in wildcard.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(c.encode_utf8(&mut literal))),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+fn compile_cached(regex: &str) -> Result<Arc<Regex>, regex::Error> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Arc<Regex>>>> = OnceLock::new();
+
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(compiled) = cache.lock().get(regex) {
+        return Ok(compiled.clone());
+    }
+
+    let compiled = Arc::new(
+        RegexBuilder::new(regex)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()?,
+    );
+    let mut cache = cache.lock();
+    if cache.len() >= REGEX_CACHE_SIZE {
+        cache.clear();
+    }
+    cache.insert(regex.to_string(), compiled.clone());
+    Ok(compiled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(condition: Match) -> KeywordPattern {
+        KeywordPattern::from_match(&condition).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_keyword_patterns() {
+        let prefix = pattern(Match::new_prefix("us-east-"));
+        assert!(prefix.is_match("us-east-1"));
+        assert!(!prefix.is_match("eu-west-1"));
+
+        let wildcard = pattern(Match::new_wildcard("us-*-?"));
+        assert!(wildcard.is_match("us-east-1"));
+        assert!(!wildcard.is_match("us-east-12"));
+        assert!(!wildcard.is_match("eu-west-1"));
+
+        // Regex metacharacters are literal in wildcards
+        let wildcard = pattern(Match::new_wildcard("v1.*"));
+        assert!(wildcard.is_match("v1.2"));
+        assert!(!wildcard.is_match("v1-2"));
+
+        let regex = pattern(Match::new_regex("us-(east|west)-[0-9]+"));
+        assert!(regex.is_match("us-west-42"));
+        assert!(!regex.is_match("us-west-42-b"));
+        assert!(!regex.is_match("xus-west-42"));
+
+        assert!(KeywordPattern::from_match(&Match::new_regex("us-(east"))
+            .unwrap()
+            .is_err());
+        assert!(KeywordPattern::from_match(&Match::new_text("us")).is_none());
+    }
+
+    #[test]
+    fn test_validate_keyword_pattern() {
+        assert!(validate_keyword_pattern(&Match::new_regex("us-(east|west)")).is_ok());
+        assert!(validate_keyword_pattern(&Match::new_wildcard("us-*")).is_ok());
+        assert!(validate_keyword_pattern(&Match::new_regex("us-(east")).is_err());
+
+        // Patterns compiling to huge automatons are rejected
+        assert!(validate_keyword_pattern(&Match::new_regex("\\w{10000}")).is_err());
+    }
+}
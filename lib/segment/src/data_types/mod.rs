@@ -1,6 +1,9 @@
+pub mod aggregations;
+pub mod bm25;
 pub mod facets;
 pub mod groups;
 pub mod index;
+pub mod keyword_pattern;
 pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
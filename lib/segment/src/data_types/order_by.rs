@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use num_cmp::NumCmp;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
@@ -6,7 +8,8 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, Payload, Range, RangeInterface,
+    DateTimePayloadType, FloatPayloadType, GeoPoint, IntPayloadType, Order, Payload, PointIdType,
+    Range,
 };
 
 const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
@@ -36,6 +39,14 @@ impl Direction {
             },
         }
     }
+
+    /// Turns an ascending comparison into one of this direction
+    pub fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            Direction::Asc => ordering,
+            Direction::Desc => ordering.reverse(),
+        }
+    }
 }
 
 impl From<Direction> for Order {
@@ -54,6 +65,9 @@ pub enum StartFrom {
 
     Float(FloatPayloadType),
 
+    /// Keyword to start from. For a field with a numeric or datetime index it is parsed as a datetime
+    Keyword(String),
+
     Datetime(DateTimePayloadType),
 }
 
@@ -68,25 +82,61 @@ pub struct OrderBy {
 
     /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
     pub start_from: Option<StartFrom>,
+
+    /// Keys to order by when the values of all previous keys are equal
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub then_by: Vec<OrderByKey>,
+
+    /// Continue right after this position, usually the `order_value` and `id` of the last point of
+    /// the previous page. Takes precedence over `start_from`
+    pub start_after: Option<OrderCursor>,
+
+    /// Order by the distance from this point instead of the values of a geo `key`, nearest first
+    /// for `asc`. The distance in meters is returned as the order value, and `start_from` is a
+    /// distance as well.
+    pub distance_from: Option<GeoPoint>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderByKey {
+    /// Payload key to order by
+    pub key: JsonPath,
+
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+}
+
+impl OrderByKey {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
+}
+
+/// Position in the ordered results: values of all order keys, and the point id to break ties
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderCursor {
+    /// Values of the order keys, one per key
+    pub values: Vec<OrderValue>,
+
+    /// Id of the point at this position
+    pub id: PointIdType,
 }
 
 impl OrderBy {
-    /// Returns a range representation of OrderBy.
-    pub fn as_range(&self) -> RangeInterface {
-        self.start_from
-            .as_ref()
-            .map(|start_from| match start_from {
-                // TODO: When we introduce integer ranges, we'll stop doing lossy conversion to f64 here
-                // Accepting an integer as start_from simplifies the client generation.
-                StartFrom::Integer(i) => {
-                    RangeInterface::Float(self.direction().as_range_from(*i as f64))
-                }
-                StartFrom::Float(f) => RangeInterface::Float(self.direction().as_range_from(*f)),
-                StartFrom::Datetime(dt) => {
-                    RangeInterface::DateTime(self.direction().as_range_from(*dt))
-                }
-            })
-            .unwrap_or_else(|| RangeInterface::Float(Range::default()))
+    /// Returns a range of values to read, from `start_from` in the direction of ordering.
+    /// Integers and datetimes are kept as integers, so that the bounds are exact.
+    pub fn as_range(&self) -> Range<OrderValue> {
+        match self.start_value() {
+            Some(value) => self.direction().as_range_from(value),
+            None => Range {
+                lt: None,
+                gt: None,
+                gte: None,
+                lte: None,
+            },
+        }
     }
 
     pub fn direction(&self) -> Direction {
@@ -94,19 +144,83 @@ impl OrderBy {
     }
 
     pub fn start_from(&self) -> OrderValue {
-        self.start_from
-            .as_ref()
-            .map(|start_from| match start_from {
-                StartFrom::Integer(i) => OrderValue::Int(*i),
-                StartFrom::Float(f) => OrderValue::Float(*f),
-                StartFrom::Datetime(dt) => OrderValue::Int(dt.timestamp()),
-            })
+        self.start_value()
             .unwrap_or_else(|| match self.direction() {
                 Direction::Asc => OrderValue::MIN,
                 Direction::Desc => OrderValue::MAX,
             })
     }
 
+    /// Value to start from, taken from `start_after` or `start_from`
+    fn start_value(&self) -> Option<OrderValue> {
+        if let Some(value) = self.cursor_start() {
+            return Some(value.clone());
+        }
+
+        self.start_from.as_ref().map(|start_from| match start_from {
+            StartFrom::Integer(i) => OrderValue::Int(*i),
+            StartFrom::Float(f) => OrderValue::Float(*f),
+            StartFrom::Datetime(dt) => OrderValue::Int(dt.timestamp()),
+            StartFrom::Keyword(keyword) => OrderValue::Keyword(keyword.clone()),
+        })
+    }
+
+    /// Value of the first key in `start_after`, if any
+    fn cursor_start(&self) -> Option<&OrderValue> {
+        self.start_after
+            .as_ref()
+            .and_then(|cursor| cursor.values.first())
+            .filter(|value| !matches!(value, OrderValue::Composite(_)))
+    }
+
+    /// All keys to order by with their directions, starting with the primary one
+    pub fn keys(&self) -> impl Iterator<Item = (&JsonPath, Direction)> {
+        std::iter::once((&self.key, self.direction())).chain(
+            self.then_by
+                .iter()
+                .map(|order_key| (&order_key.key, order_key.direction())),
+        )
+    }
+
+    /// Compares order values in the order of results, `Less` means `a` goes first.
+    /// Each key is compared in its own direction.
+    pub fn cmp_values(&self, a: &OrderValue, b: &OrderValue) -> Ordering {
+        self.cmp_components(a.components(), b.components())
+    }
+
+    fn cmp_components(&self, a: &[OrderValue], b: &[OrderValue]) -> Ordering {
+        self.keys()
+            .zip(a.iter().zip(b))
+            .map(|((_, direction), (a, b))| direction.apply(a.cmp(b)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Compares point ids of entries with equal values. Ids follow the direction of the first key,
+    /// like `(value, id)` pairs, so that the order is the same in every segment and shard.
+    pub fn cmp_ids(&self, a: &PointIdType, b: &PointIdType) -> Ordering {
+        self.direction().apply(a.cmp(b))
+    }
+
+    /// Compares `(value, id)` entries in the order of results, ties are broken by the point id
+    pub fn cmp_entries(
+        &self,
+        (a_value, a_id): &(OrderValue, PointIdType),
+        (b_value, b_id): &(OrderValue, PointIdType),
+    ) -> Ordering {
+        self.cmp_values(a_value, b_value)
+            .then_with(|| self.cmp_ids(a_id, b_id))
+    }
+
+    /// Whether the entry goes after the `start_after` cursor. Always true without a cursor.
+    pub fn is_after_cursor(&self, value: &OrderValue, id: PointIdType) -> bool {
+        self.start_after.as_ref().map_or(true, |cursor| {
+            self.cmp_components(value.components(), &cursor.values)
+                .then_with(|| self.cmp_ids(&id, &cursor.id))
+                .is_gt()
+        })
+    }
+
     pub fn insert_order_value_in_payload(
         payload: Option<Payload>,
         value: impl Into<serde_json::Value>,
@@ -148,13 +262,21 @@ fn order_value_float_example() -> FloatPayloadType {
     42.5
 }
 
-#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+fn order_value_keyword_example() -> String {
+    "alpha".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum OrderValue {
     #[schemars(example = "order_value_int_example")]
     Int(IntPayloadType),
     #[schemars(example = "order_value_float_example")]
     Float(FloatPayloadType),
+    #[schemars(example = "order_value_keyword_example")]
+    Keyword(String),
+    /// Values of all keys, when ordering by multiple keys
+    Composite(Vec<OrderValue>),
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -163,12 +285,22 @@ impl std::hash::Hash for OrderValue {
         match self {
             OrderValue::Int(i) => i.hash(state),
             OrderValue::Float(f) => f.to_bits().hash(state),
+            OrderValue::Keyword(s) => s.hash(state),
+            OrderValue::Composite(values) => values.hash(state),
         }
     }
 }
 impl OrderValue {
-    const MAX: Self = Self::Float(f64::NAN);
-    const MIN: Self = Self::Float(f64::MIN);
+    pub(crate) const MAX: Self = Self::Float(f64::NAN);
+    pub(crate) const MIN: Self = Self::Float(f64::MIN);
+
+    /// Values of the individual keys, a single one unless composite
+    pub fn components(&self) -> &[OrderValue] {
+        match self {
+            OrderValue::Composite(values) => values,
+            _ => std::slice::from_ref(self),
+        }
+    }
 }
 
 impl From<OrderValue> for serde_json::Value {
@@ -178,6 +310,10 @@ impl From<OrderValue> for serde_json::Value {
                 .map(serde_json::Value::Number)
                 .unwrap_or(serde_json::Value::Null),
             OrderValue::Int(value) => serde_json::Value::Number(serde_json::Number::from(value)),
+            OrderValue::Keyword(value) => serde_json::Value::String(value),
+            OrderValue::Composite(values) => {
+                serde_json::Value::Array(values.into_iter().map(serde_json::Value::from).collect())
+            }
         }
     }
 }
@@ -186,10 +322,19 @@ impl TryFrom<serde_json::Value> for OrderValue {
     type Error = ();
 
     fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        if let serde_json::Value::Array(values) = value {
+            return values
+                .into_iter()
+                .map(OrderValue::try_from)
+                .collect::<Result<_, _>>()
+                .map(OrderValue::Composite);
+        }
+
         value
             .as_i64()
             .map(Self::from)
             .or_else(|| value.as_f64().map(Self::from))
+            .or_else(|| value.as_str().map(|s| OrderValue::Keyword(s.to_string())))
             .ok_or(())
     }
 }
@@ -211,10 +356,15 @@ impl Eq for OrderValue {}
 impl PartialEq for OrderValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
+            (OrderValue::Composite(_), _) | (_, OrderValue::Composite(_)) => {
+                self.components() == other.components()
+            }
             (OrderValue::Float(a), OrderValue::Float(b)) => OrderedFloat(*a) == OrderedFloat(*b),
             (OrderValue::Int(a), OrderValue::Int(b)) => a == b,
             (OrderValue::Float(a), OrderValue::Int(b)) => a.num_eq(*b),
             (OrderValue::Int(a), OrderValue::Float(b)) => a.num_eq(*b),
+            (OrderValue::Keyword(a), OrderValue::Keyword(b)) => a == b,
+            (OrderValue::Keyword(_), _) | (_, OrderValue::Keyword(_)) => false,
         }
     }
 }
@@ -226,8 +376,22 @@ impl PartialOrd for OrderValue {
 }
 
 impl Ord for OrderValue {
+    /// Numbers are ordered before keywords, except NaN which is greater than anything, so that
+    /// `MIN` and `MAX` bound keywords as well. Composite values are compared lexicographically.
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match (self, other) {
+            (OrderValue::Composite(_), _) | (_, OrderValue::Composite(_)) => {
+                self.components().cmp(other.components())
+            }
+            (OrderValue::Keyword(a), OrderValue::Keyword(b)) => a.cmp(b),
+            (OrderValue::Keyword(_), OrderValue::Float(b)) if b.is_nan() => {
+                std::cmp::Ordering::Less
+            }
+            (OrderValue::Float(a), OrderValue::Keyword(_)) if a.is_nan() => {
+                std::cmp::Ordering::Greater
+            }
+            (OrderValue::Keyword(_), _) => std::cmp::Ordering::Greater,
+            (_, OrderValue::Keyword(_)) => std::cmp::Ordering::Less,
             (OrderValue::Float(a), OrderValue::Float(b)) => OrderedFloat(*a).cmp(&OrderedFloat(*b)),
             (OrderValue::Int(a), OrderValue::Int(b)) => a.cmp(b),
             (OrderValue::Float(a), OrderValue::Int(b)) => {
@@ -247,7 +411,45 @@ impl Ord for OrderValue {
 mod tests {
     use proptest::proptest;
 
-    use crate::data_types::order_by::OrderValue;
+    use crate::data_types::order_by::{Direction, OrderBy, OrderByKey, OrderCursor, OrderValue};
+
+    #[test]
+    fn test_composite_order() {
+        let order_by = OrderBy {
+            key: "group".parse().unwrap(),
+            direction: Some(Direction::Desc),
+            start_from: None,
+            then_by: vec![OrderByKey {
+                key: "title".parse().unwrap(),
+                direction: None,
+            }],
+            start_after: Some(OrderCursor {
+                values: vec![OrderValue::Int(2), OrderValue::Keyword("b".to_string())],
+                id: 5.into(),
+            }),
+            distance_from: None,
+        };
+
+        let value = |group: i64, title: &str| {
+            OrderValue::Composite(vec![
+                OrderValue::Int(group),
+                OrderValue::Keyword(title.to_string()),
+            ])
+        };
+
+        // The first key is descending, the second one ascending
+        assert!(order_by.cmp_values(&value(2, "z"), &value(1, "a")).is_lt());
+        assert!(order_by.cmp_values(&value(2, "a"), &value(2, "b")).is_lt());
+        assert!(order_by.cmp_values(&value(2, "a"), &value(2, "a")).is_eq());
+
+        // Ties of all values are broken by the point id, in the direction of the first key
+        assert!(!order_by.is_after_cursor(&value(2, "a"), 7.into()));
+        assert!(!order_by.is_after_cursor(&value(2, "b"), 5.into()));
+        assert!(!order_by.is_after_cursor(&value(2, "b"), 6.into()));
+        assert!(order_by.is_after_cursor(&value(2, "b"), 4.into()));
+        assert!(order_by.is_after_cursor(&value(2, "c"), 1.into()));
+        assert!(order_by.is_after_cursor(&value(1, "a"), 1.into()));
+    }
 
     proptest! {
 
@@ -264,5 +466,13 @@ mod tests {
             assert!(OrderValue::MAX.cmp(&OrderValue::from(b)).is_ge());
             assert!(OrderValue::MAX.cmp(&OrderValue::from(f64::NAN)).is_ge());
         }
+
+        #[test]
+        fn test_keyword_ordering_value(a in ".*", b in ".*") {
+            let (value_a, value_b) = (OrderValue::Keyword(a.clone()), OrderValue::Keyword(b.clone()));
+            assert_eq!(value_a.cmp(&value_b), a.cmp(&b));
+            assert!(OrderValue::MIN.cmp(&value_a).is_lt());
+            assert!(OrderValue::MAX.cmp(&value_a).is_gt());
+        }
     }
 }
@@ -5,13 +5,17 @@ use std::sync::atomic::AtomicBool;
 use common::tar_ext;
 use common::types::TelemetryDetail;
 
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
-use crate::data_types::facets::{FacetParams, FacetValue};
+use crate::data_types::aggregations::{AggregateParams, DistinctCountParams, PartialAggregate};
+use crate::data_types::bm25::{Bm25Query, Bm25Stats};
+use crate::data_types::facets::{FacetCount, FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
@@ -164,7 +168,51 @@ pub trait SegmentEntry {
         &self,
         request: &FacetParams,
         is_stopped: &AtomicBool,
-    ) -> OperationResult<HashMap<FacetValue, usize>>;
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>>;
+
+    /// Aggregate the numeric values of the given key, to be merged with other segments.
+    fn aggregate(
+        &self,
+        request: &AggregateParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<PartialAggregate>;
+
+    /// Sketch the distinct values of the given key, to be merged with other segments.
+    fn distinct_count(
+        &self,
+        request: &DistinctCountParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HyperLogLog>;
+
+    /// Rescore the results of the prefetches with a formula.
+    ///
+    /// Only the points present in this segment are rescored, returns the best `limit` of them.
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
+    /// Statistics of the full-text index of `query.key` for the query tokens.
+    ///
+    /// Will fail if there is no full-text index for the key.
+    fn bm25_stats(&self, query: &Bm25Query) -> OperationResult<Bm25Stats>;
+
+    /// Return the best `limit` points which satisfy the filtering condition, ranked by BM25 relevance
+    /// to the query text, using the statistics collected from all the searched segments.
+    ///
+    /// Will fail if there is no full-text index for the key.
+    /// Cancelled by `is_stopped` flag.
+    fn search_bm25(
+        &self,
+        query: &Bm25Query,
+        stats: &Bm25Stats,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>>;
 
     /// Check if there is point with `point_id` in this segment.
     fn has_point(&self, point_id: PointIdType) -> bool;
@@ -2,11 +2,12 @@ use common::types::PointOffsetType;
 use itertools::Itertools;
 
 use super::bool_index::simple_bool_index::BoolIndex;
-use super::map_index::MapIndex;
-use crate::data_types::facets::{FacetHit, FacetValueRef};
+use super::map_index::{IdIter, MapIndex};
+use super::numeric_index::{NumericIndexInner, StreamRange};
+use crate::data_types::facets::{FacetHit, FacetValue, FacetValueRef, HistogramBuckets};
 use crate::index::struct_filter_context::StructFilterContext;
 use crate::payload_storage::FilterContext;
-use crate::types::{IntPayloadType, UuidIntType};
+use crate::types::{FloatPayloadType, IntPayloadType, Range, RangeInterface, UuidIntType};
 
 pub enum FacetIndex<'a> {
     Keyword(&'a MapIndex<str>),
@@ -83,11 +84,11 @@ impl<'a> FacetIndex<'a> {
         }
     }
 
-    pub fn iter_filtered_counts_per_value(
+    /// Iterate over the values, along with the points having each value
+    pub fn iter_values_with_points(
         &self,
-        context: &'a StructFilterContext,
-    ) -> impl Iterator<Item = FacetHit<FacetValueRef<'a>>> + 'a {
-        let iter: Box<dyn Iterator<Item = _>> = match self {
+    ) -> Box<dyn Iterator<Item = (FacetValueRef<'a>, IdIter<'a>)> + 'a> {
+        match self {
             FacetIndex::Keyword(index) => {
                 let iter = index
                     .iter_values_map()
@@ -112,15 +113,22 @@ impl<'a> FacetIndex<'a> {
                     .map(|(value, ids_iter)| (FacetValueRef::Bool(value), ids_iter));
                 Box::new(iter)
             }
-        };
+        }
+    }
 
-        iter.map(|(value, internal_ids_iter)| FacetHit {
-            value,
-            count: internal_ids_iter
-                .unique()
-                .filter(|&point_id| context.check(point_id))
-                .count(),
-        })
+    pub fn iter_filtered_counts_per_value(
+        &self,
+        context: &'a StructFilterContext,
+    ) -> impl Iterator<Item = FacetHit<FacetValueRef<'a>>> + 'a {
+        self.iter_values_with_points()
+            .map(|(value, internal_ids_iter)| FacetHit {
+                value,
+                count: internal_ids_iter
+                    .unique()
+                    .filter(|&point_id| context.check(point_id))
+                    .count(),
+                centroid: None,
+            })
     }
     pub fn iter_counts_per_value(
         &'a self,
@@ -152,6 +160,129 @@ impl<'a> FacetIndex<'a> {
             }
         };
 
-        iter.map(|(value, count)| FacetHit { value, count })
+        iter.map(|(value, count)| FacetHit {
+            value,
+            count,
+            centroid: None,
+        })
+    }
+}
+
+/// Numeric index to count points per bucket of a histogram facet
+pub enum HistogramIndex<'a> {
+    Int(&'a NumericIndexInner<IntPayloadType>),
+    Float(&'a NumericIndexInner<FloatPayloadType>),
+    Datetime(&'a NumericIndexInner<IntPayloadType>),
+}
+
+impl<'a> HistogramIndex<'a> {
+    pub fn is_datetime(&self) -> bool {
+        matches!(self, HistogramIndex::Datetime(_))
+    }
+
+    /// Values of the point as numbers, datetimes as timestamps in microseconds
+    pub fn get_numbers(&self, point_id: PointOffsetType) -> Vec<FloatPayloadType> {
+        match self {
+            HistogramIndex::Int(index) | HistogramIndex::Datetime(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .map(|value| value as FloatPayloadType)
+                .collect(),
+            HistogramIndex::Float(index) => {
+                index.get_values(point_id).into_iter().flatten().collect()
+            }
+        }
+    }
+
+    /// All values of the index as numbers, in ascending order
+    pub fn iter_numbers(&self) -> Box<dyn Iterator<Item = FloatPayloadType> + 'a> {
+        let all_values = RangeInterface::Float(Range::default());
+        match *self {
+            HistogramIndex::Int(index) | HistogramIndex::Datetime(index) => Box::new(
+                index
+                    .stream_range(&all_values)
+                    .map(|(value, _)| value as FloatPayloadType),
+            ),
+            HistogramIndex::Float(index) => {
+                Box::new(index.stream_range(&all_values).map(|(value, _)| value))
+            }
+        }
+    }
+
+    /// Buckets containing values of the point, each bucket only once
+    pub fn get_buckets(
+        &self,
+        point_id: PointOffsetType,
+        buckets: &HistogramBuckets,
+    ) -> Vec<FacetValue> {
+        let mut point_buckets = match self {
+            HistogramIndex::Int(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .flat_map(|value| buckets.number_buckets(value as FloatPayloadType))
+                .collect_vec(),
+            HistogramIndex::Float(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .flat_map(|value| buckets.number_buckets(value))
+                .collect_vec(),
+            HistogramIndex::Datetime(index) => index
+                .get_values(point_id)
+                .into_iter()
+                .flatten()
+                .flat_map(|timestamp| buckets.datetime_buckets(timestamp))
+                .collect_vec(),
+        };
+        point_buckets.sort_unstable();
+        point_buckets.dedup();
+        point_buckets
+    }
+
+    /// Count points per bucket by reading the whole index in the order of the values.
+    ///
+    /// Only for sequential buckets, so that the values of each bucket are contiguous.
+    pub fn iter_counts_per_bucket(
+        &self,
+        buckets: &'a HistogramBuckets,
+    ) -> impl Iterator<Item = (FacetValue, usize)> + 'a {
+        debug_assert!(buckets.is_sequential());
+
+        let all_values = RangeInterface::Float(Range::default());
+        let iter: Box<dyn Iterator<Item = (FacetValue, PointOffsetType)> + 'a> = match *self {
+            HistogramIndex::Int(index) => Box::new(index.stream_range(&all_values).flat_map(
+                move |(value, point_id)| {
+                    buckets
+                        .number_buckets(value as FloatPayloadType)
+                        .into_iter()
+                        .map(move |bucket| (bucket, point_id))
+                },
+            )),
+            HistogramIndex::Float(index) => Box::new(index.stream_range(&all_values).flat_map(
+                move |(value, point_id)| {
+                    buckets
+                        .number_buckets(value)
+                        .into_iter()
+                        .map(move |bucket| (bucket, point_id))
+                },
+            )),
+            HistogramIndex::Datetime(index) => Box::new(index.stream_range(&all_values).flat_map(
+                move |(timestamp, point_id)| {
+                    buckets
+                        .datetime_buckets(timestamp)
+                        .into_iter()
+                        .map(move |bucket| (bucket, point_id))
+                },
+            )),
+        };
+
+        // A point with many values in the same bucket is counted once
+        iter.chunk_by(|(bucket, _)| bucket.clone())
+            .into_iter()
+            .map(|(bucket, group)| (bucket, group.map(|(_, point_id)| point_id).unique().count()))
+            .collect_vec()
+            .into_iter()
     }
 }
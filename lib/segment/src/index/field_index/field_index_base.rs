@@ -5,25 +5,26 @@ use common::types::PointOffsetType;
 use serde_json::Value;
 
 use super::bool_index::simple_bool_index::BoolIndexBuilder;
-use super::facet_index::FacetIndex;
+use super::facet_index::{FacetIndex, HistogramIndex};
 use super::full_text_index::mmap_text_index::FullTextMmapIndexBuilder;
 use super::full_text_index::text_index::{FullTextIndex, FullTextIndexBuilder};
+use super::geo_index::geo_shape_index::{GeoShapeIndex, GeoShapeIndexBuilder};
 use super::geo_index::{GeoMapIndexBuilder, GeoMapIndexMmapBuilder};
 use super::map_index::{MapIndex, MapIndexBuilder, MapIndexMmapBuilder};
-use super::numeric_index::{
-    NumericIndex, NumericIndexBuilder, NumericIndexMmapBuilder, StreamRange,
-};
+use super::numeric_index::{NumericIndex, NumericIndexBuilder, NumericIndexMmapBuilder};
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
-use crate::data_types::order_by::OrderValue;
+use crate::data_types::order_by::{Direction, OrderValue};
 use crate::index::field_index::bool_index::simple_bool_index::BoolIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
+use crate::index::field_index::histogram::Numericable;
 use crate::index::field_index::numeric_index::NumericIndexInner;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
+use crate::payload_storage::condition_checker::ValueChecker;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface, UuidIntType, UuidPayloadType,
+    DateTimePayloadType, FieldCondition, FloatPayloadType, GeoPoint, IntPayloadType, Match,
+    MatchPhrase, MatchText, PayloadKeyType, Range, RangeInterface, UuidIntType, UuidPayloadType,
 };
 
 pub trait PayloadFieldIndex {
@@ -119,6 +120,7 @@ pub enum FieldIndex {
     BoolIndex(BoolIndex),
     UuidIndex(NumericIndex<UuidIntType, UuidPayloadType>),
     UuidMapIndex(MapIndex<UuidIntType>),
+    GeoShapeIndex(GeoShapeIndex),
 }
 
 impl std::fmt::Debug for FieldIndex {
@@ -134,6 +136,7 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::FullTextIndex(_index) => write!(f, "FullTextIndex"),
             FieldIndex::UuidIndex(_index) => write!(f, "UuidIndex"),
             FieldIndex::UuidMapIndex(_index) => write!(f, "UuidMapIndex"),
+            FieldIndex::GeoShapeIndex(_index) => write!(f, "GeoShapeIndex"),
         }
     }
 }
@@ -155,11 +158,31 @@ impl FieldIndex {
             FieldIndex::IntIndex(_) => None,
             FieldIndex::DatetimeIndex(_) => None,
             FieldIndex::IntMapIndex(_) => None,
-            FieldIndex::KeywordIndex(_) => None,
+            FieldIndex::KeywordIndex(keyword_index) => {
+                // Payload keeps the original keywords, compare them normalized like indexed ones
+                keyword_index.normalizer()?;
+                let is_keyword_condition = condition.r#match.is_some()
+                    || matches!(condition.range, Some(RangeInterface::Keyword(_)));
+                if !is_keyword_condition {
+                    return None;
+                }
+                let payload_value = keyword_index.normalize_payload_value(payload_value);
+                Some(
+                    keyword_index
+                        .normalize_condition(condition)
+                        .check(&payload_value),
+                )
+            }
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BoolIndex(_) => None,
             FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
+                Some(Match::Text(MatchText { text })) if full_text_index.is_ngram() => {
+                    let matches = FullTextIndex::get_values(payload_value)
+                        .iter()
+                        .any(|value| full_text_index.check_substring(text, value));
+                    Some(matches)
+                }
                 Some(Match::Text(MatchText { text })) => {
                     let query = full_text_index.parse_query(text);
                     for value in FullTextIndex::get_values(payload_value) {
@@ -170,10 +193,28 @@ impl FieldIndex {
                     }
                     Some(false)
                 }
+                Some(Match::Phrase(MatchPhrase { phrase, slop })) => {
+                    let slop = slop.unwrap_or(0);
+                    let matches = FullTextIndex::get_values(payload_value)
+                        .iter()
+                        .any(|value| full_text_index.check_phrase_in_text(phrase, slop, value));
+                    Some(matches)
+                }
+                Some(Match::Fuzzy(fuzzy_match)) => {
+                    // Cached by the index, so not expanded again for each checked point
+                    let parsed_fuzzy = full_text_index.parse_fuzzy(fuzzy_match);
+                    let matches = FullTextIndex::get_values(payload_value)
+                        .iter()
+                        .any(|value| {
+                            parsed_fuzzy.check_match(&full_text_index.parse_document(value))
+                        });
+                    Some(matches)
+                }
                 _ => None,
             },
             FieldIndex::UuidIndex(_) => None,
             FieldIndex::UuidMapIndex(_) => None,
+            FieldIndex::GeoShapeIndex(_) => None,
         }
     }
 
@@ -189,6 +230,7 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
             FieldIndex::UuidIndex(payload_field_index) => payload_field_index.inner(),
             FieldIndex::UuidMapIndex(payload_field_index) => payload_field_index,
+            FieldIndex::GeoShapeIndex(payload_field_index) => payload_field_index,
         }
     }
 
@@ -204,6 +246,7 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::UuidIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::UuidMapIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::GeoShapeIndex(ref mut payload_field_index) => payload_field_index.load(),
         }
     }
 
@@ -219,6 +262,7 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(index) => index.cleanup(),
             FieldIndex::UuidIndex(index) => index.cleanup(),
             FieldIndex::UuidMapIndex(index) => index.cleanup(),
+            FieldIndex::GeoShapeIndex(index) => index.cleanup(),
         }
     }
 
@@ -290,6 +334,9 @@ impl FieldIndex {
             FieldIndex::UuidMapIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::GeoShapeIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
         }
     }
 
@@ -305,6 +352,7 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(index) => index.remove_point(point_id),
             FieldIndex::UuidIndex(index) => index.remove_point(point_id),
             FieldIndex::UuidMapIndex(index) => index.remove_point(point_id),
+            FieldIndex::GeoShapeIndex(index) => index.remove_point(point_id),
         }
     }
 
@@ -320,6 +368,7 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(index) => index.get_telemetry_data(),
             FieldIndex::UuidIndex(index) => index.get_telemetry_data(),
             FieldIndex::UuidMapIndex(index) => index.get_telemetry_data(),
+            FieldIndex::GeoShapeIndex(index) => index.get_telemetry_data(),
         }
     }
 
@@ -335,6 +384,7 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(index) => index.values_count(point_id),
             FieldIndex::UuidIndex(index) => index.values_count(point_id),
             FieldIndex::UuidMapIndex(index) => index.values_count(point_id),
+            FieldIndex::GeoShapeIndex(index) => index.values_count(point_id),
         }
     }
 
@@ -350,21 +400,24 @@ impl FieldIndex {
             FieldIndex::FullTextIndex(index) => index.values_is_empty(point_id),
             FieldIndex::UuidIndex(index) => index.values_is_empty(point_id),
             FieldIndex::UuidMapIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::GeoShapeIndex(index) => index.values_is_empty(point_id),
         }
     }
 
-    pub fn as_numeric(&self) -> Option<NumericFieldIndex> {
+    /// Index which can stream points ordered by their values, to be used in `order_by`
+    pub fn as_orderable(&self) -> Option<OrderableFieldIndex> {
         match self {
-            FieldIndex::IntIndex(index) => Some(NumericFieldIndex::IntIndex(index.inner())),
-            FieldIndex::DatetimeIndex(index) => Some(NumericFieldIndex::IntIndex(index.inner())),
-            FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index.inner())),
+            FieldIndex::IntIndex(index) => Some(OrderableFieldIndex::IntIndex(index.inner())),
+            FieldIndex::DatetimeIndex(index) => Some(OrderableFieldIndex::IntIndex(index.inner())),
+            FieldIndex::FloatIndex(index) => Some(OrderableFieldIndex::FloatIndex(index.inner())),
+            FieldIndex::KeywordIndex(index) => Some(OrderableFieldIndex::KeywordIndex(index)),
             FieldIndex::IntMapIndex(_)
-            | FieldIndex::KeywordIndex(_)
             | FieldIndex::GeoIndex(_)
             | FieldIndex::BoolIndex(_)
             | FieldIndex::UuidMapIndex(_)
             | FieldIndex::UuidIndex(_)
-            | FieldIndex::FullTextIndex(_) => None,
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::GeoShapeIndex(_) => None,
         }
     }
 
@@ -379,7 +432,40 @@ impl FieldIndex {
             | FieldIndex::DatetimeIndex(_)
             | FieldIndex::FloatIndex(_)
             | FieldIndex::GeoIndex(_)
-            | FieldIndex::FullTextIndex(_) => None,
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::GeoShapeIndex(_) => None,
+        }
+    }
+
+    pub fn as_histogram_index(&self) -> Option<HistogramIndex> {
+        match self {
+            FieldIndex::IntIndex(index) => Some(HistogramIndex::Int(index.inner())),
+            FieldIndex::FloatIndex(index) => Some(HistogramIndex::Float(index.inner())),
+            FieldIndex::DatetimeIndex(index) => Some(HistogramIndex::Datetime(index.inner())),
+            FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BoolIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::UuidIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::GeoShapeIndex(_) => None,
+        }
+    }
+
+    pub fn as_full_text(&self) -> Option<&FullTextIndex> {
+        match self {
+            FieldIndex::FullTextIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BoolIndex(_)
+            | FieldIndex::UuidIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::GeoShapeIndex(_) => None,
         }
     }
 }
@@ -427,6 +513,7 @@ pub enum FieldIndexBuilder {
     BoolIndex(BoolIndexBuilder),
     UuidIndex(MapIndexBuilder<UuidIntType>),
     UuidMmapIndex(MapIndexMmapBuilder<UuidIntType>),
+    GeoShapeIndex(GeoShapeIndexBuilder),
 }
 
 impl FieldIndexBuilderTrait for FieldIndexBuilder {
@@ -451,6 +538,7 @@ impl FieldIndexBuilderTrait for FieldIndexBuilder {
             Self::FullTextMmapIndex(builder) => builder.init(),
             Self::UuidIndex(index) => index.init(),
             Self::UuidMmapIndex(index) => index.init(),
+            Self::GeoShapeIndex(index) => index.init(),
         }
     }
 
@@ -475,6 +563,7 @@ impl FieldIndexBuilderTrait for FieldIndexBuilder {
             }
             Self::UuidIndex(index) => index.add_point(id, payload),
             Self::UuidMmapIndex(index) => index.add_point(id, payload),
+            Self::GeoShapeIndex(index) => index.add_point(id, payload),
         }
     }
 
@@ -497,53 +586,159 @@ impl FieldIndexBuilderTrait for FieldIndexBuilder {
             Self::FullTextMmapIndex(builder) => FieldIndex::FullTextIndex(builder.finalize()?),
             Self::UuidIndex(index) => FieldIndex::UuidMapIndex(index.finalize()?),
             Self::UuidMmapIndex(index) => FieldIndex::UuidMapIndex(index.finalize()?),
+            Self::GeoShapeIndex(index) => FieldIndex::GeoShapeIndex(index.finalize()?),
         })
     }
 }
 
-pub enum NumericFieldIndex<'a> {
+pub enum OrderableFieldIndex<'a> {
     IntIndex(&'a NumericIndexInner<IntPayloadType>),
     FloatIndex(&'a NumericIndexInner<FloatPayloadType>),
+    KeywordIndex(&'a MapIndex<str>),
+    /// Orders by the distance from the point, in meters
+    GeoDistanceIndex(&'a GeoMapIndex, GeoPoint),
+}
+
+/// Bounds of an order range converted for an index, bounds of other types are dropped
+fn map_order_range<T>(
+    range: &Range<OrderValue>,
+    convert: impl Fn(&OrderValue) -> Option<T>,
+) -> Range<T> {
+    Range {
+        lt: range.lt.as_ref().and_then(&convert),
+        gt: range.gt.as_ref().and_then(&convert),
+        gte: range.gte.as_ref().and_then(&convert),
+        lte: range.lte.as_ref().and_then(&convert),
+    }
 }
 
-impl<'a> StreamRange<OrderValue> for NumericFieldIndex<'a> {
-    fn stream_range(
+fn order_value_as_int(value: &OrderValue) -> Option<IntPayloadType> {
+    match value {
+        OrderValue::Int(int) => Some(*int),
+        OrderValue::Float(float) => Some(IntPayloadType::from_f64(*float)),
+        OrderValue::Keyword(_) | OrderValue::Composite(_) => None,
+    }
+}
+
+fn order_value_as_float(value: &OrderValue) -> Option<FloatPayloadType> {
+    match value {
+        OrderValue::Int(int) => Some(*int as FloatPayloadType),
+        OrderValue::Float(float) => Some(*float),
+        OrderValue::Keyword(_) | OrderValue::Composite(_) => None,
+    }
+}
+
+/// Distance bounds of a range, both inclusive
+fn distance_bounds(range: &Range<OrderValue>) -> (f64, f64) {
+    let range = map_order_range(range, order_value_as_float);
+    (
+        range.gte.or(range.gt).unwrap_or(0.0),
+        range.lte.or(range.lt).unwrap_or(f64::INFINITY),
+    )
+}
+
+impl<'a> OrderableFieldIndex<'a> {
+    /// Stream points with values within the range, in ascending order of values.
+    /// Integer bounds are kept as integers for integer and datetime indexes.
+    pub fn stream_range(
         &self,
-        range: &RangeInterface,
+        range: &Range<OrderValue>,
     ) -> Box<dyn DoubleEndedIterator<Item = (OrderValue, PointOffsetType)> + 'a> {
         match self {
-            NumericFieldIndex::IntIndex(index) => Box::new(
+            OrderableFieldIndex::IntIndex(index) => Box::new(
                 index
-                    .stream_range(range)
+                    .stream_values_range(&map_order_range(range, order_value_as_int))
                     .map(|(v, p)| (OrderValue::from(v), p)),
             ),
-            NumericFieldIndex::FloatIndex(index) => Box::new(
+            OrderableFieldIndex::FloatIndex(index) => Box::new(
                 index
-                    .stream_range(range)
+                    .stream_values_range(&map_order_range(range, order_value_as_float))
                     .map(|(v, p)| (OrderValue::from(v), p)),
             ),
+            OrderableFieldIndex::KeywordIndex(index) => {
+                let index = *index;
+                let keyword_range = map_order_range(range, |value| match value {
+                    OrderValue::Keyword(keyword) => Some(keyword.clone()),
+                    OrderValue::Int(_) | OrderValue::Float(_) | OrderValue::Composite(_) => None,
+                });
+                let keyword_range = index.normalize_keyword_range(&keyword_range);
+                let (start_bound, end_bound) = keyword_range.as_bounds();
+                // Bounds borrow from the range, so the values are resolved before returning
+                let values: Vec<&str> = index.iter_values_range(start_bound, end_bound).collect();
+                Box::new(values.into_iter().flat_map(move |keyword| {
+                    let mut points: Vec<_> = index.get_iterator(keyword).copied().collect();
+                    points.sort_unstable();
+                    points
+                        .into_iter()
+                        .map(move |point_id| (OrderValue::Keyword(keyword.to_string()), point_id))
+                }))
+            }
+            OrderableFieldIndex::GeoDistanceIndex(index, origin) => {
+                let (min_distance, max_distance) = distance_bounds(range);
+                let values: Vec<_> = index
+                    .iter_by_distance(origin.clone(), min_distance)
+                    .take_while(|(distance, _)| *distance <= max_distance)
+                    .map(|(distance, point_id)| (OrderValue::Float(distance), point_id))
+                    .collect();
+                Box::new(values.into_iter())
+            }
+        }
+    }
+
+    /// Stream points ordered in the given direction. Unlike `stream_range`, distances are
+    /// streamed lazily when nearest come first, so that only the area around the origin is read.
+    pub fn stream_ordered(
+        &self,
+        range: &Range<OrderValue>,
+        direction: Direction,
+    ) -> Box<dyn Iterator<Item = (OrderValue, PointOffsetType)> + 'a> {
+        match (self, direction) {
+            (OrderableFieldIndex::GeoDistanceIndex(index, origin), Direction::Asc) => {
+                let index = *index;
+                let (min_distance, max_distance) = distance_bounds(range);
+                Box::new(
+                    index
+                        .iter_by_distance(origin.clone(), min_distance)
+                        .take_while(move |(distance, _)| *distance <= max_distance)
+                        .map(|(distance, point_id)| (OrderValue::Float(distance), point_id)),
+                )
+            }
+            (_, Direction::Asc) => Box::new(self.stream_range(range)),
+            (_, Direction::Desc) => Box::new(self.stream_range(range).rev()),
         }
     }
-}
 
-impl<'a> NumericFieldIndex<'a> {
     pub fn get_ordering_values(
         &self,
         idx: PointOffsetType,
     ) -> Box<dyn Iterator<Item = OrderValue> + 'a> {
         match self {
-            NumericFieldIndex::IntIndex(index) => Box::new(
+            OrderableFieldIndex::IntIndex(index) => Box::new(
                 index
                     .get_values(idx)
                     .into_iter()
                     .flatten()
                     .map(OrderValue::Int),
             ),
-            NumericFieldIndex::FloatIndex(index) => Box::new(
+            OrderableFieldIndex::FloatIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .map(OrderValue::Float),
+            ),
+            OrderableFieldIndex::KeywordIndex(index) => Box::new(
                 index
                     .get_values(idx)
                     .into_iter()
                     .flatten()
+                    .map(|keyword| OrderValue::Keyword(keyword.to_string())),
+            ),
+            OrderableFieldIndex::GeoDistanceIndex(index, origin) => Box::new(
+                index
+                    .get_distances(idx, origin)
+                    .collect::<Vec<_>>()
+                    .into_iter()
                     .map(OrderValue::Float),
             ),
         }
@@ -49,7 +49,6 @@ impl CompressedPostingList {
         self.chunks.len() * BitPackerImpl::BLOCK_LEN + self.remainder_postings.len()
     }
 
-    #[allow(dead_code)]
     pub fn iter(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
         let reader = self.reader();
         let visitor = CompressedPostingVisitor::new(reader);
@@ -5,7 +5,9 @@ use common::types::PointOffsetType;
 use super::inverted_index::InvertedIndex;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::index::field_index::full_text_index::compressed_posting::compressed_posting_list::CompressedPostingList;
-use crate::index::field_index::full_text_index::inverted_index::{ParsedQuery, TokenId};
+use crate::index::field_index::full_text_index::inverted_index::{
+    ParsedFuzzy, ParsedPhrase, ParsedQuery, TokenId, TokenPositions,
+};
 use crate::index::field_index::full_text_index::mutable_inverted_index::MutableInvertedIndex;
 use crate::index::field_index::full_text_index::postings_iterator::intersect_compressed_postings_iterator;
 
@@ -15,7 +17,11 @@ pub struct ImmutableInvertedIndex {
     pub(in crate::index::field_index::full_text_index) postings: Vec<CompressedPostingList>,
     pub(in crate::index::field_index::full_text_index) vocab: HashMap<String, TokenId>,
     pub(in crate::index::field_index::full_text_index) point_to_tokens_count: Vec<Option<usize>>,
+    /// Only filled if the index is configured for phrase matching
+    pub(in crate::index::field_index::full_text_index) point_to_positions:
+        Vec<Option<TokenPositions>>,
     pub(in crate::index::field_index::full_text_index) points_count: usize,
+    pub(in crate::index::field_index::full_text_index) tokens_count: usize,
 }
 
 impl InvertedIndex for ImmutableInvertedIndex {
@@ -37,8 +43,12 @@ impl InvertedIndex for ImmutableInvertedIndex {
         if self.values_is_empty(idx) {
             return false; // Already removed or never actually existed
         }
-        self.point_to_tokens_count[idx as usize] = None;
+        let tokens_count = self.point_to_tokens_count[idx as usize].take();
+        if let Some(positions) = self.point_to_positions.get_mut(idx as usize) {
+            *positions = None;
+        }
         self.points_count -= 1;
+        self.tokens_count -= tokens_count.unwrap_or(0);
         true
     }
 
@@ -72,6 +82,15 @@ impl InvertedIndex for ImmutableInvertedIndex {
         self.postings.get(token_id as usize).map(|p| p.len())
     }
 
+    fn iter_posting(&self, token_id: TokenId) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let Some(posting) = self.postings.get(token_id as usize) else {
+            return Box::new(std::iter::empty());
+        };
+
+        // in case of immutable index, deleted documents are still in the postings
+        Box::new(posting.iter().filter(|&idx| !self.values_is_empty(idx)))
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.vocab.iter().filter_map(|(token, &posting_idx)| {
             self.postings
@@ -96,6 +115,30 @@ impl InvertedIndex for ImmutableInvertedIndex {
             .all(|query_token| self.postings[query_token.unwrap() as usize].contains(point_id))
     }
 
+    fn check_phrase(
+        &self,
+        parsed_phrase: &ParsedPhrase,
+        point_id: PointOffsetType,
+        _positions_buffer: &mut Vec<TokenId>,
+    ) -> bool {
+        self.point_to_positions
+            .get(point_id as usize)
+            .and_then(Option::as_ref)
+            .is_some_and(|positions| parsed_phrase.check_positions(positions.tokens()))
+    }
+
+    fn check_fuzzy(&self, parsed_fuzzy: &ParsedFuzzy, point_id: PointOffsetType) -> bool {
+        if parsed_fuzzy.tokens.is_empty() || self.values_is_empty(point_id) {
+            return false;
+        }
+        // Check that the document has some expansion of each token
+        parsed_fuzzy.tokens.iter().all(|expansions| {
+            expansions
+                .iter()
+                .any(|&token_id| self.postings[token_id as usize].contains(point_id))
+        })
+    }
+
     fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
         self.point_to_tokens_count
             .get(point_id as usize)
@@ -113,6 +156,10 @@ impl InvertedIndex for ImmutableInvertedIndex {
         self.points_count
     }
 
+    fn tokens_count(&self) -> usize {
+        self.tokens_count
+    }
+
     fn get_token_id(&self, token: &str) -> Option<TokenId> {
         self.vocab.get(token).copied()
     }
@@ -152,6 +199,28 @@ impl From<MutableInvertedIndex> for ImmutableInvertedIndex {
             .collect();
         vocab.shrink_to_fit();
 
+        // Positions only refer to the tokens of the indexed documents, so all of them are kept
+        let point_to_positions = index
+            .point_to_positions
+            .into_iter()
+            .map(|positions| {
+                positions.map(|positions| {
+                    let tokens = positions
+                        .tokens()
+                        .iter()
+                        .map(|token| match *token {
+                            TokenPositions::VALUE_DELIMITER => TokenPositions::VALUE_DELIMITER,
+                            token => orig_to_new_token
+                                .get(&token)
+                                .copied()
+                                .unwrap_or(TokenPositions::VALUE_DELIMITER),
+                        })
+                        .collect();
+                    TokenPositions::new(tokens)
+                })
+            })
+            .collect();
+
         ImmutableInvertedIndex {
             postings,
             vocab,
@@ -160,7 +229,9 @@ impl From<MutableInvertedIndex> for ImmutableInvertedIndex {
                 .iter()
                 .map(|doc| doc.as_ref().map(|doc| doc.len()))
                 .collect(),
+            point_to_positions,
             points_count: index.points_count,
+            tokens_count: index.tokens_count,
         }
     }
 }
@@ -1,9 +1,10 @@
 use common::types::PointOffsetType;
 
 use super::immutable_inverted_index::ImmutableInvertedIndex;
-use super::inverted_index::InvertedIndex;
+use super::inverted_index::{FuzzyCache, InvertedIndex};
 use super::mutable_inverted_index::MutableInvertedIndex;
 use super::text_index::FullTextIndex;
+use super::tokenizers::Tokenizer;
 use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::data_types::index::TextIndexParams;
@@ -11,7 +12,8 @@ use crate::data_types::index::TextIndexParams;
 pub struct ImmutableFullTextIndex {
     pub(super) inverted_index: ImmutableInvertedIndex,
     pub(super) db_wrapper: DatabaseColumnScheduledDeleteWrapper,
-    pub(super) config: TextIndexParams,
+    pub(super) tokenizer: Tokenizer,
+    pub(super) fuzzy_cache: FuzzyCache,
 }
 
 impl ImmutableFullTextIndex {
@@ -19,7 +21,8 @@ impl ImmutableFullTextIndex {
         Self {
             inverted_index: Default::default(),
             db_wrapper,
-            config,
+            tokenizer: Tokenizer::new(config),
+            fuzzy_cache: FuzzyCache::default(),
         }
     }
 
@@ -35,18 +38,21 @@ impl ImmutableFullTextIndex {
         let db = self.db_wrapper.lock_db();
         let iter = db.iter()?.map(|(key, value)| {
             let idx = FullTextIndex::restore_key(&key);
-            let tokens = FullTextIndex::deserialize_document(&value)?;
-            Ok((idx, tokens))
+            let document = FullTextIndex::deserialize_document(&value)?;
+            Ok((idx, document))
         });
 
         let mutable = MutableInvertedIndex::build_index(iter)?;
 
         self.inverted_index = ImmutableInvertedIndex::from(mutable);
+        self.fuzzy_cache.clear();
 
         Ok(true)
     }
 
     pub fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.fuzzy_cache.clear();
+
         if self.inverted_index.remove_document(id) {
             let db_doc_id = FullTextIndex::store_key(id);
             self.db_wrapper.remove(db_doc_id)?;
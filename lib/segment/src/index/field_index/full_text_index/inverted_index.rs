@@ -1,11 +1,14 @@
 use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
+use super::text_index::{check_phrase, levenshtein_distance};
 use crate::common::operation_error::OperationResult;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
-use crate::types::{FieldCondition, Match, PayloadKeyType};
+use crate::types::{FieldCondition, Match, MatchFuzzy, PayloadKeyType};
 
 pub type TokenId = u32;
 
@@ -37,6 +40,27 @@ impl Document {
     }
 }
 
+/// Tokens of a document in the order of appearance, the position of a token is its index.
+///
+/// Values of a multi-value payload are separated with [`TokenPositions::VALUE_DELIMITER`],
+/// so that phrases never span across different values.
+#[derive(Default, Debug, Clone)]
+pub struct TokenPositions {
+    tokens: Vec<TokenId>,
+}
+
+impl TokenPositions {
+    pub const VALUE_DELIMITER: TokenId = TokenId::MAX;
+
+    pub fn new(tokens: Vec<TokenId>) -> Self {
+        Self { tokens }
+    }
+
+    pub fn tokens(&self) -> &[TokenId] {
+        &self.tokens
+    }
+}
+
 #[derive(Debug)]
 pub struct ParsedQuery {
     pub tokens: Vec<Option<TokenId>>,
@@ -55,28 +79,128 @@ impl ParsedQuery {
     }
 }
 
+#[derive(Debug)]
+pub struct ParsedPhrase {
+    /// Tokens of the phrase in order, `None` if some of them are not in the vocabulary
+    pub tokens: Option<Vec<TokenId>>,
+    pub slop: usize,
+}
+
+impl ParsedPhrase {
+    /// Query for the points which contain all the tokens of the phrase, in any order
+    pub fn to_query(&self) -> ParsedQuery {
+        let tokens = match &self.tokens {
+            Some(tokens) => {
+                let mut tokens = tokens.clone();
+                tokens.sort_unstable();
+                tokens.dedup();
+                tokens.into_iter().map(Some).collect()
+            }
+            None => vec![None],
+        };
+        ParsedQuery { tokens }
+    }
+
+    pub fn check_positions(&self, positions: &[TokenId]) -> bool {
+        let Some(tokens) = &self.tokens else {
+            return false;
+        };
+        positions
+            .split(|&token| token == TokenPositions::VALUE_DELIMITER)
+            .any(|value| check_phrase(value, tokens, self.slop))
+    }
+}
+
+/// Query tokens, each expanded to the similar tokens of the vocabulary
+#[derive(Debug)]
+pub struct ParsedFuzzy {
+    /// Expansions of each query token, a document must contain some token of each of them
+    pub tokens: Vec<Vec<TokenId>>,
+}
+
+impl ParsedFuzzy {
+    pub fn check_match(&self, document: &Document) -> bool {
+        !self.tokens.is_empty()
+            && self
+                .tokens
+                .iter()
+                .all(|expansions| expansions.iter().any(|&token_id| document.check(token_id)))
+    }
+}
+
+/// Fuzzy queries parsed recently, so that the cardinality estimation, the filtering and
+/// the checks of the same query only expand its tokens over the vocabulary once.
+///
+/// Must be cleared whenever the vocabulary or the postings change.
+#[derive(Debug, Default)]
+pub struct FuzzyCache {
+    parsed: Mutex<Vec<(MatchFuzzy, Arc<ParsedFuzzy>)>>,
+}
+
+impl FuzzyCache {
+    const SIZE: usize = 16;
+
+    pub fn get_or_parse(
+        &self,
+        fuzzy: &MatchFuzzy,
+        parse: impl FnOnce() -> ParsedFuzzy,
+    ) -> Arc<ParsedFuzzy> {
+        if let Some((_, parsed)) = self.parsed.lock().iter().find(|(query, _)| query == fuzzy) {
+            return parsed.clone();
+        }
+
+        let parsed = Arc::new(parse());
+        let mut cache = self.parsed.lock();
+        if cache.len() >= Self::SIZE {
+            cache.remove(0);
+        }
+        cache.push((fuzzy.clone(), parsed.clone()));
+        parsed
+    }
+
+    pub fn clear(&mut self) {
+        self.parsed.get_mut().clear();
+    }
+}
+
 pub trait InvertedIndex {
     fn get_vocab_mut(&mut self) -> &mut HashMap<String, TokenId>;
 
-    fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
+    fn register_token(&mut self, token: &str) -> TokenId {
         let vocab = self.get_vocab_mut();
-        let mut document_tokens = vec![];
-        for token in tokens {
-            // check if in vocab
-            let vocab_idx = match vocab.get(token) {
-                Some(&idx) => idx,
-                None => {
-                    let next_token_id = vocab.len() as TokenId;
-                    vocab.insert(token.to_string(), next_token_id);
-                    next_token_id
-                }
-            };
-            document_tokens.push(vocab_idx);
+        // check if in vocab
+        match vocab.get(token) {
+            Some(&idx) => idx,
+            None => {
+                let next_token_id = vocab.len() as TokenId;
+                vocab.insert(token.to_string(), next_token_id);
+                next_token_id
+            }
         }
+    }
+
+    fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
+        let document_tokens = tokens
+            .iter()
+            .map(|token| self.register_token(token))
+            .collect();
 
         Document::new(document_tokens)
     }
 
+    /// Build token positions from the ordered tokens of each value of the document
+    fn positions_from_tokens(&mut self, values: &[Vec<String>]) -> TokenPositions {
+        let mut positions = vec![];
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                positions.push(TokenPositions::VALUE_DELIMITER);
+            }
+            positions.extend(value.iter().map(|token| self.register_token(token)));
+        }
+
+        TokenPositions::new(positions)
+    }
+
     fn index_document(&mut self, idx: PointOffsetType, document: Document) -> OperationResult<()>;
 
     fn remove_document(&mut self, idx: PointOffsetType) -> bool;
@@ -85,6 +209,9 @@ pub trait InvertedIndex {
 
     fn get_posting_len(&self, token_id: TokenId) -> Option<usize>;
 
+    /// Iterate over the active points which contain the token
+    fn iter_posting(&self, token_id: TokenId) -> Box<dyn Iterator<Item = PointOffsetType> + '_>;
+
     fn estimate_cardinality(
         &self,
         query: &ParsedQuery,
@@ -144,8 +271,89 @@ pub trait InvertedIndex {
         }
     }
 
+    /// Estimate the points with some expansion of each of the query tokens
+    fn estimate_fuzzy_cardinality(
+        &self,
+        parsed_fuzzy: &ParsedFuzzy,
+        condition: &FieldCondition,
+    ) -> CardinalityEstimation {
+        let points_count = self.points_count();
+
+        // Points with any expansion of the token: the largest posting is the lower bound,
+        // the sum of the postings is the upper bound
+        let bounds: Vec<(usize, usize)> = parsed_fuzzy
+            .tokens
+            .iter()
+            .map(|expansions| {
+                let lengths = expansions
+                    .iter()
+                    .map(|&token_id| self.get_posting_len(token_id).unwrap_or(0));
+                let largest = lengths.clone().max().unwrap_or(0);
+                let sum = lengths.sum::<usize>().min(points_count);
+                (largest, sum)
+            })
+            .collect();
+
+        let smallest_sum = bounds.iter().map(|&(_, sum)| sum).min().unwrap_or(0);
+        if smallest_sum == 0 {
+            // Some token has no similar tokens, or empty request -> no matches
+            return CardinalityEstimation {
+                primary_clauses: vec![PrimaryCondition::Condition(Box::new(condition.clone()))],
+                min: 0,
+                exp: 0,
+                max: 0,
+            };
+        }
+
+        let min = match bounds.as_slice() {
+            [(largest, _)] => *largest,
+            _ => 0,
+        };
+        let expected_frac: f64 = bounds
+            .iter()
+            .map(|&(_, sum)| sum as f64 / points_count as f64)
+            .product();
+        let exp = ((expected_frac * points_count as f64) as usize).clamp(min, smallest_sum);
+        CardinalityEstimation {
+            primary_clauses: vec![PrimaryCondition::Condition(Box::new(condition.clone()))],
+            min,
+            exp,
+            max: smallest_sum,
+        }
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_;
 
+    /// Tokens of the vocabulary within `max_edits` from the given token.
+    /// The closest ones are preferred, then the most frequent ones.
+    fn fuzzy_expansions(
+        &self,
+        token: &str,
+        max_edits: usize,
+        max_expansions: usize,
+    ) -> Vec<TokenId> {
+        let mut candidates: Vec<_> = self
+            .vocab_with_postings_len_iter()
+            .filter(|&(_, postings_len)| postings_len > 0)
+            .filter_map(|(candidate, postings_len)| {
+                let distance = levenshtein_distance(token, candidate, max_edits)?;
+                Some((distance, postings_len, candidate))
+            })
+            .collect();
+
+        candidates.sort_unstable_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then_with(|| b.1.cmp(&a.1))
+                .then_with(|| a.2.cmp(b.2))
+        });
+
+        candidates
+            .into_iter()
+            .take(max_expansions)
+            .filter_map(|(_, _, candidate)| self.get_token_id(candidate))
+            .collect()
+    }
+
     fn payload_blocks(
         &self,
         threshold: usize,
@@ -170,12 +378,29 @@ pub trait InvertedIndex {
 
     fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool;
 
+    /// Check the phrase against the token positions of the point.
+    /// Never matches if the index does not store positions.
+    ///
+    /// `positions_buffer` is reused between the checks of different points,
+    /// by the indexes which have to decode the positions first.
+    fn check_phrase(
+        &self,
+        parsed_phrase: &ParsedPhrase,
+        point_id: PointOffsetType,
+        positions_buffer: &mut Vec<TokenId>,
+    ) -> bool;
+
+    fn check_fuzzy(&self, parsed_fuzzy: &ParsedFuzzy, point_id: PointOffsetType) -> bool;
+
     fn values_is_empty(&self, point_id: PointOffsetType) -> bool;
 
     fn values_count(&self, point_id: PointOffsetType) -> usize;
 
     fn points_count(&self) -> usize;
 
+    /// Total number of tokens in the active documents
+    fn tokens_count(&self) -> usize;
+
     fn get_token_id(&self, token: &str) -> Option<TokenId>;
 }
 
@@ -292,9 +517,9 @@ mod tests {
 
         let path = tempfile::tempdir().unwrap().into_path();
 
-        MmapInvertedIndex::create(path.clone(), immutable.clone()).unwrap();
+        MmapInvertedIndex::create(path.clone(), immutable.clone(), false).unwrap();
 
-        let mmap = MmapInvertedIndex::open(path, false).unwrap();
+        let mmap = MmapInvertedIndex::open(path, false, false).unwrap();
 
         // Check same vocabulary
         for (token, token_id) in immutable.vocab.iter() {
@@ -327,6 +552,9 @@ mod tests {
 
         // Check same points count
         assert_eq!(mmap.active_points_count, immutable.points_count);
+
+        // Check same tokens count
+        assert_eq!(mmap.tokens_count(), immutable.tokens_count());
     }
 
     #[test]
@@ -339,9 +567,9 @@ mod tests {
 
         let path = tempfile::tempdir().unwrap().into_path();
 
-        MmapInvertedIndex::create(path.clone(), immutable).unwrap();
+        MmapInvertedIndex::create(path.clone(), immutable, false).unwrap();
 
-        let mut mmap_index = MmapInvertedIndex::open(path, false).unwrap();
+        let mut mmap_index = MmapInvertedIndex::open(path, false, false).unwrap();
 
         let queries: Vec<_> = (0..100).map(|_| generate_query()).collect();
 
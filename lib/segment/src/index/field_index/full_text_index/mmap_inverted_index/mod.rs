@@ -11,12 +11,15 @@ use memory::mmap_ops;
 use memory::mmap_type::{MmapBitSlice, MmapSlice};
 use mmap_postings::MmapPostings;
 
-use super::inverted_index::{InvertedIndex, ParsedQuery};
+use super::inverted_index::{InvertedIndex, ParsedFuzzy, ParsedPhrase, ParsedQuery};
 use super::postings_iterator::intersect_compressed_postings_iterator;
 use crate::common::mmap_bitslice_buffered_update_wrapper::MmapBitSliceBufferedUpdateWrapper;
 use crate::common::operation_error::{OperationError, OperationResult};
+use crate::index::field_index::full_text_index::compressed_posting::compressed_posting_iterator::CompressedPostingIterator;
+use crate::index::field_index::full_text_index::compressed_posting::compressed_posting_visitor::CompressedPostingVisitor;
 use crate::index::field_index::full_text_index::immutable_inverted_index::ImmutableInvertedIndex;
 use crate::index::field_index::full_text_index::inverted_index::TokenId;
+use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
 
 mod mmap_postings;
 
@@ -32,17 +35,28 @@ pub struct MmapInvertedIndex {
     pub(in crate::index::field_index::full_text_index) point_to_tokens_count: MmapSlice<usize>,
     pub(in crate::index::field_index::full_text_index) deleted_points:
         MmapBitSliceBufferedUpdateWrapper,
+    /// Token positions of each point, only stored if the index is configured for phrase matching
+    pub(in crate::index::field_index::full_text_index) point_to_positions:
+        Option<MmapPointToValues<TokenId>>,
     /// Number of points which are not deleted
     pub(in crate::index::field_index::full_text_index) active_points_count: usize,
+    /// Number of tokens in the points which are not deleted
+    pub(in crate::index::field_index::full_text_index) active_tokens_count: usize,
 }
 
 impl MmapInvertedIndex {
-    pub fn create(path: PathBuf, inverted_index: ImmutableInvertedIndex) -> OperationResult<()> {
+    pub fn create(
+        path: PathBuf,
+        inverted_index: ImmutableInvertedIndex,
+        with_positions: bool,
+    ) -> OperationResult<()> {
         let ImmutableInvertedIndex {
             postings,
             vocab,
             point_to_tokens_count,
+            point_to_positions,
             points_count: _,
+            tokens_count: _,
         } = inverted_index;
 
         debug_assert_eq!(vocab.len(), postings.len());
@@ -77,10 +91,25 @@ impl MmapInvertedIndex {
 
         MmapSlice::create(&point_to_tokens_count_path, point_to_tokens_count_iter)?;
 
+        if with_positions {
+            let point_to_positions_iter =
+                point_to_positions
+                    .iter()
+                    .enumerate()
+                    .map(|(point_id, positions)| {
+                        let tokens = positions
+                            .as_ref()
+                            .map(|positions| positions.tokens())
+                            .unwrap_or_default();
+                        (point_id as PointOffsetType, tokens.iter().copied())
+                    });
+            MmapPointToValues::<TokenId>::from_iter(&path, point_to_positions_iter)?;
+        }
+
         Ok(())
     }
 
-    pub fn open(path: PathBuf, populate: bool) -> OperationResult<Self> {
+    pub fn open(path: PathBuf, populate: bool, with_positions: bool) -> OperationResult<Self> {
         let postings_path = path.join(POSTINGS_FILE);
         let vocab_path = path.join(VOCAB_FILE);
         let point_to_tokens_count_path = path.join(POINT_TO_TOKENS_COUNT_FILE);
@@ -104,6 +133,14 @@ impl MmapInvertedIndex {
         let num_deleted_points = deleted.count_ones();
         let deleted_points = MmapBitSliceBufferedUpdateWrapper::new(deleted);
         let points_count = point_to_tokens_count.len() - num_deleted_points;
+        // deleted points have a tokens count of 0
+        let tokens_count: usize = point_to_tokens_count.iter().sum();
+
+        let point_to_positions = if with_positions {
+            Some(MmapPointToValues::open(&path)?)
+        } else {
+            None
+        };
 
         Ok(Self {
             path,
@@ -111,7 +148,9 @@ impl MmapInvertedIndex {
             vocab,
             point_to_tokens_count,
             deleted_points,
+            point_to_positions,
             active_points_count: points_count,
+            active_tokens_count: tokens_count,
         })
     }
 
@@ -128,12 +167,16 @@ impl MmapInvertedIndex {
     }
 
     pub fn files(&self) -> Vec<PathBuf> {
-        vec![
+        let mut files = vec![
             self.path.join(POSTINGS_FILE),
             self.path.join(VOCAB_FILE),
             self.path.join(POINT_TO_TOKENS_COUNT_FILE),
             self.path.join(DELETED_POINTS_FILE),
-        ]
+        ];
+        if let Some(point_to_positions) = &self.point_to_positions {
+            files.extend(point_to_positions.files());
+        }
+        files
     }
 }
 
@@ -162,7 +205,7 @@ impl InvertedIndex for MmapInvertedIndex {
         }
 
         self.deleted_points.set(idx as usize, true);
-        self.point_to_tokens_count[idx as usize] = 0;
+        self.active_tokens_count -= std::mem::take(&mut self.point_to_tokens_count[idx as usize]);
         self.active_points_count -= 1;
         true
     }
@@ -197,6 +240,18 @@ impl InvertedIndex for MmapInvertedIndex {
         self.postings.get(token_id).map(|p| p.len())
     }
 
+    fn iter_posting(&self, token_id: TokenId) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let Some(posting_reader) = self.postings.get(token_id) else {
+            return Box::new(std::iter::empty());
+        };
+
+        // in case of mmap immutable index, deleted points are still in the postings
+        Box::new(
+            CompressedPostingIterator::new(CompressedPostingVisitor::new(posting_reader))
+                .filter(|&idx| self.is_active(idx)),
+        )
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.iter_vocab().filter_map(|(token, &token_id)| {
             self.postings
@@ -226,6 +281,41 @@ impl InvertedIndex for MmapInvertedIndex {
             })
     }
 
+    fn check_phrase(
+        &self,
+        parsed_phrase: &ParsedPhrase,
+        point_id: PointOffsetType,
+        positions_buffer: &mut Vec<TokenId>,
+    ) -> bool {
+        if !self.is_active(point_id) {
+            return false;
+        }
+        let Some(positions) = self
+            .point_to_positions
+            .as_ref()
+            .and_then(|point_to_positions| point_to_positions.get_values(point_id))
+        else {
+            return false;
+        };
+        positions_buffer.clear();
+        positions_buffer.extend(positions);
+        parsed_phrase.check_positions(positions_buffer)
+    }
+
+    fn check_fuzzy(&self, parsed_fuzzy: &ParsedFuzzy, point_id: PointOffsetType) -> bool {
+        if parsed_fuzzy.tokens.is_empty() || self.values_is_empty(point_id) {
+            return false;
+        }
+        // Check that the document has some expansion of each token
+        parsed_fuzzy.tokens.iter().all(|expansions| {
+            expansions.iter().any(|&token_id| {
+                self.postings
+                    .get(token_id)
+                    .is_some_and(|posting| posting.contains(point_id))
+            })
+        })
+    }
+
     fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
         if self.deleted_points.get(point_id as usize).unwrap_or(true) {
             return true;
@@ -252,6 +342,10 @@ impl InvertedIndex for MmapInvertedIndex {
         self.active_points_count
     }
 
+    fn tokens_count(&self) -> usize {
+        self.active_tokens_count
+    }
+
     fn get_token_id(&self, token: &str) -> Option<TokenId> {
         self.vocab
             .get(token)
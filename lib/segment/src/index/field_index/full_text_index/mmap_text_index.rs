@@ -1,4 +1,3 @@
-use std::collections::BTreeSet;
 use std::fs::{create_dir_all, remove_dir};
 use std::path::PathBuf;
 
@@ -6,7 +5,7 @@ use common::types::PointOffsetType;
 use serde_json::Value;
 
 use super::immutable_inverted_index::ImmutableInvertedIndex;
-use super::inverted_index::InvertedIndex;
+use super::inverted_index::{FuzzyCache, InvertedIndex};
 use super::mmap_inverted_index::MmapInvertedIndex;
 use super::mutable_inverted_index::MutableInvertedIndex;
 use super::text_index::FullTextIndex;
@@ -18,16 +17,19 @@ use crate::index::field_index::{FieldIndexBuilderTrait, ValueIndexer};
 
 pub struct MmapFullTextIndex {
     pub(super) inverted_index: MmapInvertedIndex,
-    pub(super) config: TextIndexParams,
+    pub(super) tokenizer: Tokenizer,
+    pub(super) fuzzy_cache: FuzzyCache,
 }
 
 impl MmapFullTextIndex {
     pub fn open(path: PathBuf, config: TextIndexParams) -> OperationResult<Self> {
-        let inverted_index = MmapInvertedIndex::open(path, false)?;
+        let with_positions = config.phrase_matching.unwrap_or(false);
+        let inverted_index = MmapInvertedIndex::open(path, false, with_positions)?;
 
         Ok(Self {
             inverted_index,
-            config,
+            tokenizer: Tokenizer::new(config),
+            fuzzy_cache: FuzzyCache::default(),
         })
     }
 
@@ -50,6 +52,7 @@ impl MmapFullTextIndex {
     }
 
     pub fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.fuzzy_cache.clear();
         self.inverted_index.remove_document(id);
 
         Ok(())
@@ -63,7 +66,7 @@ impl MmapFullTextIndex {
 pub struct FullTextMmapIndexBuilder {
     path: PathBuf,
     mutable_index: MutableInvertedIndex,
-    config: TextIndexParams,
+    tokenizer: Tokenizer,
 }
 
 impl FullTextMmapIndexBuilder {
@@ -71,7 +74,7 @@ impl FullTextMmapIndexBuilder {
         Self {
             path,
             mutable_index: MutableInvertedIndex::default(),
-            config,
+            tokenizer: Tokenizer::new(config),
         }
     }
 }
@@ -95,17 +98,18 @@ impl ValueIndexer for FullTextMmapIndexBuilder {
             return Ok(());
         }
 
-        let mut tokens: BTreeSet<String> = BTreeSet::new();
+        let stored_document = FullTextIndex::tokenize_document(&values, &self.tokenizer);
 
-        for value in values {
-            Tokenizer::tokenize_doc(&value, &self.config, |token| {
-                tokens.insert(token.to_owned());
-            });
-        }
-
-        let document = self.mutable_index.document_from_tokens(&tokens);
+        let document = self
+            .mutable_index
+            .document_from_tokens(&stored_document.tokens);
         self.mutable_index.index_document(id, document)?;
 
+        if let Some(values) = &stored_document.positions {
+            let positions = self.mutable_index.positions_from_tokens(values);
+            self.mutable_index.index_positions(id, positions);
+        }
+
         Ok(())
     }
 
@@ -131,20 +135,22 @@ impl FieldIndexBuilderTrait for FullTextMmapIndexBuilder {
         let Self {
             path,
             mutable_index,
-            config,
+            tokenizer,
         } = self;
 
         let immutable = ImmutableInvertedIndex::from(mutable_index);
 
         create_dir_all(path.as_path())?;
 
-        MmapInvertedIndex::create(path.clone(), immutable)?;
+        let with_positions = tokenizer.config().phrase_matching.unwrap_or(false);
+        MmapInvertedIndex::create(path.clone(), immutable, with_positions)?;
 
-        let inverted_index = MmapInvertedIndex::open(path, false)?;
+        let inverted_index = MmapInvertedIndex::open(path, false, with_positions)?;
 
         let mmap_index = MmapFullTextIndex {
             inverted_index,
-            config,
+            tokenizer,
+            fuzzy_cache: FuzzyCache::default(),
         };
 
         Ok(FullTextIndex::Mmap(Box::new(mmap_index)))
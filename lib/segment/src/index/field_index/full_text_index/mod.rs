@@ -5,6 +5,7 @@ pub mod mmap_text_index;
 mod mutable_text_index;
 mod posting_list;
 mod postings_iterator;
+mod stop_words;
 pub mod text_index;
 mod tokenizers;
 
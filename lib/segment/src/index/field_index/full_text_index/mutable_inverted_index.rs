@@ -1,10 +1,13 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::HashMap;
 
 use common::types::PointOffsetType;
 
 use super::inverted_index::InvertedIndex;
+use super::text_index::StoredDocument;
 use crate::common::operation_error::OperationResult;
-use crate::index::field_index::full_text_index::inverted_index::{Document, ParsedQuery, TokenId};
+use crate::index::field_index::full_text_index::inverted_index::{
+    Document, ParsedFuzzy, ParsedPhrase, ParsedQuery, TokenId, TokenPositions,
+};
 use crate::index::field_index::full_text_index::posting_list::PostingList;
 use crate::index::field_index::full_text_index::postings_iterator::intersect_postings_iterator;
 
@@ -14,19 +17,23 @@ pub struct MutableInvertedIndex {
     pub(in crate::index::field_index::full_text_index) postings: Vec<Option<PostingList>>,
     pub(in crate::index::field_index::full_text_index) vocab: HashMap<String, TokenId>,
     pub(in crate::index::field_index::full_text_index) point_to_docs: Vec<Option<Document>>,
+    /// Only filled if the index is configured for phrase matching
+    pub(in crate::index::field_index::full_text_index) point_to_positions:
+        Vec<Option<TokenPositions>>,
     pub(in crate::index::field_index::full_text_index) points_count: usize,
+    pub(in crate::index::field_index::full_text_index) tokens_count: usize,
 }
 
 impl MutableInvertedIndex {
     pub fn build_index(
-        iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
+        iter: impl Iterator<Item = OperationResult<(PointOffsetType, StoredDocument)>>,
     ) -> OperationResult<Self> {
         let mut index = Self::default();
 
         // update point_to_docs
         for i in iter {
             index.points_count += 1;
-            let (idx, tokens) = i?;
+            let (idx, StoredDocument { tokens, positions }) = i?;
 
             if index.point_to_docs.len() <= idx as usize {
                 index
@@ -35,7 +42,13 @@ impl MutableInvertedIndex {
             }
 
             let document = index.document_from_tokens(&tokens);
+            index.tokens_count += document.len();
             index.point_to_docs[idx as usize] = Some(document);
+
+            if let Some(values) = positions {
+                let positions = index.positions_from_tokens(&values);
+                index.index_positions(idx, positions);
+            }
         }
 
         // build postings from point_to_docs
@@ -63,9 +76,22 @@ impl MutableInvertedIndex {
         Ok(index)
     }
 
+    /// Store token positions of an indexed document, to be used for phrase matching
+    pub fn index_positions(&mut self, idx: PointOffsetType, positions: TokenPositions) {
+        if self.point_to_positions.len() <= idx as usize {
+            self.point_to_positions
+                .resize_with(idx as usize + 1, Default::default);
+        }
+        self.point_to_positions[idx as usize] = Some(positions);
+    }
+
     fn get_doc(&self, idx: PointOffsetType) -> Option<&Document> {
         self.point_to_docs.get(idx as usize)?.as_ref()
     }
+
+    fn get_positions(&self, idx: PointOffsetType) -> Option<&TokenPositions> {
+        self.point_to_positions.get(idx as usize)?.as_ref()
+    }
 }
 
 impl InvertedIndex for MutableInvertedIndex {
@@ -99,6 +125,7 @@ impl InvertedIndex for MutableInvertedIndex {
                 Some(vec) => vec.insert(point_id),
             }
         }
+        self.tokens_count += document.len();
         self.point_to_docs[point_id as usize] = Some(document);
 
         Ok(())
@@ -114,6 +141,11 @@ impl InvertedIndex for MutableInvertedIndex {
         };
 
         self.points_count -= 1;
+        self.tokens_count -= removed_doc.len();
+
+        if let Some(positions) = self.point_to_positions.get_mut(idx as usize) {
+            *positions = None;
+        }
 
         for removed_token in removed_doc.tokens() {
             // unwrap safety: posting list exists and contains the document id
@@ -156,6 +188,13 @@ impl InvertedIndex for MutableInvertedIndex {
             .map(|x| x.len())
     }
 
+    fn iter_posting(&self, token_id: TokenId) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self.postings.get(token_id as usize) {
+            Some(Some(posting)) => Box::new(posting.iter()),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.vocab.iter().filter_map(|(token, &posting_idx)| {
             if let Some(Some(postings)) = self.postings.get(posting_idx as usize) {
@@ -174,6 +213,21 @@ impl InvertedIndex for MutableInvertedIndex {
         }
     }
 
+    fn check_phrase(
+        &self,
+        parsed_phrase: &ParsedPhrase,
+        point_id: PointOffsetType,
+        _positions_buffer: &mut Vec<TokenId>,
+    ) -> bool {
+        self.get_positions(point_id)
+            .is_some_and(|positions| parsed_phrase.check_positions(positions.tokens()))
+    }
+
+    fn check_fuzzy(&self, parsed_fuzzy: &ParsedFuzzy, point_id: PointOffsetType) -> bool {
+        self.get_doc(point_id)
+            .is_some_and(|doc| parsed_fuzzy.check_match(doc))
+    }
+
     fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
         self.get_doc(point_id).map(|x| x.is_empty()).unwrap_or(true)
     }
@@ -187,6 +241,10 @@ impl InvertedIndex for MutableInvertedIndex {
         self.points_count
     }
 
+    fn tokens_count(&self) -> usize {
+        self.tokens_count
+    }
+
     fn get_token_id(&self, token: &str) -> Option<TokenId> {
         self.vocab.get(token).copied()
     }
@@ -1,8 +1,6 @@
-use std::collections::BTreeSet;
-
 use common::types::PointOffsetType;
 
-use super::inverted_index::InvertedIndex;
+use super::inverted_index::{FuzzyCache, InvertedIndex};
 use super::mutable_inverted_index::MutableInvertedIndex;
 use super::text_index::FullTextIndex;
 use super::tokenizers::Tokenizer;
@@ -13,7 +11,8 @@ use crate::data_types::index::TextIndexParams;
 pub struct MutableFullTextIndex {
     pub(super) inverted_index: MutableInvertedIndex,
     pub(super) db_wrapper: DatabaseColumnScheduledDeleteWrapper,
-    pub(super) config: TextIndexParams,
+    pub(super) tokenizer: Tokenizer,
+    pub(super) fuzzy_cache: FuzzyCache,
 }
 
 impl MutableFullTextIndex {
@@ -21,7 +20,8 @@ impl MutableFullTextIndex {
         Self {
             inverted_index: Default::default(),
             db_wrapper,
-            config,
+            tokenizer: Tokenizer::new(config),
+            fuzzy_cache: FuzzyCache::default(),
         }
     }
 
@@ -37,11 +37,12 @@ impl MutableFullTextIndex {
         let db = self.db_wrapper.lock_db();
         let iter = db.iter()?.map(|(key, value)| {
             let idx = FullTextIndex::restore_key(&key);
-            let tokens = FullTextIndex::deserialize_document(&value)?;
-            Ok((idx, tokens))
+            let document = FullTextIndex::deserialize_document(&value)?;
+            Ok((idx, document))
         });
 
         self.inverted_index = MutableInvertedIndex::build_index(iter)?;
+        self.fuzzy_cache.clear();
 
         Ok(true)
     }
@@ -51,19 +52,22 @@ impl MutableFullTextIndex {
             return Ok(());
         }
 
-        let mut tokens: BTreeSet<String> = BTreeSet::new();
+        self.fuzzy_cache.clear();
 
-        for value in values {
-            Tokenizer::tokenize_doc(&value, &self.config, |token| {
-                tokens.insert(token.to_owned());
-            });
-        }
+        let stored_document = FullTextIndex::tokenize_document(&values, &self.tokenizer);
 
-        let document = self.inverted_index.document_from_tokens(&tokens);
+        let document = self
+            .inverted_index
+            .document_from_tokens(&stored_document.tokens);
         self.inverted_index.index_document(idx, document)?;
 
+        if let Some(values) = &stored_document.positions {
+            let positions = self.inverted_index.positions_from_tokens(values);
+            self.inverted_index.index_positions(idx, positions);
+        }
+
         let db_idx = FullTextIndex::store_key(idx);
-        let db_document = FullTextIndex::serialize_document_tokens(tokens)?;
+        let db_document = FullTextIndex::serialize_document(&stored_document)?;
 
         self.db_wrapper.put(db_idx, db_document)?;
 
@@ -71,6 +75,8 @@ impl MutableFullTextIndex {
     }
 
     pub fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.fuzzy_cache.clear();
+
         if self.inverted_index.remove_document(id) {
             let db_doc_id = FullTextIndex::store_key(id);
             self.db_wrapper.remove(db_doc_id)?;
@@ -124,7 +130,13 @@ mod tests {
             tokenizer: TokenizerType::Word,
             min_token_len: None,
             max_token_len: None,
+            min_ngram: None,
+            max_ngram: None,
             lowercase: None,
+            phrase_matching: None,
+            stopwords: None,
+            stemmer: None,
+            ascii_folding: None,
             on_disk: None,
         };
 
@@ -0,0 +1,923 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::OnceLock;
+
+use crate::data_types::index::{Language, StopwordsInterface, StopwordsSet};
+
+const DANISH: &[&str] = &[
+    "af", "alle", "andet", "andre", "at", "begge", "da", "de", "den", "denne", "der", "deres",
+    "det", "dette", "dig", "din", "dog", "du", "ej", "eller", "en", "end", "ene", "eneste",
+    "enhver", "et", "fem", "fire", "flere", "fleste", "for", "fordi", "forrige", "fra", "få",
+    "før", "god", "han", "hans", "har", "hendes", "her", "hun", "hvad", "hvem", "hver", "hvilken",
+    "hvis", "hvor", "hvordan", "hvorfor", "hvornår", "i", "ikke", "ind", "ingen", "intet", "jeg",
+    "jeres", "kan", "kom", "kommer", "lav", "lidt", "lille", "man", "mand", "mange", "med",
+    "meget", "men", "mens", "mere", "mig", "min", "mine", "mit", "mod", "ned", "nogen", "noget",
+    "nogle", "nu", "når", "og", "også", "om", "op", "os", "over", "på", "selv", "sig", "sin",
+    "sine", "sit", "skal", "skulle", "som", "sådan", "thi", "til", "ud", "under", "var", "vi",
+    "vil", "ville", "vor", "vores", "være", "været",
+];
+
+const DUTCH: &[&str] = &[
+    "aan", "al", "alles", "als", "altijd", "andere", "ben", "bij", "daar", "dan", "dat", "de",
+    "der", "deze", "die", "dit", "doch", "doen", "door", "dus", "een", "eens", "en", "er", "ge",
+    "geen", "geweest", "haar", "had", "heb", "hebben", "heeft", "hem", "het", "hier", "hij", "hoe",
+    "hun", "iemand", "iets", "ik", "in", "is", "ja", "je", "kan", "kon", "kunnen", "maar", "me",
+    "meer", "men", "met", "mij", "mijn", "moet", "na", "naar", "niet", "niets", "nog", "nu", "of",
+    "om", "omdat", "onder", "ons", "ook", "op", "over", "reeds", "te", "tegen", "toch", "toen",
+    "tot", "u", "uit", "uw", "van", "veel", "voor", "want", "waren", "was", "wat", "werd", "wezen",
+    "wie", "wil", "worden", "wordt", "zal", "ze", "zelf", "zich", "zij", "zijn", "zo", "zonder",
+    "zou",
+];
+
+const ENGLISH: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+const FINNISH: &[&str] = &[
+    "ei",
+    "eivät",
+    "emme",
+    "en",
+    "et",
+    "ette",
+    "että",
+    "he",
+    "heidän",
+    "heidät",
+    "heihin",
+    "heille",
+    "heillä",
+    "heiltä",
+    "heissä",
+    "heistä",
+    "heitä",
+    "hän",
+    "häneen",
+    "hänelle",
+    "hänellä",
+    "häneltä",
+    "hänen",
+    "hänessä",
+    "hänestä",
+    "hänet",
+    "häntä",
+    "itse",
+    "ja",
+    "johon",
+    "joiden",
+    "joihin",
+    "joiksi",
+    "joilla",
+    "joille",
+    "joilta",
+    "joina",
+    "joissa",
+    "joista",
+    "joita",
+    "joka",
+    "joksi",
+    "jolla",
+    "jolle",
+    "jolta",
+    "jona",
+    "jonka",
+    "jos",
+    "jossa",
+    "josta",
+    "jota",
+    "jotka",
+    "kanssa",
+    "keiden",
+    "keihin",
+    "keiksi",
+    "keille",
+    "keillä",
+    "keiltä",
+    "keinä",
+    "keissä",
+    "keistä",
+    "keitä",
+    "keneen",
+    "keneksi",
+    "kenelle",
+    "kenellä",
+    "keneltä",
+    "kenen",
+    "kenenä",
+    "kenessä",
+    "kenestä",
+    "kenet",
+    "ketkä",
+    "ketä",
+    "koska",
+    "kuin",
+    "kuka",
+    "kun",
+    "me",
+    "meidän",
+    "meidät",
+    "meihin",
+    "meille",
+    "meillä",
+    "meiltä",
+    "meissä",
+    "meistä",
+    "meitä",
+    "mihin",
+    "mikä",
+    "miksi",
+    "mille",
+    "millä",
+    "miltä",
+    "minkä",
+    "minua",
+    "minulla",
+    "minulle",
+    "minulta",
+    "minun",
+    "minussa",
+    "minusta",
+    "minut",
+    "minuun",
+    "minä",
+    "missä",
+    "mistä",
+    "mitkä",
+    "mitä",
+    "mukaan",
+    "mutta",
+    "ne",
+    "niiden",
+    "niihin",
+    "niiksi",
+    "niille",
+    "niillä",
+    "niiltä",
+    "niin",
+    "niinä",
+    "niissä",
+    "niistä",
+    "niitä",
+    "noiden",
+    "noihin",
+    "noiksi",
+    "noilla",
+    "noille",
+    "noilta",
+    "noin",
+    "noina",
+    "noissa",
+    "noista",
+    "noita",
+    "nuo",
+    "nyt",
+    "näiden",
+    "näihin",
+    "näiksi",
+    "näille",
+    "näillä",
+    "näiltä",
+    "näinä",
+    "näissä",
+    "näistä",
+    "näitä",
+    "nämä",
+    "ole",
+    "olemme",
+    "olen",
+    "olet",
+    "olette",
+    "oli",
+    "olimme",
+    "olin",
+    "olisi",
+    "olisimme",
+    "olisin",
+    "olisit",
+    "olisitte",
+    "olisivat",
+    "olit",
+    "olitte",
+    "olivat",
+    "olla",
+    "olleet",
+    "ollut",
+    "on",
+    "ovat",
+    "poikki",
+    "se",
+    "sekä",
+    "sen",
+    "siihen",
+    "siinä",
+    "siitä",
+    "siksi",
+    "sille",
+    "sillä",
+    "siltä",
+    "sinua",
+    "sinulla",
+    "sinulle",
+    "sinulta",
+    "sinun",
+    "sinussa",
+    "sinusta",
+    "sinut",
+    "sinuun",
+    "sinä",
+    "sitä",
+    "tai",
+    "te",
+    "teidän",
+    "teidät",
+    "teihin",
+    "teille",
+    "teillä",
+    "teiltä",
+    "teissä",
+    "teistä",
+    "teitä",
+    "tuo",
+    "tuohon",
+    "tuoksi",
+    "tuolla",
+    "tuolle",
+    "tuolta",
+    "tuon",
+    "tuona",
+    "tuossa",
+    "tuosta",
+    "tuota",
+    "tähän",
+    "täksi",
+    "tälle",
+    "tällä",
+    "tältä",
+    "tämä",
+    "tämän",
+    "tänä",
+    "tässä",
+    "tästä",
+    "tätä",
+    "vaan",
+    "vai",
+    "vaikka",
+    "yli",
+];
+
+const FRENCH: &[&str] = &[
+    "a", "ai", "aie", "aient", "aies", "ait", "as", "au", "aura", "aurai", "auraient", "aurais",
+    "aurait", "auras", "aurez", "auriez", "aurions", "aurons", "auront", "aux", "avaient", "avais",
+    "avait", "avec", "avez", "aviez", "avions", "avons", "ayant", "ayez", "ayons", "c", "ce",
+    "ceci", "cela", "ces", "cet", "cette", "d", "dans", "de", "des", "du", "elle", "en", "es",
+    "est", "et", "étaient", "étais", "était", "étant", "été", "êtes", "étiez", "étions", "être",
+    "eu", "eue", "eues", "eurent", "eus", "eut", "eux", "il", "ils", "j", "je", "l", "la", "le",
+    "les", "leur", "lui", "m", "ma", "mais", "me", "même", "mes", "moi", "mon", "n", "ne", "nos",
+    "notre", "nous", "on", "ont", "ou", "par", "pas", "pour", "qu", "que", "qui", "s", "sa", "se",
+    "sera", "serai", "seraient", "serais", "serait", "seras", "serez", "seriez", "serions",
+    "serons", "seront", "ses", "soi", "soient", "sois", "soit", "sommes", "son", "sont", "soyez",
+    "soyons", "suis", "sur", "t", "ta", "te", "tes", "toi", "ton", "tu", "un", "une", "vos",
+    "votre", "vous", "y",
+];
+
+const GERMAN: &[&str] = &[
+    "aber", "alle", "allem", "allen", "aller", "alles", "als", "also", "am", "an", "ander",
+    "andere", "anderem", "anderen", "anderer", "anderes", "auch", "auf", "aus", "bei", "bin",
+    "bis", "bist", "da", "damit", "dann", "das", "dass", "dasselbe", "dazu", "daß", "dein",
+    "deine", "dem", "den", "denn", "der", "derselbe", "des", "dich", "die", "dies", "diese",
+    "diesem", "diesen", "dieser", "dieses", "dir", "doch", "dort", "du", "durch", "ein", "eine",
+    "einem", "einen", "einer", "eines", "einig", "einige", "er", "es", "etwas", "euch", "euer",
+    "für", "gegen", "gewesen", "hab", "habe", "haben", "hat", "hatte", "hatten", "hier", "hin",
+    "hinter", "ich", "ihm", "ihn", "ihnen", "ihr", "ihre", "im", "in", "indem", "ins", "ist",
+    "jede", "jedem", "jeden", "jeder", "jedes", "jene", "jetzt", "kann", "kein", "keine", "können",
+    "könnte", "man", "manche", "mein", "meine", "mich", "mir", "mit", "muss", "musste", "nach",
+    "nicht", "nichts", "noch", "nun", "nur", "ob", "oder", "ohne", "sehr", "sein", "seine",
+    "selbst", "sich", "sie", "sind", "so", "solche", "soll", "sollte", "sondern", "sonst", "über",
+    "um", "und", "uns", "unser", "unter", "viel", "vom", "von", "vor", "während", "war", "waren",
+    "warst", "was", "weg", "weil", "weiter", "welche", "wenn", "werde", "werden", "wie", "wieder",
+    "will", "wir", "wird", "wirst", "wo", "wollen", "wollte", "würde", "würden", "zu", "zum",
+    "zur", "zwar", "zwischen",
+];
+
+const ITALIAN: &[&str] = &[
+    "a", "abbia", "abbiamo", "abbiano", "ad", "agli", "ai", "al", "alla", "alle", "allo", "anche",
+    "avere", "aveva", "avevano", "c", "che", "chi", "ci", "coi", "col", "come", "con", "contro",
+    "cui", "da", "dagli", "dai", "dal", "dall", "dalla", "dalle", "dallo", "degli", "dei", "del",
+    "dell", "della", "delle", "dello", "di", "dov", "dove", "e", "è", "ebbe", "ed", "era", "erano",
+    "essere", "fa", "fu", "gli", "ha", "hai", "hanno", "ho", "i", "il", "in", "io", "l", "la",
+    "le", "lei", "li", "lo", "loro", "lui", "ma", "mi", "mia", "mie", "miei", "mio", "ne", "negli",
+    "nei", "nel", "nell", "nella", "nelle", "nello", "noi", "non", "nostra", "nostre", "nostri",
+    "nostro", "o", "per", "perché", "più", "quale", "quanta", "quanti", "quanto", "quella",
+    "quelle", "quelli", "quello", "questa", "queste", "questi", "questo", "sarà", "se", "sei",
+    "si", "sia", "siamo", "sono", "sta", "su", "sua", "sue", "sugli", "sui", "sul", "sull",
+    "sulla", "sulle", "sullo", "suo", "suoi", "ti", "tra", "tu", "tua", "tue", "tuo", "tuoi",
+    "tutti", "tutto", "un", "una", "uno", "vi", "voi", "vostra", "vostro",
+];
+
+const NORWEGIAN: &[&str] = &[
+    "alle",
+    "at",
+    "av",
+    "bare",
+    "begge",
+    "ble",
+    "blei",
+    "bli",
+    "blir",
+    "blitt",
+    "både",
+    "da",
+    "de",
+    "deg",
+    "dei",
+    "deim",
+    "deira",
+    "deires",
+    "dem",
+    "den",
+    "denne",
+    "der",
+    "dere",
+    "deres",
+    "det",
+    "dette",
+    "di",
+    "din",
+    "disse",
+    "ditt",
+    "du",
+    "dykk",
+    "dykkar",
+    "då",
+    "eg",
+    "ein",
+    "eit",
+    "eitt",
+    "eller",
+    "elles",
+    "en",
+    "enn",
+    "er",
+    "et",
+    "ett",
+    "etter",
+    "for",
+    "fordi",
+    "fra",
+    "før",
+    "ha",
+    "hadde",
+    "han",
+    "hans",
+    "har",
+    "hennar",
+    "henne",
+    "hennes",
+    "her",
+    "hjå",
+    "ho",
+    "hoe",
+    "honom",
+    "hoss",
+    "hossen",
+    "hun",
+    "hva",
+    "hvem",
+    "hver",
+    "hvilke",
+    "hvilken",
+    "hvis",
+    "hvor",
+    "hvordan",
+    "hvorfor",
+    "i",
+    "ikke",
+    "ikkje",
+    "ingen",
+    "ingi",
+    "inkje",
+    "inn",
+    "inni",
+    "ja",
+    "jeg",
+    "kan",
+    "kom",
+    "korleis",
+    "korso",
+    "kun",
+    "kunne",
+    "kva",
+    "kvar",
+    "kvarhelst",
+    "kven",
+    "kvi",
+    "kvifor",
+    "man",
+    "mange",
+    "me",
+    "med",
+    "medan",
+    "meg",
+    "meget",
+    "mellom",
+    "men",
+    "mi",
+    "min",
+    "mine",
+    "mitt",
+    "mot",
+    "mykje",
+    "ned",
+    "no",
+    "noe",
+    "noen",
+    "noka",
+    "noko",
+    "nokon",
+    "nokor",
+    "nokre",
+    "nå",
+    "når",
+    "og",
+    "også",
+    "om",
+    "opp",
+    "oss",
+    "over",
+    "på",
+    "samme",
+    "seg",
+    "selv",
+    "si",
+    "sia",
+    "sidan",
+    "siden",
+    "sin",
+    "sine",
+    "sitt",
+    "sjøl",
+    "skal",
+    "skulle",
+    "slik",
+    "so",
+    "som",
+    "somme",
+    "somt",
+    "så",
+    "sånn",
+    "til",
+    "um",
+    "upp",
+    "ut",
+    "uten",
+    "var",
+    "vart",
+    "varte",
+    "ved",
+    "vere",
+    "verte",
+    "vi",
+    "vil",
+    "ville",
+    "vore",
+    "vors",
+    "vort",
+    "vår",
+    "være",
+    "vært",
+    "å",
+];
+
+const PORTUGUESE: &[&str] = &[
+    "a", "à", "ao", "aos", "aquela", "aquelas", "aquele", "aqueles", "aquilo", "as", "às", "até",
+    "com", "como", "da", "das", "de", "dela", "delas", "dele", "deles", "depois", "do", "dos", "e",
+    "é", "ela", "elas", "ele", "eles", "em", "entre", "era", "eram", "essa", "essas", "esse",
+    "esses", "esta", "está", "estamos", "estão", "estas", "estava", "estavam", "este", "esteve",
+    "estes", "estou", "eu", "foi", "fomos", "foram", "há", "isso", "isto", "já", "lhe", "lhes",
+    "mais", "mas", "me", "mesmo", "meu", "meus", "minha", "minhas", "muito", "na", "não", "nas",
+    "nem", "no", "nos", "nós", "nossa", "nossas", "nosso", "nossos", "num", "numa", "o", "os",
+    "ou", "para", "pela", "pelas", "pelo", "pelos", "por", "qual", "quando", "que", "quem", "são",
+    "se", "seja", "sem", "ser", "seu", "seus", "só", "sua", "suas", "também", "te", "tem", "têm",
+    "temos", "tenho", "ter", "teu", "teus", "tu", "tua", "tuas", "um", "uma", "você", "vocês",
+    "vos",
+];
+
+const RUSSIAN: &[&str] = &[
+    "а",
+    "без",
+    "более",
+    "больше",
+    "будет",
+    "будто",
+    "бы",
+    "был",
+    "была",
+    "были",
+    "было",
+    "быть",
+    "в",
+    "вам",
+    "вас",
+    "вдруг",
+    "ведь",
+    "во",
+    "вот",
+    "впрочем",
+    "все",
+    "всегда",
+    "всего",
+    "всех",
+    "всю",
+    "вы",
+    "где",
+    "да",
+    "даже",
+    "два",
+    "для",
+    "до",
+    "другой",
+    "его",
+    "ее",
+    "ей",
+    "ему",
+    "если",
+    "есть",
+    "еще",
+    "ж",
+    "же",
+    "за",
+    "зачем",
+    "здесь",
+    "и",
+    "из",
+    "или",
+    "им",
+    "иногда",
+    "их",
+    "к",
+    "как",
+    "какая",
+    "какой",
+    "когда",
+    "конечно",
+    "кто",
+    "куда",
+    "ли",
+    "лучше",
+    "между",
+    "меня",
+    "мне",
+    "много",
+    "может",
+    "можно",
+    "мой",
+    "моя",
+    "мы",
+    "на",
+    "над",
+    "надо",
+    "наконец",
+    "нас",
+    "не",
+    "него",
+    "нее",
+    "ней",
+    "нельзя",
+    "нет",
+    "ни",
+    "нибудь",
+    "никогда",
+    "ним",
+    "них",
+    "ничего",
+    "но",
+    "ну",
+    "о",
+    "об",
+    "один",
+    "он",
+    "она",
+    "они",
+    "опять",
+    "от",
+    "перед",
+    "по",
+    "под",
+    "после",
+    "потом",
+    "потому",
+    "почти",
+    "при",
+    "про",
+    "раз",
+    "разве",
+    "с",
+    "сам",
+    "свою",
+    "себе",
+    "себя",
+    "сейчас",
+    "со",
+    "совсем",
+    "так",
+    "такой",
+    "там",
+    "тебя",
+    "тем",
+    "теперь",
+    "то",
+    "тогда",
+    "того",
+    "тоже",
+    "только",
+    "том",
+    "тот",
+    "три",
+    "тут",
+    "ты",
+    "у",
+    "уж",
+    "уже",
+    "хорошо",
+    "хоть",
+    "чего",
+    "чем",
+    "через",
+    "что",
+    "чтоб",
+    "чтобы",
+    "чуть",
+    "эти",
+    "этого",
+    "этой",
+    "этом",
+    "этот",
+    "эту",
+    "я",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "al", "algo", "algunas", "algunos", "ante", "antes", "como", "con", "contra", "cual",
+    "cuando", "de", "del", "desde", "donde", "durante", "e", "el", "él", "ella", "ellas", "ellos",
+    "en", "entre", "era", "erais", "eran", "eras", "eres", "es", "esa", "esas", "ese", "eso",
+    "esos", "esta", "está", "estaba", "estado", "estamos", "están", "estar", "estas", "este",
+    "esto", "estos", "estoy", "fue", "fueron", "fui", "ha", "habéis", "había", "han", "has",
+    "hasta", "hay", "he", "hemos", "la", "las", "le", "les", "lo", "los", "más", "me", "mi", "mí",
+    "mis", "mucho", "muchos", "muy", "nada", "ni", "no", "nos", "nosotras", "nosotros", "nuestra",
+    "nuestras", "nuestro", "nuestros", "o", "os", "otra", "otras", "otro", "otros", "para", "pero",
+    "poco", "por", "porque", "que", "qué", "quien", "quienes", "se", "sea", "sean", "ser", "si",
+    "sí", "sido", "sin", "sobre", "somos", "son", "soy", "su", "sus", "también", "tanto", "te",
+    "tenemos", "tener", "tengo", "ti", "tiene", "tienen", "todo", "todos", "tu", "tú", "tus", "un",
+    "una", "uno", "unos", "vosotras", "vosotros", "vuestra", "vuestro", "y", "ya", "yo",
+];
+
+const SWEDISH: &[&str] = &[
+    "alla", "allt", "att", "av", "blev", "bli", "blir", "blivit", "de", "dem", "den", "denna",
+    "deras", "dess", "dessa", "det", "detta", "dig", "din", "dina", "ditt", "du", "där", "då",
+    "efter", "ej", "eller", "en", "er", "era", "ert", "ett", "från", "för", "ha", "hade", "han",
+    "hans", "har", "henne", "hennes", "hon", "honom", "hur", "här", "i", "icke", "ingen", "inom",
+    "inte", "jag", "ju", "kan", "kunde", "man", "med", "mellan", "men", "mig", "min", "mina",
+    "mitt", "mot", "mycket", "ni", "nu", "när", "någon", "något", "några", "och", "om", "oss",
+    "på", "samma", "sedan", "sig", "sin", "sina", "sitta", "själv", "skulle", "som", "så", "sådan",
+    "sådana", "sådant", "till", "under", "upp", "ut", "utan", "vad", "var", "vara", "varför",
+    "varit", "varje", "vars", "vart", "vem", "vi", "vid", "vilka", "vilkas", "vilken", "vilket",
+    "vår", "våra", "vårt", "än", "är", "åt", "över",
+];
+
+fn language_stopwords(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::Danish => DANISH,
+        Language::Dutch => DUTCH,
+        Language::English => ENGLISH,
+        Language::Finnish => FINNISH,
+        Language::French => FRENCH,
+        Language::German => GERMAN,
+        Language::Italian => ITALIAN,
+        Language::Norwegian => NORWEGIAN,
+        Language::Portuguese => PORTUGUESE,
+        Language::Russian => RUSSIAN,
+        Language::Spanish => SPANISH,
+        Language::Swedish => SWEDISH,
+    }
+}
+
+/// Predefined stopwords of the language, built on first use
+fn language_stopwords_set(language: Language) -> &'static HashSet<&'static str> {
+    static SETS: OnceLock<HashMap<Language, HashSet<&'static str>>> = OnceLock::new();
+
+    let sets = SETS.get_or_init(|| {
+        use strum::IntoEnumIterator as _;
+
+        Language::iter()
+            .map(|language| {
+                let set = language_stopwords(language).iter().copied().collect();
+                (language, set)
+            })
+            .collect()
+    });
+
+    // unwrap safety: all the languages are in the map
+    sets.get(&language).unwrap()
+}
+
+/// Checks tokens against the stopwords configured for a text index
+#[derive(Default)]
+pub struct StopwordsFilter {
+    languages: Vec<&'static HashSet<&'static str>>,
+    custom: Option<BTreeSet<String>>,
+}
+
+impl StopwordsFilter {
+    pub fn new(stopwords: Option<&StopwordsInterface>) -> Self {
+        match stopwords {
+            None => Self::default(),
+            Some(StopwordsInterface::Language(language)) => Self {
+                languages: vec![language_stopwords_set(*language)],
+                custom: None,
+            },
+            Some(StopwordsInterface::Set(StopwordsSet { languages, custom })) => Self {
+                languages: languages
+                    .iter()
+                    .flatten()
+                    .map(|language| language_stopwords_set(*language))
+                    .collect(),
+                custom: custom.clone(),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty() && self.custom.is_none()
+    }
+
+    /// Stopwords are lowercase, so is the token expected to be
+    pub fn is_stopword(&self, token: &str) -> bool {
+        self.languages.iter().any(|set| set.contains(token))
+            || self
+                .custom
+                .as_ref()
+                .is_some_and(|custom| custom.contains(token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator as _;
+
+    use super::*;
+
+    #[test]
+    fn test_stopwords_are_lowercase_and_unique() {
+        for language in Language::iter() {
+            let stopwords = language_stopwords(language);
+            assert!(!stopwords.is_empty(), "{language}");
+
+            let set = language_stopwords_set(language);
+            assert_eq!(set.len(), stopwords.len(), "duplicates in {language}");
+
+            for stopword in stopwords {
+                assert_eq!(stopword.to_lowercase(), *stopword, "{language}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_stopwords_filter() {
+        let stopwords = StopwordsInterface::Set(StopwordsSet {
+            languages: Some(BTreeSet::from([Language::English, Language::French])),
+            custom: Some(BTreeSet::from(["qdrant".to_string()])),
+        });
+        let filter = StopwordsFilter::new(Some(&stopwords));
+
+        assert!(filter.is_stopword("the"));
+        assert!(filter.is_stopword("avec"));
+        assert!(filter.is_stopword("qdrant"));
+        assert!(!filter.is_stopword("vector"));
+
+        let filter = StopwordsFilter::new(None);
+        assert!(filter.is_empty());
+        assert!(!filter.is_stopword("the"));
+    }
+}
@@ -1,11 +1,25 @@
+use std::sync::Arc;
+
 use common::types::PointOffsetType;
 use rstest::rstest;
 use tempfile::Builder;
 
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+use crate::data_types::bm25::Bm25Query;
 use crate::data_types::index::{TextIndexParams, TextIndexType, TokenizerType};
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
-use crate::index::field_index::{FieldIndexBuilderTrait as _, PayloadFieldIndex, ValueIndexer};
+use crate::index::field_index::{
+    FieldIndex, FieldIndexBuilderTrait as _, PayloadFieldIndex, ValueIndexer,
+};
+use crate::json_path::JsonPath;
+use crate::types::{FieldCondition, Match, MatchFuzzy};
+
+#[derive(Clone, Copy)]
+enum IndexType {
+    Mutable,
+    Immutable,
+    Mmap,
+}
 
 fn get_texts() -> Vec<String> {
     vec![
@@ -160,7 +174,13 @@ fn test_prefix_search(#[case] immutable: bool) {
         tokenizer: TokenizerType::Prefix,
         min_token_len: None,
         max_token_len: None,
+        min_ngram: None,
+        max_ngram: None,
         lowercase: None,
+        phrase_matching: None,
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
         on_disk: None,
     };
 
@@ -202,3 +222,397 @@ fn test_prefix_search(#[case] immutable: bool) {
 
     assert_eq!(res.len(), 0);
 }
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn test_bm25_scores(#[case] immutable: bool) {
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::Word,
+        min_token_len: None,
+        max_token_len: None,
+        min_ngram: None,
+        max_ngram: None,
+        lowercase: Some(true),
+        phrase_matching: None,
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
+        on_disk: None,
+    };
+
+    let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+    let mut index = FullTextIndex::builder(db.clone(), config.clone(), "text")
+        .make_empty()
+        .unwrap();
+
+    let texts = [
+        "The Last Question",
+        "The Last Answer",
+        "Question",
+        "The Fun They Had",
+    ];
+
+    for (i, text) in texts.iter().enumerate() {
+        index
+            .add_many(i as PointOffsetType, vec![text.to_string()])
+            .unwrap();
+    }
+
+    if immutable {
+        index = FullTextIndex::new_memory(db, config, "text", false);
+        index.load().unwrap();
+    }
+
+    let query = Bm25Query {
+        key: JsonPath::new("text"),
+        text: "last QUESTION".to_string(),
+        k1: Bm25Query::DEFAULT_K1,
+        b: Bm25Query::DEFAULT_B,
+    };
+
+    let stats = index.bm25_stats(&query.text);
+    assert_eq!(stats.docs_count, 4);
+    assert_eq!(stats.tokens_count, 11);
+    assert_eq!(stats.docs_with_token["last"], 2);
+    assert_eq!(stats.docs_with_token["question"], 2);
+
+    let scores = index.bm25_scores(&query, &stats);
+    assert_eq!(scores.len(), 3);
+    // Matching both tokens is better than matching one
+    assert!(scores[&0] > scores[&2]);
+    // Shorter documents are better for the same token rarity
+    assert!(scores[&2] > scores[&1]);
+    // No matching tokens, no score
+    assert!(!scores.contains_key(&3));
+
+    // Removed points are not scored, nor counted in the statistics
+    index.remove_point(0).unwrap();
+
+    let stats = index.bm25_stats(&query.text);
+    assert_eq!(stats.docs_count, 3);
+    assert_eq!(stats.tokens_count, 8);
+
+    let scores = index.bm25_scores(&query, &stats);
+    assert_eq!(scores.len(), 2);
+    assert!(!scores.contains_key(&0));
+}
+
+#[rstest]
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_phrase_match(#[case] index_type: IndexType) {
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::Word,
+        min_token_len: None,
+        max_token_len: None,
+        min_ngram: None,
+        max_ngram: None,
+        lowercase: Some(true),
+        phrase_matching: Some(true),
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
+        on_disk: None,
+    };
+
+    let payloads = [
+        serde_json::json!("The office is in New York"),
+        serde_json::json!("York is far from New Zealand"),
+        serde_json::json!("New and shiny York"),
+        serde_json::json!(["Sold in New", "York stores"]),
+        serde_json::json!("new york, new york"),
+    ];
+
+    let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+    let mut index = match index_type {
+        IndexType::Mutable | IndexType::Immutable => {
+            let mut builder = FullTextIndex::builder(db.clone(), config.clone(), "text");
+            builder.init().unwrap();
+            for (i, payload) in payloads.iter().enumerate() {
+                builder.add_point(i as PointOffsetType, &[payload]).unwrap();
+            }
+            builder.finalize().unwrap()
+        }
+        IndexType::Mmap => {
+            let mut builder =
+                FullTextIndex::builder_mmap(temp_dir.path().join("mmap"), config.clone());
+            builder.init().unwrap();
+            for (i, payload) in payloads.iter().enumerate() {
+                builder.add_point(i as PointOffsetType, &[payload]).unwrap();
+            }
+            builder.finalize().unwrap()
+        }
+    };
+
+    if matches!(index_type, IndexType::Immutable) {
+        index = FullTextIndex::new_memory(db, config, "text", false);
+        index.load().unwrap();
+    }
+
+    let filter_phrase = |index: &FullTextIndex, phrase: &str, slop: Option<usize>| {
+        let condition =
+            FieldCondition::new_match(JsonPath::new("text"), Match::new_phrase(phrase, slop));
+        index.filter(&condition).unwrap().collect::<Vec<_>>()
+    };
+
+    // Tokens must be adjacent, in order, and within the same value
+    assert_eq!(filter_phrase(&index, "new york", None), vec![0, 4]);
+    assert_eq!(filter_phrase(&index, "NEW YORK", Some(0)), vec![0, 4]);
+    assert_eq!(filter_phrase(&index, "york new", None), vec![4]);
+
+    // Slop allows other tokens in between
+    assert_eq!(filter_phrase(&index, "new york", Some(1)), vec![0, 4]);
+    assert_eq!(filter_phrase(&index, "new york", Some(2)), vec![0, 2, 4]);
+
+    // Unknown tokens never match
+    assert!(filter_phrase(&index, "new jersey", Some(10)).is_empty());
+
+    let parsed_phrase = index.parse_phrase("in new york", 0);
+    let mut positions_buffer = Vec::new();
+    assert!(index.check_phrase(&parsed_phrase, 0, &mut positions_buffer));
+    assert!(!index.check_phrase(&parsed_phrase, 3, &mut positions_buffer));
+
+    index.remove_point(0).unwrap();
+    assert!(!index.check_phrase(&parsed_phrase, 0, &mut positions_buffer));
+    assert_eq!(filter_phrase(&index, "new york", None), vec![4]);
+}
+
+#[rstest]
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_fuzzy_match(#[case] index_type: IndexType) {
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::Word,
+        min_token_len: None,
+        max_token_len: None,
+        min_ngram: None,
+        max_ngram: None,
+        lowercase: Some(true),
+        phrase_matching: None,
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
+        on_disk: None,
+    };
+
+    let payloads = [
+        serde_json::json!("The quick brown fox"),
+        serde_json::json!("A quack of the duck"),
+        serde_json::json!("Brown bear"),
+        serde_json::json!(["Crown", "jewels"]),
+    ];
+
+    let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+    let mut index = match index_type {
+        IndexType::Mutable | IndexType::Immutable => {
+            let mut builder = FullTextIndex::builder(db.clone(), config.clone(), "text");
+            builder.init().unwrap();
+            for (i, payload) in payloads.iter().enumerate() {
+                builder.add_point(i as PointOffsetType, &[payload]).unwrap();
+            }
+            builder.finalize().unwrap()
+        }
+        IndexType::Mmap => {
+            let mut builder =
+                FullTextIndex::builder_mmap(temp_dir.path().join("mmap"), config.clone());
+            builder.init().unwrap();
+            for (i, payload) in payloads.iter().enumerate() {
+                builder.add_point(i as PointOffsetType, &[payload]).unwrap();
+            }
+            builder.finalize().unwrap()
+        }
+    };
+
+    if matches!(index_type, IndexType::Immutable) {
+        index = FullTextIndex::new_memory(db, config, "text", false);
+        index.load().unwrap();
+    }
+
+    let fuzzy_condition = |text: &str, max_edits: Option<usize>| {
+        FieldCondition::new_match(JsonPath::new("text"), Match::new_fuzzy(text, max_edits))
+    };
+    let filter_fuzzy = |index: &FullTextIndex, text: &str, max_edits: Option<usize>| {
+        let condition = fuzzy_condition(text, max_edits);
+        index.filter(&condition).unwrap().collect::<Vec<_>>()
+    };
+
+    // Typos within the distance match
+    assert_eq!(filter_fuzzy(&index, "quikc", Some(2)), vec![0]);
+    assert_eq!(
+        filter_fuzzy(&index, "qiuck", Some(1)),
+        Vec::<PointOffsetType>::new()
+    );
+    assert_eq!(filter_fuzzy(&index, "quick", None), vec![0, 1]);
+    assert_eq!(filter_fuzzy(&index, "brwn", None), vec![0, 2]);
+    assert_eq!(filter_fuzzy(&index, "brwn", Some(2)), vec![0, 2, 3]);
+
+    // Each query token must match
+    assert_eq!(filter_fuzzy(&index, "brwn fix", None), vec![0]);
+    assert!(filter_fuzzy(&index, "brown zebra", None).is_empty());
+
+    // Expansions are limited, the closest tokens are preferred
+    let fuzzy_match = MatchFuzzy {
+        fuzzy: "brown".to_string(),
+        max_edits: Some(1),
+        max_expansions: Some(1),
+    };
+    let parsed_fuzzy = index.parse_fuzzy(&fuzzy_match);
+    assert_eq!(parsed_fuzzy.tokens.len(), 1);
+    assert_eq!(parsed_fuzzy.tokens[0].len(), 1);
+    assert!(index.check_fuzzy(&parsed_fuzzy, 2));
+    assert!(!index.check_fuzzy(&parsed_fuzzy, 3));
+
+    // The same query is expanded only once
+    assert!(Arc::ptr_eq(&parsed_fuzzy, &index.parse_fuzzy(&fuzzy_match)));
+
+    // Cardinality estimation is consistent with the filter
+    let estimation = index
+        .estimate_cardinality(&fuzzy_condition("brwn", Some(2)))
+        .unwrap();
+    assert!(estimation.min <= 3 && 3 <= estimation.max);
+    assert!(estimation.min <= estimation.exp && estimation.exp <= estimation.max);
+    let estimation = index
+        .estimate_cardinality(&fuzzy_condition("zebra", None))
+        .unwrap();
+    assert_eq!(estimation.max, 0);
+
+    index.remove_point(0).unwrap();
+    assert_eq!(filter_fuzzy(&index, "brwn", Some(2)), vec![2, 3]);
+}
+
+#[test]
+fn test_levenshtein_distance() {
+    use crate::index::field_index::full_text_index::text_index::levenshtein_distance;
+
+    assert_eq!(levenshtein_distance("kitten", "sitting", 3), Some(3));
+    assert_eq!(levenshtein_distance("kitten", "sitting", 2), None);
+    assert_eq!(levenshtein_distance("flaw", "lawn", 2), Some(2));
+    assert_eq!(levenshtein_distance("мир", "мор", 1), Some(1));
+    assert_eq!(levenshtein_distance("same", "same", 0), Some(0));
+    assert_eq!(levenshtein_distance("", "abc", 2), None);
+}
+
+#[rstest]
+#[case(IndexType::Mutable)]
+#[case(IndexType::Immutable)]
+#[case(IndexType::Mmap)]
+fn test_ngram_substring_match(#[case] index_type: IndexType) {
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::Ngram,
+        min_token_len: None,
+        max_token_len: None,
+        min_ngram: Some(3),
+        max_ngram: Some(3),
+        lowercase: Some(true),
+        phrase_matching: None,
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
+        on_disk: None,
+    };
+
+    let payloads = [
+        serde_json::json!("SKU-12345-XL"),
+        serde_json::json!("sku-67890-s"),
+        serde_json::json!("abc-bcd"),
+        serde_json::json!("ABCD"),
+    ];
+
+    let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+    let mut index = match index_type {
+        IndexType::Mutable | IndexType::Immutable => {
+            let mut builder = FullTextIndex::builder(db.clone(), config.clone(), "text");
+            builder.init().unwrap();
+            for (i, payload) in payloads.iter().enumerate() {
+                builder.add_point(i as PointOffsetType, &[payload]).unwrap();
+            }
+            builder.finalize().unwrap()
+        }
+        IndexType::Mmap => {
+            let mut builder =
+                FullTextIndex::builder_mmap(temp_dir.path().join("mmap"), config.clone());
+            builder.init().unwrap();
+            for (i, payload) in payloads.iter().enumerate() {
+                builder.add_point(i as PointOffsetType, &[payload]).unwrap();
+            }
+            builder.finalize().unwrap()
+        }
+    };
+
+    if matches!(index_type, IndexType::Immutable) {
+        index = FullTextIndex::new_memory(db, config, "text", false);
+        index.load().unwrap();
+    }
+
+    let text_condition =
+        |text: &str| FieldCondition::new_match(JsonPath::new("text"), Match::new_text(text));
+    let candidates = |index: &FullTextIndex, text: &str| {
+        index
+            .filter(&text_condition(text))
+            .map(|points| points.collect::<Vec<_>>())
+    };
+
+    // Substrings in the middle of the values, across punctuation
+    assert_eq!(candidates(&index, "2345"), Some(vec![0]));
+    assert_eq!(candidates(&index, "45-x"), Some(vec![0]));
+    assert_eq!(candidates(&index, "SKU-"), Some(vec![0, 1]));
+
+    // N-grams only give candidates, which are verified against the payload
+    assert_eq!(candidates(&index, "abcd"), Some(vec![2, 3]));
+    let estimation = index.estimate_cardinality(&text_condition("abcd")).unwrap();
+    assert_eq!(estimation.min, 0);
+
+    let field_index = FieldIndex::FullTextIndex(index);
+    let check = |text: &str, point_id: usize| {
+        field_index
+            .check_condition(&text_condition(text), &payloads[point_id])
+            .unwrap()
+    };
+    assert!(!check("abcd", 2));
+    assert!(check("abcd", 3));
+    assert!(check("u-12", 0));
+
+    // Too short for the n-grams: no index search, but the payload check still works
+    let FieldIndex::FullTextIndex(index) = &field_index else {
+        unreachable!()
+    };
+    assert!(candidates(index, "ab").is_none());
+    assert!(index.estimate_cardinality(&text_condition("ab")).is_none());
+    assert!(check("ab", 3));
+    assert!(!check("ab", 0));
+}
+
+#[test]
+fn test_ngram_range_validation() {
+    use validator::Validate;
+
+    let ngram_params = |min_ngram, max_ngram| TextIndexParams {
+        tokenizer: TokenizerType::Ngram,
+        min_ngram,
+        max_ngram,
+        ..Default::default()
+    };
+
+    assert!(ngram_params(None, None).validate().is_ok());
+    assert!(ngram_params(Some(2), Some(4)).validate().is_ok());
+    assert!(ngram_params(Some(3), Some(3)).validate().is_ok());
+
+    // Out of bounds sizes
+    assert!(ngram_params(Some(0), Some(3)).validate().is_err());
+    assert!(ngram_params(Some(3), Some(11)).validate().is_err());
+
+    // Minimal size above the maximal one, explicit or default
+    assert!(ngram_params(Some(4), Some(2)).validate().is_err());
+    assert!(ngram_params(Some(4), None).validate().is_err());
+}
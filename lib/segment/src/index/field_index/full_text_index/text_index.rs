@@ -1,15 +1,17 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::immutable_text_index::ImmutableFullTextIndex;
-use super::inverted_index::{Document, InvertedIndex, ParsedQuery, TokenId};
+use super::inverted_index::{
+    Document, FuzzyCache, InvertedIndex, ParsedFuzzy, ParsedPhrase, ParsedQuery, TokenId,
+};
 use super::mmap_text_index::{FullTextMmapIndexBuilder, MmapFullTextIndex};
 use super::mutable_text_index::MutableFullTextIndex;
 use super::tokenizers::Tokenizer;
@@ -17,13 +19,110 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
-use crate::data_types::index::TextIndexParams;
+use crate::data_types::bm25::{Bm25Query, Bm25Stats};
+use crate::data_types::index::{TextIndexParams, TokenizerType};
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndexBuilderTrait, PayloadBlockCondition, PayloadFieldIndex,
     ValueIndexer,
 };
 use crate::telemetry::PayloadIndexTelemetry;
-use crate::types::{FieldCondition, Match, PayloadKeyType};
+use crate::types::{FieldCondition, Match, MatchFuzzy, PayloadKeyType};
+
+/// Checks that the phrase tokens appear in the sequence in the same order,
+/// with at most `slop` other tokens between them in total.
+pub fn check_phrase<T: PartialEq>(sequence: &[T], phrase: &[T], slop: usize) -> bool {
+    let Some((first, rest)) = phrase.split_first() else {
+        return false;
+    };
+
+    sequence
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| *token == first)
+        .any(|(start, _)| {
+            // Taking the nearest occurrence of each next token leaves the smallest gaps
+            let mut position = start;
+            let mut gaps = 0;
+            rest.iter().all(|token| {
+                let Some(offset) = sequence[position + 1..].iter().position(|t| t == token) else {
+                    return false;
+                };
+                gaps += offset;
+                position += offset + 1;
+                gaps <= slop
+            })
+        })
+}
+
+/// Levenshtein distance between the strings, counted in characters.
+/// `None` if it exceeds `max_distance`, in which case the computation stops early.
+pub fn levenshtein_distance(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        // Distance never decreases in the next rows
+        if current
+            .iter()
+            .min()
+            .is_some_and(|&row_min| row_min > max_distance)
+        {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max_distance).then_some(distance)
+}
+
+/// Checks the fuzzy query tokens against the tokens of a text which is not indexed.
+///
+/// The tokens of the text are the whole vocabulary, so a query token matches
+/// if some of them is within `max_edits`, unless no expansions are allowed at all.
+pub fn check_fuzzy_in_tokens(
+    text_tokens: &[String],
+    query_tokens: &[String],
+    max_edits: usize,
+    max_expansions: usize,
+) -> bool {
+    max_expansions > 0
+        && !query_tokens.is_empty()
+        && query_tokens.iter().all(|query_token| {
+            text_tokens
+                .iter()
+                .any(|token| levenshtein_distance(token, query_token, max_edits).is_some())
+        })
+}
+
+/// Ordered tokens of the text, as a text index with the default parameters produces them.
+///
+/// Used to check text conditions against a payload without a text index,
+/// consistently with the segments where the field is indexed with the default parameters.
+pub fn default_text_tokens(text: &str) -> Vec<String> {
+    static TOKENIZER: OnceLock<Tokenizer> = OnceLock::new();
+    let tokenizer = TOKENIZER.get_or_init(|| Tokenizer::new(TextIndexParams::default()));
+    FullTextIndex::tokenize_phrase(text, tokenizer)
+}
+
+/// Document as it is persisted in RocksDB
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct StoredDocument {
+    pub tokens: BTreeSet<String>,
+    /// Ordered tokens of each value, only stored if the index is configured for phrase matching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positions: Option<Vec<Vec<String>>>,
+}
 
 pub enum FullTextIndex {
     Mutable(MutableFullTextIndex),
@@ -78,14 +177,44 @@ impl FullTextIndex {
         format!("{field}_fts")
     }
 
+    fn tokenizer(&self) -> &Tokenizer {
+        match self {
+            Self::Mutable(index) => &index.tokenizer,
+            Self::Immutable(index) => &index.tokenizer,
+            Self::Mmap(index) => &index.tokenizer,
+        }
+    }
+
     fn config(&self) -> &TextIndexParams {
+        self.tokenizer().config()
+    }
+
+    fn fuzzy_cache(&self) -> &FuzzyCache {
         match self {
-            Self::Mutable(index) => &index.config,
-            Self::Immutable(index) => &index.config,
-            Self::Mmap(index) => &index.config,
+            Self::Mutable(index) => &index.fuzzy_cache,
+            Self::Immutable(index) => &index.fuzzy_cache,
+            Self::Mmap(index) => &index.fuzzy_cache,
         }
     }
 
+    /// Whether token positions are stored, which is required to filter by phrase
+    pub fn has_positions(&self) -> bool {
+        self.config().phrase_matching.unwrap_or(false)
+    }
+
+    /// Whether the tokens are n-grams, so that the postings only give candidates for a substring,
+    /// which must be verified against the payload
+    pub fn is_ngram(&self) -> bool {
+        self.config().tokenizer == TokenizerType::Ngram
+    }
+
+    /// Check that the text is a substring of the value, normalized the same way as the n-grams
+    pub fn check_substring(&self, text: &str, value: &str) -> bool {
+        let text = self.tokenizer().normalize_text(text);
+        let value = self.tokenizer().normalize_text(value);
+        value.contains(&text)
+    }
+
     fn points_count(&self) -> usize {
         match self {
             Self::Mutable(index) => index.inverted_index.points_count(),
@@ -94,6 +223,30 @@ impl FullTextIndex {
         }
     }
 
+    fn tokens_count(&self) -> usize {
+        match self {
+            Self::Mutable(index) => index.inverted_index.tokens_count(),
+            Self::Immutable(index) => index.inverted_index.tokens_count(),
+            Self::Mmap(index) => index.inverted_index.tokens_count(),
+        }
+    }
+
+    fn get_posting_len(&self, token_id: TokenId) -> Option<usize> {
+        match self {
+            Self::Mutable(index) => index.inverted_index.get_posting_len(token_id),
+            Self::Immutable(index) => index.inverted_index.get_posting_len(token_id),
+            Self::Mmap(index) => index.inverted_index.get_posting_len(token_id),
+        }
+    }
+
+    fn iter_posting(&self, token_id: TokenId) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            Self::Mutable(index) => index.inverted_index.iter_posting(token_id),
+            Self::Immutable(index) => index.inverted_index.iter_posting(token_id),
+            Self::Mmap(index) => index.inverted_index.iter_posting(token_id),
+        }
+    }
+
     fn get_token(&self, token: &str) -> Option<TokenId> {
         match self {
             Self::Mutable(index) => index.inverted_index.get_token_id(token),
@@ -142,6 +295,129 @@ impl FullTextIndex {
         }
     }
 
+    /// Check the phrase against the token positions of the point.
+    ///
+    /// `positions_buffer` is scratch space, to be reused when checking many points.
+    pub fn check_phrase(
+        &self,
+        phrase: &ParsedPhrase,
+        point_id: PointOffsetType,
+        positions_buffer: &mut Vec<TokenId>,
+    ) -> bool {
+        match self {
+            Self::Mutable(index) => {
+                index
+                    .inverted_index
+                    .check_phrase(phrase, point_id, positions_buffer)
+            }
+            Self::Immutable(index) => {
+                index
+                    .inverted_index
+                    .check_phrase(phrase, point_id, positions_buffer)
+            }
+            Self::Mmap(index) => {
+                index
+                    .inverted_index
+                    .check_phrase(phrase, point_id, positions_buffer)
+            }
+        }
+    }
+
+    pub fn check_fuzzy(&self, fuzzy: &ParsedFuzzy, point_id: PointOffsetType) -> bool {
+        match self {
+            Self::Mutable(index) => index.inverted_index.check_fuzzy(fuzzy, point_id),
+            Self::Immutable(index) => index.inverted_index.check_fuzzy(fuzzy, point_id),
+            Self::Mmap(index) => index.inverted_index.check_fuzzy(fuzzy, point_id),
+        }
+    }
+
+    fn fuzzy_expansions(
+        &self,
+        token: &str,
+        max_edits: usize,
+        max_expansions: usize,
+    ) -> Vec<TokenId> {
+        match self {
+            Self::Mutable(index) => {
+                index
+                    .inverted_index
+                    .fuzzy_expansions(token, max_edits, max_expansions)
+            }
+            Self::Immutable(index) => {
+                index
+                    .inverted_index
+                    .fuzzy_expansions(token, max_edits, max_expansions)
+            }
+            Self::Mmap(index) => {
+                index
+                    .inverted_index
+                    .fuzzy_expansions(token, max_edits, max_expansions)
+            }
+        }
+    }
+
+    fn estimate_fuzzy_cardinality(
+        &self,
+        fuzzy: &ParsedFuzzy,
+        condition: &FieldCondition,
+    ) -> CardinalityEstimation {
+        match self {
+            Self::Mutable(index) => index
+                .inverted_index
+                .estimate_fuzzy_cardinality(fuzzy, condition),
+            Self::Immutable(index) => index
+                .inverted_index
+                .estimate_fuzzy_cardinality(fuzzy, condition),
+            Self::Mmap(index) => index
+                .inverted_index
+                .estimate_fuzzy_cardinality(fuzzy, condition),
+        }
+    }
+
+    fn filter_fuzzy(
+        &self,
+        fuzzy: Arc<ParsedFuzzy>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        // Candidates are the points with some expansion of the most selective query token
+        let candidates_posting_len = |expansions: &&Vec<TokenId>| -> usize {
+            expansions
+                .iter()
+                .map(|&token_id| self.get_posting_len(token_id).unwrap_or(0))
+                .sum()
+        };
+        let Some(expansions) = fuzzy.tokens.iter().min_by_key(candidates_posting_len) else {
+            // Empty request -> no matches
+            return Box::new(std::iter::empty());
+        };
+
+        let mut candidates: Vec<_> = expansions
+            .iter()
+            .flat_map(|&token_id| self.iter_posting(token_id))
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        Box::new(
+            candidates
+                .into_iter()
+                .filter(move |&point_id| self.check_fuzzy(&fuzzy, point_id)),
+        )
+    }
+
+    fn filter_phrase(
+        &self,
+        phrase: ParsedPhrase,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        // Candidates contain all the tokens of the phrase, positions are checked afterwards
+        let candidates = self.filter(&phrase.to_query());
+        let mut positions_buffer = Vec::new();
+        Box::new(
+            candidates.filter(move |&point_id| {
+                self.check_phrase(&phrase, point_id, &mut positions_buffer)
+            }),
+        )
+    }
+
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
         match self {
             Self::Mutable(index) => index.inverted_index.values_count(point_id),
@@ -166,27 +442,47 @@ impl FullTextIndex {
         bincode::deserialize(data).unwrap()
     }
 
-    pub(super) fn serialize_document_tokens(tokens: BTreeSet<String>) -> OperationResult<Vec<u8>> {
-        #[derive(Serialize)]
-        struct StoredDocument {
-            tokens: BTreeSet<String>,
-        }
-        let doc = StoredDocument { tokens };
-        serde_cbor::to_vec(&doc).map_err(|e| {
+    pub(super) fn serialize_document(doc: &StoredDocument) -> OperationResult<Vec<u8>> {
+        serde_cbor::to_vec(doc).map_err(|e| {
             OperationError::service_error(format!("Failed to serialize document: {e}"))
         })
     }
 
-    pub(super) fn deserialize_document(data: &[u8]) -> OperationResult<BTreeSet<String>> {
-        #[derive(Deserialize)]
-        struct StoredDocument {
-            tokens: BTreeSet<String>,
+    pub(super) fn deserialize_document(data: &[u8]) -> OperationResult<StoredDocument> {
+        serde_cbor::from_slice::<StoredDocument>(data).map_err(|e| {
+            OperationError::service_error(format!("Failed to deserialize document: {e}"))
+        })
+    }
+
+    /// Tokenize the values of a document: the set of tokens to index,
+    /// and the ordered tokens of each value if positions are stored
+    pub(super) fn tokenize_document(values: &[String], tokenizer: &Tokenizer) -> StoredDocument {
+        let mut tokens = BTreeSet::new();
+        for value in values {
+            tokenizer.tokenize_doc(value, |token| {
+                tokens.insert(token.to_owned());
+            });
         }
-        serde_cbor::from_slice::<StoredDocument>(data)
-            .map_err(|e| {
-                OperationError::service_error(format!("Failed to deserialize document: {e}"))
-            })
-            .map(|doc| doc.tokens)
+
+        let positions = tokenizer
+            .config()
+            .phrase_matching
+            .unwrap_or(false)
+            .then(|| {
+                values
+                    .iter()
+                    .map(|value| Self::tokenize_phrase(value, tokenizer))
+                    .collect()
+            });
+
+        StoredDocument { tokens, positions }
+    }
+
+    /// Ordered tokens of the text, one per word even for the prefix tokenizer
+    fn tokenize_phrase(text: &str, tokenizer: &Tokenizer) -> Vec<String> {
+        let mut tokens = vec![];
+        tokenizer.tokenize_query(text, |token| tokens.push(token.to_owned()));
+        tokens
     }
 
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
@@ -200,7 +496,7 @@ impl FullTextIndex {
 
     pub fn parse_query(&self, text: &str) -> ParsedQuery {
         let mut tokens = HashSet::new();
-        Tokenizer::tokenize_query(text, self.config(), |token| {
+        self.tokenizer().tokenize_query(text, |token| {
             tokens.insert(self.get_token(token));
         });
         ParsedQuery {
@@ -208,9 +504,94 @@ impl FullTextIndex {
         }
     }
 
+    /// Statistics of the documents and of the query tokens in this index, for BM25 scoring.
+    ///
+    /// Document frequencies are taken from the posting lengths, which may still count deleted points
+    /// in immutable indexes.
+    pub fn bm25_stats(&self, text: &str) -> Bm25Stats {
+        let mut docs_with_token = HashMap::new();
+        self.tokenizer().tokenize_query(text, |token| {
+            let count = self
+                .get_token(token)
+                .and_then(|token_id| self.get_posting_len(token_id))
+                .unwrap_or(0);
+            docs_with_token.insert(token.to_string(), count);
+        });
+        Bm25Stats {
+            docs_count: self.points_count(),
+            tokens_count: self.tokens_count(),
+            docs_with_token,
+        }
+    }
+
+    /// BM25 scores of all the points which contain at least one of the query tokens
+    pub fn bm25_scores(
+        &self,
+        query: &Bm25Query,
+        stats: &Bm25Stats,
+    ) -> HashMap<PointOffsetType, ScoreType> {
+        let mut query_tokens = HashSet::new();
+        self.tokenizer().tokenize_query(&query.text, |token| {
+            query_tokens.insert(token.to_string());
+        });
+
+        let avg_doc_len = stats.avg_doc_len();
+        let mut scores = HashMap::new();
+        for token in &query_tokens {
+            let Some(token_id) = self.get_token(token) else {
+                continue;
+            };
+            let idf = stats.idf(token);
+            for point_id in self.iter_posting(token_id) {
+                let doc_len = self.values_count(point_id);
+                *scores.entry(point_id).or_insert(0.0) +=
+                    query.token_score(idf, doc_len, avg_doc_len);
+            }
+        }
+        scores
+    }
+
+    pub fn parse_phrase(&self, phrase: &str, slop: usize) -> ParsedPhrase {
+        let tokens = Self::tokenize_phrase(phrase, self.tokenizer())
+            .iter()
+            .map(|token| self.get_token(token))
+            .collect();
+        ParsedPhrase { tokens, slop }
+    }
+
+    /// Expand each token of the query to the indexed tokens within `max_edits`.
+    ///
+    /// Expanding scans the whole vocabulary, so the parsed query is cached
+    /// and reused by the following checks of the same query, until the index changes.
+    pub fn parse_fuzzy(&self, fuzzy: &MatchFuzzy) -> Arc<ParsedFuzzy> {
+        self.fuzzy_cache().get_or_parse(fuzzy, || {
+            let mut query_tokens = vec![];
+            self.tokenizer().tokenize_query(&fuzzy.fuzzy, |token| {
+                if !query_tokens.iter().any(|query_token| query_token == token) {
+                    query_tokens.push(token.to_owned());
+                }
+            });
+
+            let tokens = query_tokens
+                .iter()
+                .map(|token| {
+                    self.fuzzy_expansions(token, fuzzy.max_edits(), fuzzy.max_expansions())
+                })
+                .collect();
+            ParsedFuzzy { tokens }
+        })
+    }
+
+    /// Check the phrase against a raw text value, tokenized the same way as the indexed documents
+    pub fn check_phrase_in_text(&self, phrase: &str, slop: usize, text: &str) -> bool {
+        let phrase_tokens = Self::tokenize_phrase(phrase, self.tokenizer());
+        let text_tokens = Self::tokenize_phrase(text, self.tokenizer());
+        check_phrase(&text_tokens, &phrase_tokens, slop)
+    }
+
     pub fn parse_document(&self, text: &str) -> Document {
         let mut document_tokens = vec![];
-        Tokenizer::tokenize_doc(text, self.config(), |token| {
+        self.tokenizer().tokenize_doc(text, |token| {
             if let Some(token_id) = self.get_token(token) {
                 document_tokens.push(token_id);
             }
@@ -315,19 +696,57 @@ impl PayloadFieldIndex for FullTextIndex {
         &self,
         condition: &FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
-            return Some(self.filter(&parsed_query));
+        match &condition.r#match {
+            Some(Match::Text(text_match)) => {
+                let parsed_query = self.parse_query(&text_match.text);
+                if self.is_ngram() && parsed_query.tokens.is_empty() {
+                    // Shorter than the n-grams, can't be searched in the index
+                    return None;
+                }
+                Some(self.filter(&parsed_query))
+            }
+            Some(Match::Phrase(phrase_match)) if self.has_positions() => {
+                let parsed_phrase =
+                    self.parse_phrase(&phrase_match.phrase, phrase_match.slop.unwrap_or(0));
+                Some(self.filter_phrase(parsed_phrase))
+            }
+            Some(Match::Fuzzy(fuzzy_match)) => {
+                let parsed_fuzzy = self.parse_fuzzy(fuzzy_match);
+                Some(self.filter_fuzzy(parsed_fuzzy))
+            }
+            _ => None,
         }
-        None
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
-        if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
-            return Some(self.estimate_cardinality(&parsed_query, condition));
+        match &condition.r#match {
+            Some(Match::Text(text_match)) => {
+                let parsed_query = self.parse_query(&text_match.text);
+                if self.is_ngram() && parsed_query.tokens.is_empty() {
+                    return None;
+                }
+                let mut estimation = self.estimate_cardinality(&parsed_query, condition);
+                if self.is_ngram() {
+                    // Points with all the n-grams may still not contain the substring
+                    estimation.min = 0;
+                }
+                Some(estimation)
+            }
+            Some(Match::Phrase(phrase_match)) if self.has_positions() => {
+                // Points with all the tokens of the phrase are the upper bound
+                let parsed_phrase =
+                    self.parse_phrase(&phrase_match.phrase, phrase_match.slop.unwrap_or(0));
+                let mut estimation =
+                    self.estimate_cardinality(&parsed_phrase.to_query(), condition);
+                estimation.min = 0;
+                Some(estimation)
+            }
+            Some(Match::Fuzzy(fuzzy_match)) => {
+                let parsed_fuzzy = self.parse_fuzzy(fuzzy_match);
+                Some(self.estimate_fuzzy_cardinality(&parsed_fuzzy, condition))
+            }
+            _ => None,
         }
-        None
     }
 
     fn payload_blocks(
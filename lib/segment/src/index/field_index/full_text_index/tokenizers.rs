@@ -1,6 +1,14 @@
+use std::borrow::Cow;
+
 use charabia::Tokenize;
+use rust_stemmers::{Algorithm, Stemmer};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
-use crate::data_types::index::{TextIndexParams, TokenizerType};
+use super::stop_words::StopwordsFilter;
+use crate::data_types::index::{
+    Language, SnowballParams, StemmingAlgorithm, TextIndexParams, TokenizerType,
+};
 
 struct WhiteSpaceTokenizer;
 
@@ -62,6 +70,41 @@ impl PrefixTokenizer {
     }
 }
 
+struct NgramTokenizer;
+
+impl NgramTokenizer {
+    /// All the character n-grams of the text, including whitespaces and punctuation,
+    /// so that any substring of at least `min_ngram` characters can be found.
+    fn tokenize<C: FnMut(&str)>(text: &str, min_ngram: usize, max_ngram: usize, mut callback: C) {
+        let boundaries: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .collect();
+        let chars_count = boundaries.len() - 1;
+
+        for n in min_ngram..=max_ngram.min(chars_count) {
+            for start in 0..=chars_count - n {
+                callback(&text[boundaries[start]..boundaries[start + n]]);
+            }
+        }
+    }
+
+    /// For querying, n-grams of a single size are enough: the largest one which fits the query.
+    /// E.g. with trigrams:
+    ///
+    /// Query tokens: `"abcd"` -> `["abc", "bcd"]`
+    /// Query tokens: `"ab"`   -> `[]`, too short to be searched in the index
+    fn tokenize_query<C: FnMut(&str)>(text: &str, min_ngram: usize, max_ngram: usize, callback: C) {
+        let chars_count = text.chars().count();
+        if chars_count < min_ngram {
+            return;
+        }
+        let n = max_ngram.min(chars_count);
+        Self::tokenize(text, n, n, callback);
+    }
+}
+
 struct MultilingualTokenizer;
 
 impl MultilingualTokenizer {
@@ -74,13 +117,116 @@ impl MultilingualTokenizer {
     }
 }
 
-pub struct Tokenizer;
+fn stemmer_algorithm(language: Language) -> Algorithm {
+    match language {
+        Language::Danish => Algorithm::Danish,
+        Language::Dutch => Algorithm::Dutch,
+        Language::English => Algorithm::English,
+        Language::Finnish => Algorithm::Finnish,
+        Language::French => Algorithm::French,
+        Language::German => Algorithm::German,
+        Language::Italian => Algorithm::Italian,
+        Language::Norwegian => Algorithm::Norwegian,
+        Language::Portuguese => Algorithm::Portuguese,
+        Language::Russian => Algorithm::Russian,
+        Language::Spanish => Algorithm::Spanish,
+        Language::Swedish => Algorithm::Swedish,
+    }
+}
+
+/// Replace accented and other non-ASCII latin letters with their ASCII counterparts.
+///
+/// Characters without such counterpart are kept as is.
+fn fold_to_ascii(token: &str) -> String {
+    let mut folded = String::with_capacity(token.len());
+    for c in token.nfkd() {
+        if is_combining_mark(c) {
+            continue;
+        }
+        match c {
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            'Æ' => folded.push_str("AE"),
+            'œ' => folded.push_str("oe"),
+            'Œ' => folded.push_str("OE"),
+            'ø' => folded.push('o'),
+            'Ø' => folded.push('O'),
+            'ł' => folded.push('l'),
+            'Ł' => folded.push('L'),
+            'đ' => folded.push('d'),
+            'Đ' => folded.push('D'),
+            'þ' => folded.push_str("th"),
+            'Þ' => folded.push_str("TH"),
+            _ => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// Tokenizes documents and queries the way configured for a text index.
+///
+/// The stopwords and the stemmer are prepared on creation, so a tokenizer is built once per index.
+pub struct Tokenizer {
+    config: TextIndexParams,
+    stopwords: StopwordsFilter,
+    stemmer: Option<Stemmer>,
+}
 
 impl Tokenizer {
+    pub fn new(config: TextIndexParams) -> Self {
+        // N-grams and prefixes are not words, neither stopwords nor stemming apply to them
+        let is_ngram = config.tokenizer == TokenizerType::Ngram;
+        let is_prefix = config.tokenizer == TokenizerType::Prefix;
+        let stopwords = if is_ngram {
+            StopwordsFilter::default()
+        } else {
+            StopwordsFilter::new(config.stopwords.as_ref())
+        };
+        let stemmer = config
+            .stemmer
+            .as_ref()
+            .filter(|_| !is_ngram && !is_prefix)
+            .map(|stemmer| match stemmer {
+                StemmingAlgorithm::Snowball(SnowballParams {
+                    r#type: _,
+                    language,
+                }) => Stemmer::create(stemmer_algorithm(*language)),
+            });
+
+        Self {
+            config,
+            stopwords,
+            stemmer,
+        }
+    }
+
+    pub fn config(&self) -> &TextIndexParams {
+        &self.config
+    }
+
+    /// Normalize the whole text the same way as the n-grams, to check substrings
+    pub fn normalize_text(&self, text: &str) -> String {
+        let mut text = if self.config.lowercase.unwrap_or(true) {
+            text.to_lowercase()
+        } else {
+            text.to_owned()
+        };
+        if self.config.ascii_folding.unwrap_or(false) {
+            text = fold_to_ascii(&text);
+        }
+        text
+    }
+
+    /// Filters and normalizes tokens, the same way for documents and queries:
+    /// length limits, lowercasing, stopwords removal, stemming and ASCII folding.
     fn doc_token_filter<'a, C: FnMut(&str) + 'a>(
-        config: &'a TextIndexParams,
+        &'a self,
         mut callback: C,
     ) -> impl FnMut(&str) + 'a {
+        let config = &self.config;
+        let lowercase = config.lowercase.unwrap_or(true);
+        let ascii_folding = config.ascii_folding.unwrap_or(false);
+
         move |token: &str| {
             if config
                 .min_token_len
@@ -96,20 +242,49 @@ impl Tokenizer {
             {
                 return;
             }
-            if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
+
+            let mut token = if lowercase {
+                Cow::Owned(token.to_lowercase())
             } else {
-                callback(token);
+                Cow::Borrowed(token)
+            };
+
+            if !self.stopwords.is_empty() {
+                let is_stopword = if lowercase {
+                    self.stopwords.is_stopword(&token)
+                } else {
+                    self.stopwords.is_stopword(&token.to_lowercase())
+                };
+                if is_stopword {
+                    return;
+                }
+            }
+
+            if let Some(stemmer) = &self.stemmer {
+                token = Cow::Owned(stemmer.stem(&token).into_owned());
+            }
+
+            if ascii_folding {
+                token = Cow::Owned(fold_to_ascii(&token));
+            }
+
+            if !token.is_empty() {
+                callback(&token);
             }
         }
     }
 
-    pub fn tokenize_doc<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
+    pub fn tokenize_doc<C: FnMut(&str)>(&self, text: &str, mut callback: C) {
+        let config = &self.config;
+        let token_filter = self.doc_token_filter(&mut callback);
         match config.tokenizer {
             TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
             TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
             TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
+            TokenizerType::Ngram => {
+                let (min_ngram, max_ngram) = config.ngram_range();
+                NgramTokenizer::tokenize(text, min_ngram, max_ngram, token_filter)
+            }
             TokenizerType::Prefix => PrefixTokenizer::tokenize(
                 text,
                 config.min_token_len.unwrap_or(1),
@@ -119,12 +294,17 @@ impl Tokenizer {
         }
     }
 
-    pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
+    pub fn tokenize_query<C: FnMut(&str)>(&self, text: &str, mut callback: C) {
+        let config = &self.config;
+        let token_filter = self.doc_token_filter(&mut callback);
         match config.tokenizer {
             TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
             TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
             TokenizerType::Multilingual => MultilingualTokenizer::tokenize(text, token_filter),
+            TokenizerType::Ngram => {
+                let (min_ngram, max_ngram) = config.ngram_range();
+                NgramTokenizer::tokenize_query(text, min_ngram, max_ngram, token_filter)
+            }
             TokenizerType::Prefix => PrefixTokenizer::tokenize_query(
                 text,
                 config.max_token_len.unwrap_or(usize::MAX),
@@ -136,8 +316,10 @@ impl Tokenizer {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
-    use crate::data_types::index::TextIndexType;
+    use crate::data_types::index::{SnowballType, StopwordsInterface, StopwordsSet, TextIndexType};
 
     #[test]
     fn test_whitespace_tokenizer() {
@@ -188,6 +370,37 @@ mod tests {
         assert_eq!(tokens.get(1), Some(&"мир".to_owned()));
     }
 
+    #[test]
+    fn test_ngram_tokenizer() {
+        let text = "ab-cd";
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize(text, 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["ab", "b-", "-c", "cd", "ab-", "b-c", "-cd"],);
+
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize("мир", 2, 2, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["ми", "ир"]);
+
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize("ab", 3, 3, |token| tokens.push(token.to_owned()));
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn test_ngram_query_tokenizer() {
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize_query("abcd", 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["abc", "bcd"]);
+
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize_query("ab", 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["ab"]);
+
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize_query("a", 2, 3, |token| tokens.push(token.to_owned()));
+        assert!(tokens.is_empty());
+    }
+
     #[cfg(feature = "multiling-japanese")]
     #[test]
     fn test_multilingual_tokenizer_japanese() {
@@ -246,18 +459,21 @@ mod tests {
     fn test_tokenizer() {
         let text = "Hello, Мир!";
         let mut tokens = Vec::new();
-        Tokenizer::tokenize_doc(
-            text,
-            &TextIndexParams {
-                r#type: TextIndexType::Text,
-                tokenizer: TokenizerType::Prefix,
-                min_token_len: Some(1),
-                max_token_len: Some(4),
-                lowercase: Some(true),
-                on_disk: None,
-            },
-            |token| tokens.push(token.to_owned()),
-        );
+        Tokenizer::new(TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(1),
+            max_token_len: Some(4),
+            min_ngram: None,
+            max_ngram: None,
+            lowercase: Some(true),
+            phrase_matching: None,
+            stopwords: None,
+            stemmer: None,
+            ascii_folding: None,
+            on_disk: None,
+        })
+        .tokenize_doc(text, |token| tokens.push(token.to_owned()));
         eprintln!("tokens = {tokens:#?}");
         assert_eq!(tokens.len(), 7);
         assert_eq!(tokens.first(), Some(&"h".to_owned()));
@@ -268,4 +484,59 @@ mod tests {
         assert_eq!(tokens.get(5), Some(&"ми".to_owned()));
         assert_eq!(tokens.get(6), Some(&"мир".to_owned()));
     }
+
+    #[test]
+    fn test_tokenizer_stopwords_stemming_folding() {
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            min_ngram: None,
+            max_ngram: None,
+            lowercase: Some(true),
+            phrase_matching: None,
+            stopwords: Some(StopwordsInterface::Set(StopwordsSet {
+                languages: Some(BTreeSet::from([Language::English])),
+                custom: Some(BTreeSet::from(["cat".to_string()])),
+            })),
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: SnowballType::Snowball,
+                language: Language::English,
+            })),
+            ascii_folding: Some(true),
+            on_disk: None,
+        };
+
+        let tokenizer = Tokenizer::new(config.clone());
+
+        let mut doc_tokens = Vec::new();
+        tokenizer.tokenize_doc("The Cat is running to the Café", |token| {
+            doc_tokens.push(token.to_owned())
+        });
+        assert_eq!(doc_tokens, vec!["run", "cafe"]);
+
+        let mut query_tokens = Vec::new();
+        tokenizer.tokenize_query("runs cafe", |token| query_tokens.push(token.to_owned()));
+        assert_eq!(query_tokens, vec!["run", "cafe"]);
+
+        // Prefixes are not stemmed
+        let prefix_tokenizer = Tokenizer::new(TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            stopwords: None,
+            ascii_folding: None,
+            ..config
+        });
+        let mut prefix_tokens = Vec::new();
+        prefix_tokenizer.tokenize_doc("runs", |token| prefix_tokens.push(token.to_owned()));
+        assert_eq!(prefix_tokens, vec!["r", "ru", "run", "runs"]);
+    }
+
+    #[test]
+    fn test_fold_to_ascii() {
+        assert_eq!(fold_to_ascii("crème brûlée"), "creme brulee");
+        assert_eq!(fold_to_ascii("Straße"), "Strasse");
+        assert_eq!(fold_to_ascii("smørrebrød"), "smorrebrod");
+        assert_eq!(fold_to_ascii("мир"), "мир");
+    }
 }
@@ -0,0 +1,422 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use itertools::Itertools;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
+
+use super::GEO_QUERY_MAX_REGION;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::geo_hash::{
+    encode_max_precision, polygon_hashes, rectangle_hashes, GeoHash,
+};
+use crate::index::field_index::{
+    CardinalityEstimation, FieldIndexBuilderTrait, PayloadBlockCondition, PayloadFieldIndex,
+    PrimaryCondition, ValueIndexer,
+};
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{FieldCondition, GeoShape, GeoShapeCondition, PayloadKeyType};
+
+/// Max number of geo-hashes used to cover the bounding box of a single indexed shape
+const GEO_SHAPE_MAX_REGION: usize = 16;
+
+/// Payload index for geo shapes (polygons and multipolygons), persisted in a RocksDB column family
+///
+/// Each shape is indexed by the geo-hashes covering its bounding box.
+/// Queries select candidates from the cells overlapping the query geometry,
+/// and check the exact relation against the stored shapes.
+pub struct GeoShapeIndex {
+    point_to_shapes: Vec<Vec<GeoShape>>,
+    cells: BTreeMap<GeoHash, BTreeSet<PointOffsetType>>,
+    points_count: usize,
+    values_count: usize,
+    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+}
+
+impl GeoShapeIndex {
+    pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> GeoShapeIndex {
+        let store_cf_name = Self::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
+            db,
+            &store_cf_name,
+        ));
+        Self {
+            point_to_shapes: Vec::new(),
+            cells: BTreeMap::new(),
+            points_count: 0,
+            values_count: 0,
+            db_wrapper,
+        }
+    }
+
+    pub fn builder(db: Arc<RwLock<DB>>, field_name: &str) -> GeoShapeIndexBuilder {
+        GeoShapeIndexBuilder(Self::new(db, field_name))
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_geo_shape")
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        PayloadIndexTelemetry {
+            field_name: None,
+            points_count: self.points_count,
+            points_values_count: self.values_count,
+            histogram_bucket_size: None,
+        }
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        self.point_to_shapes
+            .get(point_id as usize)
+            .map_or(0, |shapes| shapes.len())
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        self.values_count(point_id) == 0
+    }
+
+    /// Geo-hashes covering the bounding box of the shape.
+    /// Falls back to the whole world if the shape can't be covered.
+    fn shape_hashes(shape: &GeoShape) -> Vec<GeoHash> {
+        shape
+            .bounding_box()
+            .and_then(|bounding_box| rectangle_hashes(&bounding_box, GEO_SHAPE_MAX_REGION).ok())
+            .unwrap_or_else(|| vec![GeoHash::default()])
+    }
+
+    fn insert_shapes(&mut self, id: PointOffsetType, shapes: Vec<GeoShape>) {
+        let hashes = shapes
+            .iter()
+            .flat_map(Self::shape_hashes)
+            .unique()
+            .collect_vec();
+        for hash in hashes {
+            self.cells.entry(hash).or_default().insert(id);
+        }
+
+        if self.point_to_shapes.len() <= id as usize {
+            self.point_to_shapes.resize_with(id as usize + 1, Vec::new);
+        }
+        self.points_count += 1;
+        self.values_count += shapes.len();
+        self.point_to_shapes[id as usize] = shapes;
+    }
+
+    /// Points with at least one shape covering a cell that overlaps any of the `hashes`
+    fn candidates(&self, hashes: &[GeoHash]) -> BTreeSet<PointOffsetType> {
+        let mut candidates = BTreeSet::new();
+        for hash in hashes {
+            // Cells containing the query cell
+            for len in 0..=hash.len() {
+                if let Some(points) = self.cells.get(&hash.truncate(len)) {
+                    candidates.extend(points);
+                }
+            }
+            // Cells contained in the query cell
+            for (_, points) in self
+                .cells
+                .range((Bound::Excluded(*hash), Bound::Unbounded))
+                .take_while(|(cell, _)| cell.starts_with(*hash))
+            {
+                candidates.extend(points);
+            }
+        }
+        candidates
+    }
+
+    fn condition_hashes(condition: &GeoShapeCondition) -> Option<Vec<GeoHash>> {
+        match condition {
+            GeoShapeCondition::Contains(point) => encode_max_precision(point.lon, point.lat)
+                .ok()
+                .map(|hash| vec![hash]),
+            GeoShapeCondition::Intersects(polygon) | GeoShapeCondition::Within(polygon) => {
+                polygon_hashes(polygon, GEO_QUERY_MAX_REGION).ok()
+            }
+        }
+    }
+
+    pub fn check_values_any(
+        &self,
+        point_id: PointOffsetType,
+        check_fn: impl Fn(&GeoShape) -> bool,
+    ) -> bool {
+        self.point_to_shapes
+            .get(point_id as usize)
+            .is_some_and(|shapes| shapes.iter().any(check_fn))
+    }
+}
+
+pub struct GeoShapeIndexBuilder(GeoShapeIndex);
+
+impl FieldIndexBuilderTrait for GeoShapeIndexBuilder {
+    type FieldIndexType = GeoShapeIndex;
+
+    fn init(&mut self) -> OperationResult<()> {
+        self.0.db_wrapper.recreate_column_family()
+    }
+
+    fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
+        self.0.add_point(id, payload)
+    }
+
+    fn finalize(self) -> OperationResult<Self::FieldIndexType> {
+        Ok(self.0)
+    }
+}
+
+impl PayloadFieldIndex for GeoShapeIndex {
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        let mut stored = Vec::new();
+        for (key, value) in self.db_wrapper.lock_db().iter()? {
+            let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
+            let shapes: Value = serde_json::from_slice(&value).map_err(|err| {
+                OperationError::service_error(format!("Failed to load geo shape index: {err}"))
+            })?;
+            stored.push((idx, Self::get_values(&shapes)));
+        }
+        for (idx, shapes) in stored {
+            self.insert_shapes(idx, shapes);
+        }
+        Ok(true)
+    }
+
+    fn cleanup(self) -> OperationResult<()> {
+        self.db_wrapper.remove_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        let geo_shape = condition.geo_shape.as_ref()?;
+        let hashes = Self::condition_hashes(geo_shape)?;
+        Some(Box::new(self.candidates(&hashes).into_iter().filter(
+            move |point_id| self.check_values_any(*point_id, |shape| geo_shape.check_shape(shape)),
+        )))
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        let geo_shape = condition.geo_shape.as_ref()?;
+        let hashes = Self::condition_hashes(geo_shape)?;
+        let candidates = self.candidates(&hashes).len();
+        Some(CardinalityEstimation {
+            primary_clauses: vec![PrimaryCondition::Condition(Box::new(condition.clone()))],
+            min: 0,
+            exp: candidates / 2,
+            max: candidates,
+        })
+    }
+
+    fn payload_blocks(
+        &self,
+        _threshold: usize,
+        _key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        // Shapes may span many cells, so there are no disjoint blocks to build HNSW for
+        Box::new(std::iter::empty())
+    }
+}
+
+impl ValueIndexer for GeoShapeIndex {
+    type ValueType = GeoShape;
+
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<GeoShape>) -> OperationResult<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let stored = Value::Array(values.iter().map(GeoShape::to_geojson).collect());
+        let stored = serde_json::to_vec(&stored).map_err(|err| {
+            OperationError::service_error(format!("Failed to store geo shape: {err}"))
+        })?;
+        self.db_wrapper.put(id.to_be_bytes(), stored)?;
+
+        self.insert_shapes(id, values);
+        Ok(())
+    }
+
+    fn get_value(value: &Value) -> Option<GeoShape> {
+        GeoShape::from_geojson(value)
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        let Some(shapes) = self.point_to_shapes.get_mut(id as usize) else {
+            return Ok(());
+        };
+        if shapes.is_empty() {
+            return Ok(());
+        }
+        let shapes = std::mem::take(shapes);
+
+        for hash in shapes.iter().flat_map(Self::shape_hashes).unique() {
+            if let Some(points) = self.cells.get_mut(&hash) {
+                points.remove(&id);
+                if points.is_empty() {
+                    self.cells.remove(&hash);
+                }
+            }
+        }
+        self.points_count -= 1;
+        self.values_count -= shapes.len();
+
+        self.db_wrapper.remove(id.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::json_path::JsonPath;
+    use crate::types::{GeoLineString, GeoPoint, GeoPolygon};
+
+    const FIELD_NAME: &str = "zone";
+
+    fn square(min_lon: f64, min_lat: f64, size: f64) -> GeoPolygon {
+        let points = [
+            (min_lon, min_lat),
+            (min_lon + size, min_lat),
+            (min_lon + size, min_lat + size),
+            (min_lon, min_lat + size),
+            (min_lon, min_lat),
+        ];
+        GeoPolygon {
+            exterior: GeoLineString {
+                points: points
+                    .into_iter()
+                    .map(|(lon, lat)| GeoPoint { lon, lat })
+                    .collect(),
+            },
+            interiors: None,
+        }
+    }
+
+    fn square_geojson(min_lon: f64, min_lat: f64, size: f64) -> Value {
+        json!({
+            "type": "Polygon",
+            "coordinates": [[
+                [min_lon, min_lat],
+                [min_lon + size, min_lat],
+                [min_lon + size, min_lat + size],
+                [min_lon, min_lat + size],
+                [min_lon, min_lat],
+            ]]
+        })
+    }
+
+    fn filter(index: &GeoShapeIndex, condition: GeoShapeCondition) -> Vec<PointOffsetType> {
+        let condition = FieldCondition::new_geo_shape(JsonPath::new(FIELD_NAME), condition);
+        index.filter(&condition).unwrap().collect()
+    }
+
+    #[test]
+    fn test_geo_shape_index() {
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let db = open_db_with_existing_cf(temp_dir.path()).unwrap();
+
+        let mut builder = GeoShapeIndex::builder(db.clone(), FIELD_NAME);
+        builder.init().unwrap();
+        // Small zone in Berlin
+        builder
+            .add_point(0, &[&square_geojson(13.3, 52.4, 0.2)])
+            .unwrap();
+        // Large zone covering Berlin
+        builder
+            .add_point(1, &[&square_geojson(12.0, 52.0, 2.0)])
+            .unwrap();
+        // Zones in Berlin and Paris
+        let multi_polygon = json!({
+            "type": "MultiPolygon",
+            "coordinates": [
+                square_geojson(13.0, 52.6, 0.1)["coordinates"].clone(),
+                square_geojson(2.2, 48.8, 0.2)["coordinates"].clone(),
+            ]
+        });
+        builder.add_point(2, &[&multi_polygon]).unwrap();
+        // Not a shape
+        builder
+            .add_point(3, &[&json!({"lon": 13.4, "lat": 52.5})])
+            .unwrap();
+        let mut index = builder.finalize().unwrap();
+
+        assert_eq!(index.count_indexed_points(), 3);
+
+        let berlin_center = GeoPoint {
+            lon: 13.4,
+            lat: 52.5,
+        };
+        assert_eq!(
+            filter(&index, GeoShapeCondition::Contains(berlin_center.clone())),
+            vec![0, 1],
+        );
+        assert_eq!(
+            filter(
+                &index,
+                GeoShapeCondition::Contains(GeoPoint {
+                    lon: 2.3,
+                    lat: 48.9
+                })
+            ),
+            vec![2],
+        );
+        assert_eq!(
+            filter(
+                &index,
+                GeoShapeCondition::Intersects(square(13.05, 52.55, 0.1))
+            ),
+            vec![1, 2],
+        );
+        assert_eq!(
+            filter(&index, GeoShapeCondition::Within(square(11.0, 51.0, 4.0))),
+            vec![0, 1],
+        );
+        assert_eq!(
+            filter(&index, GeoShapeCondition::Within(square(0.0, 45.0, 20.0))),
+            vec![0, 1, 2],
+        );
+
+        index.remove_point(0).unwrap();
+        assert_eq!(
+            filter(&index, GeoShapeCondition::Contains(berlin_center.clone())),
+            vec![1],
+        );
+        index.flusher()().unwrap();
+        drop(index);
+
+        let mut loaded = GeoShapeIndex::new(db, FIELD_NAME);
+        assert!(loaded.load().unwrap());
+        assert_eq!(loaded.count_indexed_points(), 2);
+        assert_eq!(
+            filter(&loaded, GeoShapeCondition::Contains(berlin_center)),
+            vec![1],
+        );
+    }
+}
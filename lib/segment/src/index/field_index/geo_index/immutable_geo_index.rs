@@ -84,6 +84,10 @@ impl ImmutableGeoMapIndex {
             .unwrap_or_default()
     }
 
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = &GeoPoint> + '_> {
+        self.point_to_values.get_values(idx)
+    }
+
     pub fn points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)> {
         self.counts_per_hash
             .iter()
@@ -255,6 +255,13 @@ impl MmapGeoMapIndex {
             .unwrap_or(0)
     }
 
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = GeoPoint> + '_> {
+        self.deleted
+            .get(idx as usize)
+            .filter(|b| !b)
+            .and_then(|_| self.point_to_values.get_values(idx))
+    }
+
     pub fn points_per_hash(&self) -> impl Iterator<Item = (GeoHash, usize)> + '_ {
         self.counts_per_hash
             .iter()
@@ -4,6 +4,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use geo::{Distance, Haversine, Point};
 use itertools::Itertools;
 use mutable_geo_index::InMemoryGeoMapIndex;
 use parking_lot::RwLock;
@@ -18,16 +19,17 @@ use super::FieldIndexBuilderTrait;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::Flusher;
 use crate::index::field_index::geo_hash::{
-    circle_hashes, common_hash_prefix, geo_hash_to_box, polygon_hashes, polygon_hashes_estimation,
-    rectangle_hashes, GeoHash,
+    circle_hashes, common_hash_prefix, encode_max_precision, geo_hash_to_box, polygon_hashes,
+    polygon_hashes_estimation, rectangle_hashes, GeoHash,
 };
 use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
 use crate::telemetry::PayloadIndexTelemetry;
-use crate::types::{FieldCondition, GeoPoint, PayloadKeyType};
+use crate::types::{FieldCondition, GeoPoint, GeoRadius, PayloadKeyType};
 
+pub mod geo_shape_index;
 pub mod immutable_geo_index;
 pub mod mmap_geo_index;
 pub mod mutable_geo_index;
@@ -36,6 +38,12 @@ pub mod mutable_geo_index;
 // TODO discuss value, should it be dynamically computed?
 const GEO_QUERY_MAX_REGION: usize = 12;
 
+/// Radius of the first circle searched when iterating points by distance, in meters
+const DISTANCE_SEARCH_INITIAL_RADIUS: f64 = 1_000.0;
+
+/// Radius which covers the whole Earth, in meters
+const DISTANCE_SEARCH_MAX_RADIUS: f64 = 20_100_000.0;
+
 pub enum GeoMapIndex {
     Mutable(MutableGeoMapIndex),
     Immutable(ImmutableGeoMapIndex),
@@ -198,6 +206,23 @@ impl GeoMapIndex {
         }
     }
 
+    pub fn get_values(
+        &self,
+        idx: PointOffsetType,
+    ) -> Option<Box<dyn Iterator<Item = GeoPoint> + '_>> {
+        match self {
+            GeoMapIndex::Mutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values.cloned()) as Box<dyn Iterator<Item = _>>),
+            GeoMapIndex::Immutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values.cloned()) as Box<dyn Iterator<Item = _>>),
+            GeoMapIndex::Mmap(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values) as Box<dyn Iterator<Item = _>>),
+        }
+    }
+
     pub fn match_cardinality(&self, values: &[GeoHash]) -> CardinalityEstimation {
         let max_values_per_point = self.max_values_per_point();
         if max_values_per_point == 0 {
@@ -317,9 +342,135 @@ impl GeoMapIndex {
         Box::new(edge_region.into_iter())
     }
 
+    /// Get iterator over geo-hash cells of length `precision` with the number of points in them
+    pub fn points_per_cell(
+        &self,
+        precision: usize,
+    ) -> Box<dyn Iterator<Item = (GeoHash, usize)> + '_> {
+        let filter_condition =
+            move |(hash, size): &(GeoHash, usize)| *size > 0 && hash.len() == precision;
+        match self {
+            GeoMapIndex::Mutable(index) => Box::new(
+                index
+                    .points_per_hash()
+                    .map(|(&hash, size)| (hash, size))
+                    .filter(filter_condition),
+            ),
+            GeoMapIndex::Immutable(index) => Box::new(
+                index
+                    .points_per_hash()
+                    .map(|(&hash, size)| (hash, size))
+                    .filter(filter_condition),
+            ),
+            GeoMapIndex::Mmap(index) => Box::new(index.points_per_hash().filter(filter_condition)),
+        }
+    }
+
+    /// Geo-hash cells of length `precision` containing values of the point.
+    ///
+    /// Each cell comes with the mean location of the point's values in it, so that a point with
+    /// many values in the same cell is only counted once.
+    pub fn point_cells(&self, idx: PointOffsetType, precision: usize) -> Vec<(GeoHash, GeoPoint)> {
+        let mut cells: Vec<(GeoHash, usize, GeoPoint)> = vec![];
+        for value in self.get_values(idx).into_iter().flatten() {
+            let Ok(hash) = encode_max_precision(value.lon, value.lat) else {
+                continue;
+            };
+            let cell = hash.truncate(precision.min(hash.len()));
+            match cells.iter_mut().find(|(hash, _, _)| *hash == cell) {
+                Some((_, count, sum)) => {
+                    *count += 1;
+                    sum.lon += value.lon;
+                    sum.lat += value.lat;
+                }
+                None => cells.push((cell, 1, value)),
+            }
+        }
+
+        cells
+            .into_iter()
+            .map(|(cell, count, sum)| {
+                let centroid = GeoPoint {
+                    lon: sum.lon / count as f64,
+                    lat: sum.lat / count as f64,
+                };
+                (cell, centroid)
+            })
+            .collect()
+    }
+
     pub fn values_is_empty(&self, idx: PointOffsetType) -> bool {
         self.values_count(idx) == 0
     }
+
+    /// Distances in meters from `origin` to each value of the point
+    pub fn get_distances(
+        &self,
+        idx: PointOffsetType,
+        origin: &GeoPoint,
+    ) -> impl Iterator<Item = f64> + '_ {
+        let origin = Point::new(origin.lon, origin.lat);
+        self.get_values(idx)
+            .into_iter()
+            .flatten()
+            .map(move |value| Haversine::distance(origin, Point::new(value.lon, value.lat)))
+    }
+
+    /// Iterate over values of points ordered by distance from `origin`, nearest first, skipping
+    /// those closer than `min_distance`.
+    ///
+    /// Points are read from the geo-hash cells of a circle around the origin, and the circle is
+    /// doubled until it covers the whole Earth. So only the cells around the origin are read to
+    /// find the nearest points.
+    pub fn iter_by_distance(
+        &self,
+        origin: GeoPoint,
+        min_distance: f64,
+    ) -> impl Iterator<Item = (f64, PointOffsetType)> + '_ {
+        let mut inner_radius = min_distance.max(0.0);
+        let mut is_last_circle = false;
+
+        std::iter::from_fn(move || {
+            if is_last_circle {
+                return None;
+            }
+
+            let outer_radius = (inner_radius * 2.0).max(DISTANCE_SEARCH_INITIAL_RADIUS);
+            let circle = GeoRadius {
+                center: origin.clone(),
+                radius: outer_radius,
+            };
+            let geo_hashes = match circle_hashes(&circle, GEO_QUERY_MAX_REGION) {
+                Ok(geo_hashes) if outer_radius < DISTANCE_SEARCH_MAX_RADIUS => geo_hashes,
+                // The circle covers the whole Earth, read all cells
+                _ => {
+                    is_last_circle = true;
+                    vec![GeoHash::default()]
+                }
+            };
+
+            // Only the ring between the previous circle and this one, the rest is already read
+            let mut ring = self
+                .iterator(geo_hashes)
+                .flat_map(|point| {
+                    self.get_distances(point, &origin)
+                        .filter(|distance| {
+                            *distance >= inner_radius
+                                && (is_last_circle || *distance < outer_radius)
+                        })
+                        .map(move |distance| (distance, point))
+                        .collect_vec()
+                })
+                .collect_vec();
+            ring.sort_unstable_by(|(distance_a, point_a), (distance_b, point_b)| {
+                distance_a.total_cmp(distance_b).then(point_a.cmp(point_b))
+            });
+
+            inner_radius = outer_radius;
+            Some(ring)
+        })
+        .flatten()
+    }
 }
 
 pub struct GeoMapIndexBuilder(GeoMapIndex);
@@ -1391,4 +1542,36 @@ mod tests {
         // Only LOS_ANGELES is in the bounding box
         assert_eq!(point_offsets, vec![2]);
     }
+
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_iter_by_distance(#[case] index_type: IndexType) {
+        let num_points = 1000;
+        let (field_index, _, _) = build_random_index(num_points, 3, index_type);
+
+        let mut expected = (0..num_points as PointOffsetType)
+            .flat_map(|point| {
+                field_index
+                    .get_distances(point, &BERLIN)
+                    .map(move |distance| (distance, point))
+                    .collect_vec()
+            })
+            .collect_vec();
+        expected.sort_by(|(distance_a, point_a), (distance_b, point_b)| {
+            distance_a.total_cmp(distance_b).then(point_a.cmp(point_b))
+        });
+
+        let by_distance = field_index.iter_by_distance(BERLIN, 0.0).collect_vec();
+        assert_eq!(by_distance, expected);
+
+        // Nearer values are skipped
+        let min_distance = expected[expected.len() / 2].0;
+        let by_distance = field_index
+            .iter_by_distance(BERLIN, min_distance)
+            .collect_vec();
+        expected.retain(|(distance, _)| *distance >= min_distance);
+        assert_eq!(by_distance, expected);
+    }
 }
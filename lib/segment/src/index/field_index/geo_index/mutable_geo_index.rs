@@ -171,6 +171,7 @@ impl MutableGeoMapIndex {
         to self.in_memory_index {
             pub fn check_values_any(&self, idx: PointOffsetType, check_fn: impl Fn(&GeoPoint) -> bool) -> bool;
             pub fn values_count(&self, idx: PointOffsetType) -> usize;
+            pub fn get_values(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = &GeoPoint> + '_>;
             pub fn points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)>;
             pub fn points_of_hash(&self, hash: &GeoHash) -> usize;
             pub fn values_of_hash(&self, hash: &GeoHash) -> usize;
@@ -219,6 +220,12 @@ impl InMemoryGeoMapIndex {
             .unwrap_or_default()
     }
 
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = &GeoPoint> + '_> {
+        self.point_to_values
+            .get(idx as usize)
+            .map(|values| values.iter())
+    }
+
     pub fn points_per_hash(&self) -> impl Iterator<Item = (&GeoHash, usize)> {
         self.points_per_hash
             .iter()
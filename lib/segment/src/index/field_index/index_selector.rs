@@ -5,6 +5,7 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::bool_index::simple_bool_index::BoolIndex;
+use super::geo_index::geo_shape_index::GeoShapeIndex;
 use super::geo_index::{GeoMapIndexBuilder, GeoMapIndexMmapBuilder};
 use super::histogram::Numericable;
 use super::map_index::{MapIndex, MapIndexBuilder, MapIndexKey, MapIndexMmapBuilder};
@@ -48,7 +49,10 @@ impl IndexSelector<'_> {
         payload_schema: &PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         Ok(match payload_schema.expand().as_ref() {
-            PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(self.map_new(field)?)],
+            PayloadSchemaParams::Keyword(keyword_params) => vec![FieldIndex::KeywordIndex(
+                self.map_new(field)?
+                    .with_normalizer(keyword_params.normalizer),
+            )],
             PayloadSchemaParams::Integer(integer_params) => itertools::chain(
                 integer_params
                     .lookup
@@ -81,6 +85,12 @@ impl IndexSelector<'_> {
             PayloadSchemaParams::Uuid(_) => {
                 vec![FieldIndex::UuidMapIndex(self.map_new(field)?)]
             }
+            PayloadSchemaParams::GeoShape(_) => {
+                vec![FieldIndex::GeoShapeIndex(GeoShapeIndex::new(
+                    self.as_rocksdb()?.db.clone(),
+                    &field.to_string(),
+                ))]
+            }
         })
     }
 
@@ -91,11 +101,14 @@ impl IndexSelector<'_> {
         payload_schema: &PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndexBuilder>> {
         Ok(match payload_schema.expand().as_ref() {
-            PayloadSchemaParams::Keyword(_) => {
+            PayloadSchemaParams::Keyword(keyword_params) => {
+                let normalizer = keyword_params.normalizer;
                 vec![self.map_builder(
                     field,
-                    FieldIndexBuilder::KeywordIndex,
-                    FieldIndexBuilder::KeywordMmapIndex,
+                    |builder| FieldIndexBuilder::KeywordIndex(builder.with_normalizer(normalizer)),
+                    |builder| {
+                        FieldIndexBuilder::KeywordMmapIndex(builder.with_normalizer(normalizer))
+                    },
                 )]
             }
             PayloadSchemaParams::Integer(integer_params) => itertools::chain(
@@ -152,6 +165,12 @@ impl IndexSelector<'_> {
                     FieldIndexBuilder::UuidMmapIndex,
                 )]
             }
+            PayloadSchemaParams::GeoShape(_) => {
+                vec![FieldIndexBuilder::GeoShapeIndex(GeoShapeIndex::builder(
+                    self.as_rocksdb()?.db.clone(),
+                    &field.to_string(),
+                ))]
+            }
         })
     }
 
@@ -169,8 +188,8 @@ impl IndexSelector<'_> {
     fn map_builder<N: MapIndexKey + ?Sized>(
         &self,
         field: &JsonPath,
-        make_rocksdb: fn(MapIndexBuilder<N>) -> FieldIndexBuilder,
-        make_mmap: fn(MapIndexMmapBuilder<N>) -> FieldIndexBuilder,
+        make_rocksdb: impl FnOnce(MapIndexBuilder<N>) -> FieldIndexBuilder,
+        make_mmap: impl FnOnce(MapIndexMmapBuilder<N>) -> FieldIndexBuilder,
     ) -> FieldIndexBuilder {
         match self {
             IndexSelector::RocksDb(IndexSelectorRocksDb { db, .. }) => {
@@ -1,7 +1,7 @@
 use std::borrow::Borrow as _;
 use std::collections::HashMap;
 use std::iter;
-use std::ops::Range;
+use std::ops::{Bound, Range};
 use std::sync::Arc;
 
 use bitvec::vec::BitVec;
@@ -10,14 +10,18 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::mutable_map_index::MutableMapIndex;
-use super::{IdIter, IdRefIter, MapIndex, MapIndexKey};
+use super::{is_below_start, is_within_end, IdIter, IdRefIter, MapIndex, MapIndexKey};
 use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::data_types::index::KeywordNormalizer;
 use crate::index::field_index::immutable_point_to_values::ImmutablePointToValues;
 
 pub struct ImmutableMapIndex<N: MapIndexKey + ?Sized> {
     value_to_points: HashMap<N::Owned, ContainerSegment>,
+    /// Unique values in ascending order, used to find ranges of values with binary search.
+    /// Values of removed points are not removed from here, they just have no points left.
+    sorted_values: Vec<N::Owned>,
     /// Container holding a slice of point IDs per value. `value_to_point` holds the range per value.
     /// Each slice MUST be sorted so that we can binary search over it.
     value_to_points_container: Vec<PointOffsetType>,
@@ -26,6 +30,7 @@ pub struct ImmutableMapIndex<N: MapIndexKey + ?Sized> {
     /// Amount of point which have at least one indexed payload value
     indexed_points: usize,
     values_count: usize,
+    pub(super) normalizer: Option<KeywordNormalizer>,
     db_wrapper: DatabaseColumnScheduledDeleteWrapper,
 }
 
@@ -45,11 +50,13 @@ impl<N: MapIndexKey + ?Sized> ImmutableMapIndex<N> {
         ));
         Self {
             value_to_points: Default::default(),
+            sorted_values: Default::default(),
             value_to_points_container: Default::default(),
             deleted_value_to_points_container: Default::default(),
             point_to_values: Default::default(),
             indexed_points: 0,
             values_count: 0,
+            normalizer: None,
             db_wrapper,
         }
     }
@@ -189,6 +196,7 @@ impl<N: MapIndexKey + ?Sized> ImmutableMapIndex<N> {
             point_to_values: Vec::new(),
             indexed_points: 0,
             values_count: 0,
+            normalizer: self.normalizer,
             db_wrapper: self.db_wrapper.clone(),
         };
         let result = mutable.load_from_db()?;
@@ -224,6 +232,10 @@ impl<N: MapIndexKey + ?Sized> ImmutableMapIndex<N> {
 
         self.value_to_points.shrink_to_fit();
 
+        self.sorted_values = self.value_to_points.keys().cloned().collect();
+        self.sorted_values
+            .sort_unstable_by(|a, b| N::cmp(a.borrow(), b.borrow()));
+
         // Sort IDs in each slice of points
         // This is very important because we binary search
         for value in self.value_to_points.keys() {
@@ -284,6 +296,24 @@ impl<N: MapIndexKey + ?Sized> ImmutableMapIndex<N> {
             .map(|(k, entry)| (k.borrow(), entry.count as usize))
     }
 
+    /// Iterate over values within the given bounds, in ascending order
+    pub fn iter_values_range(
+        &self,
+        start_bound: Bound<&N>,
+        end_bound: Bound<&N>,
+    ) -> impl DoubleEndedIterator<Item = &N> + '_ {
+        let start = self
+            .sorted_values
+            .partition_point(|value| is_below_start(value.borrow(), start_bound));
+        let end = self
+            .sorted_values
+            .partition_point(|value| is_within_end(value.borrow(), end_bound));
+
+        self.sorted_values[start..end.max(start)]
+            .iter()
+            .map(|value| value.borrow())
+    }
+
     pub fn iter_values_map(&self) -> impl Iterator<Item = (&N, IdIter<'_>)> + '_ {
         self.value_to_points.keys().map(|k| {
             (
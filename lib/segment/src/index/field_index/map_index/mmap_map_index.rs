@@ -1,7 +1,8 @@
 use std::borrow::Borrow;
-use std::fs::{create_dir_all, remove_dir};
+use std::fs::{create_dir_all, remove_dir, remove_dir_all, rename};
 use std::iter;
 use std::mem::size_of;
+use std::ops::Bound;
 use std::path::{Path, PathBuf};
 
 use ahash::HashMap;
@@ -15,23 +16,29 @@ use memory::mmap_ops::{self, create_and_ensure_length};
 use memory::mmap_type::MmapBitSlice;
 use serde::{Deserialize, Serialize};
 
-use super::{IdIter, MapIndexKey};
+use super::{is_below_start, is_within_end, IdIter, MapIndexKey};
 use crate::common::mmap_bitslice_buffered_update_wrapper::MmapBitSliceBufferedUpdateWrapper;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::index::KeywordNormalizer;
 use crate::index::field_index::mmap_point_to_values::MmapPointToValues;
 
 const DELETED_PATH: &str = "deleted.bin";
 const HASHMAP_PATH: &str = "values_to_points.bin";
 const CONFIG_PATH: &str = "mmap_field_index_config.json";
+const SORTED_VALUES_DIR: &str = "sorted_values";
+const SORTED_VALUES_TMP_DIR: &str = "sorted_values.tmp";
 
 pub struct MmapMapIndex<N: MapIndexKey + Key + ?Sized> {
     path: PathBuf,
     value_to_points: MmapHashMap<N, PointOffsetType>,
     point_to_values: MmapPointToValues<N>,
+    /// Dictionary of unique values in ascending order, value at rank `i` is stored under id `i`
+    sorted_values: MmapPointToValues<N>,
     deleted: MmapBitSliceBufferedUpdateWrapper,
     deleted_count: usize,
     total_key_value_pairs: usize,
+    pub(super) normalizer: Option<KeywordNormalizer>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +57,18 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
         let hashmap = MmapHashMap::open(&hashmap_path)?;
         let point_to_values = MmapPointToValues::open(path)?;
 
+        let sorted_values_path = path.join(SORTED_VALUES_DIR);
+        if !sorted_values_path.exists() {
+            // Indexes created before the sorted dictionary existed get it on the first load
+            let tmp_path = path.join(SORTED_VALUES_TMP_DIR);
+            if tmp_path.exists() {
+                remove_dir_all(&tmp_path)?;
+            }
+            Self::build_sorted_values(&tmp_path, hashmap.keys())?;
+            rename(&tmp_path, &sorted_values_path)?;
+        }
+        let sorted_values = MmapPointToValues::open(&sorted_values_path)?;
+
         let deleted = mmap_ops::open_write_mmap(&deleted_path, AdviceSetting::Global, false)?;
         let deleted = MmapBitSlice::from(deleted, 0);
         let deleted_count = deleted.count_ones();
@@ -58,9 +77,11 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
             path: path.to_path_buf(),
             value_to_points: hashmap,
             point_to_values,
+            sorted_values,
             deleted: MmapBitSliceBufferedUpdateWrapper::new(deleted),
             deleted_count,
             total_key_value_pairs: config.total_key_value_pairs,
+            normalizer: None,
         })
     }
 
@@ -99,6 +120,11 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
             }),
         )?;
 
+        Self::build_sorted_values(
+            &path.join(SORTED_VALUES_DIR),
+            values_to_points.keys().map(Borrow::<N>::borrow),
+        )?;
+
         {
             let deleted_flags_count = point_to_values.len();
             let deleted_file = create_and_ensure_length(
@@ -130,10 +156,29 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
         for file in files {
             std::fs::remove_file(file)?;
         }
+        let _ = remove_dir(path.join(SORTED_VALUES_DIR));
         let _ = remove_dir(path);
         Ok(())
     }
 
+    /// Save the unique values in ascending order, the value at rank `i` under id `i`
+    fn build_sorted_values<'a>(
+        path: &Path,
+        values: impl Iterator<Item = &'a N>,
+    ) -> OperationResult<()>
+    where
+        N: 'a,
+    {
+        create_dir_all(path)?;
+        MmapPointToValues::<N>::from_iter(
+            path,
+            values.sorted().enumerate().map(|(rank, value)| {
+                (rank as PointOffsetType, iter::once(N::as_referenced(value)))
+            }),
+        )?;
+        Ok(())
+    }
+
     pub fn files(&self) -> Vec<PathBuf> {
         let mut files = vec![
             self.path.join(HASHMAP_PATH),
@@ -141,6 +186,7 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
             self.path.join(CONFIG_PATH),
         ];
         files.extend(self.point_to_values.files());
+        files.extend(self.sorted_values.files());
         files
     }
 
@@ -235,6 +281,31 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
         Box::new(self.value_to_points.keys())
     }
 
+    /// Iterate over values within the given bounds.
+    ///
+    /// Uses binary search over the sorted dictionary, values are returned in ascending order.
+    pub fn iter_values_range(
+        &self,
+        start_bound: Bound<&N>,
+        end_bound: Bound<&N>,
+    ) -> Box<dyn DoubleEndedIterator<Item = N::Referenced<'_>> + '_> {
+        let sorted_values = &self.sorted_values;
+        let value_at = move |rank: usize| {
+            sorted_values
+                .get_values(rank as PointOffsetType)
+                .and_then(|mut values| values.next())
+        };
+        let start = partition_point(sorted_values.len(), |rank| {
+            value_at(rank)
+                .is_some_and(|value| is_below_start(N::from_referenced(&value), start_bound))
+        });
+        let end = partition_point(sorted_values.len(), |rank| {
+            value_at(rank).is_some_and(|value| is_within_end(N::from_referenced(&value), end_bound))
+        });
+
+        Box::new((start..end).filter_map(value_at))
+    }
+
     pub fn iter_counts_per_value(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
         self.value_to_points.iter().map(|(k, v)| {
             let count = v
@@ -252,3 +323,19 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
             .map(|(k, v)| (k, Box::new(v.iter().copied()) as IdIter))
     }
 }
+
+/// Binary search for the first rank in `0..len` for which `pred` does not hold.
+///
+/// `pred` must hold for a prefix of the ranks, like in [`slice::partition_point`].
+fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
@@ -1,7 +1,8 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::fmt::{Debug, Display};
 use std::hash::{BuildHasher, Hash};
 use std::iter;
+use std::ops::Bound;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -9,6 +10,7 @@ use std::sync::Arc;
 use ahash::HashMap;
 use common::mmap_hashmap::Key;
 use common::types::PointOffsetType;
+use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
 use itertools::Itertools;
 use mmap_map_index::MmapMapIndex;
@@ -24,15 +26,18 @@ use super::mmap_point_to_values::MmapValue;
 use super::FieldIndexBuilderTrait;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::Flusher;
+use crate::data_types::index::KeywordNormalizer;
+use crate::data_types::keyword_pattern::KeywordPattern;
 use crate::index::field_index::stat_tools::number_of_selected_points;
 use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
-use crate::index::query_estimator::combine_should_estimations;
+use crate::index::query_estimator::{combine_must_estimations, combine_should_estimations};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, UuidIntType, ValueVariants,
+    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAll, MatchAny, MatchExcept,
+    MatchPrefix, MatchValue, MatchWildcard, PayloadKeyType, Range, RangeInterface, UuidIntType,
+    ValueVariants,
 };
 
 pub mod immutable_map_index;
@@ -42,10 +47,15 @@ pub mod mutable_map_index;
 pub type IdRefIter<'a> = Box<dyn Iterator<Item = &'a PointOffsetType> + 'a>;
 pub type IdIter<'a> = Box<dyn Iterator<Item = PointOffsetType> + 'a>;
 
-pub trait MapIndexKey: Key + MmapValue + Eq + Display + Debug {
-    type Owned: Borrow<Self> + Hash + Eq + Clone + FromStr + Default;
+pub trait MapIndexKey: Key + MmapValue + Eq + Ord + Display + Debug {
+    type Owned: Borrow<Self> + Hash + Eq + Ord + Clone + FromStr + Default;
 
     fn to_owned(&self) -> Self::Owned;
+
+    /// Normalize a value before indexing it, only keywords are affected
+    fn normalize(value: Self::Owned, _normalizer: KeywordNormalizer) -> Self::Owned {
+        value
+    }
 }
 
 impl MapIndexKey for str {
@@ -54,6 +64,13 @@ impl MapIndexKey for str {
     fn to_owned(&self) -> Self::Owned {
         SmolStr::from(self)
     }
+
+    fn normalize(value: Self::Owned, normalizer: KeywordNormalizer) -> Self::Owned {
+        match normalizer.normalize(&value) {
+            Cow::Borrowed(_) => value,
+            Cow::Owned(normalized) => SmolStr::from(normalized),
+        }
+    }
 }
 
 impl MapIndexKey for IntPayloadType {
@@ -100,6 +117,25 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
             path: path.to_owned(),
             point_to_values: Default::default(),
             values_to_points: Default::default(),
+            normalizer: None,
+        }
+    }
+
+    /// Normalize keywords when indexing and when matching them
+    pub fn with_normalizer(mut self, normalizer: Option<KeywordNormalizer>) -> Self {
+        match &mut self {
+            MapIndex::Mutable(index) => index.normalizer = normalizer,
+            MapIndex::Immutable(index) => index.normalizer = normalizer,
+            MapIndex::Mmap(index) => index.normalizer = normalizer,
+        }
+        self
+    }
+
+    pub fn normalizer(&self) -> Option<KeywordNormalizer> {
+        match self {
+            MapIndex::Mutable(index) => index.normalizer,
+            MapIndex::Immutable(index) => index.normalizer,
+            MapIndex::Mmap(index) => index.normalizer,
         }
     }
 
@@ -175,7 +211,7 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
         }
     }
 
-    fn get_iterator(&self, value: &N) -> IdRefIter<'_> {
+    pub fn get_iterator(&self, value: &N) -> IdRefIter<'_> {
         match self {
             MapIndex::Mutable(index) => index.get_iterator(value),
             MapIndex::Immutable(index) => index.get_iterator(value),
@@ -191,6 +227,27 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
         }
     }
 
+    /// Iterate over indexed values within the given bounds, in ascending order
+    pub fn iter_values_range(
+        &self,
+        start_bound: Bound<&N>,
+        end_bound: Bound<&N>,
+    ) -> Box<dyn DoubleEndedIterator<Item = N::Referenced<'_>> + '_> {
+        match self {
+            MapIndex::Mutable(index) => Box::new(
+                index
+                    .iter_values_range(start_bound, end_bound)
+                    .map(N::as_referenced),
+            ),
+            MapIndex::Immutable(index) => Box::new(
+                index
+                    .iter_values_range(start_bound, end_bound)
+                    .map(N::as_referenced),
+            ),
+            MapIndex::Mmap(index) => index.iter_values_range(start_bound, end_bound),
+        }
+    }
+
     pub fn iter_counts_per_value(&self) -> Box<dyn Iterator<Item = (&N, usize)> + '_> {
         match self {
             MapIndex::Mutable(index) => Box::new(index.iter_counts_per_value()),
@@ -225,6 +282,39 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
         CardinalityEstimation::exact(values_count)
     }
 
+    /// Estimates cardinality of points having any of the given values
+    fn any_values_cardinality<'a>(
+        &'a self,
+        values: impl Iterator<Item = &'a N>,
+    ) -> CardinalityEstimation {
+        let estimations = values
+            .map(|value| self.match_cardinality(value))
+            .collect::<Vec<_>>();
+        if estimations.is_empty() {
+            CardinalityEstimation::exact(0)
+        } else {
+            combine_should_estimations(&estimations, self.get_indexed_points())
+        }
+    }
+
+    /// Estimates cardinality of points having all of the given values
+    fn all_values_cardinality<'b>(
+        &self,
+        values: impl Iterator<Item = &'b N>,
+    ) -> CardinalityEstimation
+    where
+        N: 'b,
+    {
+        let estimations = values
+            .map(|value| self.match_cardinality(value))
+            .collect::<Vec<_>>();
+        if estimations.is_empty() {
+            CardinalityEstimation::exact(0)
+        } else {
+            combine_must_estimations(&estimations, self.get_indexed_points())
+        }
+    }
+
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
@@ -410,10 +500,208 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
                 .unique(),
         )
     }
+
+    /// Points having all of the given values.
+    ///
+    /// The smallest posting list is intersected with the others by checking the values of each of
+    /// its points. An empty list of values matches nothing.
+    fn all_values_iterator<'a>(
+        &'a self,
+        values: Vec<N::Owned>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+        let Some(rarest) = values.iter().min_by_key(|value| {
+            self.get_count_for_value(Borrow::<N>::borrow(*value))
+                .unwrap_or(0)
+        }) else {
+            return Box::new(iter::empty());
+        };
+        let postings = self.get_iterator(Borrow::<N>::borrow(rarest)).copied();
+        Box::new(postings.filter(move |&point_id| {
+            values.iter().all(|value| {
+                self.check_values_any(point_id, |point_value| {
+                    point_value == Borrow::<N>::borrow(value)
+                })
+            })
+        }))
+    }
+}
+
+/// Whether the value lies before the start bound of a range
+fn is_below_start<N: Ord + ?Sized>(value: &N, start_bound: Bound<&N>) -> bool {
+    match start_bound {
+        Bound::Included(start) => value < start,
+        Bound::Excluded(start) => value <= start,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Whether the value does not exceed the end bound of a range
+fn is_within_end<N: Ord + ?Sized>(value: &N, end_bound: Bound<&N>) -> bool {
+    match end_bound {
+        Bound::Included(end) => value <= end,
+        Bound::Excluded(end) => value < end,
+        Bound::Unbounded => true,
+    }
+}
+
+impl MapIndex<str> {
+    /// Normalize a keyword from a query the same way as indexed keywords
+    pub fn normalize_keyword<'a>(&self, keyword: &'a str) -> Cow<'a, str> {
+        match self.normalizer() {
+            Some(normalizer) => normalizer.normalize(keyword),
+            None => Cow::Borrowed(keyword),
+        }
+    }
+
+    /// Normalize the keywords of a match condition the same way as indexed keywords.
+    /// Regexes are kept as is and matched against the normalized keywords.
+    pub fn normalize_match<'a>(&self, condition: &'a Match) -> Cow<'a, Match> {
+        if self.normalizer().is_none() {
+            return Cow::Borrowed(condition);
+        }
+
+        let normalize_set = |keywords: &IndexSet<String, FnvBuildHasher>| {
+            keywords
+                .iter()
+                .map(|keyword| self.normalize_keyword(keyword).into_owned())
+                .collect()
+        };
+
+        let normalized = match condition {
+            Match::Value(MatchValue {
+                value: ValueVariants::String(keyword),
+            }) => Match::new_value(ValueVariants::String(
+                self.normalize_keyword(keyword).into_owned(),
+            )),
+            Match::Any(MatchAny {
+                any: AnyVariants::Strings(keywords),
+            }) => Match::Any(MatchAny {
+                any: AnyVariants::Strings(normalize_set(keywords)),
+            }),
+            Match::All(MatchAll {
+                all: AnyVariants::Strings(keywords),
+            }) => Match::All(MatchAll {
+                all: AnyVariants::Strings(normalize_set(keywords)),
+            }),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Strings(keywords),
+            }) => Match::Except(MatchExcept {
+                except: AnyVariants::Strings(normalize_set(keywords)),
+            }),
+            Match::Prefix(MatchPrefix { prefix }) => Match::Prefix(MatchPrefix {
+                prefix: self.normalize_keyword(prefix).into_owned(),
+            }),
+            Match::Wildcard(MatchWildcard { wildcard }) => Match::Wildcard(MatchWildcard {
+                wildcard: self.normalize_keyword(wildcard).into_owned(),
+            }),
+            _ => return Cow::Borrowed(condition),
+        };
+
+        if &normalized == condition {
+            Cow::Borrowed(condition)
+        } else {
+            Cow::Owned(normalized)
+        }
+    }
+
+    /// Normalize the bounds of a keyword range the same way as indexed keywords
+    pub fn normalize_keyword_range<'a>(&self, range: &'a Range<String>) -> Cow<'a, Range<String>> {
+        if self.normalizer().is_none() {
+            return Cow::Borrowed(range);
+        }
+
+        let normalize = |bound: &Option<String>| {
+            bound
+                .as_ref()
+                .map(|keyword| self.normalize_keyword(keyword).into_owned())
+        };
+        let normalized = Range {
+            lt: normalize(&range.lt),
+            gt: normalize(&range.gt),
+            gte: normalize(&range.gte),
+            lte: normalize(&range.lte),
+        };
+
+        if &normalized == range {
+            Cow::Borrowed(range)
+        } else {
+            Cow::Owned(normalized)
+        }
+    }
+
+    /// Normalize the keywords of a condition, so it can be checked against normalized payload values
+    pub fn normalize_condition<'a>(
+        &self,
+        condition: &'a FieldCondition,
+    ) -> Cow<'a, FieldCondition> {
+        let r#match = condition
+            .r#match
+            .as_ref()
+            .map(|cond_match| self.normalize_match(cond_match));
+        let range = match &condition.range {
+            Some(RangeInterface::Keyword(range)) => {
+                Some(self.normalize_keyword_range(&range.keyword))
+            }
+            _ => None,
+        };
+
+        let match_changed = matches!(r#match, Some(Cow::Owned(_)));
+        let range_changed = matches!(range, Some(Cow::Owned(_)));
+        if !match_changed && !range_changed {
+            return Cow::Borrowed(condition);
+        }
+
+        let mut normalized = condition.clone();
+        if let Some(Cow::Owned(cond_match)) = r#match {
+            normalized.r#match = Some(cond_match);
+        }
+        if let Some(Cow::Owned(range)) = range {
+            normalized.range = Some(RangeInterface::from(range));
+        }
+        Cow::Owned(normalized)
+    }
+
+    /// Normalize the keywords of a payload value the same way as indexed keywords
+    pub fn normalize_payload_value<'a>(&self, value: &'a Value) -> Cow<'a, Value> {
+        match value {
+            Value::String(keyword) => match self.normalize_keyword(keyword) {
+                Cow::Borrowed(_) => Cow::Borrowed(value),
+                Cow::Owned(normalized) => Cow::Owned(Value::String(normalized)),
+            },
+            Value::Array(values) if self.normalizer().is_some() => Cow::Owned(Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.normalize_payload_value(value).into_owned())
+                    .collect(),
+            )),
+            _ => Cow::Borrowed(value),
+        }
+    }
+
+    /// Indexed keywords matching the pattern.
+    /// Keywords with a prefix are a range of the sorted dictionary, other patterns scan it.
+    fn iter_pattern_values(&self, pattern: KeywordPattern) -> Box<dyn Iterator<Item = &str> + '_> {
+        match pattern {
+            KeywordPattern::Prefix(prefix) => Box::new(
+                self.iter_values_range(Bound::Included(prefix.as_str()), Bound::Unbounded)
+                    .take_while(move |keyword| keyword.starts_with(prefix.as_str())),
+            ),
+            KeywordPattern::Regex(_) => Box::new(
+                self.iter_values()
+                    .filter(move |keyword| pattern.is_match(keyword)),
+            ),
+        }
+    }
 }
 
 pub struct MapIndexBuilder<N: MapIndexKey + ?Sized>(MapIndex<N>);
 
+impl<N: MapIndexKey + ?Sized> MapIndexBuilder<N> {
+    pub fn with_normalizer(self, normalizer: Option<KeywordNormalizer>) -> Self {
+        Self(self.0.with_normalizer(normalizer))
+    }
+}
+
 impl<N: MapIndexKey + ?Sized> FieldIndexBuilderTrait for MapIndexBuilder<N>
 where
     MapIndex<N>: PayloadFieldIndex + ValueIndexer,
@@ -441,6 +729,14 @@ pub struct MapIndexMmapBuilder<N: MapIndexKey + ?Sized> {
     path: PathBuf,
     point_to_values: Vec<Vec<N::Owned>>,
     values_to_points: HashMap<N::Owned, Vec<PointOffsetType>>,
+    normalizer: Option<KeywordNormalizer>,
+}
+
+impl<N: MapIndexKey + ?Sized> MapIndexMmapBuilder<N> {
+    pub fn with_normalizer(mut self, normalizer: Option<KeywordNormalizer>) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
 }
 
 impl<N: MapIndexKey + ?Sized> FieldIndexBuilderTrait for MapIndexMmapBuilder<N>
@@ -460,7 +756,13 @@ where
             let payload_values = <MapIndex<N> as ValueIndexer>::get_values(value);
             flatten_values.extend(payload_values);
         }
-        let flatten_values: Vec<N::Owned> = flatten_values.into_iter().map(Into::into).collect();
+        let flatten_values: Vec<N::Owned> = flatten_values
+            .into_iter()
+            .map(|value| match self.normalizer {
+                Some(normalizer) => N::normalize(value.into(), normalizer),
+                None => value.into(),
+            })
+            .collect();
 
         if self.point_to_values.len() <= id as usize {
             self.point_to_values.resize_with(id as usize + 1, Vec::new);
@@ -474,11 +776,12 @@ where
     }
 
     fn finalize(self) -> OperationResult<Self::FieldIndexType> {
-        Ok(MapIndex::Mmap(Box::new(MmapMapIndex::build(
+        let index = MapIndex::Mmap(Box::new(MmapMapIndex::build(
             &self.path,
             self.point_to_values,
             self.values_to_points,
-        )?)))
+        )?));
+        Ok(index.with_normalizer(self.normalizer))
     }
 }
 
@@ -507,11 +810,21 @@ impl PayloadFieldIndex for MapIndex<str> {
         &'a self,
         condition: &'a FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        if let Some(RangeInterface::Keyword(range)) = &condition.range {
+            let range = self.normalize_keyword_range(&range.keyword);
+            let (start_bound, end_bound) = range.as_bounds();
+            return Some(Box::new(
+                self.iter_values_range(start_bound, end_bound)
+                    .flat_map(|keyword| self.get_iterator(keyword).copied())
+                    .unique(),
+            ));
+        }
+
         match &condition.r#match {
             Some(Match::Value(MatchValue { value })) => match value {
-                ValueVariants::String(keyword) => {
-                    Some(Box::new(self.get_iterator(keyword.as_str()).copied()))
-                }
+                ValueVariants::String(keyword) => Some(Box::new(
+                    self.get_iterator(&self.normalize_keyword(keyword)).copied(),
+                )),
                 ValueVariants::Integer(_) => None,
                 ValueVariants::Bool(_) => None,
             },
@@ -519,7 +832,9 @@ impl PayloadFieldIndex for MapIndex<str> {
                 AnyVariants::Strings(keywords) => Some(Box::new(
                     keywords
                         .iter()
-                        .flat_map(|keyword| self.get_iterator(keyword.as_str()).copied())
+                        .flat_map(|keyword| {
+                            self.get_iterator(&self.normalize_keyword(keyword)).copied()
+                        })
                         .unique(),
                 )),
                 AnyVariants::Integers(integers) => {
@@ -530,7 +845,36 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(Match::All(MatchAll { all })) => match all {
+                AnyVariants::Strings(keywords) => Some(
+                    self.all_values_iterator(
+                        keywords
+                            .iter()
+                            .map(|keyword| SmolStr::from(self.normalize_keyword(keyword).as_ref()))
+                            .collect(),
+                    ),
+                ),
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Some(Box::new(iter::empty()))
+                    } else {
+                        None
+                    }
+                }
+            },
             Some(Match::Except(MatchExcept { except })) => match except {
+                AnyVariants::Strings(keywords) if self.normalizer().is_some() => {
+                    let excluded: IndexSet<_> = keywords
+                        .iter()
+                        .map(|keyword| self.normalize_keyword(keyword).into_owned())
+                        .collect();
+                    let exclude_iter = self
+                        .iter_values()
+                        .filter(move |key| !excluded.contains(*key))
+                        .flat_map(|key| self.get_iterator(key).copied())
+                        .unique();
+                    Some(Box::new(exclude_iter))
+                }
                 AnyVariants::Strings(keywords) => Some(self.except_set(keywords)),
                 AnyVariants::Integers(other) => {
                     if other.is_empty() {
@@ -540,15 +884,35 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(cond_match @ (Match::Prefix(_) | Match::Wildcard(_) | Match::Regex(_))) => {
+                let pattern =
+                    KeywordPattern::from_match(&self.normalize_match(cond_match))?.ok()?;
+                Some(Box::new(
+                    self.iter_pattern_values(pattern)
+                        .flat_map(|keyword| self.get_iterator(keyword).copied())
+                        .unique(),
+                ))
+            }
             _ => None,
         }
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        if let Some(RangeInterface::Keyword(range)) = &condition.range {
+            let range = self.normalize_keyword_range(&range.keyword);
+            let (start_bound, end_bound) = range.as_bounds();
+            let estimation =
+                self.any_values_cardinality(self.iter_values_range(start_bound, end_bound));
+            return Some(
+                estimation
+                    .with_primary_clause(PrimaryCondition::Condition(Box::new(condition.clone()))),
+            );
+        }
+
         match &condition.r#match {
             Some(Match::Value(MatchValue { value })) => match value {
                 ValueVariants::String(keyword) => {
-                    let mut estimation = self.match_cardinality(keyword.as_str());
+                    let mut estimation = self.match_cardinality(&self.normalize_keyword(keyword));
                     estimation
                         .primary_clauses
                         .push(PrimaryCondition::Condition(Box::new(condition.clone())));
@@ -561,7 +925,9 @@ impl PayloadFieldIndex for MapIndex<str> {
                 AnyVariants::Strings(keywords) => {
                     let estimations = keywords
                         .iter()
-                        .map(|keyword| self.match_cardinality(keyword.as_str()))
+                        .map(|keyword| self.normalize_keyword(keyword))
+                        .unique()
+                        .map(|keyword| self.match_cardinality(&keyword))
                         .collect::<Vec<_>>();
                     let estimation = if estimations.is_empty() {
                         CardinalityEstimation::exact(0)
@@ -584,9 +950,39 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(Match::All(MatchAll { all })) => match all {
+                AnyVariants::Strings(keywords) => {
+                    let keywords: Vec<_> = keywords
+                        .iter()
+                        .map(|keyword| self.normalize_keyword(keyword))
+                        .unique()
+                        .collect();
+                    let estimation =
+                        self.all_values_cardinality(keywords.iter().map(|k| k.as_ref()));
+                    Some(
+                        estimation.with_primary_clause(PrimaryCondition::Condition(Box::new(
+                            condition.clone(),
+                        ))),
+                    )
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Some(CardinalityEstimation::exact(0).with_primary_clause(
+                            PrimaryCondition::Condition(Box::new(condition.clone())),
+                        ))
+                    } else {
+                        None
+                    }
+                }
+            },
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(keywords) => {
-                    Some(self.except_cardinality(keywords.iter().map(|k| k.as_str())))
+                    let excluded: Vec<_> = keywords
+                        .iter()
+                        .map(|keyword| self.normalize_keyword(keyword))
+                        .unique()
+                        .collect();
+                    Some(self.except_cardinality(excluded.iter().map(|k| k.as_ref())))
                 }
                 AnyVariants::Integers(others) => {
                     if others.is_empty() {
@@ -598,6 +994,17 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(cond_match @ (Match::Prefix(_) | Match::Wildcard(_) | Match::Regex(_))) => {
+                // Matching values are found in the dictionary, their points are combined like `any`
+                let pattern =
+                    KeywordPattern::from_match(&self.normalize_match(cond_match))?.ok()?;
+                let estimation = self.any_values_cardinality(self.iter_pattern_values(pattern));
+                Some(
+                    estimation.with_primary_clause(PrimaryCondition::Condition(Box::new(
+                        condition.clone(),
+                    ))),
+                )
+            }
             _ => None,
         }
     }
@@ -677,6 +1084,23 @@ impl PayloadFieldIndex for MapIndex<UuidIntType> {
                     }
                 }
             },
+            Some(Match::All(MatchAll { all })) => match all {
+                AnyVariants::Strings(uuids_string) => {
+                    let uuids: Result<Vec<u128>, _> = uuids_string
+                        .iter()
+                        .map(|uuid_string| Uuid::from_str(uuid_string).map(|x| x.as_u128()))
+                        .collect();
+
+                    Some(self.all_values_iterator(uuids.ok()?))
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Some(Box::new(iter::empty()))
+                    } else {
+                        None
+                    }
+                }
+            },
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(uuids_string) => {
                     let uuids: Result<IndexSet<u128>, _> = uuids_string
@@ -752,6 +1176,30 @@ impl PayloadFieldIndex for MapIndex<UuidIntType> {
                     }
                 }
             },
+            Some(Match::All(MatchAll { all })) => match all {
+                AnyVariants::Strings(uuids_string) => {
+                    let uuids: Result<IndexSet<u128>, _> = uuids_string
+                        .iter()
+                        .map(|uuid_string| Uuid::from_str(uuid_string).map(|x| x.as_u128()))
+                        .collect();
+
+                    let estimation = self.all_values_cardinality(uuids.ok()?.iter());
+                    Some(
+                        estimation.with_primary_clause(PrimaryCondition::Condition(Box::new(
+                            condition.clone(),
+                        ))),
+                    )
+                }
+                AnyVariants::Integers(integers) => {
+                    if integers.is_empty() {
+                        Some(CardinalityEstimation::exact(0).with_primary_clause(
+                            PrimaryCondition::Condition(Box::new(condition.clone())),
+                        ))
+                    } else {
+                        None
+                    }
+                }
+            },
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(uuids_string) => {
                     let uuids: Result<IndexSet<u128>, _> = uuids_string
@@ -845,6 +1293,18 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
                         .unique(),
                 )),
             },
+            Some(Match::All(MatchAll { all })) => match all {
+                AnyVariants::Strings(keywords) => {
+                    if keywords.is_empty() {
+                        Some(Box::new(iter::empty()))
+                    } else {
+                        None
+                    }
+                }
+                AnyVariants::Integers(integers) => {
+                    Some(self.all_values_iterator(integers.iter().copied().collect()))
+                }
+            },
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(other) => {
                     if other.is_empty() {
@@ -899,6 +1359,23 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
                     )
                 }
             },
+            Some(Match::All(MatchAll { all })) => match all {
+                AnyVariants::Strings(keywords) => {
+                    if keywords.is_empty() {
+                        Some(CardinalityEstimation::exact(0).with_primary_clause(
+                            PrimaryCondition::Condition(Box::new(condition.clone())),
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                AnyVariants::Integers(integers) => Some(
+                    self.all_values_cardinality(integers.iter())
+                        .with_primary_clause(PrimaryCondition::Condition(Box::new(
+                            condition.clone(),
+                        ))),
+                ),
+            },
             Some(Match::Except(MatchExcept { except })) => match except {
                 AnyVariants::Strings(others) => {
                     if others.is_empty() {
@@ -1024,10 +1501,14 @@ mod tests {
     use std::path::Path;
 
     use rstest::rstest;
+    use serde_json::json;
     use tempfile::Builder;
 
     use super::*;
     use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::json_path::JsonPath;
+    use crate::payload_storage::condition_checker::ValueChecker;
+    use crate::types::Range;
 
     const FIELD_NAME: &str = "test";
 
@@ -1207,4 +1688,347 @@ mod tests {
             .except_cardinality(vec![].into_iter())
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
+
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_keyword_pattern_match(#[case] index_type: IndexType) {
+        let data = vec![
+            vec![SmolStr::from("us-east-1"), SmolStr::from("us-west-2")],
+            vec![SmolStr::from("us-east-2")],
+            vec![SmolStr::from("eu-west-1")],
+            vec![SmolStr::from("ap-south-1"), SmolStr::from("us-east-1")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        let filter = |r#match: Match| {
+            let condition = FieldCondition::new_match(JsonPath::new(FIELD_NAME), r#match);
+            let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+            points.sort_unstable();
+
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert!(estimation.min <= points.len() && points.len() <= estimation.max);
+            assert_eq!(estimation.primary_clauses.len(), 1);
+
+            points
+        };
+
+        assert_eq!(filter(Match::new_prefix("us-east-")), vec![0, 1, 3]);
+        assert_eq!(filter(Match::new_prefix("ap-")), vec![3]);
+        assert_eq!(
+            filter(Match::new_prefix("sa-")),
+            Vec::<PointOffsetType>::new()
+        );
+
+        assert_eq!(filter(Match::new_wildcard("*-west-?")), vec![0, 2]);
+        assert_eq!(filter(Match::new_wildcard("us-*-2")), vec![0, 1]);
+
+        assert_eq!(filter(Match::new_regex("(eu|ap)-.*")), vec![2, 3]);
+        assert_eq!(
+            filter(Match::new_regex("east")),
+            Vec::<PointOffsetType>::new()
+        );
+    }
+
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_keyword_range(#[case] index_type: IndexType) {
+        let data = vec![
+            vec![SmolStr::from("US-CA"), SmolStr::from("US-WA")],
+            vec![SmolStr::from("US-NY")],
+            vec![SmolStr::from("DE-BE")],
+            vec![SmolStr::from("FR-IDF"), SmolStr::from("US-CA")],
+            vec![SmolStr::from("US")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        let filter = |range: Range<String>| {
+            let condition = FieldCondition::new_keyword_range(JsonPath::new(FIELD_NAME), range);
+            let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+            points.sort_unstable();
+
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert!(estimation.min <= points.len() && points.len() <= estimation.max);
+            assert_eq!(estimation.primary_clauses.len(), 1);
+
+            points
+        };
+
+        let range =
+            |gt: Option<&str>, gte: Option<&str>, lt: Option<&str>, lte: Option<&str>| Range {
+                lt: lt.map(String::from),
+                gt: gt.map(String::from),
+                gte: gte.map(String::from),
+                lte: lte.map(String::from),
+            };
+
+        assert_eq!(filter(range(None, Some("US-"), None, None)), vec![0, 1, 3]);
+        assert_eq!(
+            filter(range(None, Some("US-CA"), Some("US-NY"), None)),
+            vec![0, 3]
+        );
+        assert_eq!(
+            filter(range(None, Some("US-CA"), None, Some("US-NY"))),
+            vec![0, 1, 3]
+        );
+        assert_eq!(filter(range(Some("US-CA"), None, None, None)), vec![0, 1]);
+        assert_eq!(filter(range(None, None, Some("FR"), None)), vec![2]);
+        assert_eq!(
+            filter(range(None, Some("DE"), Some("US-"), None)),
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            filter(range(Some("US-WA"), None, None, None)),
+            Vec::<PointOffsetType>::new()
+        );
+        assert_eq!(
+            filter(range(None, Some("US-NY"), Some("US-CA"), None)),
+            Vec::<PointOffsetType>::new()
+        );
+        assert_eq!(
+            filter(range(Some("US-CA"), None, Some("US-CA"), None)),
+            Vec::<PointOffsetType>::new()
+        );
+        assert_eq!(
+            filter(range(None, Some("US-CA"), None, Some("US-CA"))),
+            vec![0, 3]
+        );
+    }
+
+    #[test]
+    fn test_mutable_removed_values() {
+        let data = vec![
+            vec![SmolStr::from("US-CA"), SmolStr::from("US-WA")],
+            vec![SmolStr::from("US-NY")],
+            vec![SmolStr::from("US-CA")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), IndexType::Mutable, |v| {
+            v.to_string().into()
+        });
+        let mut index = load_map_index::<str>(&data, temp_dir.path(), IndexType::Mutable);
+
+        index.remove_point(0).unwrap();
+        index.remove_point(1).unwrap();
+
+        // Values without points are not visited by range scans anymore
+        let values: Vec<_> = index
+            .iter_values_range(Bound::Unbounded, Bound::Unbounded)
+            .collect();
+        assert_eq!(values, vec!["US-CA"]);
+        assert_eq!(index.get_unique_values_count(), 1);
+    }
+
+    #[test]
+    fn test_mmap_sorted_values_created_on_load() {
+        let data = vec![
+            vec![SmolStr::from("b"), SmolStr::from("d")],
+            vec![SmolStr::from("a")],
+            vec![SmolStr::from("c")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), IndexType::Mmap, |v| {
+            v.to_string().into()
+        });
+
+        // Indexes created before the sorted dictionary existed don't have it
+        let sorted_values_path = temp_dir.path().join("sorted_values");
+        std::fs::remove_dir_all(&sorted_values_path).unwrap();
+
+        let index = load_map_index::<str>(&data, temp_dir.path(), IndexType::Mmap);
+        assert!(sorted_values_path.exists());
+
+        let values: Vec<_> = index
+            .iter_values_range(Bound::Excluded("a"), Bound::Included("c"))
+            .collect();
+        assert_eq!(values, vec!["b", "c"]);
+    }
+
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_keyword_normalizer(#[case] index_type: IndexType) {
+        let normalizer = Some(KeywordNormalizer::LowercaseNfkc);
+        let data = vec![
+            vec![json!("US-CA"), json!("us-wa")],
+            vec![json!("Ｕｓ-ＮＹ")],
+            vec![json!("us-ca")],
+            vec![json!("DE-BE")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        let path = temp_dir.path();
+        match index_type {
+            IndexType::Mutable | IndexType::Immutable => {
+                let mut builder =
+                    MapIndex::<str>::builder(open_db_with_existing_cf(path).unwrap(), FIELD_NAME)
+                        .with_normalizer(normalizer);
+                builder.init().unwrap();
+                for (idx, values) in data.iter().enumerate() {
+                    let values: Vec<_> = values.iter().collect();
+                    builder.add_point(idx as PointOffsetType, &values).unwrap();
+                }
+                builder.finalize().unwrap();
+            }
+            IndexType::Mmap => {
+                let mut builder = MapIndex::<str>::mmap_builder(path).with_normalizer(normalizer);
+                builder.init().unwrap();
+                for (idx, values) in data.iter().enumerate() {
+                    let values: Vec<_> = values.iter().collect();
+                    builder.add_point(idx as PointOffsetType, &values).unwrap();
+                }
+                builder.finalize().unwrap();
+            }
+        }
+
+        let mut index = match index_type {
+            IndexType::Mutable => MapIndex::<str>::new_memory(
+                open_db_with_existing_cf(path).unwrap(),
+                FIELD_NAME,
+                true,
+            ),
+            IndexType::Immutable => MapIndex::<str>::new_memory(
+                open_db_with_existing_cf(path).unwrap(),
+                FIELD_NAME,
+                false,
+            ),
+            IndexType::Mmap => MapIndex::<str>::new_mmap(path).unwrap(),
+        }
+        .with_normalizer(normalizer);
+        index.load_from_db().unwrap();
+
+        let values: Vec<_> = index.get_values(1).unwrap().collect();
+        assert_eq!(values, vec!["us-ny"]);
+
+        let counts: Vec<_> = index
+            .iter_counts_per_value()
+            .map(|(value, count)| (value.to_string(), count))
+            .sorted()
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("de-be".to_string(), 1),
+                ("us-ca".to_string(), 2),
+                ("us-ny".to_string(), 1),
+                ("us-wa".to_string(), 1),
+            ]
+        );
+
+        let filter = |r#match: Match| {
+            let condition = FieldCondition::new_match(JsonPath::new(FIELD_NAME), r#match);
+            let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+            points.sort_unstable();
+
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert!(estimation.min <= points.len() && points.len() <= estimation.max);
+
+            points
+        };
+
+        assert_eq!(filter("US-CA".to_string().into()), vec![0, 2]);
+        assert_eq!(filter("us-ny".to_string().into()), vec![1]);
+        assert_eq!(
+            filter(vec!["Us-Wa".to_string(), "ＤＥ-be".to_string()].into()),
+            vec![0, 3]
+        );
+        assert_eq!(
+            filter(Match::Except(MatchExcept::from(vec![
+                "us-CA".to_string(),
+                "US-NY".to_string(),
+            ]))),
+            vec![0, 3]
+        );
+        assert_eq!(filter(Match::new_prefix("US-")), vec![0, 1, 2]);
+        assert_eq!(filter(Match::new_wildcard("US-?A")), vec![0, 2]);
+
+        let range_condition = FieldCondition::new_keyword_range(
+            JsonPath::new(FIELD_NAME),
+            Range {
+                gte: Some("US-N".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut points: Vec<_> = index.filter(&range_condition).unwrap().collect();
+        points.sort_unstable();
+        assert_eq!(points, vec![0, 1]);
+
+        // Payload keeps the original keywords, the checks normalize both sides
+        let check = |condition: &FieldCondition, payload: Value| {
+            index
+                .normalize_condition(condition)
+                .check(&index.normalize_payload_value(&payload))
+        };
+        let condition =
+            FieldCondition::new_match(JsonPath::new(FIELD_NAME), "us-ca".to_string().into());
+        assert!(check(&condition, json!("US-CA")));
+        assert!(check(&condition, json!(["DE-BE", "Us-Ca"])));
+        assert!(!check(&condition, json!("US-NY")));
+        let condition = FieldCondition::new_match(
+            JsonPath::new(FIELD_NAME),
+            Match::All(MatchAll::from(vec![
+                "US-ca".to_string(),
+                "us-WA".to_string(),
+            ])),
+        );
+        assert!(check(&condition, json!(["US-CA", "us-wa"])));
+        assert!(!check(&condition, json!(["US-CA"])));
+        assert!(check(&range_condition, json!("Ｕｓ-ＮＹ")));
+        assert!(!check(&range_condition, json!("DE-BE")));
+    }
+
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_match_all(#[case] index_type: IndexType) {
+        let data = vec![
+            vec![SmolStr::from("red"), SmolStr::from("green")],
+            vec![
+                SmolStr::from("red"),
+                SmolStr::from("green"),
+                SmolStr::from("blue"),
+            ],
+            vec![SmolStr::from("green")],
+            vec![],
+            vec![SmolStr::from("blue"), SmolStr::from("red")],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<str>(&data, temp_dir.path(), index_type, |v| v.to_string().into());
+        let index = load_map_index::<str>(&data, temp_dir.path(), index_type);
+
+        let filter = |values: &[&str]| {
+            let values: Vec<_> = values.iter().map(|value| value.to_string()).collect();
+            let condition = FieldCondition::new_match(
+                JsonPath::new(FIELD_NAME),
+                Match::All(MatchAll::from(values)),
+            );
+            let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+            points.sort_unstable();
+
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert!(estimation.min <= points.len() && points.len() <= estimation.max);
+
+            points
+        };
+
+        assert_eq!(filter(&["red"]), vec![0, 1, 4]);
+        assert_eq!(filter(&["red", "green"]), vec![0, 1]);
+        assert_eq!(filter(&["blue", "red", "green"]), vec![1]);
+        assert_eq!(filter(&["red", "yellow"]), Vec::<PointOffsetType>::new());
+        assert_eq!(filter(&[]), Vec::<PointOffsetType>::new());
+    }
 }
@@ -1,6 +1,8 @@
 use std::borrow::Borrow;
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeSet, HashMap};
 use std::iter;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
@@ -11,13 +13,17 @@ use super::{IdIter, IdRefIter, MapIndex, MapIndexKey};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::data_types::index::KeywordNormalizer;
 
 pub struct MutableMapIndex<N: MapIndexKey + ?Sized> {
     pub(super) map: HashMap<N::Owned, BTreeSet<PointOffsetType>>,
+    /// Keys of `map` in ascending order, for iterating over ranges of values
+    pub(super) sorted_values: BTreeSet<N::Owned>,
     pub(super) point_to_values: Vec<Vec<N::Owned>>,
     /// Amount of point which have at least one indexed payload value
     pub(super) indexed_points: usize,
     pub(super) values_count: usize,
+    pub(super) normalizer: Option<KeywordNormalizer>,
     pub(super) db_wrapper: DatabaseColumnScheduledDeleteWrapper,
 }
 
@@ -30,9 +36,11 @@ impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
         ));
         Self {
             map: Default::default(),
+            sorted_values: BTreeSet::new(),
             point_to_values: Vec::new(),
             indexed_points: 0,
             values_count: 0,
+            normalizer: None,
             db_wrapper,
         }
     }
@@ -56,7 +64,14 @@ impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
 
         self.point_to_values[idx as usize] = Vec::with_capacity(values.len());
         for value in values {
-            let entry = self.map.entry(value.into());
+            let value = match self.normalizer {
+                Some(normalizer) => N::normalize(value.into(), normalizer),
+                None => value.into(),
+            };
+            let entry = self.map.entry(value);
+            if let Entry::Vacant(_) = &entry {
+                self.sorted_values.insert(entry.key().clone());
+            }
             self.point_to_values[idx as usize].push(entry.key().clone());
             let db_record = MapIndex::encode_db_record(entry.key().borrow(), idx);
             entry.or_default().insert(idx);
@@ -81,6 +96,11 @@ impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
         for value in &removed_values {
             if let Some(vals) = self.map.get_mut(value.borrow()) {
                 vals.remove(&idx);
+                // Drop values without points, so that range and prefix scans don't visit them
+                if vals.is_empty() {
+                    self.map.remove(value.borrow());
+                    self.sorted_values.remove(value.borrow());
+                }
             }
             let key = MapIndex::encode_db_record(value.borrow(), idx);
             self.db_wrapper.remove(key)?;
@@ -115,6 +135,9 @@ impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
             self.values_count += 1;
 
             point_values.push(value.clone());
+            if !self.map.contains_key(&value) {
+                self.sorted_values.insert(value.clone());
+            }
             self.map.entry(value).or_default().insert(idx);
         }
         Ok(true)
@@ -160,6 +183,38 @@ impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
         self.map.iter().map(|(k, v)| (k.borrow(), v.len()))
     }
 
+    /// Iterate over values within the given bounds, in ascending order
+    pub fn iter_values_range(
+        &self,
+        start_bound: Bound<&N>,
+        end_bound: Bound<&N>,
+    ) -> impl DoubleEndedIterator<Item = &N> + '_ {
+        // An empty range would make `BTreeSet::range` panic
+        let is_empty = match (start_bound, end_bound) {
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => {
+                let is_inclusive = matches!(
+                    (start_bound, end_bound),
+                    (Bound::Included(_), Bound::Included(_))
+                );
+                start > end || (start == end && !is_inclusive)
+            }
+            _ => false,
+        };
+        let values = if is_empty {
+            None
+        } else {
+            Some(
+                self.sorted_values
+                    .range::<N, _>((start_bound, end_bound))
+                    .map(Borrow::<N>::borrow),
+            )
+        };
+        values.into_iter().flatten()
+    }
+
     pub fn iter_values_map(&self) -> impl Iterator<Item = (&N, IdIter<'_>)> + '_ {
         self.map
             .iter()
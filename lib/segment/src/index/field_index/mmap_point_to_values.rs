@@ -102,6 +102,30 @@ impl MmapValue for UuidIntType {
     }
 }
 
+impl MmapValue for u32 {
+    type Referenced<'a> = Self;
+
+    fn mmapped_size(_value: Self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    fn read_from_mmap(bytes: &[u8]) -> Option<Self> {
+        Self::read_from_prefix(bytes)
+    }
+
+    fn write_to_mmap(value: Self, bytes: &mut [u8]) -> Option<()> {
+        value.write_to_prefix(bytes)
+    }
+
+    fn from_referenced<'a>(value: &'a Self::Referenced<'_>) -> &'a Self {
+        value
+    }
+
+    fn as_referenced(&self) -> Self::Referenced<'_> {
+        *self
+    }
+}
+
 impl MmapValue for GeoPoint {
     type Referenced<'a> = Self;
 
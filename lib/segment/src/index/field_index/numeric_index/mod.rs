@@ -287,6 +287,8 @@ impl<T: Encodable + Numericable + MmapValue + Default> NumericIndexInner<T> {
             RangeInterface::DateTime(datetime_range) => {
                 datetime_range.map(|dt| T::from_u128(dt.timestamp() as u128))
             }
+            // Numbers never match a keyword range
+            RangeInterface::Keyword(_) => return CardinalityEstimation::exact(0),
         };
 
         let lbound = if let Some(lte) = range.lte {
@@ -648,6 +650,7 @@ impl<T: Encodable + Numericable + MmapValue + Default> PayloadFieldIndex for Num
             RangeInterface::DateTime(datetime_range) => {
                 datetime_range.map(|dt| T::from_u128(dt.timestamp() as u128))
             }
+            RangeInterface::Keyword(_) => return Some(Box::new(std::iter::empty())),
         }
         .as_index_key_bounds();
 
@@ -929,7 +932,21 @@ where
             RangeInterface::DateTime(datetime_range) => {
                 datetime_range.map(|dt| T::from_u128(dt.timestamp() as u128))
             }
+            RangeInterface::Keyword(_) => return Box::new(std::iter::empty()),
         };
+        self.stream_values_range(&range)
+    }
+}
+
+impl<T> NumericIndexInner<T>
+where
+    T: Encodable + Numericable + MmapValue + Default,
+{
+    /// Stream values within the range in ascending order, along with their points
+    pub fn stream_values_range(
+        &self,
+        range: &Range<T>,
+    ) -> Box<dyn DoubleEndedIterator<Item = (T, PointOffsetType)> + '_> {
         let (start_bound, end_bound) = range.as_index_key_bounds();
 
         // map.range
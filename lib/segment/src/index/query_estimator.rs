@@ -284,6 +284,7 @@ mod tests {
             geo_radius: None,
             values_count: None,
             geo_polygon: None,
+            geo_shape: None,
         })
     }
 
@@ -291,6 +292,7 @@ mod tests {
         match condition {
             Condition::Filter(_) => panic!("unexpected Filter"),
             Condition::Nested(_) => panic!("unexpected Nested"),
+            Condition::Join(_) => panic!("unexpected Join"),
             Condition::CustomIdChecker(_) => panic!("unexpected CustomIdChecker"),
             Condition::Field(field) => match field.key.to_string().as_str() {
                 "color" => CardinalityEstimation {
@@ -14,7 +14,8 @@ use crate::payload_storage::query_checker::{
 };
 use crate::types::{
     Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPolygon,
-    GeoRadius, IntPayloadType, OwnedPayloadRef, PayloadContainer, Range, RangeInterface,
+    GeoRadius, GeoShapeCondition, IntPayloadType, OwnedPayloadRef, PayloadContainer, Range,
+    RangeInterface,
 };
 use crate::vector_storage::VectorStorage;
 
@@ -144,6 +145,8 @@ impl StructPayloadIndex {
 
                 Box::new(move |internal_id| segment_ids.contains(&internal_id))
             }
+            // Joins are resolved into conditions on the key before reaching the segments
+            Condition::Join(_) => unreachable!("unresolved join condition"),
             Condition::Filter(_) => unreachable!(),
         }
     }
@@ -178,6 +181,11 @@ pub fn field_condition_index<'a>(
             ..
         } => get_geo_polygon_checkers(index, geo_polygon.clone()),
 
+        FieldCondition {
+            geo_shape: Some(geo_shape),
+            ..
+        } => get_geo_shape_checkers(index, geo_shape.clone()),
+
         FieldCondition {
             key: _,
             r#match: None,
@@ -185,6 +193,7 @@ pub fn field_condition_index<'a>(
             geo_radius: None,
             geo_bounding_box: None,
             geo_polygon: None,
+            geo_shape: None,
             // We can't use index for this condition, since some indices don't count values,
             // like boolean index, where [true, true, true] is the same as [true]. Count should be 3 but they think is 1.
             //
@@ -211,6 +220,30 @@ pub fn get_geo_polygon_checkers(
         | FieldIndex::IntMapIndex(_)
         | FieldIndex::KeywordIndex(_)
         | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
+    }
+}
+
+pub fn get_geo_shape_checkers(
+    index: &FieldIndex,
+    geo_shape: GeoShapeCondition,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::GeoShapeIndex(geo_shape_index) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                geo_shape_index.check_values_any(point_id, |shape| geo_shape.check_shape(shape))
+            }))
+        }
+        FieldIndex::BoolIndex(_)
+        | FieldIndex::DatetimeIndex(_)
+        | FieldIndex::FloatIndex(_)
+        | FieldIndex::FullTextIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::IntIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::KeywordIndex(_)
+        | FieldIndex::UuidIndex(_)
         | FieldIndex::UuidMapIndex(_) => None,
     }
 }
@@ -231,7 +264,8 @@ pub fn get_geo_radius_checkers(
         | FieldIndex::IntMapIndex(_)
         | FieldIndex::KeywordIndex(_)
         | FieldIndex::UuidIndex(_)
-        | FieldIndex::UuidMapIndex(_) => None,
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
     }
 }
 
@@ -251,7 +285,8 @@ pub fn get_geo_bounding_box_checkers(
         | FieldIndex::IntMapIndex(_)
         | FieldIndex::KeywordIndex(_)
         | FieldIndex::UuidIndex(_)
-        | FieldIndex::UuidMapIndex(_) => None,
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
     }
 }
 
@@ -259,6 +294,7 @@ pub fn get_range_checkers(index: &FieldIndex, range: RangeInterface) -> Option<C
     match range {
         RangeInterface::Float(range) => get_float_range_checkers(index, range),
         RangeInterface::DateTime(range) => get_datetime_range_checkers(index, range),
+        RangeInterface::Keyword(range) => get_keyword_range_checkers(index, range.keyword),
     }
 }
 
@@ -283,7 +319,8 @@ pub fn get_float_range_checkers(
         | FieldIndex::IntMapIndex(_)
         | FieldIndex::KeywordIndex(_)
         | FieldIndex::UuidIndex(_)
-        | FieldIndex::UuidMapIndex(_) => None,
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
     }
 }
 
@@ -306,7 +343,32 @@ pub fn get_datetime_range_checkers(
         | FieldIndex::IntMapIndex(_)
         | FieldIndex::KeywordIndex(_)
         | FieldIndex::UuidIndex(_)
-        | FieldIndex::UuidMapIndex(_) => None,
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
+    }
+}
+
+pub fn get_keyword_range_checkers(
+    index: &FieldIndex,
+    range: Range<String>,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::KeywordIndex(keyword_index) => {
+            let range = keyword_index.normalize_keyword_range(&range).into_owned();
+            Some(Box::new(move |point_id: PointOffsetType| {
+                keyword_index.check_values_any(point_id, |value| range.check_keyword(value))
+            }))
+        }
+        FieldIndex::BoolIndex(_)
+        | FieldIndex::DatetimeIndex(_)
+        | FieldIndex::FloatIndex(_)
+        | FieldIndex::FullTextIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::IntIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
     }
 }
 
@@ -1,19 +1,33 @@
+use std::cell::RefCell;
+
 use common::types::PointOffsetType;
+use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
 use uuid::Uuid;
 
+use crate::data_types::keyword_pattern::KeywordPattern;
+use crate::index::field_index::map_index::MapIndex;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::payload_storage::condition_checker::INDEXSET_ITER_THRESHOLD;
 use crate::types::{
-    AnyVariants, Match, MatchAny, MatchExcept, MatchText, MatchValue, ValueVariants,
+    AnyVariants, Match, MatchAll, MatchAny, MatchExcept, MatchFuzzy, MatchPhrase, MatchText,
+    MatchValue, ValueVariants,
 };
 
 pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<ConditionCheckerFn> {
     match cond_match {
         Match::Value(MatchValue { value }) => get_match_value_checker(value, index),
         Match::Text(MatchText { text }) => get_match_text_checker(text, index),
+        Match::Phrase(MatchPhrase { phrase, slop }) => {
+            get_match_phrase_checker(phrase, slop.unwrap_or(0), index)
+        }
+        Match::Fuzzy(fuzzy_match) => get_match_fuzzy_checker(fuzzy_match, index),
+        Match::Prefix(_) | Match::Wildcard(_) | Match::Regex(_) => {
+            get_match_pattern_checker(&cond_match, index)
+        }
         Match::Any(MatchAny { any }) => get_match_any_checker(any, index),
+        Match::All(MatchAll { all }) => get_match_all_checker(all, index),
         Match::Except(MatchExcept { except }) => get_match_except_checker(except, index),
     }
 }
@@ -24,6 +38,7 @@ fn get_match_value_checker(
 ) -> Option<ConditionCheckerFn> {
     match (value_variant, index) {
         (ValueVariants::String(keyword), FieldIndex::KeywordIndex(index)) => {
+            let keyword = index.normalize_keyword(&keyword).into_owned();
             Some(Box::new(move |point_id: PointOffsetType| {
                 index.check_values_any(point_id, |k| k == keyword)
             }))
@@ -73,7 +88,8 @@ fn get_match_value_checker(
         | (ValueVariants::String(_), FieldIndex::GeoIndex(_))
         | (ValueVariants::String(_), FieldIndex::IntIndex(_))
         | (ValueVariants::String(_), FieldIndex::IntMapIndex(_))
-        | (ValueVariants::String(_), FieldIndex::UuidIndex(_)) => None,
+        | (ValueVariants::String(_), FieldIndex::UuidIndex(_))
+        | (_, FieldIndex::GeoShapeIndex(_)) => None,
     }
 }
 
@@ -83,6 +99,7 @@ fn get_match_any_checker(
 ) -> Option<ConditionCheckerFn> {
     match (any_variant, index) {
         (AnyVariants::Strings(list), FieldIndex::KeywordIndex(index)) => {
+            let list = normalize_keywords(index, list);
             if list.len() < INDEXSET_ITER_THRESHOLD {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.check_values_any(point_id, |value| {
@@ -138,13 +155,85 @@ fn get_match_any_checker(
         | (AnyVariants::Strings(_), FieldIndex::GeoIndex(_))
         | (AnyVariants::Strings(_), FieldIndex::IntIndex(_))
         | (AnyVariants::Strings(_), FieldIndex::IntMapIndex(_))
-        | (AnyVariants::Strings(_), FieldIndex::UuidIndex(_)) => None,
+        | (AnyVariants::Strings(_), FieldIndex::UuidIndex(_))
+        | (_, FieldIndex::GeoShapeIndex(_)) => None,
+    }
+}
+
+fn get_match_all_checker(
+    all_variant: AnyVariants,
+    index: &FieldIndex,
+) -> Option<ConditionCheckerFn> {
+    match (all_variant, index) {
+        // Empty list of values matches nothing
+        (AnyVariants::Strings(list), FieldIndex::KeywordIndex(index)) => {
+            let list = normalize_keywords(index, list);
+            Some(Box::new(move |point_id: PointOffsetType| {
+                !list.is_empty()
+                    && list.iter().all(|keyword| {
+                        index.check_values_any(point_id, |value| keyword.as_str() == value)
+                    })
+            }))
+        }
+        (AnyVariants::Strings(list), FieldIndex::UuidMapIndex(index)) => {
+            let list = list
+                .iter()
+                .map(|s| Uuid::parse_str(s).map(|uuid| uuid.as_u128()).ok())
+                .collect::<Option<IndexSet<_>>>()?;
+            Some(Box::new(move |point_id: PointOffsetType| {
+                !list.is_empty()
+                    && list
+                        .iter()
+                        .all(|uuid| index.check_values_any(point_id, |value| value == uuid))
+            }))
+        }
+        (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                !list.is_empty()
+                    && list
+                        .iter()
+                        .all(|integer| index.check_values_any(point_id, |value| value == integer))
+            }))
+        }
+        (AnyVariants::Integers(_), FieldIndex::BoolIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::DatetimeIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::FloatIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::FullTextIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::GeoIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::IntIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::KeywordIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::UuidIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::UuidMapIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::BoolIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::DatetimeIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::FloatIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::FullTextIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::GeoIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::IntIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::IntMapIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::UuidIndex(_))
+        | (_, FieldIndex::GeoShapeIndex(_)) => None,
+    }
+}
+
+/// Normalize keywords from a query the same way as keywords in the index
+fn normalize_keywords(
+    index: &MapIndex<str>,
+    keywords: IndexSet<String, FnvBuildHasher>,
+) -> IndexSet<String, FnvBuildHasher> {
+    if index.normalizer().is_none() {
+        return keywords;
     }
+    keywords
+        .iter()
+        .map(|keyword| index.normalize_keyword(keyword).into_owned())
+        .collect()
 }
 
 fn get_match_except_checker(except: AnyVariants, index: &FieldIndex) -> Option<ConditionCheckerFn> {
     let checker: Option<ConditionCheckerFn> = match (except, index) {
         (AnyVariants::Strings(list), FieldIndex::KeywordIndex(index)) => {
+            let list = normalize_keywords(index, list);
             if list.len() < INDEXSET_ITER_THRESHOLD {
                 Some(Box::new(move |point_id: PointOffsetType| {
                     index.check_values_any(point_id, |value| {
@@ -200,7 +289,8 @@ fn get_match_except_checker(except: AnyVariants, index: &FieldIndex) -> Option<C
         | (AnyVariants::Integers(_), FieldIndex::FullTextIndex(_))
         | (AnyVariants::Integers(_), FieldIndex::BoolIndex(_))
         | (AnyVariants::Integers(_), FieldIndex::UuidIndex(_))
-        | (AnyVariants::Integers(_), FieldIndex::UuidMapIndex(_)) => None,
+        | (AnyVariants::Integers(_), FieldIndex::UuidMapIndex(_))
+        | (_, FieldIndex::GeoShapeIndex(_)) => None,
     };
 
     if checker.is_none() {
@@ -213,8 +303,87 @@ fn get_match_except_checker(except: AnyVariants, index: &FieldIndex) -> Option<C
     checker
 }
 
+fn get_match_phrase_checker(
+    phrase: String,
+    slop: usize,
+    index: &FieldIndex,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::FullTextIndex(full_text_index) if full_text_index.has_positions() => {
+            let parsed_phrase = full_text_index.parse_phrase(&phrase, slop);
+            // Reused for all the checked points, mmap indexes decode the positions into it
+            let positions_buffer = RefCell::new(Vec::new());
+            Some(Box::new(move |point_id: PointOffsetType| {
+                full_text_index.check_phrase(
+                    &parsed_phrase,
+                    point_id,
+                    &mut positions_buffer.borrow_mut(),
+                )
+            }))
+        }
+        FieldIndex::BoolIndex(_)
+        | FieldIndex::DatetimeIndex(_)
+        | FieldIndex::FloatIndex(_)
+        | FieldIndex::FullTextIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::IntIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::KeywordIndex(_)
+        | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
+    }
+}
+
+fn get_match_fuzzy_checker(
+    fuzzy_match: MatchFuzzy,
+    index: &FieldIndex,
+) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::FullTextIndex(full_text_index) => {
+            let parsed_fuzzy = full_text_index.parse_fuzzy(&fuzzy_match);
+            Some(Box::new(move |point_id: PointOffsetType| {
+                full_text_index.check_fuzzy(&parsed_fuzzy, point_id)
+            }))
+        }
+        FieldIndex::BoolIndex(_)
+        | FieldIndex::DatetimeIndex(_)
+        | FieldIndex::FloatIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::IntIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::KeywordIndex(_)
+        | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
+    }
+}
+
+fn get_match_pattern_checker(cond_match: &Match, index: &FieldIndex) -> Option<ConditionCheckerFn> {
+    match index {
+        FieldIndex::KeywordIndex(index) => {
+            let pattern = KeywordPattern::from_match(&index.normalize_match(cond_match))?.ok()?;
+            Some(Box::new(move |point_id: PointOffsetType| {
+                index.check_values_any(point_id, |keyword| pattern.is_match(keyword))
+            }))
+        }
+        FieldIndex::BoolIndex(_)
+        | FieldIndex::DatetimeIndex(_)
+        | FieldIndex::FloatIndex(_)
+        | FieldIndex::FullTextIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::IntIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
+    }
+}
+
 fn get_match_text_checker(text: String, index: &FieldIndex) -> Option<ConditionCheckerFn> {
     match index {
+        // N-grams only give candidates, the substring is verified against the payload
+        FieldIndex::FullTextIndex(full_text_index) if full_text_index.is_ngram() => None,
         FieldIndex::FullTextIndex(full_text_index) => {
             let parsed_query = full_text_index.parse_query(&text);
             Some(Box::new(move |point_id: PointOffsetType| {
@@ -229,6 +398,7 @@ fn get_match_text_checker(text: String, index: &FieldIndex) -> Option<ConditionC
         | FieldIndex::IntMapIndex(_)
         | FieldIndex::KeywordIndex(_)
         | FieldIndex::UuidIndex(_)
-        | FieldIndex::UuidMapIndex(_) => None,
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
     }
 }
@@ -2,3 +2,4 @@ pub mod condition_converter;
 pub mod optimized_filter;
 pub mod optimizer;
 pub mod payload_provider;
+pub mod rescore_formula;
@@ -0,0 +1,508 @@
+use std::collections::HashMap;
+
+use ahash::AHashMap;
+use common::types::{PointOffsetType, ScoreType};
+use geo::{Distance, Haversine, Point};
+use serde_json::Value;
+
+use super::parsed_formula::{
+    DatetimeExpression, ParsedExpression, ParsedFormula, PreciseScore, VariableId,
+};
+use super::value_retriever::{
+    value_to_geo_point, value_to_timestamp, DatetimeRetrieverFn, GeoRetrieverFn,
+    VariableRetrieverFn,
+};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::index::struct_filter_context::StructFilterContext;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::json_path::JsonPath;
+use crate::payload_storage::FilterContext;
+
+/// Score to use for a point which was not found in the referenced prefetch, if there is no default for it
+const DEFAULT_SCORE: PreciseScore = 0.0;
+
+/// Datetimes are evaluated as seconds since UNIX epoch, while stored as microseconds
+const MICROS_PER_SECOND: PreciseScore = 1_000_000.0;
+
+/// Evaluates a [`ParsedFormula`] for the points of a single segment
+pub struct FormulaScorer<'a> {
+    /// The formula to evaluate
+    formula: &'a ParsedExpression,
+    /// One hashmap for each prefetch results
+    prefetches_scores: &'a [AHashMap<PointOffsetType, ScoreType>],
+    /// Retrievers for the payload variables used in the formula
+    payload_retrievers: HashMap<JsonPath, VariableRetrieverFn<'a>>,
+    /// Retrievers for the payload keys used as datetimes
+    datetime_retrievers: HashMap<JsonPath, DatetimeRetrieverFn<'a>>,
+    /// Retrievers for the payload keys used as geo points
+    geo_retrievers: HashMap<JsonPath, GeoRetrieverFn<'a>>,
+    /// Checkers for the conditions used in the formula, in the same order as in the formula
+    condition_checkers: Vec<StructFilterContext<'a>>,
+    /// Values to use when a variable is missing
+    defaults: &'a HashMap<VariableId, Value>,
+}
+
+impl StructPayloadIndex {
+    pub fn formula_scorer<'a>(
+        &'a self,
+        parsed_formula: &'a ParsedFormula,
+        prefetches_scores: &'a [AHashMap<PointOffsetType, ScoreType>],
+    ) -> FormulaScorer<'a> {
+        let ParsedFormula {
+            payload_vars,
+            datetime_vars,
+            geo_vars,
+            conditions,
+            defaults,
+            formula,
+        } = parsed_formula;
+
+        let payload_retrievers = payload_vars
+            .iter()
+            .map(|key| (key.clone(), self.variable_retriever(key)))
+            .collect();
+
+        let datetime_retrievers = datetime_vars
+            .iter()
+            .map(|key| (key.clone(), self.datetime_retriever(key)))
+            .collect();
+
+        let geo_retrievers = geo_vars
+            .iter()
+            .map(|key| (key.clone(), self.geo_retriever(key)))
+            .collect();
+
+        let condition_checkers = conditions
+            .iter()
+            .map(|filter| self.struct_filtered_context(filter))
+            .collect();
+
+        FormulaScorer {
+            formula,
+            prefetches_scores,
+            payload_retrievers,
+            datetime_retrievers,
+            geo_retrievers,
+            condition_checkers,
+            defaults,
+        }
+    }
+}
+
+impl FormulaScorer<'_> {
+    /// Computes the score of the formula for the given point
+    pub fn score(&self, point_id: PointOffsetType) -> OperationResult<ScoreType> {
+        let score = self.eval_expression(self.formula, point_id)?;
+        let score = score as ScoreType;
+
+        if !score.is_finite() {
+            return Err(OperationError::NonFiniteNumber {
+                expression: format!("{score} as f32"),
+            });
+        }
+
+        Ok(score)
+    }
+
+    fn eval_expression(
+        &self,
+        expression: &ParsedExpression,
+        point_id: PointOffsetType,
+    ) -> OperationResult<PreciseScore> {
+        match expression {
+            ParsedExpression::Constant(constant) => Ok(*constant),
+            ParsedExpression::Variable(variable) => self.eval_variable(variable, point_id),
+            ParsedExpression::Mult(expressions) => {
+                let mut product = 1.0;
+                for expression in expressions {
+                    let value = self.eval_expression(expression, point_id)?;
+                    // Avoid evaluating the rest of the expressions if the product is already zero
+                    if value == 0.0 {
+                        return Ok(0.0);
+                    }
+                    product *= value;
+                }
+                Ok(product)
+            }
+            ParsedExpression::Sum(expressions) => {
+                expressions.iter().try_fold(0.0, |sum, expression| {
+                    Ok(sum + self.eval_expression(expression, point_id)?)
+                })
+            }
+            ParsedExpression::Neg(expression) => Ok(-self.eval_expression(expression, point_id)?),
+            ParsedExpression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => {
+                let left = self.eval_expression(left, point_id)?;
+                if left == 0.0 {
+                    return Ok(0.0);
+                }
+
+                let right = self.eval_expression(right, point_id)?;
+                if right == 0.0 {
+                    return by_zero_default.ok_or_else(|| OperationError::NonFiniteNumber {
+                        expression: format!("{left}/{right}"),
+                    });
+                }
+
+                check_finite(left / right, || format!("{left}/{right}"))
+            }
+            ParsedExpression::Sqrt(expression) => {
+                let value = self.eval_expression(expression, point_id)?;
+                check_finite(value.sqrt(), || format!("√{value}"))
+            }
+            ParsedExpression::Pow { base, exponent } => {
+                let base = self.eval_expression(base, point_id)?;
+                let exponent = self.eval_expression(exponent, point_id)?;
+                check_finite(base.powf(exponent), || format!("{base}^{exponent}"))
+            }
+            ParsedExpression::Exp(expression) => {
+                let value = self.eval_expression(expression, point_id)?;
+                check_finite(value.exp(), || format!("exp({value})"))
+            }
+            ParsedExpression::Log10(expression) => {
+                let value = self.eval_expression(expression, point_id)?;
+                check_finite(value.log10(), || format!("log10({value})"))
+            }
+            ParsedExpression::Ln(expression) => {
+                let value = self.eval_expression(expression, point_id)?;
+                check_finite(value.ln(), || format!("ln({value})"))
+            }
+            ParsedExpression::Abs(expression) => {
+                Ok(self.eval_expression(expression, point_id)?.abs())
+            }
+            ParsedExpression::Datetime(DatetimeExpression::Constant(datetime)) => {
+                Ok(datetime.timestamp() as PreciseScore / MICROS_PER_SECOND)
+            }
+            ParsedExpression::Datetime(DatetimeExpression::PayloadVariable(key)) => {
+                let timestamp = self
+                    .datetime_retrievers
+                    .get(key)
+                    .and_then(|retriever| retriever(point_id));
+
+                let timestamp = match timestamp {
+                    Some(timestamp) => timestamp,
+                    None => self
+                        .default_for(key)
+                        .and_then(value_to_timestamp)
+                        .ok_or_else(|| missing_value_error(key, "datetime"))?,
+                };
+
+                Ok(timestamp as PreciseScore / MICROS_PER_SECOND)
+            }
+            ParsedExpression::GeoDistance { origin, key } => {
+                let point = self
+                    .geo_retrievers
+                    .get(key)
+                    .and_then(|retriever| retriever(point_id));
+
+                let point = match point {
+                    Some(point) => point,
+                    None => self
+                        .default_for(key)
+                        .cloned()
+                        .and_then(value_to_geo_point)
+                        .ok_or_else(|| missing_value_error(key, "geo point"))?,
+                };
+
+                Ok(Haversine::distance(
+                    Point::new(origin.lon, origin.lat),
+                    Point::new(point.lon, point.lat),
+                ))
+            }
+            ParsedExpression::Decay {
+                kind,
+                x,
+                origin,
+                params,
+            } => {
+                let x = self.eval_expression(x, point_id)?;
+                let origin = match origin {
+                    Some(origin) => self.eval_expression(origin, point_id)?,
+                    None => 0.0,
+                };
+                check_finite(kind.decay(x - origin, *params), || {
+                    format!("{kind:?} decay of {x} from {origin}")
+                })
+            }
+        }
+    }
+
+    fn default_for(&self, key: &JsonPath) -> Option<&Value> {
+        self.defaults.get(&VariableId::Payload(key.clone()))
+    }
+
+    fn eval_variable(
+        &self,
+        variable: &VariableId,
+        point_id: PointOffsetType,
+    ) -> OperationResult<PreciseScore> {
+        match variable {
+            VariableId::Score(prefetch_idx) => {
+                let score = self
+                    .prefetches_scores
+                    .get(*prefetch_idx)
+                    .and_then(|scores| scores.get(&point_id));
+
+                match score {
+                    Some(score) => Ok(PreciseScore::from(*score)),
+                    None => match self.defaults.get(variable) {
+                        Some(value) => value_to_number(variable, value),
+                        None => Ok(DEFAULT_SCORE),
+                    },
+                }
+            }
+            VariableId::Payload(path) => {
+                let value = self
+                    .payload_retrievers
+                    .get(path)
+                    .and_then(|retriever| retriever(point_id));
+
+                match value {
+                    Some(value) => value_to_number(variable, &value),
+                    None => {
+                        let value = self.defaults.get(variable).ok_or_else(|| {
+                            OperationError::VariableTypeError {
+                                field_name: variable.to_string(),
+                                expected_type: "number".to_string(),
+                                description: "no value found in the payload nor in the defaults"
+                                    .to_string(),
+                            }
+                        })?;
+                        value_to_number(variable, value)
+                    }
+                }
+            }
+            VariableId::Condition(condition_idx) => {
+                let checker = self.condition_checkers.get(*condition_idx).ok_or_else(|| {
+                    OperationError::service_error(format!(
+                        "Condition {condition_idx} is not prepared for the formula"
+                    ))
+                })?;
+
+                if checker.check(point_id) {
+                    Ok(1.0)
+                } else {
+                    Ok(0.0)
+                }
+            }
+        }
+    }
+}
+
+fn missing_value_error(key: &JsonPath, expected_type: &str) -> OperationError {
+    OperationError::VariableTypeError {
+        field_name: key.to_string(),
+        expected_type: expected_type.to_string(),
+        description: "no valid value found in the payload nor in the defaults".to_string(),
+    }
+}
+
+fn check_finite(
+    value: PreciseScore,
+    expression: impl FnOnce() -> String,
+) -> OperationResult<PreciseScore> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(OperationError::NonFiniteNumber {
+            expression: expression(),
+        })
+    }
+}
+
+/// Interprets a payload or default value as a number. Booleans become 1.0 or 0.0
+fn value_to_number(variable: &VariableId, value: &Value) -> OperationResult<PreciseScore> {
+    match value {
+        Value::Number(number) => number
+            .as_f64()
+            .ok_or_else(|| OperationError::VariableTypeError {
+                field_name: variable.to_string(),
+                expected_type: "number".to_string(),
+                description: format!("{number} can't be represented as a float"),
+            }),
+        Value::Bool(true) => Ok(1.0),
+        Value::Bool(false) => Ok(0.0),
+        other => Err(OperationError::VariableTypeError {
+            field_name: variable.to_string(),
+            expected_type: "number".to_string(),
+            description: format!("found {other}"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::index::query_optimization::rescore_formula::parsed_formula::{
+        DecayKind, DecayParams,
+    };
+    use crate::types::GeoPoint;
+
+    fn make_scorer<'a>(
+        formula: &'a ParsedExpression,
+        prefetches_scores: &'a [AHashMap<PointOffsetType, ScoreType>],
+        defaults: &'a HashMap<VariableId, Value>,
+    ) -> FormulaScorer<'a> {
+        let price: JsonPath = "price".parse().unwrap();
+        let mut payload_retrievers: HashMap<JsonPath, VariableRetrieverFn> = HashMap::new();
+        payload_retrievers.insert(
+            price,
+            Box::new(|point_id| (point_id < 2).then(|| json!(point_id * 10))),
+        );
+
+        let created_at: JsonPath = "created_at".parse().unwrap();
+        let mut datetime_retrievers: HashMap<JsonPath, DatetimeRetrieverFn> = HashMap::new();
+        datetime_retrievers.insert(
+            created_at,
+            // One hour apart, in microseconds
+            Box::new(|point_id| (point_id < 2).then(|| i64::from(point_id) * 3_600_000_000)),
+        );
+
+        let location: JsonPath = "location".parse().unwrap();
+        let mut geo_retrievers: HashMap<JsonPath, GeoRetrieverFn> = HashMap::new();
+        geo_retrievers.insert(
+            location,
+            Box::new(|point_id| {
+                (point_id < 2).then(|| GeoPoint {
+                    lon: 0.0,
+                    lat: f64::from(point_id),
+                })
+            }),
+        );
+
+        FormulaScorer {
+            formula,
+            prefetches_scores,
+            payload_retrievers,
+            datetime_retrievers,
+            geo_retrievers,
+            condition_checkers: vec![],
+            defaults,
+        }
+    }
+
+    #[test]
+    fn test_formula_scorer() {
+        let price = VariableId::Payload("price".parse().unwrap());
+
+        // $score * 0.5 + price / 10
+        let formula = ParsedExpression::Sum(vec![
+            ParsedExpression::Mult(vec![
+                ParsedExpression::Variable(VariableId::Score(0)),
+                ParsedExpression::Constant(0.5),
+            ]),
+            ParsedExpression::new_div(
+                ParsedExpression::Variable(price.clone()),
+                ParsedExpression::Constant(10.0),
+                None,
+            ),
+        ]);
+
+        let prefetches_scores = [AHashMap::from([(0, 1.0), (1, 2.0), (2, 4.0)])];
+
+        let defaults = HashMap::new();
+        let scorer = make_scorer(&formula, &prefetches_scores, &defaults);
+
+        assert_eq!(scorer.score(0).unwrap(), 0.5);
+        assert_eq!(scorer.score(1).unwrap(), 2.0);
+        // No price for point 2, and no default
+        assert!(scorer.score(2).is_err());
+        // Point 3 is not in prefetch, so its score is 0.0, but it has no price either
+        assert!(scorer.score(3).is_err());
+
+        let defaults = HashMap::from([(price, json!(20))]);
+        let scorer = make_scorer(&formula, &prefetches_scores, &defaults);
+        assert_eq!(scorer.score(2).unwrap(), 4.0);
+        assert_eq!(scorer.score(3).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_decay_expressions() {
+        let prefetches_scores = [];
+        let location: JsonPath = "location".parse().unwrap();
+        let created_at: JsonPath = "created_at".parse().unwrap();
+
+        let params = DecayParams {
+            scale: 3600.0,
+            offset: 0.0,
+            decay: 0.5,
+        };
+
+        // Datetime decay: one hour away from origin halves the score
+        let datetime_decay = ParsedExpression::Decay {
+            kind: DecayKind::Exp,
+            x: Box::new(ParsedExpression::Datetime(
+                DatetimeExpression::PayloadVariable(created_at.clone()),
+            )),
+            origin: Some(Box::new(ParsedExpression::Datetime(
+                DatetimeExpression::Constant("1970-01-01T00:00:00Z".parse().unwrap()),
+            ))),
+            params,
+        };
+
+        let defaults = HashMap::new();
+        let scorer = make_scorer(&datetime_decay, &prefetches_scores, &defaults);
+        assert_eq!(scorer.score(0).unwrap(), 1.0);
+        assert!((scorer.score(1).unwrap() - 0.5).abs() < 1e-6);
+        // No datetime, no default
+        assert!(matches!(
+            scorer.score(2),
+            Err(OperationError::VariableTypeError { .. })
+        ));
+
+        let defaults = HashMap::from([(
+            VariableId::Payload(created_at),
+            json!("1970-01-01T02:00:00Z"),
+        )]);
+        let scorer = make_scorer(&datetime_decay, &prefetches_scores, &defaults);
+        assert!((scorer.score(2).unwrap() - 0.25).abs() < 1e-6);
+
+        // Geo distance: one degree of latitude is roughly 111km
+        let geo_distance = ParsedExpression::GeoDistance {
+            origin: GeoPoint { lon: 0.0, lat: 0.0 },
+            key: location.clone(),
+        };
+        let defaults = HashMap::from([(
+            VariableId::Payload(location),
+            json!({ "lon": 0.0, "lat": 2.0 }),
+        )]);
+        let scorer = make_scorer(&geo_distance, &prefetches_scores, &defaults);
+        assert_eq!(scorer.score(0).unwrap(), 0.0);
+        assert!((scorer.score(1).unwrap() - 111_195.0).abs() < 100.0);
+        assert!((scorer.score(2).unwrap() - 222_390.0).abs() < 200.0);
+    }
+
+    #[test]
+    fn test_non_finite_results() {
+        let prefetches_scores = [AHashMap::from([(0, 0.0)])];
+        let defaults = HashMap::new();
+
+        let div_by_zero = ParsedExpression::new_div(
+            ParsedExpression::Constant(1.0),
+            ParsedExpression::Variable(VariableId::Score(0)),
+            None,
+        );
+        let scorer = make_scorer(&div_by_zero, &prefetches_scores, &defaults);
+        assert!(matches!(
+            scorer.score(0),
+            Err(OperationError::NonFiniteNumber { .. })
+        ));
+
+        let div_by_zero_default = ParsedExpression::new_div(
+            ParsedExpression::Constant(1.0),
+            ParsedExpression::Variable(VariableId::Score(0)),
+            Some(42.0),
+        );
+        let scorer = make_scorer(&div_by_zero_default, &prefetches_scores, &defaults);
+        assert_eq!(scorer.score(0).unwrap(), 42.0);
+
+        let sqrt_negative = ParsedExpression::Sqrt(Box::new(ParsedExpression::Constant(-1.0)));
+        let scorer = make_scorer(&sqrt_negative, &prefetches_scores, &defaults);
+        assert!(scorer.score(0).is_err());
+    }
+}
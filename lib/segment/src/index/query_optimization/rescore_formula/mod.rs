@@ -0,0 +1,3 @@
+pub mod formula_scorer;
+pub mod parsed_formula;
+pub mod value_retriever;
@@ -0,0 +1,286 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::json_path::JsonPath;
+use crate::types::{DateTimePayloadType, Filter, GeoPoint};
+
+/// Precision used while evaluating formulas. Converted to `ScoreType` only for the final score.
+pub type PreciseScore = f64;
+
+const SCORE_KEYWORD: &str = "$score";
+
+/// Formula ready to be evaluated on the points of a segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedFormula {
+    /// Payload keys used by the formula, their values are read from the field indexes or payload storage
+    pub payload_vars: HashSet<JsonPath>,
+
+    /// Payload keys used as datetimes by the formula
+    pub datetime_vars: HashSet<JsonPath>,
+
+    /// Payload keys used as geo points by the formula
+    pub geo_vars: HashSet<JsonPath>,
+
+    /// Conditions used by the formula, each wrapped into a single-condition filter.
+    /// Referenced by their position in this list.
+    pub conditions: Vec<Filter>,
+
+    /// Values to use when a variable is not present for a point
+    pub defaults: HashMap<VariableId, Value>,
+
+    /// The root of the expression tree
+    pub formula: ParsedExpression,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VariableId {
+    /// Score of the point in the prefetch with this index
+    Score(usize),
+    /// Value of a payload field
+    Payload(JsonPath),
+    /// Result of the condition with this index, as 1.0 or 0.0
+    Condition(usize),
+}
+
+impl FromStr for VariableId {
+    type Err = String;
+
+    /// Parses `$score`, `$score[<idx>]` into score references, any other string is a payload key.
+    fn from_str(var_str: &str) -> Result<Self, Self::Err> {
+        let Some(rest) = var_str.strip_prefix(SCORE_KEYWORD) else {
+            return var_str
+                .parse()
+                .map(VariableId::Payload)
+                .map_err(|_| format!("Invalid payload key or variable: {var_str}"));
+        };
+
+        if rest.is_empty() {
+            return Ok(VariableId::Score(0));
+        }
+
+        rest.strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .and_then(|idx| idx.parse().ok())
+            .map(VariableId::Score)
+            .ok_or_else(|| {
+                format!("Invalid score reference: {var_str}, expected `$score` or `$score[<idx>]`")
+            })
+    }
+}
+
+impl fmt::Display for VariableId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableId::Score(0) => write!(f, "{SCORE_KEYWORD}"),
+            VariableId::Score(idx) => write!(f, "{SCORE_KEYWORD}[{idx}]"),
+            VariableId::Payload(path) => write!(f, "{path}"),
+            VariableId::Condition(idx) => write!(f, "condition[{idx}]"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedExpression {
+    Constant(PreciseScore),
+    Variable(VariableId),
+    Mult(Vec<ParsedExpression>),
+    Sum(Vec<ParsedExpression>),
+    Neg(Box<ParsedExpression>),
+    Div {
+        left: Box<ParsedExpression>,
+        right: Box<ParsedExpression>,
+        by_zero_default: Option<PreciseScore>,
+    },
+    Sqrt(Box<ParsedExpression>),
+    Pow {
+        base: Box<ParsedExpression>,
+        exponent: Box<ParsedExpression>,
+    },
+    Exp(Box<ParsedExpression>),
+    Log10(Box<ParsedExpression>),
+    Ln(Box<ParsedExpression>),
+    Abs(Box<ParsedExpression>),
+    /// Datetime, evaluated as seconds since UNIX epoch
+    Datetime(DatetimeExpression),
+    /// Haversine distance in meters between the origin and the geo point in the payload key
+    GeoDistance {
+        origin: GeoPoint,
+        key: JsonPath,
+    },
+    /// Decay function over the distance between `x` and `origin`
+    Decay {
+        kind: DecayKind,
+        x: Box<ParsedExpression>,
+        origin: Option<Box<ParsedExpression>>,
+        params: DecayParams,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatetimeExpression {
+    Constant(DateTimePayloadType),
+    PayloadVariable(JsonPath),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayKind {
+    /// Linear decay, reaches zero at `scale / (1 - decay)` from the origin
+    Lin,
+    /// Exponential decay
+    Exp,
+    /// Gaussian decay
+    Gauss,
+}
+
+/// Shape of a decay function. The result is `decay` at `offset + scale` distance from the origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecayParams {
+    /// Distance from the origin at which the decay reaches the `decay` value, on top of the offset
+    pub scale: PreciseScore,
+    /// Distance from the origin within which there is no decay
+    pub offset: PreciseScore,
+    /// Value of the function at `offset + scale` distance from the origin
+    pub decay: PreciseScore,
+}
+
+impl DecayParams {
+    pub const DEFAULT_SCALE: PreciseScore = 1.0;
+    pub const DEFAULT_OFFSET: PreciseScore = 0.0;
+    pub const DEFAULT_DECAY: PreciseScore = 0.5;
+
+    /// Checks that the decay function is well defined with these parameters
+    pub fn validate(&self) -> Result<(), String> {
+        let DecayParams {
+            scale,
+            offset,
+            decay,
+        } = *self;
+
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(format!("Decay scale must be positive, got {scale}"));
+        }
+        if !offset.is_finite() || offset < 0.0 {
+            return Err(format!("Decay offset must be non-negative, got {offset}"));
+        }
+        if decay.is_nan() || decay <= 0.0 || decay >= 1.0 {
+            return Err(format!("Decay value must be in (0, 1) range, got {decay}"));
+        }
+        Ok(())
+    }
+}
+
+impl DecayKind {
+    /// Evaluates the decay function for a given distance from the origin
+    pub fn decay(self, distance: PreciseScore, params: DecayParams) -> PreciseScore {
+        let DecayParams {
+            scale,
+            offset,
+            decay,
+        } = params;
+
+        let distance = (distance.abs() - offset).max(0.0);
+
+        match self {
+            DecayKind::Lin => (1.0 - distance * (1.0 - decay) / scale).max(0.0),
+            DecayKind::Exp => (decay.ln() / scale * distance).exp(),
+            DecayKind::Gauss => (decay.ln() / scale.powi(2) * distance.powi(2)).exp(),
+        }
+    }
+}
+
+impl ParsedExpression {
+    pub fn new_div(
+        left: ParsedExpression,
+        right: ParsedExpression,
+        by_zero_default: Option<PreciseScore>,
+    ) -> Self {
+        ParsedExpression::Div {
+            left: Box::new(left),
+            right: Box::new(right),
+            by_zero_default,
+        }
+    }
+
+    pub fn new_pow(base: ParsedExpression, exponent: ParsedExpression) -> Self {
+        ParsedExpression::Pow {
+            base: Box::new(base),
+            exponent: Box::new(exponent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variable_id() {
+        assert_eq!(
+            "$score".parse::<VariableId>().unwrap(),
+            VariableId::Score(0)
+        );
+        assert_eq!(
+            "$score[3]".parse::<VariableId>().unwrap(),
+            VariableId::Score(3)
+        );
+        assert_eq!(
+            "price".parse::<VariableId>().unwrap(),
+            VariableId::Payload("price".parse().unwrap())
+        );
+        assert_eq!(
+            "nested.field[0]".parse::<VariableId>().unwrap(),
+            VariableId::Payload("nested.field[0]".parse().unwrap())
+        );
+
+        assert!("$score[".parse::<VariableId>().is_err());
+        assert!("$score[a]".parse::<VariableId>().is_err());
+        assert!("$scores".parse::<VariableId>().is_err());
+    }
+
+    #[test]
+    fn test_decay_functions() {
+        let params = DecayParams {
+            scale: 10.0,
+            offset: 5.0,
+            decay: 0.5,
+        };
+
+        for kind in [DecayKind::Lin, DecayKind::Exp, DecayKind::Gauss] {
+            // No decay within the offset
+            assert_eq!(kind.decay(0.0, params), 1.0);
+            assert_eq!(kind.decay(-5.0, params), 1.0);
+            // Reaches `decay` at `offset + scale`
+            assert!((kind.decay(15.0, params) - 0.5).abs() < 1e-9);
+            assert!((kind.decay(-15.0, params) - 0.5).abs() < 1e-9);
+            // Keeps decreasing after that
+            assert!(kind.decay(20.0, params) < 0.5);
+        }
+
+        assert_eq!(DecayKind::Lin.decay(25.0, params), 0.0);
+        assert_eq!(DecayKind::Lin.decay(100.0, params), 0.0);
+        assert!(DecayKind::Exp.decay(100.0, params) > 0.0);
+
+        assert!(params.validate().is_ok());
+        assert!(DecayParams {
+            scale: 0.0,
+            ..params
+        }
+        .validate()
+        .is_err());
+        assert!(DecayParams {
+            decay: 1.0,
+            ..params
+        }
+        .validate()
+        .is_err());
+        assert!(DecayParams {
+            offset: -1.0,
+            ..params
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use std::str::FromStr;
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use crate::common::utils::MultiValue;
+use crate::index::field_index::FieldIndex;
+use crate::index::query_optimization::payload_provider::PayloadProvider;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::json_path::JsonPath;
+use crate::types::{DateTimePayloadType, GeoPoint, IntPayloadType, PayloadContainer};
+
+/// Reads the value of a variable for a given point
+pub type VariableRetrieverFn<'a> = Box<dyn Fn(PointOffsetType) -> Option<Value> + 'a>;
+
+/// Reads a datetime for a given point, as a timestamp in microseconds
+pub type DatetimeRetrieverFn<'a> = Box<dyn Fn(PointOffsetType) -> Option<IntPayloadType> + 'a>;
+
+/// Reads a geo point for a given point
+pub type GeoRetrieverFn<'a> = Box<dyn Fn(PointOffsetType) -> Option<GeoPoint> + 'a>;
+
+impl StructPayloadIndex {
+    /// Prepares a retriever for the value of a payload key.
+    ///
+    /// Reads from a field index if there is a suitable one, falls back to the payload storage otherwise.
+    pub fn variable_retriever(&self, key: &JsonPath) -> VariableRetrieverFn<'_> {
+        let indexed_retriever = self
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(indexed_variable_retriever));
+
+        if let Some(retriever) = indexed_retriever {
+            return retriever;
+        }
+
+        let payload_provider = PayloadProvider::new(self.payload.clone());
+        let key = key.clone();
+        Box::new(move |point_id| {
+            payload_provider.with_payload(point_id, |payload| first_value(payload.get_value(&key)))
+        })
+    }
+
+    /// Prepares a retriever for a datetime payload key.
+    ///
+    /// Reads from the datetime index if there is one, falls back to parsing the payload otherwise.
+    pub fn datetime_retriever(&self, key: &JsonPath) -> DatetimeRetrieverFn<'_> {
+        let indexed_retriever = self.field_indexes.get(key).and_then(|indexes| {
+            indexes.iter().find_map(|index| match index {
+                FieldIndex::DatetimeIndex(index) => {
+                    Some(Box::new(move |point_id| index.get_values(point_id)?.next())
+                        as DatetimeRetrieverFn)
+                }
+                _ => None,
+            })
+        });
+
+        if let Some(retriever) = indexed_retriever {
+            return retriever;
+        }
+
+        let payload_provider = PayloadProvider::new(self.payload.clone());
+        let key = key.clone();
+        Box::new(move |point_id| {
+            payload_provider.with_payload(point_id, |payload| {
+                first_value(payload.get_value(&key)).and_then(|value| value_to_timestamp(&value))
+            })
+        })
+    }
+
+    /// Prepares a retriever for a geo point payload key.
+    ///
+    /// Reads from the geo index if there is one, falls back to parsing the payload otherwise.
+    pub fn geo_retriever(&self, key: &JsonPath) -> GeoRetrieverFn<'_> {
+        let indexed_retriever = self.field_indexes.get(key).and_then(|indexes| {
+            indexes.iter().find_map(|index| match index {
+                FieldIndex::GeoIndex(index) => {
+                    Some(Box::new(move |point_id| index.get_values(point_id)?.next())
+                        as GeoRetrieverFn)
+                }
+                _ => None,
+            })
+        });
+
+        if let Some(retriever) = indexed_retriever {
+            return retriever;
+        }
+
+        let payload_provider = PayloadProvider::new(self.payload.clone());
+        let key = key.clone();
+        Box::new(move |point_id| {
+            payload_provider.with_payload(point_id, |payload| {
+                first_value(payload.get_value(&key)).and_then(value_to_geo_point)
+            })
+        })
+    }
+}
+
+/// Parses a datetime string into a timestamp in microseconds
+pub fn value_to_timestamp(value: &Value) -> Option<IntPayloadType> {
+    let datetime = DateTimePayloadType::from_str(value.as_str()?).ok()?;
+    Some(datetime.timestamp())
+}
+
+/// Parses a `{"lon": .., "lat": ..}` object into a geo point
+pub fn value_to_geo_point(value: Value) -> Option<GeoPoint> {
+    serde_json::from_value(value).ok()
+}
+
+/// Only indexes which keep the original representation of the value can be used as a source.
+fn indexed_variable_retriever(index: &FieldIndex) -> Option<VariableRetrieverFn<'_>> {
+    match index {
+        FieldIndex::IntIndex(index) => Some(Box::new(move |point_id| {
+            index.get_values(point_id)?.next().map(Value::from)
+        })),
+        FieldIndex::FloatIndex(index) => Some(Box::new(move |point_id| {
+            index.get_values(point_id)?.next().map(Value::from)
+        })),
+        FieldIndex::DatetimeIndex(_)
+        | FieldIndex::IntMapIndex(_)
+        | FieldIndex::KeywordIndex(_)
+        | FieldIndex::GeoIndex(_)
+        | FieldIndex::FullTextIndex(_)
+        | FieldIndex::BoolIndex(_)
+        | FieldIndex::UuidIndex(_)
+        | FieldIndex::UuidMapIndex(_)
+        | FieldIndex::GeoShapeIndex(_) => None,
+    }
+}
+
+/// Takes the first non-null value, looking into arrays if needed
+fn first_value(values: MultiValue<&Value>) -> Option<Value> {
+    values.into_iter().find_map(|value| match value {
+        Value::Array(array) => array.iter().find(|value| !value.is_null()).cloned(),
+        Value::Null => None,
+        other => Some(other.clone()),
+    })
+}
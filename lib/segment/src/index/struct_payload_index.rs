@@ -11,7 +11,9 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use schemars::_serde_json::Value;
 
-use super::field_index::facet_index::FacetIndex;
+use super::field_index::facet_index::{FacetIndex, HistogramIndex};
+use super::field_index::full_text_index::text_index::FullTextIndex;
+use super::field_index::geo_index::GeoMapIndex;
 use super::field_index::index_selector::{
     IndexSelector, IndexSelectorOnDisk, IndexSelectorRocksDb,
 };
@@ -45,7 +47,7 @@ use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 #[derive(Debug)]
 pub struct StructPayloadIndex {
     /// Payload storage
-    payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
+    pub(super) payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
     /// Used for `has_id` condition and estimating cardinality
     pub(super) id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     /// Vector storages for each field, used for `has_vector` condition
@@ -323,6 +325,9 @@ impl StructPayloadIndex {
                 .estimate_field_condition(field_condition, nested_path)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.available_point_count())),
 
+            // Joins are resolved into conditions on the key before reaching the segments
+            Condition::Join(_) => unreachable!("unresolved join condition"),
+
             Condition::CustomIdChecker(cond) => {
                 cond.estimate_cardinality(self.id_tracker.borrow().available_point_count())
             }
@@ -422,6 +427,38 @@ impl StructPayloadIndex {
                 key: key.to_string(),
             })
     }
+
+    pub fn get_histogram_index(&self, key: &JsonPath) -> OperationResult<HistogramIndex> {
+        self.field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_histogram_index()))
+            .ok_or_else(|| OperationError::MissingRangeIndexForFacet {
+                key: key.to_string(),
+            })
+    }
+
+    pub fn get_geo_index(&self, key: &JsonPath) -> OperationResult<&GeoMapIndex> {
+        self.field_indexes
+            .get(key)
+            .and_then(|indexes| {
+                indexes.iter().find_map(|index| match index {
+                    FieldIndex::GeoIndex(index) => Some(index),
+                    _ => None,
+                })
+            })
+            .ok_or_else(|| OperationError::MissingGeoIndexForFacet {
+                key: key.to_string(),
+            })
+    }
+
+    pub fn get_full_text_index(&self, key: &JsonPath) -> OperationResult<&FullTextIndex> {
+        self.field_indexes
+            .get(key)
+            .and_then(|index| index.iter().find_map(|index| index.as_full_text()))
+            .ok_or_else(|| OperationError::MissingFullTextIndex {
+                key: key.to_string(),
+            })
+    }
 }
 
 impl PayloadIndex for StructPayloadIndex {
@@ -4,10 +4,14 @@ use std::str::FromStr;
 
 use serde_json::Value;
 
+use crate::data_types::keyword_pattern::KeywordPattern;
+use crate::index::field_index::full_text_index::text_index::{
+    check_fuzzy_in_tokens, check_phrase, default_text_tokens,
+};
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
-    RangeInterface, ValueVariants, ValuesCount,
+    GeoPolygon, GeoRadius, GeoShape, GeoShapeCondition, Match, MatchAll, MatchAny, MatchExcept,
+    MatchPhrase, MatchText, MatchValue, Range, RangeInterface, ValueVariants, ValuesCount,
 };
 
 /// Threshold representing the point to which iterating through an IndexSet is more efficient than using hashing.
@@ -41,6 +45,7 @@ impl ValueChecker for FieldCondition {
             geo_radius,
             geo_bounding_box,
             geo_polygon,
+            geo_shape,
             values_count,
             key: _,
         } = self;
@@ -53,6 +58,7 @@ impl ValueChecker for FieldCondition {
                 .is_some_and(|range_interface| match range_interface {
                     RangeInterface::Float(condition) => condition.check_match(payload),
                     RangeInterface::DateTime(condition) => condition.check_match(payload),
+                    RangeInterface::Keyword(condition) => condition.keyword.check_match(payload),
                 })
             || geo_radius
                 .as_ref()
@@ -63,6 +69,9 @@ impl ValueChecker for FieldCondition {
             || geo_polygon
                 .as_ref()
                 .is_some_and(|condition| condition.check_match(payload))
+            || geo_shape
+                .as_ref()
+                .is_some_and(|condition| condition.check_match(payload))
             || values_count
                 .as_ref()
                 .is_some_and(|condition| condition.check_match(payload))
@@ -74,6 +83,9 @@ impl ValueChecker for FieldCondition {
                 .as_ref()
                 .unwrap()
                 .check_count_from(payload)
+        } else if let Some(Match::All(match_all)) = &self.r#match {
+            // Needs to see all values of the array at once
+            match_all.check_all(payload)
         } else {
             self._check(payload)
         }
@@ -95,6 +107,37 @@ impl ValueChecker for Match {
                 Value::String(stored) => stored.contains(text),
                 _ => false,
             },
+            Match::Phrase(MatchPhrase { phrase, slop }) => match payload {
+                Value::String(stored) => {
+                    // Without an index, tokenize the same way as a default text index
+                    check_phrase(
+                        &default_text_tokens(stored),
+                        &default_text_tokens(phrase),
+                        slop.unwrap_or(0),
+                    )
+                }
+                _ => false,
+            },
+            Match::Fuzzy(fuzzy_match) => match payload {
+                Value::String(stored) => {
+                    // Without an index, tokenize the same way as a default text index
+                    check_fuzzy_in_tokens(
+                        &default_text_tokens(stored),
+                        &default_text_tokens(&fuzzy_match.fuzzy),
+                        fuzzy_match.max_edits(),
+                        fuzzy_match.max_expansions(),
+                    )
+                }
+                _ => false,
+            },
+            Match::Prefix(_) | Match::Wildcard(_) | Match::Regex(_) => match payload {
+                Value::String(stored) => match KeywordPattern::from_match(self) {
+                    Some(Ok(pattern)) => pattern.is_match(stored),
+                    // Invalid patterns are rejected by the request validation
+                    Some(Err(_)) | None => false,
+                },
+                _ => false,
+            },
             Match::Any(MatchAny { any }) => match (payload, any) {
                 (Value::String(stored), AnyVariants::Strings(list)) => {
                     if list.len() < INDEXSET_ITER_THRESHOLD {
@@ -115,6 +158,7 @@ impl ValueChecker for Match {
                     .unwrap_or(false),
                 _ => false,
             },
+            Match::All(match_all) => match_all.check_all(payload),
             Match::Except(MatchExcept { except }) => match (payload, except) {
                 (Value::String(stored), AnyVariants::Strings(list)) => {
                     if list.len() < INDEXSET_ITER_THRESHOLD {
@@ -144,6 +188,33 @@ impl ValueChecker for Match {
     }
 }
 
+impl MatchAll {
+    /// Every value of the condition must be present among the payload values.
+    /// An empty list of values matches nothing.
+    fn check_all(&self, payload: &Value) -> bool {
+        let values = match payload {
+            Value::Array(values) => values.as_slice(),
+            value => std::slice::from_ref(value),
+        };
+        match &self.all {
+            AnyVariants::Strings(list) => {
+                !list.is_empty()
+                    && list.iter().all(|keyword| {
+                        values
+                            .iter()
+                            .any(|value| value.as_str() == Some(keyword.as_str()))
+                    })
+            }
+            AnyVariants::Integers(list) => {
+                !list.is_empty()
+                    && list
+                        .iter()
+                        .all(|integer| values.iter().any(|value| value.as_i64() == Some(*integer)))
+            }
+        }
+    }
+}
+
 impl ValueChecker for Range<FloatPayloadType> {
     fn check_match(&self, payload: &Value) -> bool {
         match payload {
@@ -165,6 +236,14 @@ impl ValueChecker for Range<DateTimePayloadType> {
     }
 }
 
+impl ValueChecker for Range<String> {
+    fn check_match(&self, payload: &Value) -> bool {
+        payload
+            .as_str()
+            .is_some_and(|keyword| self.check_keyword(keyword))
+    }
+}
+
 impl ValueChecker for GeoBoundingBox {
     fn check_match(&self, payload: &Value) -> bool {
         match payload {
@@ -216,6 +295,12 @@ impl ValueChecker for GeoPolygon {
     }
 }
 
+impl ValueChecker for GeoShapeCondition {
+    fn check_match(&self, payload: &Value) -> bool {
+        GeoShape::from_geojson(payload).is_some_and(|shape| self.check_shape(&shape))
+    }
+}
+
 impl ValueChecker for ValuesCount {
     fn check_match(&self, payload: &Value) -> bool {
         self.check_count_from(payload)
@@ -231,6 +316,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::json_path::JsonPath;
     use crate::types::GeoPoint;
 
     #[test]
@@ -300,4 +386,27 @@ mod tests {
         };
         assert!(gte_two_countries_query.check(&countries));
     }
+
+    #[test]
+    fn test_match_all() {
+        let tags = json!(["red", "green", "blue"]);
+        let condition = |values: &[&str]| {
+            let values: Vec<_> = values.iter().map(|value| value.to_string()).collect();
+            FieldCondition::new_match(JsonPath::new("tags"), Match::All(MatchAll::from(values)))
+        };
+
+        assert!(condition(&["red", "blue"]).check(&tags));
+        assert!(condition(&["green"]).check(&tags));
+        assert!(!condition(&["red", "yellow"]).check(&tags));
+        assert!(!condition(&[]).check(&tags));
+        assert!(condition(&["red"]).check(&json!("red")));
+        assert!(!condition(&["red", "green"]).check(&json!("red")));
+
+        let sizes = FieldCondition::new_match(
+            JsonPath::new("sizes"),
+            Match::All(MatchAll::from(vec![1, 3])),
+        );
+        assert!(sizes.check(&json!([1, 2, 3])));
+        assert!(!sizes.check(&json!([1, 2])));
+    }
 }
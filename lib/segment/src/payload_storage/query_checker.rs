@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
+use serde_json::Value;
 
 use crate::common::utils::{check_is_empty, check_is_null, IndexesMap};
 use crate::id_tracker::IdTrackerSS;
@@ -15,7 +16,7 @@ use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::{ConditionChecker, PayloadStorage};
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
+    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, Match, MinShould,
     OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType, VectorName,
 };
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
@@ -161,6 +162,9 @@ where
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .is_some_and(|point_id| cond.check(point_id)),
 
+        // Joins are resolved into conditions on the key before reaching the segments
+        Condition::Join(_) => unreachable!("unresolved join condition"),
+
         Condition::Filter(_) => unreachable!(),
     };
 
@@ -189,35 +193,33 @@ where
     let field_values = payload.get_value(&field_condition.key);
     let field_indexes = field_indexes.get(&field_condition.key);
 
-    // This covers a case, when a field index affects the result of the condition.
-    if let Some(field_indexes) = field_indexes {
-        for p in field_values {
-            let mut index_checked = false;
-            for index in field_indexes.as_ref() {
-                if let Some(index_check_res) = index.check_condition(field_condition, p) {
-                    if index_check_res {
-                        // If at least one object matches the condition, we can return true
-                        return true;
-                    }
-                    index_checked = true;
-                    // If index check of the condition returned something, we don't need to check
-                    // other indexes
-                    break;
-                }
-            }
-            if !index_checked {
-                // If none of the indexes returned anything, we need to check the condition
-                // against the payload
-                if field_condition.check(p) {
-                    return true;
-                }
-            }
-        }
-        false
-    } else {
-        // Fallback to regular condition check if there are no indexes for the field
-        field_values.into_iter().any(|p| field_condition.check(p))
+    let check_value = |value: &Value| {
+        // This covers a case, when a field index affects the result of the condition.
+        // If index check of the condition returned something, we don't need to check
+        // other indexes. If none of the indexes returned anything, we need to check the
+        // condition against the payload.
+        field_indexes
+            .into_iter()
+            .flat_map(|field_indexes| field_indexes.as_ref())
+            .find_map(|index| index.check_condition(field_condition, value))
+            .unwrap_or_else(|| field_condition.check(value))
+    };
+
+    if let Some(Match::All(_)) = &field_condition.r#match {
+        // Values of the condition may be found in different resolved values,
+        // e.g. in different elements of `items[].tag`, so all of them are checked at once
+        let all_values = field_values
+            .iter()
+            .flat_map(|&value| match value {
+                Value::Array(values) => values.clone(),
+                value => vec![value.clone()],
+            })
+            .collect();
+        return check_value(&Value::Array(all_values));
     }
+
+    // If at least one value matches the condition, we can return true
+    field_values.iter().any(|&value| check_value(value))
 }
 
 /// Only used for testing
@@ -321,7 +323,8 @@ mod tests {
     use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
     use crate::payload_storage::PayloadStorage;
     use crate::types::{
-        DateTimeWrapper, FieldCondition, GeoBoundingBox, GeoPoint, PayloadField, Range, ValuesCount,
+        DateTimeWrapper, FieldCondition, GeoBoundingBox, GeoPoint, MatchAll, PayloadField, Range,
+        ValuesCount,
     };
 
     #[test]
@@ -647,4 +650,27 @@ mod tests {
         let query = Filter::new_must(Condition::HasId(ids.into()));
         assert!(payload_checker.check(2, &query));
     }
+
+    #[test]
+    fn test_match_all_across_values() {
+        let payload: Payload = json!({
+            "tags": ["red", "green"],
+            "items": [{"tag": "red"}, {"tag": "green"}, {"tag": "blue"}],
+        })
+        .into();
+        let field_indexes: HashMap<PayloadKeyType, Vec<FieldIndex>> = HashMap::new();
+
+        let check = |key: &str, values: &[&str]| {
+            let values: Vec<_> = values.iter().map(|value| value.to_string()).collect();
+            let condition =
+                FieldCondition::new_match(JsonPath::new(key), Match::All(MatchAll::from(values)));
+            check_field_condition(&condition, &payload, &field_indexes)
+        };
+
+        assert!(check("tags", &["red", "green"]));
+        assert!(check("tags[]", &["red", "green"]));
+        assert!(!check("tags[]", &["red", "blue"]));
+        assert!(check("items[].tag", &["red", "green", "blue"]));
+        assert!(!check("items[].tag", &["red", "yellow"]));
+    }
 }
@@ -212,6 +212,7 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
         geo_bounding_box,
         geo_radius,
         geo_polygon,
+        geo_shape,
         values_count,
     } = field_condition;
 
@@ -220,19 +221,47 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     if let Some(r#match) = r#match {
         inferred.extend(match r#match {
             Match::Value(match_value) => infer_schema_from_match_value(match_value),
-            Match::Text(_match_text) => {
+            Match::Text(_) | Match::Fuzzy(_) => {
                 vec![PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(
                     TextIndexParams {
                         r#type: TextIndexType::Text,
                         tokenizer: TokenizerType::default(),
                         min_token_len: None,
                         max_token_len: None,
+                        min_ngram: None,
+                        max_ngram: None,
                         lowercase: None,
+                        phrase_matching: None,
+                        stopwords: None,
+                        stemmer: None,
+                        ascii_folding: None,
                         on_disk: None,
                     },
                 ))]
             }
+            Match::Phrase(_match_phrase) => {
+                vec![PayloadFieldSchema::FieldParams(PayloadSchemaParams::Text(
+                    TextIndexParams {
+                        r#type: TextIndexType::Text,
+                        tokenizer: TokenizerType::default(),
+                        min_token_len: None,
+                        max_token_len: None,
+                        min_ngram: None,
+                        max_ngram: None,
+                        lowercase: None,
+                        phrase_matching: Some(true),
+                        stopwords: None,
+                        stemmer: None,
+                        ascii_folding: None,
+                        on_disk: None,
+                    },
+                ))]
+            }
+            Match::Prefix(_) | Match::Wildcard(_) | Match::Regex(_) => {
+                vec![PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)]
+            }
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
+            Match::All(match_all) => infer_schema_from_any_variants(&match_all.all),
             Match::Except(match_except) => infer_schema_from_any_variants(&match_except.except),
         })
     }
@@ -245,11 +274,17 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
                 inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Float));
                 inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Integer));
             }
+            RangeInterface::Keyword(_) => {
+                inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword));
+            }
         }
     }
     if geo_bounding_box.is_some() || geo_radius.is_some() || geo_polygon.is_some() {
         inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Geo));
     }
+    if geo_shape.is_some() {
+        inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::GeoShape));
+    }
     if values_count.is_some() {
         // Any index will do, let user choose depending on their data type
         inferred.extend(all_indexes());
@@ -378,6 +413,18 @@ impl<'a> Extractor<'a> {
                 key = &is_null.is_null.key;
                 inferred = all_indexes().collect();
             }
+            // Matched with the ids or keys of the related points
+            Condition::Join(join) => {
+                key = &join.join.key;
+                inferred = [
+                    PayloadSchemaType::Keyword,
+                    PayloadSchemaType::Integer,
+                    PayloadSchemaType::Uuid,
+                ]
+                .into_iter()
+                .map(PayloadFieldSchema::FieldType)
+                .collect();
+            }
             // No index needed
             Condition::HasId(_) => return,
             Condition::CustomIdChecker(_) => return,
@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use common::iterator_ext::IteratorExt;
+use itertools::Either;
+use serde_json::Value;
+
+use super::Segment;
+use crate::common::operation_error::OperationResult;
+use crate::data_types::aggregations::{AggregateParams, PartialAggregate};
+use crate::index::PayloadIndex;
+use crate::types::PayloadContainer;
+
+const AGGREGATE_STOP_CHECK_INTERVAL: usize = 100;
+
+impl Segment {
+    /// Aggregate the values of the key from the numeric index if there is one,
+    /// otherwise from the numbers in the payload.
+    pub(super) fn aggregate_values(
+        &self,
+        request: &AggregateParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<PartialAggregate> {
+        let payload_index = self.payload_index.borrow();
+        let numeric_index = payload_index.get_histogram_index(&request.key).ok();
+
+        let mut aggregate = PartialAggregate::new(
+            numeric_index
+                .as_ref()
+                .is_some_and(|index| index.is_datetime()),
+        );
+
+        if let (None, Some(numeric_index)) = (&request.filter, &numeric_index) {
+            // The index only contains values of existing points
+            numeric_index
+                .iter_numbers()
+                .check_stop(|| is_stopped.load(Ordering::Relaxed))
+                .for_each(|value| aggregate.add(value));
+            return Ok(aggregate);
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let points_iter = match &request.filter {
+            Some(filter) => {
+                let filter_cardinality = payload_index.estimate_cardinality(filter);
+                Either::Left(payload_index.iter_filtered_points(
+                    filter,
+                    &*id_tracker,
+                    &filter_cardinality,
+                ))
+            }
+            None => Either::Right(id_tracker.iter_ids()),
+        };
+
+        let points_iter = points_iter
+            .check_stop_every(AGGREGATE_STOP_CHECK_INTERVAL, || {
+                is_stopped.load(Ordering::Relaxed)
+            })
+            .filter(|point_id| !id_tracker.is_deleted_point(*point_id));
+
+        for point_id in points_iter {
+            match &numeric_index {
+                Some(numeric_index) => numeric_index
+                    .get_numbers(point_id)
+                    .into_iter()
+                    .for_each(|value| aggregate.add(value)),
+                None => {
+                    let payload = payload_index.get_payload(point_id)?;
+                    for value in payload.get_value(&request.key).iter() {
+                        match value {
+                            Value::Array(values) => values
+                                .iter()
+                                .filter_map(Value::as_f64)
+                                .for_each(|number| aggregate.add(number)),
+                            value => {
+                                if let Some(number) = value.as_f64() {
+                                    aggregate.add(number);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(aggregate)
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::atomic::AtomicBool;
+
+use common::types::ScoredPointOffset;
+
+use super::Segment;
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::data_types::bm25::{Bm25Query, Bm25Stats};
+use crate::index::PayloadIndex;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::types::{Filter, ScoredPoint, WithPayload, WithVector};
+
+impl Segment {
+    pub(super) fn bm25_stats_internal(&self, query: &Bm25Query) -> OperationResult<Bm25Stats> {
+        let payload_index = self.payload_index.borrow();
+        let full_text_index = payload_index.get_full_text_index(&query.key)?;
+
+        Ok(full_text_index.bm25_stats(&query.text))
+    }
+
+    /// Scores the points matching at least one query token, and returns the best `limit` of them.
+    ///
+    /// `stats` are expected to cover all the searched segments, so that scores are comparable.
+    pub(super) fn search_bm25_internal(
+        &self,
+        query: &Bm25Query,
+        stats: &Bm25Stats,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let internal_results = {
+            let payload_index = self.payload_index.borrow();
+            let id_tracker = self.id_tracker.borrow();
+
+            let full_text_index = payload_index.get_full_text_index(&query.key)?;
+            let scores = full_text_index.bm25_scores(query, stats);
+
+            check_process_stopped(is_stopped)?;
+
+            let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+            let scored = scores
+                .into_iter()
+                .filter(|&(internal_id, _)| !id_tracker.is_deleted_point(internal_id))
+                .filter(|&(internal_id, _)| {
+                    filter_context
+                        .as_ref()
+                        .map_or(true, |context| context.check(internal_id))
+                })
+                .map(|(idx, score)| ScoredPointOffset { idx, score });
+
+            peek_top_largest_iterable(scored, limit)
+        };
+
+        self.process_search_result(
+            internal_results,
+            &WithPayload::from(false),
+            &WithVector::from(false),
+        )
+    }
+}
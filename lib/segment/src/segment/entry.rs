@@ -11,16 +11,20 @@ use io::storage_version::VERSION_FILE;
 use uuid::Uuid;
 
 use super::Segment;
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{OperationError, OperationResult, SegmentFailedState};
 use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
-use crate::data_types::facets::{FacetParams, FacetValue};
+use crate::data_types::aggregations::{AggregateParams, DistinctCountParams, PartialAggregate};
+use crate::data_types::bm25::{Bm25Query, Bm25Stats};
+use crate::data_types::facets::{FacetCount, FacetParams, FacetValue};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::entry_point::SegmentEntry;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::json_path::JsonPath;
 use crate::payload_storage::PayloadStorage;
@@ -426,10 +430,51 @@ impl SegmentEntry for Segment {
         &self,
         request: &FacetParams,
         is_stopped: &AtomicBool,
-    ) -> OperationResult<HashMap<FacetValue, usize>> {
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>> {
         self.approximate_facet(request, is_stopped)
     }
 
+    fn aggregate(
+        &self,
+        request: &AggregateParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<PartialAggregate> {
+        self.aggregate_values(request, is_stopped)
+    }
+
+    fn distinct_count(
+        &self,
+        request: &DistinctCountParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HyperLogLog> {
+        self.approximate_distinct_count(request, is_stopped)
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        self.rescore_with_formula_internal(formula, prefetches_results, limit, is_stopped)
+    }
+
+    fn bm25_stats(&self, query: &Bm25Query) -> OperationResult<Bm25Stats> {
+        self.bm25_stats_internal(query)
+    }
+
+    fn search_bm25(
+        &self,
+        query: &Bm25Query,
+        stats: &Bm25Stats,
+        filter: Option<&Filter>,
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        self.search_bm25_internal(query, stats, filter, limit, is_stopped)
+    }
+
     fn segment_type(&self) -> SegmentType {
         self.segment_type
     }
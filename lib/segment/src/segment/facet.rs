@@ -3,22 +3,39 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use common::iterator_ext::IteratorExt;
 use itertools::{Either, Itertools};
+use smol_str::SmolStr;
 
 use super::Segment;
+use crate::common::hyperloglog::HyperLogLog;
 use crate::common::operation_error::OperationResult;
-use crate::data_types::facets::{FacetHit, FacetParams, FacetValue};
+use crate::data_types::aggregations::DistinctCountParams;
+use crate::data_types::facets::{
+    FacetCount, FacetHit, FacetParams, FacetValue, FacetValueRef, GeoGridParams, HistogramParams,
+};
 use crate::entry::entry_point::SegmentEntry;
+use crate::index::field_index::geo_hash::GeoHash;
 use crate::index::PayloadIndex;
 use crate::json_path::JsonPath;
+use crate::payload_storage::FilterContext;
 use crate::types::Filter;
 
+const FACET_STOP_CHECK_INTERVAL: usize = 100;
+
 impl Segment {
     pub(super) fn approximate_facet(
         &self,
         request: &FacetParams,
         is_stopped: &AtomicBool,
-    ) -> OperationResult<HashMap<FacetValue, usize>> {
-        const STOP_CHECK_INTERVAL: usize = 100;
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>> {
+        if let Some(geo_grid) = &request.geo_grid {
+            return self.approximate_geo_grid_facet(request, geo_grid, is_stopped);
+        }
+        if let Some(histogram) = &request.histogram {
+            return self.approximate_histogram_facet(request, histogram, is_stopped);
+        }
+        if !request.pivot.is_empty() {
+            return self.approximate_pivot_facet(request, is_stopped);
+        }
 
         let payload_index = self.payload_index.borrow();
 
@@ -50,7 +67,9 @@ impl Segment {
                 // aka. read from other indexes
                 let iter = payload_index
                     .iter_filtered_points(filter, &*id_tracker, &filter_cardinality)
-                    .check_stop_every(STOP_CHECK_INTERVAL, || is_stopped.load(Ordering::Relaxed))
+                    .check_stop_every(FACET_STOP_CHECK_INTERVAL, || {
+                        is_stopped.load(Ordering::Relaxed)
+                    })
                     .filter(|point_id| !id_tracker.is_deleted_point(*point_id))
                     .fold(HashMap::new(), |mut map, point_id| {
                         facet_index.get_values(point_id).unique().for_each(|value| {
@@ -59,7 +78,11 @@ impl Segment {
                         map
                     })
                     .into_iter()
-                    .map(|(value, count)| FacetHit { value, count });
+                    .map(|(value, count)| FacetHit {
+                        value,
+                        count,
+                        centroid: None,
+                    });
 
                 Either::Left(iter)
             } else {
@@ -92,12 +115,260 @@ impl Segment {
         //
         // We need all values to be able to aggregate correctly across segments
         let hits: HashMap<_, _> = hits_iter
-            .map(|hit| (hit.value.to_owned(), hit.count))
+            .map(|hit| (hit.value.to_owned(), FacetCount::from(hit.count)))
+            .collect();
+
+        Ok(hits)
+    }
+
+    /// Count points per geo-hash cell of the geo index, along with the centroid of each cell
+    fn approximate_geo_grid_facet(
+        &self,
+        request: &FacetParams,
+        geo_grid: &GeoGridParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>> {
+        let payload_index = self.payload_index.borrow();
+        let geo_index = payload_index.get_geo_index(&request.key)?;
+
+        let cell_value = |cell: GeoHash| FacetValue::GeoHash(SmolStr::from(cell).to_string());
+
+        if request.filter.is_none() && !geo_grid.with_centroid {
+            // The index already counts points per cell
+            let hits = geo_index
+                .points_per_cell(geo_grid.precision)
+                .check_stop(|| is_stopped.load(Ordering::Relaxed))
+                .map(|(cell, count)| (cell_value(cell), FacetCount::from(count)))
+                .collect();
+            return Ok(hits);
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let points_iter = match &request.filter {
+            Some(filter) => {
+                let filter_cardinality = payload_index.estimate_cardinality(filter);
+                Either::Left(payload_index.iter_filtered_points(
+                    filter,
+                    &*id_tracker,
+                    &filter_cardinality,
+                ))
+            }
+            None => Either::Right(id_tracker.iter_ids()),
+        };
+
+        let hits = points_iter
+            .check_stop_every(FACET_STOP_CHECK_INTERVAL, || {
+                is_stopped.load(Ordering::Relaxed)
+            })
+            .filter(|point_id| !id_tracker.is_deleted_point(*point_id))
+            .fold(HashMap::<_, FacetCount>::new(), |mut map, point_id| {
+                for (cell, location) in geo_index.point_cells(point_id, geo_grid.precision) {
+                    let centroid = geo_grid.with_centroid.then_some(location);
+                    map.entry(cell_value(cell))
+                        .or_default()
+                        .merge(FacetCount { count: 1, centroid });
+                }
+                map
+            });
+
+        Ok(hits)
+    }
+
+    /// Count points per bucket of the numeric index
+    fn approximate_histogram_facet(
+        &self,
+        request: &FacetParams,
+        histogram: &HistogramParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>> {
+        let payload_index = self.payload_index.borrow();
+        let histogram_index = payload_index.get_histogram_index(&request.key)?;
+        let buckets = histogram.resolve(histogram_index.is_datetime())?;
+
+        if request.filter.is_none() && buckets.is_sequential() {
+            // Read buckets one after another from the index, which is sorted by value
+            let hits = histogram_index
+                .iter_counts_per_bucket(&buckets)
+                .check_stop(|| is_stopped.load(Ordering::Relaxed))
+                .map(|(bucket, count)| (bucket, FacetCount::from(count)))
+                .collect();
+            return Ok(hits);
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let points_iter = match &request.filter {
+            Some(filter) => {
+                let filter_cardinality = payload_index.estimate_cardinality(filter);
+                Either::Left(payload_index.iter_filtered_points(
+                    filter,
+                    &*id_tracker,
+                    &filter_cardinality,
+                ))
+            }
+            None => Either::Right(id_tracker.iter_ids()),
+        };
+
+        let hits = points_iter
+            .check_stop_every(FACET_STOP_CHECK_INTERVAL, || {
+                is_stopped.load(Ordering::Relaxed)
+            })
+            .filter(|point_id| !id_tracker.is_deleted_point(*point_id))
+            .fold(HashMap::<_, FacetCount>::new(), |mut map, point_id| {
+                for bucket in histogram_index.get_buckets(point_id, &buckets) {
+                    map.entry(bucket).or_default().merge(FacetCount::from(1));
+                }
+                map
+            });
+
+        Ok(hits)
+    }
+
+    /// Count points per combination of values of the facet indexes of all levels.
+    ///
+    /// Walks the points of each value of the first key, and intersects them with
+    /// the values of the other keys. Selective filters iterate the filtered points instead.
+    fn approximate_pivot_facet(
+        &self,
+        request: &FacetParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HashMap<FacetValue, FacetCount>> {
+        let payload_index = self.payload_index.borrow();
+        let facet_index = payload_index.get_facet_index(&request.key)?;
+        let pivot_indexes = request
+            .pivot
+            .iter()
+            .map(|level| payload_index.get_facet_index(&level.key))
+            .collect::<OperationResult<Vec<_>>>()?;
+
+        let available_points = self.available_point_count();
+        if available_points == 0 {
+            return Ok(HashMap::new());
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+
+        let mut counts: HashMap<Vec<FacetValueRef<'_>>, usize> = HashMap::new();
+        let mut count_point = |first_values, point_id| {
+            let level_values = pivot_indexes
+                .iter()
+                .map(|index| index.get_values(point_id).unique().collect_vec());
+            for combination in std::iter::once(first_values)
+                .chain(level_values)
+                .multi_cartesian_product()
+            {
+                *counts.entry(combination).or_insert(0) += 1;
+            }
+        };
+
+        let filter_cardinality = request
+            .filter
+            .as_ref()
+            .map(|filter| (filter, payload_index.estimate_cardinality(filter)));
+
+        match &filter_cardinality {
+            // Same trade-off as for single values
+            Some((filter, cardinality))
+                if (cardinality.exp as f64 / available_points as f64) < 0.3 =>
+            {
+                payload_index
+                    .iter_filtered_points(filter, &*id_tracker, cardinality)
+                    .check_stop_every(FACET_STOP_CHECK_INTERVAL, || {
+                        is_stopped.load(Ordering::Relaxed)
+                    })
+                    .filter(|point_id| !id_tracker.is_deleted_point(*point_id))
+                    .for_each(|point_id| {
+                        let first_values = facet_index.get_values(point_id).unique().collect_vec();
+                        count_point(first_values, point_id);
+                    });
+            }
+            _ => {
+                let context = request
+                    .filter
+                    .as_ref()
+                    .map(|filter| payload_index.struct_filtered_context(filter));
+
+                for (value, point_ids) in facet_index
+                    .iter_values_with_points()
+                    .check_stop(|| is_stopped.load(Ordering::Relaxed))
+                {
+                    point_ids
+                        .unique()
+                        .filter(|&point_id| !id_tracker.is_deleted_point(point_id))
+                        .filter(|&point_id| {
+                            context
+                                .as_ref()
+                                .map_or(true, |context| context.check(point_id))
+                        })
+                        .for_each(|point_id| count_point(vec![value.clone()], point_id));
+                }
+            }
+        }
+
+        let hits = counts
+            .into_iter()
+            .map(|(values, count)| {
+                let tuple = values.iter().map(FacetValueRef::to_owned).collect();
+                (FacetValue::Tuple(tuple), FacetCount::from(count))
+            })
             .collect();
 
         Ok(hits)
     }
 
+    /// Sketch the distinct values of the facet index, of the points matching the filter
+    pub(super) fn approximate_distinct_count(
+        &self,
+        request: &DistinctCountParams,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<HyperLogLog> {
+        let payload_index = self.payload_index.borrow();
+        let facet_index = payload_index.get_facet_index(&request.key)?;
+
+        let mut sketch = HyperLogLog::default();
+
+        let Some(filter) = &request.filter else {
+            facet_index
+                .iter_counts_per_value()
+                .check_stop(|| is_stopped.load(Ordering::Relaxed))
+                .filter(|hit| hit.count > 0)
+                .for_each(|hit| sketch.add(&hit.value));
+            return Ok(sketch);
+        };
+
+        let available_points = self.available_point_count();
+        if available_points == 0 {
+            return Ok(sketch);
+        }
+
+        let filter_cardinality = payload_index.estimate_cardinality(filter);
+        let percentage_filtered = filter_cardinality.exp as f64 / available_points as f64;
+
+        // Same trade-off as for facets, but adding a value to the sketch is cheaper than counting it
+        if percentage_filtered < 0.3 {
+            let id_tracker = self.id_tracker.borrow();
+            payload_index
+                .iter_filtered_points(filter, &*id_tracker, &filter_cardinality)
+                .check_stop_every(FACET_STOP_CHECK_INTERVAL, || {
+                    is_stopped.load(Ordering::Relaxed)
+                })
+                .filter(|point_id| !id_tracker.is_deleted_point(*point_id))
+                .for_each(|point_id| {
+                    facet_index
+                        .get_values(point_id)
+                        .for_each(|value| sketch.add(&value));
+                });
+        } else {
+            let context = payload_index.struct_filtered_context(filter);
+            facet_index
+                .iter_filtered_counts_per_value(&context)
+                .check_stop(|| is_stopped.load(Ordering::Relaxed))
+                .filter(|hit| hit.count > 0)
+                .for_each(|hit| sketch.add(&hit.value));
+        }
+
+        Ok(sketch)
+    }
+
     pub(super) fn facet_values(
         &self,
         key: &JsonPath,
@@ -0,0 +1,85 @@
+use std::sync::atomic::AtomicBool;
+
+use ahash::AHashMap;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+
+use super::Segment;
+use crate::common::operation_error::{check_process_stopped, OperationResult};
+use crate::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::types::{ScoredPoint, WithPayload, WithVector};
+
+impl Segment {
+    /// Rescores points of the prefetches, and returns the internal ids with the scores.
+    ///
+    /// Only points which are present in this segment are considered.
+    pub fn do_rescore_with_formula(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        // Union of the points of all prefetches, as internal ids
+        let mut points_to_rescore: Vec<PointOffsetType> = Vec::new();
+
+        // Transform prefetches results into hashmaps for faster lookup
+        let prefetches_scores = prefetches_results
+            .iter()
+            .map(|scored_points| {
+                scored_points
+                    .iter()
+                    .filter_map(|scored_point| {
+                        // Discard points without internal ids
+                        let internal_id = self.get_internal_id(scored_point.id)?;
+                        points_to_rescore.push(internal_id);
+                        Some((internal_id, scored_point.score))
+                    })
+                    .collect::<AHashMap<PointOffsetType, ScoreType>>()
+            })
+            .collect::<Vec<_>>();
+
+        points_to_rescore.sort_unstable();
+        points_to_rescore.dedup();
+
+        let payload_index = self.payload_index.borrow();
+        let scorer = payload_index.formula_scorer(formula, &prefetches_scores);
+
+        // Perform rescoring
+        let mut error = None;
+        let rescored = points_to_rescore.into_iter().map_while(|internal_id| {
+            let score = check_process_stopped(is_stopped)
+                .and_then(|()| scorer.score(internal_id))
+                .map_err(|err| error = Some(err))
+                .ok()?;
+
+            Some(ScoredPointOffset {
+                idx: internal_id,
+                score,
+            })
+        });
+        let top = peek_top_largest_iterable(rescored, limit);
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(top),
+        }
+    }
+
+    pub(super) fn rescore_with_formula_internal(
+        &self,
+        formula: &ParsedFormula,
+        prefetches_results: &[Vec<ScoredPoint>],
+        limit: usize,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let internal_results =
+            self.do_rescore_with_formula(formula, prefetches_results, limit, is_stopped)?;
+
+        self.process_search_result(
+            internal_results,
+            &WithPayload::from(false),
+            &WithVector::from(false),
+        )
+    }
+}
@@ -1,5 +1,8 @@
+mod aggregate;
+mod bm25;
 mod entry;
 mod facet;
+mod formula_rescore;
 mod order_by;
 mod sampling;
 mod scroll;
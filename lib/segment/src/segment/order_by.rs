@@ -1,15 +1,23 @@
+use std::borrow::Cow;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use common::iterator_ext::IteratorExt;
+use common::types::PointOffsetType;
 use itertools::Either;
 
 use super::Segment;
 use crate::common::operation_error::{OperationError, OperationResult};
-use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
-use crate::index::field_index::numeric_index::StreamRange;
+use crate::data_types::order_by::{Direction, OrderBy, OrderValue, StartFrom};
+use crate::index::field_index::{FieldIndex, OrderableFieldIndex};
+use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::PayloadIndex;
-use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
-use crate::types::{Filter, PointIdType};
+use crate::json_path::JsonPath;
+use crate::types::{DateTimeWrapper, Filter, GeoPoint, PointIdType};
+
+/// Limits above this are not preallocated for the top entries
+const TOP_ENTRIES_PREALLOCATE: usize = 1024;
 
 impl Segment {
     pub fn filtered_read_by_index_ordered(
@@ -22,13 +30,9 @@ impl Segment {
         let payload_index = self.payload_index.borrow();
         let id_tracker = self.id_tracker.borrow();
 
-        let numeric_index = payload_index
-            .field_indexes
-            .get(&order_by.key)
-            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
-            .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
-                key: order_by.key.to_string(),
-            })?;
+        let orderable_index = get_orderable_index(&payload_index, order_by)?;
+        let order_by = &resolve_start_from(order_by, &orderable_index)?;
+        let secondary_indexes = get_secondary_indexes(&payload_index, order_by)?;
 
         let cardinality_estimation = payload_index.estimate_cardinality(condition);
 
@@ -39,7 +43,7 @@ impl Segment {
             .check_stop(|| is_stopped.load(Ordering::Relaxed))
             .flat_map(|internal_id| {
                 // Repeat a point for as many values as it has
-                numeric_index
+                orderable_index
                     .get_ordering_values(internal_id)
                     // But only those which start from `start_from`
                     .filter(|value| match order_by.direction() {
@@ -49,26 +53,18 @@ impl Segment {
                     .map(move |ordering_value| (ordering_value, internal_id))
             })
             .filter_map(|(value, internal_id)| {
-                id_tracker
-                    .external_id(internal_id)
-                    .map(|external_id| (value, external_id))
-            });
+                id_tracker.external_id(internal_id).map(|external_id| {
+                    let value = with_secondary_values(value, internal_id, &secondary_indexes);
+                    (value, external_id)
+                })
+            })
+            .filter(|(value, external_id)| order_by.is_after_cursor(value, *external_id));
 
-        let page = match order_by.direction() {
-            Direction::Asc => {
-                let mut page = match limit {
-                    Some(limit) => peek_top_smallest_iterable(values_ids_iterator, limit),
-                    None => values_ids_iterator.collect(),
-                };
-                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_a.cmp(value_b));
-                page
-            }
-            Direction::Desc => {
-                let mut page = match limit {
-                    Some(limit) => peek_top_largest_iterable(values_ids_iterator, limit),
-                    None => values_ids_iterator.collect(),
-                };
-                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a));
+        let page = match limit {
+            Some(limit) => top_entries(values_ids_iterator, order_by, limit),
+            None => {
+                let mut page: Vec<_> = values_ids_iterator.collect();
+                page.sort_unstable_by(|a, b| order_by.cmp_entries(a, b));
                 page
             }
         };
@@ -85,20 +81,12 @@ impl Segment {
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
         let payload_index = self.payload_index.borrow();
 
-        let numeric_index = payload_index
-            .field_indexes
-            .get(&order_by.key)
-            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()))
-            .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
-                key: order_by.key.to_string(),
-            })?;
-
-        let range_iter = numeric_index.stream_range(&order_by.as_range());
+        let orderable_index = get_orderable_index(&payload_index, order_by)?;
+        let order_by = &resolve_start_from(order_by, &orderable_index)?;
+        let secondary_indexes = get_secondary_indexes(&payload_index, order_by)?;
 
-        let directed_range_iter = match order_by.direction() {
-            Direction::Asc => Either::Left(range_iter),
-            Direction::Desc => Either::Right(range_iter.rev()),
-        };
+        let directed_range_iter =
+            orderable_index.stream_ordered(&order_by.as_range(), order_by.direction());
 
         let id_tracker = self.id_tracker.borrow();
 
@@ -117,12 +105,290 @@ impl Segment {
         let reads = filtered_iter
             .check_stop(|| is_stopped.load(Ordering::Relaxed))
             .filter_map(|(value, internal_id)| {
-                id_tracker
-                    .external_id(internal_id)
-                    .map(|external_id| (value, external_id))
+                id_tracker.external_id(internal_id).map(|external_id| {
+                    let value = with_secondary_values(value, internal_id, &secondary_indexes);
+                    (value, external_id)
+                })
             })
-            .take(limit.unwrap_or(usize::MAX))
-            .collect();
-        Ok(reads)
+            .filter(|(value, external_id)| order_by.is_after_cursor(value, *external_id));
+
+        Ok(page_from_stream(
+            reads,
+            order_by,
+            limit.unwrap_or(usize::MAX),
+        ))
+    }
+}
+
+/// First `limit` entries in the order of results, from a stream ordered by the primary key only.
+///
+/// With secondary keys or a cursor, all entries tied with the last one are read before sorting
+/// by the remaining keys and ids. Otherwise the stream is read only up to the limit.
+fn page_from_stream(
+    entries: impl Iterator<Item = (OrderValue, PointIdType)>,
+    order_by: &OrderBy,
+    limit: usize,
+) -> Vec<(OrderValue, PointIdType)> {
+    if order_by.then_by.is_empty() && order_by.start_after.is_none() {
+        let mut page: Vec<_> = entries.take(limit).collect();
+        page.sort_by(|a, b| order_by.cmp_entries(a, b));
+        return page;
+    }
+
+    let mut page: Vec<(OrderValue, PointIdType)> = Vec::new();
+    for entry in entries {
+        if let Some((last_value, _)) = page.last() {
+            if page.len() >= limit
+                && entry.0.components().first() != last_value.components().first()
+            {
+                break;
+            }
+        }
+        page.push(entry);
+    }
+    page.sort_by(|a, b| order_by.cmp_entries(a, b));
+    page.truncate(limit);
+    page
+}
+
+/// Entry of the results, the heap keeps the one which goes last on top
+struct HeapEntry<'a> {
+    entry: (OrderValue, PointIdType),
+    order_by: &'a OrderBy,
+}
+
+impl PartialEq for HeapEntry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for HeapEntry<'_> {}
+
+impl PartialOrd for HeapEntry<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order_by.cmp_entries(&self.entry, &other.entry)
+    }
+}
+
+/// First `limit` entries in the order of results, without collecting all of them
+fn top_entries(
+    entries: impl Iterator<Item = (OrderValue, PointIdType)>,
+    order_by: &OrderBy,
+    limit: usize,
+) -> Vec<(OrderValue, PointIdType)> {
+    let mut heap = BinaryHeap::with_capacity(limit.min(TOP_ENTRIES_PREALLOCATE));
+    for entry in entries {
+        if heap.len() < limit {
+            heap.push(HeapEntry { entry, order_by });
+        } else if let Some(mut last) = heap.peek_mut() {
+            if order_by.cmp_entries(&entry, &last.entry).is_lt() {
+                last.entry = entry;
+            }
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|heap_entry| heap_entry.entry)
+        .collect()
+}
+
+/// Appends values of the secondary keys to the primary one, if ordering by multiple keys.
+/// A point with several values is ordered by its first one in the direction of the key,
+/// a point without values goes last.
+fn with_secondary_values(
+    primary: OrderValue,
+    internal_id: PointOffsetType,
+    secondary_indexes: &[(OrderableFieldIndex, Direction)],
+) -> OrderValue {
+    if secondary_indexes.is_empty() {
+        return primary;
+    }
+
+    let mut values = Vec::with_capacity(secondary_indexes.len() + 1);
+    values.push(primary);
+    values.extend(secondary_indexes.iter().map(|(index, direction)| {
+        let point_values = index.get_ordering_values(internal_id);
+        match direction {
+            Direction::Asc => point_values.min().unwrap_or(OrderValue::MAX),
+            Direction::Desc => point_values.max().unwrap_or(OrderValue::MIN),
+        }
+    }));
+    OrderValue::Composite(values)
+}
+
+/// Find an index to order by the key, and check that `start_after` has a value for each key
+fn get_orderable_index<'a>(
+    payload_index: &'a StructPayloadIndex,
+    order_by: &OrderBy,
+) -> OperationResult<OrderableFieldIndex<'a>> {
+    let orderable_index = match &order_by.distance_from {
+        Some(origin) => find_geo_distance_index(payload_index, &order_by.key, origin)?,
+        None => find_orderable_index(payload_index, &order_by.key)?,
+    };
+
+    if let Some(cursor) = &order_by.start_after {
+        let keys_count = order_by.then_by.len() + 1;
+        if cursor.values.len() != keys_count {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "`start_after` must have {keys_count} value(s), one for each order key",
+                ),
+            });
+        }
+    }
+
+    Ok(orderable_index)
+}
+
+/// Check that `start_from` is comparable with the values of the index.
+/// Strings are deserialized as keywords, they are parsed as datetimes unless the index is a keyword one.
+fn resolve_start_from<'a>(
+    order_by: &'a OrderBy,
+    orderable_index: &OrderableFieldIndex,
+) -> OperationResult<Cow<'a, OrderBy>> {
+    let is_keyword_index = matches!(orderable_index, OrderableFieldIndex::KeywordIndex(_));
+    let is_geo_distance_index =
+        matches!(orderable_index, OrderableFieldIndex::GeoDistanceIndex(..));
+    match &order_by.start_from {
+        None => {}
+        Some(StartFrom::Keyword(_) | StartFrom::Datetime(_)) if is_geo_distance_index => {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "`start_from` must be a distance in meters to order by distance from a point in `{}`",
+                    order_by.key,
+                ),
+            });
+        }
+        Some(StartFrom::Keyword(keyword)) if !is_keyword_index => {
+            let datetime = DateTimeWrapper::from_str(keyword).map_err(|_| {
+                OperationError::ValidationError {
+                    description: format!(
+                        "`start_from` must be a number or a datetime to order by `{}`",
+                        order_by.key,
+                    ),
+                }
+            })?;
+            let mut resolved = order_by.clone();
+            resolved.start_from = Some(StartFrom::Datetime(datetime));
+            return Ok(Cow::Owned(resolved));
+        }
+        Some(StartFrom::Integer(_) | StartFrom::Float(_) | StartFrom::Datetime(_))
+            if is_keyword_index =>
+        {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "`start_from` must be a keyword to order by keyword field `{}`",
+                    order_by.key,
+                ),
+            });
+        }
+        Some(_) => {}
+    }
+
+    Ok(Cow::Borrowed(order_by))
+}
+
+/// Find indexes for the keys which break ties of the primary one
+fn get_secondary_indexes<'a>(
+    payload_index: &'a StructPayloadIndex,
+    order_by: &OrderBy,
+) -> OperationResult<Vec<(OrderableFieldIndex<'a>, Direction)>> {
+    order_by
+        .then_by
+        .iter()
+        .map(|order_key| {
+            find_orderable_index(payload_index, &order_key.key)
+                .map(|index| (index, order_key.direction()))
+        })
+        .collect()
+}
+
+fn find_geo_distance_index<'a>(
+    payload_index: &'a StructPayloadIndex,
+    key: &JsonPath,
+    origin: &GeoPoint,
+) -> OperationResult<OrderableFieldIndex<'a>> {
+    payload_index
+        .field_indexes
+        .get(key)
+        .and_then(|indexes| {
+            indexes.iter().find_map(|index| match index {
+                FieldIndex::GeoIndex(index) => {
+                    Some(OrderableFieldIndex::GeoDistanceIndex(index, origin.clone()))
+                }
+                _ => None,
+            })
+        })
+        .ok_or_else(|| OperationError::MissingGeoIndexForOrderBy {
+            key: key.to_string(),
+        })
+}
+
+fn find_orderable_index<'a>(
+    payload_index: &'a StructPayloadIndex,
+    key: &JsonPath,
+) -> OperationResult<OrderableFieldIndex<'a>> {
+    payload_index
+        .field_indexes
+        .get(key)
+        .and_then(|indexes| indexes.iter().find_map(|index| index.as_orderable()))
+        .ok_or_else(|| OperationError::MissingRangeIndexForOrderBy {
+            key: key.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::data_types::order_by::OrderByKey;
+
+    fn order_by(then_by: Vec<OrderByKey>) -> OrderBy {
+        OrderBy {
+            key: JsonPath::new("status"),
+            direction: None,
+            start_from: None,
+            then_by,
+            start_after: None,
+            distance_from: None,
+        }
+    }
+
+    #[test]
+    fn test_page_from_stream_reads() {
+        // Low cardinality primary key, all entries are tied
+        let entries = || (0..100u64).map(|id| (OrderValue::Int(1), PointIdType::from(id)));
+
+        // Single key without cursor: only `limit` entries are read
+        let read = Cell::new(0);
+        let page = page_from_stream(
+            entries().inspect(|_| read.set(read.get() + 1)),
+            &order_by(vec![]),
+            10,
+        );
+        assert_eq!(page.len(), 10);
+        assert_eq!(read.get(), 10);
+
+        // Secondary keys need all the ties to order them
+        let read = Cell::new(0);
+        let then_by = vec![OrderByKey {
+            key: JsonPath::new("title"),
+            direction: None,
+        }];
+        let page = page_from_stream(
+            entries().inspect(|_| read.set(read.get() + 1)),
+            &order_by(then_by),
+            10,
+        );
+        assert_eq!(page.len(), 10);
+        assert_eq!(read.get(), 100);
     }
 }
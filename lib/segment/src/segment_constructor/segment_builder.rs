@@ -217,6 +217,7 @@ impl SegmentBuilder {
                 FieldIndex::GeoIndex(_) => {}
                 FieldIndex::FullTextIndex(_) => {}
                 FieldIndex::BoolIndex(_) => {}
+                FieldIndex::GeoShapeIndex(_) => {}
             }
         }
         ordering
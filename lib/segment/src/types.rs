@@ -3,14 +3,17 @@ use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
 use std::hash::Hash;
-use std::ops::Deref;
+use std::ops::{Bound, Deref, RangeBounds};
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::ScoreType;
 use fnv::FnvBuildHasher;
-use geo::{Contains, Coord, Distance as GeoDistance, Haversine, LineString, Point, Polygon};
+use geo::{
+    BoundingRect, Contains, Coord, Distance as GeoDistance, Haversine, Intersects, LineString,
+    Point, Polygon,
+};
 use indexmap::IndexSet;
 use itertools::Itertools;
 use merge::Merge;
@@ -26,15 +29,16 @@ use validator::{Validate, ValidationError, ValidationErrors};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
 use crate::data_types::index::{
-    BoolIndexParams, DatetimeIndexParams, FloatIndexParams, GeoIndexParams, IntegerIndexParams,
-    KeywordIndexParams, TextIndexParams, UuidIndexParams,
+    BoolIndexParams, DatetimeIndexParams, FloatIndexParams, GeoIndexParams, GeoShapeIndexParams,
+    IntegerIndexParams, KeywordIndexParams, TextIndexParams, UuidIndexParams,
 };
+use crate::data_types::keyword_pattern::validate_keyword_pattern;
 use crate::data_types::order_by::OrderValue;
-use crate::data_types::vectors::VectorStructInternal;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorStructInternal};
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
 use crate::json_path::JsonPath;
-use crate::spaces::metric::MetricPostProcessing;
+use crate::spaces::metric::{Metric, MetricPostProcessing};
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 
 pub type PayloadKeyType = JsonPath;
@@ -207,6 +211,30 @@ impl Distance {
         }
     }
 
+    /// Raw similarity between two vectors, greater is closer.
+    ///
+    /// Vectors are expected to be preprocessed, see [`Distance::preprocess_vector`].
+    pub fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        match self {
+            Distance::Cosine => CosineMetric::similarity(v1, v2),
+            Distance::Euclid => EuclidMetric::similarity(v1, v2),
+            Distance::Dot => DotProductMetric::similarity(v1, v2),
+            Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+        }
+    }
+
+    /// Transforms the vector the same way as it is done before storing it, e.g. normalization for Cosine
+    pub fn preprocess_vector(&self, vector: DenseVector) -> DenseVector {
+        match self {
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::preprocess(vector),
+            Distance::Manhattan => {
+                <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
+            }
+        }
+    }
+
     /// Checks if score satisfies threshold condition
     pub fn check_threshold(&self, score: ScoreType, threshold: ScoreType) -> bool {
         match self.distance_order() {
@@ -1253,6 +1281,7 @@ pub enum PayloadSchemaType {
     Bool,
     Datetime,
     Uuid,
+    GeoShape,
 }
 
 impl PayloadSchemaType {
@@ -1271,6 +1300,7 @@ impl PayloadSchemaType {
             Self::Bool => PayloadSchemaParams::Bool(BoolIndexParams::default()),
             Self::Datetime => PayloadSchemaParams::Datetime(DatetimeIndexParams::default()),
             Self::Uuid => PayloadSchemaParams::Uuid(UuidIndexParams::default()),
+            Self::GeoShape => PayloadSchemaParams::GeoShape(GeoShapeIndexParams::default()),
         }
     }
 }
@@ -1287,6 +1317,7 @@ pub enum PayloadSchemaParams {
     Bool(BoolIndexParams),
     Datetime(DatetimeIndexParams),
     Uuid(UuidIndexParams),
+    GeoShape(GeoShapeIndexParams),
 }
 
 impl PayloadSchemaParams {
@@ -1305,6 +1336,7 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Bool(_) => PayloadSchemaType::Bool,
             PayloadSchemaParams::Datetime(_) => PayloadSchemaType::Datetime,
             PayloadSchemaParams::Uuid(_) => PayloadSchemaType::Uuid,
+            PayloadSchemaParams::GeoShape(_) => PayloadSchemaType::GeoShape,
         }
     }
 
@@ -1317,7 +1349,8 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Uuid(uuid) => uuid.is_tenant.unwrap_or_default(),
             PayloadSchemaParams::Geo(_)
             | PayloadSchemaParams::Text(_)
-            | PayloadSchemaParams::Bool(_) => false,
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::GeoShape(_) => false,
         }
     }
 
@@ -1330,7 +1363,24 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Uuid(i) => i.on_disk.unwrap_or_default(),
             PayloadSchemaParams::Text(i) => i.on_disk.unwrap_or_default(),
             PayloadSchemaParams::Geo(i) => i.on_disk.unwrap_or_default(),
-            PayloadSchemaParams::Bool(_) => false,
+            PayloadSchemaParams::Bool(_) | PayloadSchemaParams::GeoShape(_) => false,
+        }
+    }
+}
+
+// The validator crate does not support deriving for enums.
+impl Validate for PayloadSchemaParams {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            PayloadSchemaParams::Text(text) => text.validate(),
+            PayloadSchemaParams::Keyword(_)
+            | PayloadSchemaParams::Integer(_)
+            | PayloadSchemaParams::Float(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::Datetime(_)
+            | PayloadSchemaParams::Uuid(_)
+            | PayloadSchemaParams::GeoShape(_) => Ok(()),
         }
     }
 }
@@ -1342,6 +1392,15 @@ pub enum PayloadFieldSchema {
     FieldParams(PayloadSchemaParams),
 }
 
+impl Validate for PayloadFieldSchema {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            PayloadFieldSchema::FieldType(_) => Ok(()),
+            PayloadFieldSchema::FieldParams(params) => params.validate(),
+        }
+    }
+}
+
 impl Display for PayloadFieldSchema {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -1434,6 +1493,9 @@ pub fn value_type(value: &Value) -> Option<PayloadSchemaType> {
             if let (Some(_), Some(_)) = (lon_op, lat_op) {
                 return Some(PayloadSchemaType::Geo);
             }
+            if GeoShape::from_geojson(value).is_some() {
+                return Some(PayloadSchemaType::GeoShape);
+            }
             None
         }
     }
@@ -1503,6 +1565,68 @@ impl From<String> for MatchText {
     }
 }
 
+/// Full-text phrase match of the strings.
+/// Tokens of the phrase must appear in the document in the same order, next to each other.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPhrase {
+    pub phrase: String,
+    /// Maximal total number of other tokens allowed between the phrase tokens. Default: 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slop: Option<usize>,
+}
+
+/// Full-text match of the strings, tolerant to typos.
+/// Each token of the query matches the indexed tokens within the given edit distance.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchFuzzy {
+    pub fuzzy: String,
+    /// Maximal Levenshtein distance between a query token and a matching token. Default: 1, at most 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(max = 2))]
+    pub max_edits: Option<usize>,
+    /// Maximal number of indexed tokens each query token expands to, the closest ones are taken. Default: 50, at most 1000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 1000))]
+    pub max_expansions: Option<usize>,
+}
+
+impl MatchFuzzy {
+    pub const DEFAULT_MAX_EDITS: usize = 1;
+    pub const DEFAULT_MAX_EXPANSIONS: usize = 50;
+
+    pub fn max_edits(&self) -> usize {
+        self.max_edits.unwrap_or(Self::DEFAULT_MAX_EDITS)
+    }
+
+    pub fn max_expansions(&self) -> usize {
+        self.max_expansions.unwrap_or(Self::DEFAULT_MAX_EXPANSIONS)
+    }
+}
+
+/// Match of the keywords starting with the given prefix
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchPrefix {
+    pub prefix: String,
+}
+
+/// Match of the keywords by a wildcard pattern.
+/// `*` matches any sequence of characters, `?` matches a single character.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchWildcard {
+    pub wildcard: String,
+}
+
+/// Match of the keywords by a regular expression, which must match the whole keyword
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchRegex {
+    pub regex: String,
+}
+
 /// Exact match on any of the given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1510,6 +1634,13 @@ pub struct MatchAny {
     pub any: AnyVariants,
 }
 
+/// Exact match on all of the given values
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchAll {
+    pub all: AnyVariants,
+}
+
 /// Should have at least one value not matching the any given values
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1523,7 +1654,13 @@ pub struct MatchExcept {
 pub enum MatchInterface {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
+    Fuzzy(MatchFuzzy),
+    Prefix(MatchPrefix),
+    Wildcard(MatchWildcard),
+    Regex(MatchRegex),
     Any(MatchAny),
+    All(MatchAll),
     Except(MatchExcept),
 }
 
@@ -1533,7 +1670,13 @@ pub enum MatchInterface {
 pub enum Match {
     Value(MatchValue),
     Text(MatchText),
+    Phrase(MatchPhrase),
+    Fuzzy(MatchFuzzy),
+    Prefix(MatchPrefix),
+    Wildcard(MatchWildcard),
+    Regex(MatchRegex),
     Any(MatchAny),
+    All(MatchAll),
     Except(MatchExcept),
 }
 
@@ -1546,10 +1689,47 @@ impl Match {
         Self::Text(MatchText { text: text.into() })
     }
 
+    pub fn new_phrase(phrase: &str, slop: Option<usize>) -> Self {
+        Self::Phrase(MatchPhrase {
+            phrase: phrase.into(),
+            slop,
+        })
+    }
+
+    pub fn new_fuzzy(fuzzy: &str, max_edits: Option<usize>) -> Self {
+        Self::Fuzzy(MatchFuzzy {
+            fuzzy: fuzzy.into(),
+            max_edits,
+            max_expansions: None,
+        })
+    }
+
+    pub fn new_prefix(prefix: &str) -> Self {
+        Self::Prefix(MatchPrefix {
+            prefix: prefix.into(),
+        })
+    }
+
+    pub fn new_wildcard(wildcard: &str) -> Self {
+        Self::Wildcard(MatchWildcard {
+            wildcard: wildcard.into(),
+        })
+    }
+
+    pub fn new_regex(regex: &str) -> Self {
+        Self::Regex(MatchRegex {
+            regex: regex.into(),
+        })
+    }
+
     pub fn new_any(any: AnyVariants) -> Self {
         Self::Any(MatchAny { any })
     }
 
+    pub fn new_all(all: AnyVariants) -> Self {
+        Self::All(MatchAll { all })
+    }
+
     pub fn new_except(except: AnyVariants) -> Self {
         Self::Except(MatchExcept { except })
     }
@@ -1561,12 +1741,47 @@ impl From<AnyVariants> for Match {
     }
 }
 
+// The validator crate does not support deriving for enums.
+impl Validate for Match {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Match::Fuzzy(fuzzy) => fuzzy.validate(),
+            Match::Prefix(_) | Match::Wildcard(_) | Match::Regex(_) => {
+                validate_keyword_pattern(self)
+            }
+            Match::Value(_)
+            | Match::Text(_)
+            | Match::Phrase(_)
+            | Match::Any(_)
+            | Match::All(_)
+            | Match::Except(_) => Ok(()),
+        }
+    }
+}
+
 impl From<MatchInterface> for Match {
     fn from(value: MatchInterface) -> Self {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
             MatchInterface::Text(text) => Self::Text(MatchText { text: text.text }),
+            MatchInterface::Phrase(phrase) => Self::Phrase(MatchPhrase {
+                phrase: phrase.phrase,
+                slop: phrase.slop,
+            }),
+            MatchInterface::Fuzzy(fuzzy) => Self::Fuzzy(MatchFuzzy {
+                fuzzy: fuzzy.fuzzy,
+                max_edits: fuzzy.max_edits,
+                max_expansions: fuzzy.max_expansions,
+            }),
+            MatchInterface::Prefix(prefix) => Self::Prefix(MatchPrefix {
+                prefix: prefix.prefix,
+            }),
+            MatchInterface::Wildcard(wildcard) => Self::Wildcard(MatchWildcard {
+                wildcard: wildcard.wildcard,
+            }),
+            MatchInterface::Regex(regex) => Self::Regex(MatchRegex { regex: regex.regex }),
             MatchInterface::Any(any) => Self::Any(MatchAny { any: any.any }),
+            MatchInterface::All(all) => Self::All(MatchAll { all: all.all }),
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
             }),
@@ -1621,6 +1836,15 @@ impl From<ValueVariants> for Match {
     }
 }
 
+impl From<Vec<String>> for MatchAll {
+    fn from(keywords: Vec<String>) -> Self {
+        let keywords: IndexSet<String, FnvBuildHasher> = keywords.into_iter().collect();
+        MatchAll {
+            all: AnyVariants::Strings(keywords),
+        }
+    }
+}
+
 impl From<Vec<String>> for MatchExcept {
     fn from(keywords: Vec<String>) -> Self {
         let keywords: IndexSet<String, FnvBuildHasher> = keywords.into_iter().collect();
@@ -1639,6 +1863,15 @@ impl From<Vec<IntPayloadType>> for Match {
     }
 }
 
+impl From<Vec<IntPayloadType>> for MatchAll {
+    fn from(integers: Vec<IntPayloadType>) -> Self {
+        let integers: IndexSet<_, FnvBuildHasher> = integers.into_iter().collect();
+        MatchAll {
+            all: AnyVariants::Integers(integers),
+        }
+    }
+}
+
 impl From<Vec<IntPayloadType>> for MatchExcept {
     fn from(integers: Vec<IntPayloadType>) -> Self {
         let integers: IndexSet<_, FnvBuildHasher> = integers.into_iter().collect();
@@ -1651,13 +1884,32 @@ impl From<Vec<IntPayloadType>> for MatchExcept {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum RangeInterface {
+    Keyword(KeywordRangeInterface),
     Float(Range<FloatPayloadType>),
     DateTime(Range<DateTimePayloadType>),
 }
 
+/// Lexicographic range over keywords.
+/// The bounds are nested, so that a malformed datetime range is rejected instead of being compared as keywords.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct KeywordRangeInterface {
+    pub keyword: Range<String>,
+}
+
+impl From<Range<String>> for RangeInterface {
+    fn from(keyword: Range<String>) -> Self {
+        Self::Keyword(KeywordRangeInterface { keyword })
+    }
+}
+
 /// Range filter request
 #[macro_rules_attribute::macro_rules_derive(crate::common::macros::schemars_rename_generics)]
-#[derive_args(< FloatPayloadType > => "Range", < DateTimePayloadType > => "DatetimeRange")]
+#[derive_args(
+    < FloatPayloadType > => "Range",
+    < DateTimePayloadType > => "DatetimeRange",
+    < String > => "KeywordRange"
+)]
 #[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Range<T> {
@@ -1724,6 +1976,33 @@ impl<T: Copy + PartialOrd> Range<T> {
     }
 }
 
+impl Range<String> {
+    /// Check if the keyword lies in the range, comparing strings lexicographically by bytes
+    pub fn check_keyword(&self, keyword: &str) -> bool {
+        self.as_bounds().contains(&keyword)
+    }
+
+    /// Start and end bounds of the range, the tighter one is taken if both a strict
+    /// and a non-strict bound are given on the same side
+    pub fn as_bounds(&self) -> (Bound<&str>, Bound<&str>) {
+        let start_bound = match (&self.gt, &self.gte) {
+            (Some(gt), Some(gte)) if gte > gt => Bound::Included(gte.as_str()),
+            (Some(gt), _) => Bound::Excluded(gt.as_str()),
+            (None, Some(gte)) => Bound::Included(gte.as_str()),
+            (None, None) => Bound::Unbounded,
+        };
+
+        let end_bound = match (&self.lt, &self.lte) {
+            (Some(lt), Some(lte)) if lte < lt => Bound::Included(lte.as_str()),
+            (Some(lt), _) => Bound::Excluded(lt.as_str()),
+            (None, Some(lte)) => Bound::Included(lte.as_str()),
+            (None, None) => Bound::Unbounded,
+        };
+
+        (start_bound, end_bound)
+    }
+}
+
 /// Values count filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1910,6 +2189,151 @@ impl TryFrom<GeoPolygonShadow> for GeoPolygon {
     }
 }
 
+/// Geo shape payload, parsed from a GeoJSON `Polygon` or `MultiPolygon` geometry
+///
+/// Coordinates are given as `[lon, lat]` pairs, rings must be closed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoShape {
+    pub polygons: Vec<GeoPolygon>,
+}
+
+impl GeoShape {
+    /// Parse GeoJSON geometry object, returns `None` if the value is not a valid polygon or multipolygon
+    pub fn from_geojson(value: &Value) -> Option<Self> {
+        let obj = value.as_object()?;
+        let coordinates = obj.get("coordinates")?.as_array()?;
+        let polygons = match obj.get("type")?.as_str()? {
+            "Polygon" => vec![Self::polygon_from_geojson(coordinates)?],
+            "MultiPolygon" => coordinates
+                .iter()
+                .map(|polygon| Self::polygon_from_geojson(polygon.as_array()?))
+                .collect::<Option<Vec<_>>>()?,
+            _ => return None,
+        };
+        if polygons.is_empty() {
+            return None;
+        }
+        Some(Self { polygons })
+    }
+
+    fn polygon_from_geojson(rings: &[Value]) -> Option<GeoPolygon> {
+        let mut rings = rings
+            .iter()
+            .map(|ring| Self::line_string_from_geojson(ring.as_array()?))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter();
+        let exterior = rings.next()?;
+        let interiors = rings.collect_vec();
+        GeoPolygon::try_from(GeoPolygonShadow {
+            exterior,
+            interiors: (!interiors.is_empty()).then_some(interiors),
+        })
+        .ok()
+    }
+
+    fn line_string_from_geojson(points: &[Value]) -> Option<GeoLineString> {
+        let points = points
+            .iter()
+            .map(|point| match point.as_array()?.as_slice() {
+                [lon, lat, ..] => GeoPoint::new(lon.as_f64()?, lat.as_f64()?).ok(),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(GeoLineString { points })
+    }
+
+    /// Serialize shape back into GeoJSON `MultiPolygon` geometry
+    pub fn to_geojson(&self) -> Value {
+        let line_string = |line: &GeoLineString| {
+            Value::Array(
+                line.points
+                    .iter()
+                    .map(|point| serde_json::json!([point.lon, point.lat]))
+                    .collect(),
+            )
+        };
+        let coordinates = self
+            .polygons
+            .iter()
+            .map(|polygon| {
+                let mut rings = vec![line_string(&polygon.exterior)];
+                rings.extend(polygon.interiors.iter().flatten().map(line_string));
+                Value::Array(rings)
+            })
+            .collect();
+        serde_json::json!({
+            "type": "MultiPolygon",
+            "coordinates": Value::Array(coordinates),
+        })
+    }
+
+    /// Smallest rectangle containing all polygons of the shape
+    pub fn bounding_box(&self) -> Option<GeoBoundingBox> {
+        self.polygons
+            .iter()
+            .filter_map(|polygon| polygon.convert().polygon.bounding_rect())
+            .reduce(|a, b| {
+                geo::Rect::new(
+                    Coord {
+                        x: a.min().x.min(b.min().x),
+                        y: a.min().y.min(b.min().y),
+                    },
+                    Coord {
+                        x: a.max().x.max(b.max().x),
+                        y: a.max().y.max(b.max().y),
+                    },
+                )
+            })
+            .map(|rect| GeoBoundingBox {
+                top_left: GeoPoint {
+                    lon: rect.min().x,
+                    lat: rect.max().y,
+                },
+                bottom_right: GeoPoint {
+                    lon: rect.max().x,
+                    lat: rect.min().y,
+                },
+            })
+    }
+}
+
+/// Geo shape filter request
+///
+/// Matches geo shapes (GeoJSON polygons and multipolygons) by their relation to the given geometry
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoShapeCondition {
+    /// Shape contains the given point
+    Contains(GeoPoint),
+    /// Shape and the given polygon have at least one point in common
+    Intersects(GeoPolygon),
+    /// Shape lies entirely within the given polygon
+    Within(GeoPolygon),
+}
+
+impl GeoShapeCondition {
+    pub fn check_shape(&self, shape: &GeoShape) -> bool {
+        let mut polygons = shape
+            .polygons
+            .iter()
+            .map(|polygon| polygon.convert().polygon);
+        match self {
+            GeoShapeCondition::Contains(point) => {
+                let point = Point::new(point.lon, point.lat);
+                polygons.any(|polygon| polygon.contains(&point))
+            }
+            GeoShapeCondition::Intersects(query) => {
+                let query = query.convert().polygon;
+                polygons.any(|polygon| polygon.intersects(&query))
+            }
+            GeoShapeCondition::Within(query) => {
+                let query = query.convert().polygon;
+                polygons.all(|polygon| query.contains(&polygon))
+            }
+        }
+    }
+}
+
 /// All possible payload filtering conditions
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[validate(schema(function = "validate_field_condition"))]
@@ -1918,6 +2342,7 @@ pub struct FieldCondition {
     /// Payload key
     pub key: PayloadKeyType,
     /// Check if point has field with a given value
+    #[validate(nested)]
     pub r#match: Option<Match>,
     /// Check if points value lies in a given range
     pub range: Option<RangeInterface>,
@@ -1927,6 +2352,8 @@ pub struct FieldCondition {
     pub geo_radius: Option<GeoRadius>,
     /// Check if geo point is within a given polygon
     pub geo_polygon: Option<GeoPolygon>,
+    /// Check relation of geo shape to a given point or polygon
+    pub geo_shape: Option<GeoShapeCondition>,
     /// Check number of values of the field
     pub values_count: Option<ValuesCount>,
 }
@@ -1940,6 +2367,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1952,6 +2380,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1964,6 +2393,20 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
+            values_count: None,
+        }
+    }
+
+    pub fn new_keyword_range(key: JsonPath, keyword_range: Range<String>) -> Self {
+        Self {
+            key,
+            r#match: None,
+            range: Some(RangeInterface::from(keyword_range)),
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1976,6 +2419,7 @@ impl FieldCondition {
             geo_bounding_box: Some(geo_bounding_box),
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1988,6 +2432,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: Some(geo_radius),
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -2000,6 +2445,20 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: Some(geo_polygon),
+            geo_shape: None,
+            values_count: None,
+        }
+    }
+
+    pub fn new_geo_shape(key: JsonPath, geo_shape: GeoShapeCondition) -> Self {
+        Self {
+            key,
+            r#match: None,
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            geo_shape: Some(geo_shape),
             values_count: None,
         }
     }
@@ -2012,6 +2471,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: Some(values_count),
         }
     }
@@ -2025,6 +2485,7 @@ impl FieldCondition {
                 geo_bounding_box: None,
                 geo_radius: None,
                 geo_polygon: None,
+                geo_shape: None,
                 values_count: None,
                 key: _,
             }
@@ -2034,12 +2495,11 @@ impl FieldCondition {
 
 pub fn validate_field_condition(field_condition: &FieldCondition) -> Result<(), ValidationError> {
     if field_condition.all_fields_none() {
-        Err(ValidationError::new(
+        return Err(ValidationError::new(
             "At least one field condition must be specified",
-        ))
-    } else {
-        Ok(())
+        ));
     }
+    Ok(())
 }
 
 /// Payload field
@@ -2144,6 +2604,35 @@ impl NestedCondition {
     }
 }
 
+/// Select points whose payload field refers to points of another collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
+pub struct Join {
+    /// Payload field holding the ids or keys of the related points
+    pub key: PayloadKeyType,
+    /// Name of the collection of the related points
+    #[validate(length(min = 1, max = 255))]
+    pub collection: String,
+    /// Select only related points which satisfy this filter
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+    /// Payload field of the related points to match the key with. If not provided - their ids are used.
+    pub related_key: Option<PayloadKeyType>,
+    /// Maximum amount of related points. The request fails if more of them match the filter. Default is 10000.
+    #[validate(range(min = 1, max = 100_000))]
+    pub limit: Option<usize>,
+}
+
+impl Join {
+    pub const DEFAULT_LIMIT: usize = 10_000;
+}
+
+/// Container to workaround the untagged enum limitation for condition
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
+pub struct JoinCondition {
+    #[validate(nested)]
+    pub join: Join,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged)]
 #[serde(
@@ -2163,6 +2652,8 @@ pub enum Condition {
     HasVector(HasVectorCondition),
     /// Nested filters
     Nested(NestedCondition),
+    /// Check if payload field refers to points of another collection
+    Join(JoinCondition),
     /// Nested filter
     Filter(Filter),
 
@@ -2179,6 +2670,7 @@ impl PartialEq for Condition {
             (Self::HasId(this), Self::HasId(other)) => this == other,
             (Self::HasVector(this), Self::HasVector(other)) => this == other,
             (Self::Nested(this), Self::Nested(other)) => this == other,
+            (Self::Join(this), Self::Join(other)) => this == other,
             (Self::Filter(this), Self::Filter(other)) => this == other,
             (Self::CustomIdChecker(_), Self::CustomIdChecker(_)) => false,
             _ => false,
@@ -2204,6 +2696,7 @@ impl Validate for Condition {
             | Condition::HasVector(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
+            Condition::Join(join_condition) => join_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
             Condition::CustomIdChecker(_) => Ok(()),
         }
@@ -2942,6 +3435,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_range_query() {
+        let query = r#"
+        {
+            "key": "hello",
+            "range": { "gte": 1.5, "lt": 10 }
+        }
+        "#;
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert!(matches!(condition.range, Some(RangeInterface::Float(_))));
+
+        let query = r#"
+        {
+            "key": "hello",
+            "range": { "gte": "2024-01-01T00:00:00Z" }
+        }
+        "#;
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert!(matches!(condition.range, Some(RangeInterface::DateTime(_))));
+
+        let query = r#"
+        {
+            "key": "hello",
+            "range": { "keyword": { "gte": "US-CA", "lt": "US-NY" } }
+        }
+        "#;
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        let Some(RangeInterface::Keyword(range)) = condition.range else {
+            panic!("expected keyword range, got {:?}", condition.range);
+        };
+        let range = range.keyword;
+        assert!(range.check_keyword("US-CA"));
+        assert!(range.check_keyword("US-MA"));
+        assert!(!range.check_keyword("US-NY"));
+        assert!(!range.check_keyword("US"));
+
+        // Both bounds of a side are respected
+        let range = Range {
+            gt: Some("US-CA".to_string()),
+            gte: Some("US-MA".to_string()),
+            lt: None,
+            lte: Some("US-NY".to_string()),
+        };
+        assert_eq!(
+            range.as_bounds(),
+            (Bound::Included("US-MA"), Bound::Included("US-NY"))
+        );
+        assert!(!range.check_keyword("US-CO"));
+        assert!(range.check_keyword("US-MA"));
+
+        // Malformed datetimes are not compared as keywords
+        let query = r#"
+        {
+            "key": "hello",
+            "range": { "gte": "2024-13-01" }
+        }
+        "#;
+        assert!(serde_json::from_str::<FieldCondition>(query).is_err());
+    }
+
     #[test]
     fn test_parse_match_any() {
         let query = r#"
@@ -3008,6 +3561,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_match_all() {
+        let query = r#"
+        {
+            "key": "tags",
+            "match": { "all": ["red", "green"] }
+        }
+        "#;
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert_eq!(
+            condition.r#match.unwrap(),
+            Match::All(MatchAll::from(vec!["red".to_string(), "green".to_string()]))
+        );
+
+        let query = r#"
+        {
+            "key": "sizes",
+            "match": { "all": [1, 2, 3] }
+        }
+        "#;
+        let condition: FieldCondition = serde_json::from_str(query).unwrap();
+        assert_eq!(
+            condition.r#match.unwrap(),
+            Match::All(MatchAll::from(vec![1, 2, 3]))
+        );
+    }
+
     #[test]
     fn test_parse_nested_match_query() {
         let query = r#"
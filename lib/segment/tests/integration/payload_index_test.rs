@@ -12,7 +12,11 @@ use indexmap::IndexSet;
 use itertools::Itertools;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
-use segment::data_types::facets::{FacetParams, FacetValue};
+use segment::data_types::aggregations::{AggregateParams, AggregateValue, DistinctCountParams};
+use segment::data_types::facets::{
+    CalendarInterval, FacetCount, FacetParams, FacetPivotParams, FacetValue, GeoGridParams,
+    HistogramBound, HistogramParams, HistogramRange,
+};
 use segment::data_types::index::{
     FloatIndexParams, FloatIndexType, IntegerIndexParams, IntegerIndexType, KeywordIndexParams,
     KeywordIndexType, TextIndexParams, TextIndexType,
@@ -25,6 +29,7 @@ use segment::fixtures::payload_fixtures::{
     random_vector, FLICKING_KEY, FLT_KEY, GEO_KEY, INT_KEY, INT_KEY_2, INT_KEY_3, LAT_RANGE,
     LON_RANGE, STR_KEY, STR_PROJ_KEY, STR_ROOT_PROJ_KEY, TEXT_KEY,
 };
+use segment::index::field_index::geo_hash::{geo_hash_to_box, GeoHash};
 use segment::index::field_index::{FieldIndex, PrimaryCondition};
 use segment::index::struct_payload_index::StructPayloadIndex;
 use segment::index::PayloadIndex;
@@ -39,7 +44,7 @@ use segment::types::PayloadSchemaType::{Integer, Keyword};
 use segment::types::{
     AnyVariants, Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoLineString,
     GeoPoint, GeoPolygon, GeoRadius, HnswConfig, Indexes, IsEmptyCondition, Match, Payload,
-    PayloadField, PayloadSchemaParams, PayloadSchemaType, Range, SegmentConfig, ValueVariants,
+    PayloadContainer, PayloadField, PayloadSchemaParams, PayloadSchemaType, Range, SegmentConfig,
     VectorDataConfig, VectorStorageType, WithPayload,
 };
 use segment::utils::scored_point_ties::ScoredPointTies;
@@ -257,6 +262,7 @@ impl TestSegments {
                         r#type: KeywordIndexType::Keyword,
                         is_tenant: None,
                         on_disk: Some(true),
+                        normalizer: None,
                     },
                 ))),
             )
@@ -1177,24 +1183,20 @@ fn test_any_matcher_cardinality_estimation() {
 /// Checks that the counts are the same as counting each value exactly.
 fn validate_facet_result(
     segment: &Segment,
-    facet_hits: HashMap<FacetValue, usize>,
+    keys: &[JsonPath],
+    facet_hits: HashMap<FacetValue, FacetCount>,
     filter: Option<Filter>,
 ) {
     for (value, count) in facet_hits.iter() {
         // Compare against exact count
-        let value = ValueVariants::from(value.clone());
-
-        let count_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
-            JsonPath::new(STR_KEY),
-            Match::from(value),
-        )));
+        let count_filter = value.to_filter(keys);
         let count_filter = Filter::merge_opts(Some(count_filter), filter.clone());
 
         let exact = segment
             .read_filtered(None, None, count_filter.as_ref(), &Default::default())
             .len();
 
-        assert_eq!(*count, exact);
+        assert_eq!(count.count, exact);
     }
 }
 
@@ -1212,6 +1214,9 @@ fn test_keyword_facet() {
         limit,
         filter: None,
         exact,
+        geo_grid: None,
+        histogram: None,
+        pivot: vec![],
     };
 
     // Plain segment should fail, as it does not have a keyword index
@@ -1226,7 +1231,12 @@ fn test_keyword_facet() {
         .facet(&request, &Default::default())
         .unwrap();
 
-    validate_facet_result(&test_segments.struct_segment, facet_hits, None);
+    validate_facet_result(
+        &test_segments.struct_segment,
+        &[key.clone()],
+        facet_hits,
+        None,
+    );
 
     // Mmap segment
     let facet_hits = test_segments
@@ -1234,16 +1244,24 @@ fn test_keyword_facet() {
         .facet(&request, &Default::default())
         .unwrap();
 
-    validate_facet_result(&test_segments.mmap_segment, facet_hits, None);
+    validate_facet_result(
+        &test_segments.mmap_segment,
+        &[key.clone()],
+        facet_hits,
+        None,
+    );
 
     // *** With filter ***
     let mut rng = rand::thread_rng();
     let filter = random_filter(&mut rng, 3);
     let request = FacetParams {
-        key,
+        key: key.clone(),
         limit,
         filter: Some(filter.clone()),
         exact,
+        geo_grid: None,
+        histogram: None,
+        pivot: vec![],
     };
 
     // Struct segment
@@ -1254,6 +1272,7 @@ fn test_keyword_facet() {
 
     validate_facet_result(
         &test_segments.struct_segment,
+        &[key.clone()],
         facet_hits,
         Some(filter.clone()),
     );
@@ -1264,5 +1283,298 @@ fn test_keyword_facet() {
         .facet(&request, &Default::default())
         .unwrap();
 
-    validate_facet_result(&test_segments.mmap_segment, facet_hits, Some(filter));
+    validate_facet_result(
+        &test_segments.mmap_segment,
+        &[key.clone()],
+        facet_hits,
+        Some(filter),
+    );
+}
+
+#[test]
+fn test_geo_grid_facet() {
+    let test_segments = TestSegments::new();
+    let segment = &test_segments.struct_segment;
+
+    let precision = 2;
+    let mut request = FacetParams {
+        key: JsonPath::new(GEO_KEY),
+        limit: 100,
+        filter: None,
+        exact: false,
+        geo_grid: Some(GeoGridParams {
+            precision,
+            with_centroid: false,
+        }),
+        histogram: None,
+        pivot: vec![],
+    };
+
+    // Plain segment should fail, as it does not have a geo index
+    assert!(test_segments
+        .plain_segment
+        .facet(&request, &Default::default())
+        .is_err());
+
+    // Counts are read from the geo index
+    let indexed_hits = segment.facet(&request, &Default::default()).unwrap();
+    assert!(!indexed_hits.is_empty());
+
+    // Counts are read point by point to compute the centroids
+    request.geo_grid = Some(GeoGridParams {
+        precision,
+        with_centroid: true,
+    });
+    let facet_hits = segment.facet(&request, &Default::default()).unwrap();
+    assert_eq!(facet_hits.len(), indexed_hits.len());
+
+    for (value, count) in facet_hits {
+        assert_eq!(count.count, indexed_hits[&value].count);
+
+        let FacetValue::GeoHash(hash) = value else {
+            panic!("expected a geo-hash value, got {value:?}");
+        };
+        assert_eq!(hash.len(), precision);
+
+        let cell = geo_hash_to_box(GeoHash::new(&hash).unwrap());
+        let centroid = count.centroid.expect("centroid should be computed");
+        assert!(cell.check_point(&centroid));
+    }
+
+    // With a filter, only matching points are counted
+    let mut rng = rand::thread_rng();
+    let filter = random_filter(&mut rng, 3);
+    request.filter = Some(filter.clone());
+
+    let filtered_hits = segment.facet(&request, &Default::default()).unwrap();
+    for (value, count) in &filtered_hits {
+        assert!(count.count <= indexed_hits[value].count);
+    }
+
+    // Points with many values can be in many cells, but each matching point is in at least one
+    let total_count: usize = filtered_hits.values().map(|count| count.count).sum();
+    let matching_points = segment
+        .read_filtered(None, None, Some(&filter), &Default::default())
+        .len();
+    assert!(total_count >= matching_points);
+}
+
+#[test]
+fn test_histogram_facet() {
+    let test_segments = TestSegments::new();
+    let segment = &test_segments.struct_segment;
+    let key = JsonPath::new(INT_KEY);
+
+    let mut rng = rand::thread_rng();
+    let filter = random_filter(&mut rng, 3);
+
+    let histograms = [
+        HistogramParams::Interval(5.0),
+        HistogramParams::Ranges(vec![
+            HistogramRange {
+                from: None,
+                to: Some(HistogramBound::Float(0.0)),
+            },
+            HistogramRange {
+                from: Some(HistogramBound::Float(-10.0)),
+                to: Some(HistogramBound::Float(10.0)),
+            },
+            HistogramRange {
+                from: Some(HistogramBound::Float(10.0)),
+                to: None,
+            },
+        ]),
+    ];
+
+    for histogram in histograms {
+        for filter in [None, Some(filter.clone())] {
+            let request = FacetParams {
+                key: key.clone(),
+                limit: 100,
+                filter: filter.clone(),
+                exact: false,
+                geo_grid: None,
+                histogram: Some(histogram.clone()),
+                pivot: vec![],
+            };
+
+            // Plain segment should fail, as it does not have a range index
+            assert!(test_segments
+                .plain_segment
+                .facet(&request, &Default::default())
+                .is_err());
+
+            let facet_hits = segment.facet(&request, &Default::default()).unwrap();
+            assert!(facet_hits
+                .keys()
+                .all(|value| matches!(value, FacetValue::FloatRange(_))));
+
+            validate_facet_result(segment, &[key.clone()], facet_hits, filter);
+        }
+    }
+
+    // Calendar intervals are only for datetime fields
+    let request = FacetParams {
+        key,
+        limit: 100,
+        filter: None,
+        exact: false,
+        geo_grid: None,
+        histogram: Some(HistogramParams::CalendarInterval(CalendarInterval::Month)),
+        pivot: vec![],
+    };
+    assert!(segment.facet(&request, &Default::default()).is_err());
+}
+
+#[test]
+fn test_pivot_facet() {
+    let test_segments = TestSegments::new();
+    let keys = [JsonPath::new(STR_KEY), JsonPath::new(INT_KEY_2)];
+
+    let mut rng = rand::thread_rng();
+    let filter = random_filter(&mut rng, 3);
+
+    for filter in [None, Some(filter)] {
+        let request = FacetParams {
+            key: keys[0].clone(),
+            limit: 100,
+            filter: filter.clone(),
+            exact: false,
+            geo_grid: None,
+            histogram: None,
+            pivot: vec![FacetPivotParams {
+                key: keys[1].clone(),
+                limit: 100,
+            }],
+        };
+
+        // Plain segment should fail, as it does not have a keyword index
+        assert!(test_segments
+            .plain_segment
+            .facet(&request, &Default::default())
+            .is_err());
+
+        for segment in [&test_segments.struct_segment, &test_segments.mmap_segment] {
+            let facet_hits = segment.facet(&request, &Default::default()).unwrap();
+            assert!(facet_hits.keys().all(
+                |value| matches!(value, FacetValue::Tuple(values) if values.len() == keys.len())
+            ));
+
+            validate_facet_result(segment, &keys, facet_hits, filter.clone());
+        }
+    }
+}
+
+#[test]
+fn test_aggregate() {
+    let test_segments = TestSegments::new();
+    let key = JsonPath::new(INT_KEY);
+
+    let mut rng = rand::thread_rng();
+    let filter = random_filter(&mut rng, 3);
+
+    for filter in [None, Some(filter)] {
+        let request = AggregateParams {
+            key: key.clone(),
+            filter: filter.clone(),
+            percentiles: vec![0.0, 50.0, 100.0],
+        };
+
+        // Compute the expected statistics from the payloads of the matching points
+        let mut values = test_segments
+            .plain_segment
+            .read_filtered(None, None, filter.as_ref(), &Default::default())
+            .into_iter()
+            .flat_map(|point_id| {
+                let payload = test_segments.plain_segment.payload(point_id).unwrap();
+                payload
+                    .get_value(&key)
+                    .into_iter()
+                    .flat_map(|value| match value {
+                        serde_json::Value::Array(values) => values.clone(),
+                        value => vec![value.clone()],
+                    })
+                    .filter_map(|value| value.as_f64())
+                    .collect_vec()
+            })
+            .collect_vec();
+        values.sort_by(f64::total_cmp);
+
+        // The struct segment reads the numeric index, the plain one reads the payload
+        for segment in [&test_segments.struct_segment, &test_segments.plain_segment] {
+            let response = segment
+                .aggregate(&request, &Default::default())
+                .unwrap()
+                .finalize(&request.percentiles);
+
+            assert_eq!(response.count, values.len());
+            assert_eq!(
+                response.min,
+                values.first().copied().map(AggregateValue::Number)
+            );
+            assert_eq!(
+                response.max,
+                values.last().copied().map(AggregateValue::Number)
+            );
+            let expected_sum: f64 = values.iter().sum();
+            assert!(response
+                .sum
+                .map_or(values.is_empty(), |sum| (sum - expected_sum).abs() < 1e-6));
+
+            if !values.is_empty() {
+                assert_eq!(response.percentiles.len(), 3);
+                assert_eq!(response.percentiles[0].value, response.min.unwrap());
+                assert_eq!(response.percentiles[2].value, response.max.unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_distinct_count() {
+    let test_segments = TestSegments::new();
+    let key = JsonPath::new(STR_KEY);
+
+    let mut rng = rand::thread_rng();
+    let filter = random_filter(&mut rng, 3);
+
+    for filter in [None, Some(filter)] {
+        let request = DistinctCountParams {
+            key: key.clone(),
+            filter: filter.clone(),
+            exact: false,
+        };
+
+        // Plain segment should fail, as it does not have a keyword index
+        assert!(test_segments
+            .plain_segment
+            .distinct_count(&request, &Default::default())
+            .is_err());
+
+        for segment in [&test_segments.struct_segment, &test_segments.mmap_segment] {
+            let expected = segment
+                .unique_values(&key, filter.as_ref(), &Default::default())
+                .unwrap()
+                .into_iter()
+                .filter(|value| {
+                    let value_filter = value.to_filter(&[key.clone()]);
+                    let value_filter = Filter::merge_opts(Some(value_filter), filter.clone());
+                    !segment
+                        .read_filtered(None, Some(1), value_filter.as_ref(), &Default::default())
+                        .is_empty()
+                })
+                .count();
+
+            let estimate = segment
+                .distinct_count(&request, &Default::default())
+                .unwrap()
+                .estimate();
+
+            // Few values are counted almost exactly, two of them may share a register
+            assert!(
+                estimate.abs_diff(expected) <= 1 + expected / 50,
+                "estimate {estimate} vs {expected}",
+            );
+        }
+    }
 }
@@ -79,6 +79,7 @@ fn test_on_disk_segment_snapshot(#[case] format: SnapshotFormat) {
                     r#type: segment::data_types::index::KeywordIndexType::Keyword,
                     is_tenant: None,
                     on_disk: Some(true),
+                    normalizer: None,
                 }),
             )),
         )
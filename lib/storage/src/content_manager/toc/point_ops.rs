@@ -4,24 +4,30 @@ use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
 use collection::collection::Collection;
-use collection::grouping::group_by::GroupRequest;
+use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::grouping::GroupBy;
+use collection::lookup::join;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::point_ops::WriteOrdering;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
-use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use collection::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest,
+};
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
+use segment::data_types::aggregations::{
+    AggregateParams, AggregateResponse, DistinctCountParams, DistinctCountResponse,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse};
-use segment::types::{ScoredPoint, ShardKey};
+use segment::types::{Filter, ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::{StorageError, StorageResult};
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -46,6 +52,13 @@ impl TableOfContent {
         hw_measurement_acc: &HwMeasurementAcc,
     ) -> StorageResult<Vec<ScoredPoint>> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
         recommendations::recommend_by(
@@ -88,6 +101,16 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
+        self.resolve_joins(
+            requests
+                .iter_mut()
+                .filter_map(|(request, _)| request.filter.as_mut()),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
+
         let collection = self.get_collection(&collection_pass).await?;
         recommendations::recommend_batch_by(
             requests,
@@ -134,6 +157,17 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
+        self.resolve_joins(
+            request
+                .searches
+                .iter_mut()
+                .filter_map(|request| request.filter.as_mut()),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
+
         let collection = self.get_collection(&collection_pass).await?;
         collection
             .core_search_batch(
@@ -171,6 +205,13 @@ impl TableOfContent {
         hw_measurement_acc: &HwMeasurementAcc,
     ) -> StorageResult<CountResult> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
@@ -226,6 +267,13 @@ impl TableOfContent {
         hw_measurement_acc: &HwMeasurementAcc,
     ) -> StorageResult<GroupsResult> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            group_request_filters_mut(&mut request),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
 
@@ -255,6 +303,13 @@ impl TableOfContent {
         hw_measurement_acc: &HwMeasurementAcc,
     ) -> StorageResult<Vec<ScoredPoint>> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
         discovery::discover(
@@ -287,6 +342,16 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
+        self.resolve_joins(
+            requests
+                .iter_mut()
+                .filter_map(|(request, _)| request.filter.as_mut()),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
+
         let collection = self.get_collection(&collection_pass).await?;
 
         discovery::discover_batch(
@@ -322,6 +387,13 @@ impl TableOfContent {
         access: Access,
     ) -> StorageResult<ScrollResult> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
@@ -348,6 +420,16 @@ impl TableOfContent {
             return Ok(vec![]);
         };
 
+        self.resolve_joins(
+            requests
+                .iter_mut()
+                .flat_map(|(request, _)| query_filters_mut(request)),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
+
         let collection = self.get_collection(&collection_pass).await?;
 
         collection
@@ -373,6 +455,13 @@ impl TableOfContent {
         timeout: Option<Duration>,
     ) -> StorageResult<FacetResponse> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
 
@@ -382,6 +471,60 @@ impl TableOfContent {
             .map_err(StorageError::from)
     }
 
+    // Return count, sum, min, max, average and percentiles of a numeric or datetime payload key.
+    pub async fn aggregate(
+        &self,
+        collection_name: &str,
+        mut request: AggregateParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<AggregateResponse> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .aggregate(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(StorageError::from)
+    }
+
+    // Return the number of distinct values of a keyword, integer or uuid payload key.
+    pub async fn distinct_count(
+        &self,
+        collection_name: &str,
+        mut request: DistinctCountParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<DistinctCountResponse> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .distinct_count(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(StorageError::from)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn search_points_matrix(
         &self,
@@ -394,6 +537,13 @@ impl TableOfContent {
         hw_measurement_acc: &HwMeasurementAcc,
     ) -> Result<CollectionSearchMatrixResponse, StorageError> {
         let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.resolve_joins(
+            request.filter.iter_mut(),
+            &access,
+            read_consistency,
+            timeout,
+        )
+        .await?;
 
         let collection = self.get_collection(&collection_pass).await?;
 
@@ -409,6 +559,36 @@ impl TableOfContent {
             .map_err(StorageError::from)
     }
 
+    /// Replace the join conditions of the filters with conditions on the related points
+    ///
+    /// Related points are selected with an arbitrary filter, so the joined collections
+    /// require access to the whole collection.
+    async fn resolve_joins(
+        &self,
+        filters: impl IntoIterator<Item = &mut Filter>,
+        access: &Access,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> StorageResult<()> {
+        for filter in filters {
+            for joined_collection in join::joined_collections(filter) {
+                access.check_collection_access(
+                    joined_collection,
+                    AccessRequirements::new().whole(),
+                )?;
+            }
+
+            join::resolve_joins(
+                filter,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+                timeout,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -454,6 +634,13 @@ impl TableOfContent {
     ) -> StorageResult<UpdateResult> {
         let collection_pass = access.check_point_op(collection_name, &mut operation.operation)?;
 
+        // Joins are only resolved for reads, the points to update are selected on their own
+        if operation.operation.filter().is_some_and(join::has_joins) {
+            return Err(StorageError::bad_input(
+                "Join conditions are not supported in the filters of updates",
+            ));
+        }
+
         // `TableOfContent::_update_shard_keys` and `Collection::update_from_*` are cancel safe,
         // so this method is cancel safe.
 
@@ -557,3 +744,30 @@ impl TableOfContent {
         Ok(res)
     }
 }
+
+fn group_request_filters_mut(request: &mut GroupRequest) -> Vec<&mut Filter> {
+    match &mut request.source {
+        SourceRequest::Search(request) => request.filter.iter_mut().collect(),
+        SourceRequest::Recommend(request) => request.filter.iter_mut().collect(),
+        SourceRequest::Query(request) => query_filters_mut(request),
+    }
+}
+
+/// Filters of the query and of all its prefetches
+fn query_filters_mut(request: &mut CollectionQueryRequest) -> Vec<&mut Filter> {
+    fn prefetch_filters_mut<'a>(
+        prefetch: &'a mut CollectionPrefetch,
+        filters: &mut Vec<&'a mut Filter>,
+    ) {
+        filters.extend(prefetch.filter.as_mut());
+        for prefetch in &mut prefetch.prefetch {
+            prefetch_filters_mut(prefetch, filters);
+        }
+    }
+
+    let mut filters: Vec<_> = request.filter.iter_mut().collect();
+    for prefetch in &mut request.prefetch {
+        prefetch_filters_mut(prefetch, &mut filters);
+    }
+    filters
+}
@@ -7,6 +7,9 @@ use collection::operations::types::UpdateResult;
 use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use segment::data_types::aggregations::{
+    AggregateParams, DistinctCountParams, PartialAggregate, PartialDistinctCount,
+};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 
 use super::TableOfContent;
@@ -47,6 +50,38 @@ impl TableOfContent {
         Ok(res)
     }
 
+    pub async fn aggregate_internal(
+        &self,
+        collection_name: &str,
+        request: AggregateParams,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> StorageResult<PartialAggregate> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection
+            .aggregate_partial(request, shard_selection, None, timeout)
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn distinct_count_internal(
+        &self,
+        collection_name: &str,
+        request: DistinctCountParams,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> StorageResult<PartialDistinctCount> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection
+            .distinct_count_partial(request, shard_selection, None, timeout)
+            .await?;
+
+        Ok(res)
+    }
+
     pub async fn cleanup_local_shard(
         &self,
         collection_name: &str,
@@ -17,6 +17,7 @@ use collection::operations::universal_query::collection_query::{
 };
 use collection::operations::vector_ops::VectorOperations;
 use collection::operations::CollectionUpdateOperations;
+use segment::data_types::aggregations::{AggregateParams, DistinctCountParams};
 use segment::data_types::facets::FacetParams;
 use segment::types::{Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload};
 
@@ -148,6 +149,7 @@ impl CollectionAccessView<'_> {
                     self.check_vector_input(vector_input)?
                 }
             }
+            VectorQuery::Mmr(mmr) => self.check_vector_input(&mmr.vector)?,
         };
 
         Ok(())
@@ -386,6 +388,44 @@ impl CheckableCollectionOperation for FacetParams {
     }
 }
 
+impl CheckableCollectionOperation for AggregateParams {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> StorageResult<()> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for DistinctCountParams {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> StorageResult<()> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -671,6 +711,7 @@ mod tests_ops {
         CollectionUpdateOperationsDiscriminants, CreateIndex, FieldIndexOperations,
         FieldIndexOperationsDiscriminants,
     };
+    use segment::data_types::groups::GroupScore;
     use segment::data_types::vectors::NamedVectorStruct;
     use segment::types::{PointIdType, SearchParams, WithPayloadInterface, WithVector};
     use strum::IntoEnumIterator as _;
@@ -918,7 +959,8 @@ mod tests_ops {
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: Some(42.0),
             }),
-            group_by: "path".parse().unwrap(),
+            group_by: vec!["path".parse().unwrap()],
+            group_score: GroupScore::Max,
             group_size: 100,
             limit: 100,
             with_lookup: Some(WithLookup {
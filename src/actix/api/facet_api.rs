@@ -1,6 +1,6 @@
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{FacetRequest, FacetResponse};
+use api::rest::{DistinctCountRequest, DistinctCountResponse, FacetRequest, FacetResponse};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use storage::content_manager::collection_verification::check_strict_mode;
 use storage::dispatcher::Dispatcher;
@@ -72,6 +72,64 @@ async fn facet(
     process_response(response, timing, request_hw_counter.to_rest_api())
 }
 
+#[post("/collections/{name}/facet/distinct")]
+async fn distinct_count(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<DistinctCountRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let DistinctCountRequest {
+        distinct_count_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &distinct_count_request,
+        params.timeout_as_secs(),
+        &collection.name,
+        &dispatcher,
+        &access,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, timing, None),
+    };
+
+    let distinct_count_params = From::from(distinct_count_request);
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.name.clone(),
+        service_config.hardware_reporting(),
+    );
+
+    let response = dispatcher
+        .toc(&access, &pass)
+        .distinct_count(
+            &collection.name,
+            distinct_count_params,
+            shard_selection,
+            params.consistency,
+            access,
+            params.timeout(),
+        )
+        .await
+        .map(DistinctCountResponse::from);
+
+    process_response(response, timing, request_hw_counter.to_rest_api())
+}
+
 pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
-    cfg.service(facet);
+    cfg.service(facet).service(distinct_count);
 }
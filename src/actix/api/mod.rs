@@ -2,6 +2,7 @@ use common::validation::validate_collection_name;
 use serde::Deserialize;
 use validator::Validate;
 
+pub mod aggregate_api;
 pub mod cluster_api;
 pub mod collections_api;
 pub mod count_api;
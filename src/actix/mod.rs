@@ -17,6 +17,7 @@ use actix_multipart::form::MultipartFormConfig;
 use actix_web::middleware::{Compress, Condition, Logger};
 use actix_web::{error, get, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use actix_web_extras::middleware::Condition as ConditionEx;
+use api::aggregate_api::config_aggregate_api;
 use api::facet_api::config_facet_api;
 use collection::operations::validation;
 use collection::operations::verification::new_unchecked_verification_pass;
@@ -155,6 +156,7 @@ pub fn init(
                 .configure(config_discovery_api)
                 .configure(config_query_api)
                 .configure(config_facet_api)
+                .configure(config_aggregate_api)
                 .configure(config_shards_api)
                 .configure(config_issues_api)
                 .configure(config_debugger_api)
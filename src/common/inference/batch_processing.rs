@@ -98,7 +98,12 @@ fn collect_query(query: &Query, batch: &mut BatchAccum) {
                 }
             }
         }
-        Query::OrderBy(_) | Query::Fusion(_) | Query::Sample(_) => {}
+        Query::Mmr(mmr) => collect_vector_input(&mmr.mmr.vector, batch),
+        Query::OrderBy(_)
+        | Query::Fusion(_)
+        | Query::Sample(_)
+        | Query::Formula(_)
+        | Query::Bm25(_) => {}
     }
 }
 
@@ -194,7 +199,7 @@ pub fn collect_query_request(request: &QueryRequestInternal) -> BatchAccum {
 #[cfg(test)]
 mod tests {
     use api::rest::schema::{DiscoverQuery, Document, Image, InferenceObject, NearestQuery};
-    use api::rest::QueryBaseGroupRequest;
+    use api::rest::{GroupByInterface, QueryBaseGroupRequest};
     use serde_json::json;
 
     use super::*;
@@ -342,7 +347,8 @@ mod tests {
             with_payload: None,
             lookup_from: None,
             group_request: QueryBaseGroupRequest {
-                group_by: "test".parse().unwrap(),
+                group_by: GroupByInterface::Field("test".parse().unwrap()),
+                group_score: None,
                 group_size: None,
                 limit: None,
                 with_lookup: None,
@@ -158,9 +158,17 @@ pub(crate) fn collect_query(query: &Query, batch: &mut BatchAccumGrpc) -> Result
         query::Variant::Recommend(recommend) => collect_recommend_input(recommend, batch)?,
         query::Variant::Discover(discover) => collect_discover_input(discover, batch)?,
         query::Variant::Context(context) => collect_context_input(context, batch)?,
+        query::Variant::Mmr(mmr) => {
+            if let Some(vector) = &mmr.vector {
+                collect_vector_input(vector, batch)?;
+            }
+        }
         query::Variant::OrderBy(_) => {}
         query::Variant::Fusion(_) => {}
+        query::Variant::FusionQuery(_) => {}
         query::Variant::Sample(_) => {}
+        query::Variant::Formula(_) => {}
+        query::Variant::Bm25(_) => {}
     }
 
     Ok(())
@@ -1,14 +1,18 @@
-use api::conversions::json::json_path_from_proto;
+use api::grpc::conversions::{group_by_from_proto, group_score_from_proto};
 use api::grpc::qdrant as grpc;
 use api::grpc::qdrant::query::Variant;
 use api::grpc::qdrant::RecommendInput;
 use api::rest;
 use api::rest::RecommendStrategy;
 use collection::operations::universal_query::collection_query::{
-    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Query,
+    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, MmrQuery, Query,
     VectorInputInternal, VectorQuery,
 };
-use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
+use collection::operations::universal_query::formula::FormulaInternal;
+use collection::operations::universal_query::shard_query::{
+    FusionInternal, MmrInternal, SampleInternal,
+};
+use segment::data_types::bm25::Bm25Query;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{VectorInternal, DEFAULT_VECTOR_NAME};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
@@ -42,6 +46,8 @@ pub async fn convert_query_point_groups_from_grpc(
         read_consistency: _,
         timeout: _,
         shard_key_selector: _,
+        extra_group_by,
+        group_score,
     } = query;
 
     let mut batch = BatchAccumGrpc::new();
@@ -85,7 +91,11 @@ pub async fn convert_query_point_groups_from_grpc(
             .transpose()?
             .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
         lookup_from: lookup_from.map(From::from),
-        group_by: json_path_from_proto(&group_by)?,
+        group_by: group_by_from_proto(&group_by, &extra_group_by)?.into(),
+        group_score: group_score
+            .map(group_score_from_proto)
+            .transpose()?
+            .unwrap_or_default(),
         group_size: group_size
             .map(|s| s as usize)
             .unwrap_or(CollectionQueryRequest::DEFAULT_GROUP_SIZE),
@@ -276,9 +286,25 @@ fn convert_query_with_inferred(
             let context_query = context_query_from_grpc_with_inferred(context, inferred)?;
             Query::Vector(VectorQuery::Context(context_query))
         }
+        Variant::Mmr(mmr) => {
+            let grpc::Mmr { vector, lambda } = mmr;
+
+            let vector = vector
+                .map(|v| convert_vector_input_with_inferred(v, inferred))
+                .transpose()?
+                .ok_or_else(|| Status::invalid_argument("Mmr vector is missing"))?;
+
+            Query::Vector(VectorQuery::Mmr(MmrQuery {
+                vector,
+                lambda: lambda.unwrap_or(MmrInternal::DEFAULT_LAMBDA),
+            }))
+        }
         Variant::OrderBy(order_by) => Query::OrderBy(OrderBy::try_from(order_by)?),
         Variant::Fusion(fusion) => Query::Fusion(FusionInternal::try_from(fusion)?),
+        Variant::FusionQuery(fusion) => Query::Fusion(FusionInternal::try_from(fusion)?),
         Variant::Sample(sample) => Query::Sample(SampleInternal::try_from(sample)?),
+        Variant::Formula(formula) => Query::Formula(FormulaInternal::try_from(formula)?),
+        Variant::Bm25(bm25) => Query::Bm25(Bm25Query::try_from(bm25)?),
     };
 
     Ok(query)
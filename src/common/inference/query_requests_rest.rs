@@ -1,10 +1,14 @@
 use api::rest::schema as rest;
 use collection::lookup::WithLookup;
 use collection::operations::universal_query::collection_query::{
-    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, Query,
+    CollectionPrefetch, CollectionQueryGroupsRequest, CollectionQueryRequest, MmrQuery, Query,
     VectorInputInternal, VectorQuery,
 };
-use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
+use collection::operations::universal_query::formula::FormulaInternal;
+use collection::operations::universal_query::shard_query::{
+    FusionInternal, MmrInternal, SampleInternal,
+};
+use segment::data_types::bm25::Bm25Query;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{MultiDenseVectorInternal, VectorInternal, DEFAULT_VECTOR_NAME};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
@@ -61,7 +65,8 @@ pub async fn convert_query_groups_request_from_rest(
         limit: group_request
             .limit
             .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
-        group_by: group_request.group_by,
+        group_by: group_request.group_by.into(),
+        group_score: group_request.group_score.unwrap_or_default(),
         group_size: group_request
             .group_size
             .unwrap_or(CollectionQueryRequest::DEFAULT_GROUP_SIZE),
@@ -221,9 +226,19 @@ fn convert_query_with_inferred(
                 context,
             ))))
         }
+        rest::Query::Mmr(mmr) => {
+            let rest::MmrInput { vector, lambda } = mmr.mmr;
+            let vector = convert_vector_input_with_inferred(vector, inferred)?;
+            Ok(Query::Vector(VectorQuery::Mmr(MmrQuery {
+                vector,
+                lambda: lambda.unwrap_or(MmrInternal::DEFAULT_LAMBDA),
+            })))
+        }
         rest::Query::OrderBy(order_by) => Ok(Query::OrderBy(OrderBy::from(order_by.order_by))),
-        rest::Query::Fusion(fusion) => Ok(Query::Fusion(FusionInternal::from(fusion.fusion))),
+        rest::Query::Fusion(fusion) => Ok(Query::Fusion(FusionInternal::from(fusion))),
         rest::Query::Sample(sample) => Ok(Query::Sample(SampleInternal::from(sample.sample))),
+        rest::Query::Formula(formula) => Ok(Query::Formula(FormulaInternal::from(formula))),
+        rest::Query::Bm25(bm25) => Ok(Query::Bm25(Bm25Query::from(bm25.bm25))),
     }
 }
 
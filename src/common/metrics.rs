@@ -21,6 +21,8 @@ use crate::common::telemetry_ops::requests_telemetry::{
 ///
 /// This array *must* be sorted.
 const REST_ENDPOINT_WHITELIST: &[&str] = &[
+    "/collections/{name}/aggregate",
+    "/collections/{name}/facet/distinct",
     "/collections/{name}/index",
     "/collections/{name}/points",
     "/collections/{name}/points/batch",
@@ -54,12 +56,14 @@ const REST_ENDPOINT_WHITELIST: &[&str] = &[
 ///
 /// This array *must* be sorted.
 const GRPC_ENDPOINT_WHITELIST: &[&str] = &[
+    "/qdrant.Points/Aggregate",
     "/qdrant.Points/ClearPayload",
     "/qdrant.Points/Count",
     "/qdrant.Points/Delete",
     "/qdrant.Points/DeletePayload",
     "/qdrant.Points/Discover",
     "/qdrant.Points/DiscoverBatch",
+    "/qdrant.Points/DistinctCount",
     "/qdrant.Points/Facet",
     "/qdrant.Points/Get",
     "/qdrant.Points/OverwritePayload",
@@ -57,6 +57,7 @@ use crate::common::inference::update_requests::{
 pub struct CreateFieldIndex {
     pub field_name: PayloadKeyType,
     #[serde(alias = "field_type")]
+    #[validate(nested)]
     pub field_schema: Option<PayloadFieldSchema>,
 }
 
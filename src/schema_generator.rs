@@ -1,9 +1,10 @@
 use api::rest::models::{CollectionsResponse, HardwareUsage, VersionInfo};
 use api::rest::schema::PointInsertOperations;
 use api::rest::{
-    FacetRequest, FacetResponse, QueryGroupsRequest, QueryRequest, QueryRequestBatch,
-    QueryResponse, Record, ScoredPoint, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
-    SearchMatrixRequest, UpdateVectors,
+    AggregateRequest, AggregateResponse, DistinctCountRequest, DistinctCountResponse, FacetRequest,
+    FacetResponse, QueryGroupsRequest, QueryRequest, QueryRequestBatch, QueryResponse, Record,
+    ScoredPoint, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest,
+    UpdateVectors,
 };
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
@@ -96,6 +97,10 @@ struct AllDefinitions {
     bl: FacetRequest,
     bm: FacetResponse,
     bn: HardwareUsage,
+    bo: AggregateRequest,
+    bp: AggregateResponse,
+    bq: DistinctCountRequest,
+    br: DistinctCountResponse,
 }
 
 fn save_schema<T: JsonSchema>() {
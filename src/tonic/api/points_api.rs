@@ -3,17 +3,17 @@ use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
-    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints,
-    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
-    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
-    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
-    SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsets, SearchMatrixOffsetsResponse,
-    SearchMatrixPairs, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse,
-    UpdatePointVectors, UpsertPoints,
+    AggregatePoints, AggregateResponse, ClearPayloadPoints, CountPoints, CountResponse,
+    CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
+    DeletePointVectors, DeletePoints, DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints,
+    DiscoverResponse, DistinctCountPoints, DistinctCountResponse, FacetCounts, FacetResponse,
+    GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints, QueryBatchResponse,
+    QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse, RecommendBatchPoints,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse,
+    SearchGroupsResponse, SearchMatrixOffsets, SearchMatrixOffsetsResponse, SearchMatrixPairs,
+    SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse,
+    SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
@@ -22,8 +22,9 @@ use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, facet, query, query_batch, query_groups,
-    recommend_groups, scroll, search_groups, search_points_matrix, update_batch, update_vectors,
+    aggregate, delete_vectors, discover, discover_batch, distinct_count, facet, query, query_batch,
+    query_groups, recommend_groups, scroll, search_groups, search_points_matrix, update_batch,
+    update_vectors,
 };
 use super::validate;
 use crate::settings::ServiceConfig;
@@ -597,6 +598,34 @@ impl Points for PointsService {
         .await
     }
 
+    async fn aggregate(
+        &self,
+        mut request: Request<AggregatePoints>,
+    ) -> Result<Response<AggregateResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        aggregate(
+            StrictModeCheckedTocProvider::new(&self.dispatcher),
+            request.into_inner(),
+            access,
+        )
+        .await
+    }
+
+    async fn distinct_count(
+        &self,
+        mut request: Request<DistinctCountPoints>,
+    ) -> Result<Response<DistinctCountResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        distinct_count(
+            StrictModeCheckedTocProvider::new(&self.dispatcher),
+            request.into_inner(),
+            access,
+        )
+        .await
+    }
+
     async fn search_matrix_pairs(
         &self,
         mut request: Request<SearchMatrixPoints>,
@@ -5,12 +5,13 @@ use api::conversions::json::{json_path_from_proto, proto_to_payloads};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
-    DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, GroupsResult,
-    PayloadIndexParams, PointsOperationResponseInternal, PointsSelector, QueryBatchResponse,
-    QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
+    points_update_operation, AggregatePoints, AggregateResponse, AggregateResult, BatchResult,
+    ClearPayloadPoints, CoreSearchPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
+    DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, DistinctCountPoints,
+    DistinctCountResponse, DistinctCountResult, FacetCounts, FacetResponse, FieldType, GetPoints,
+    GetResponse, GroupsResult, PayloadIndexParams, PointsOperationResponseInternal, PointsSelector,
+    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
     ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
     RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
     SearchBatchResponse, SearchGroupsResponse, SearchMatrixPoints, SearchPointGroups, SearchPoints,
@@ -41,6 +42,7 @@ use collection::operations::{ClockTag, CollectionUpdateOperations, OperationWith
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
+use segment::data_types::aggregations::{AggregateParams, DistinctCountParams};
 use segment::data_types::facets::FacetParams;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
@@ -839,6 +841,12 @@ fn convert_field_type(
                         TryFrom::try_from(uuid_index_params).map(PayloadSchemaParams::Uuid)
                     })
                 }
+                // Parameterized geo shape type
+                IndexParams::GeoShapeIndexParams(geo_shape_index_params) => {
+                    matches!(field_type, FieldType::GeoShape).then(|| {
+                        TryFrom::try_from(geo_shape_index_params).map(PayloadSchemaParams::GeoShape)
+                    })
+                }
             }
             .ok_or_else(|| {
                 Status::invalid_argument(format!(
@@ -858,6 +866,7 @@ fn convert_field_type(
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
             FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
             FieldType::Uuid => Some(PayloadSchemaType::Uuid.into()),
+            FieldType::GeoShape => Some(PayloadSchemaType::GeoShape.into()),
         },
         (None, Some(_)) => return Err(Status::invalid_argument("field type is missing")),
         (None, None) => None,
@@ -1937,6 +1946,9 @@ pub async fn facet(
         read_consistency,
         shard_key_selector,
         timeout,
+        geo_grid,
+        histogram,
+        pivot,
     } = facet_counts;
 
     let facet_request = FacetParams {
@@ -1948,6 +1960,12 @@ pub async fn facet(
             .map_err(|_| Status::invalid_argument("could not parse limit param into usize"))?
             .unwrap_or(FacetParams::DEFAULT_LIMIT),
         exact: exact.unwrap_or(FacetParams::DEFAULT_EXACT),
+        geo_grid: geo_grid.map(From::from),
+        histogram: histogram.map(TryFrom::try_from).transpose()?,
+        pivot: pivot
+            .into_iter()
+            .map(TryFrom::try_from)
+            .collect::<Result<_, _>>()?,
     };
 
     let toc = toc_provider
@@ -1986,6 +2004,116 @@ pub async fn facet(
     Ok(Response::new(response))
 }
 
+pub async fn aggregate(
+    toc_provider: impl CheckedTocProvider,
+    aggregate_points: AggregatePoints,
+    access: Access,
+) -> Result<Response<AggregateResponse>, Status> {
+    let AggregatePoints {
+        collection_name,
+        key,
+        filter,
+        percentiles,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = aggregate_points;
+
+    let aggregate_request = AggregateParams {
+        key: json_path_from_proto(&key)?,
+        filter: filter.map(TryInto::try_into).transpose()?,
+        percentiles,
+    };
+
+    let toc = toc_provider
+        .check_strict_mode(
+            &aggregate_request,
+            &collection_name,
+            timeout.map(|i| i as usize),
+            &access,
+        )
+        .await?;
+
+    let timeout = timeout.map(Duration::from_secs);
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timing = Instant::now();
+    let aggregate_response = toc
+        .aggregate(
+            &collection_name,
+            aggregate_request,
+            shard_selector,
+            read_consistency,
+            access,
+            timeout,
+        )
+        .await?;
+
+    let response = AggregateResponse {
+        result: Some(AggregateResult::from(aggregate_response)),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn distinct_count(
+    toc_provider: impl CheckedTocProvider,
+    distinct_count_points: DistinctCountPoints,
+    access: Access,
+) -> Result<Response<DistinctCountResponse>, Status> {
+    let DistinctCountPoints {
+        collection_name,
+        key,
+        filter,
+        exact,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = distinct_count_points;
+
+    let distinct_count_request = DistinctCountParams {
+        key: json_path_from_proto(&key)?,
+        filter: filter.map(TryInto::try_into).transpose()?,
+        exact: exact.unwrap_or(DistinctCountParams::DEFAULT_EXACT),
+    };
+
+    let toc = toc_provider
+        .check_strict_mode(
+            &distinct_count_request,
+            &collection_name,
+            timeout.map(|i| i as usize),
+            &access,
+        )
+        .await?;
+
+    let timeout = timeout.map(Duration::from_secs);
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    let timing = Instant::now();
+    let distinct_count_response = toc
+        .distinct_count(
+            &collection_name,
+            distinct_count_request,
+            shard_selector,
+            read_consistency,
+            access,
+            timeout,
+        )
+        .await?;
+
+    let response = DistinctCountResponse {
+        result: Some(DistinctCountResult::from(distinct_count_response)),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 pub async fn search_points_matrix(
     toc_provider: impl CheckedTocProvider,
     search_matrix_points: SearchMatrixPoints,
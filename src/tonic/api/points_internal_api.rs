@@ -4,9 +4,11 @@ use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
-    ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
+    AggregatePointsInternal, AggregateResponseInternal, ClearPayloadPointsInternal,
+    CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal,
+    DistinctCountPointsInternal, DistinctCountResponseInternal, FacetCountsInternal,
     FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult,
     PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
     QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
@@ -18,6 +20,7 @@ use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
+use segment::data_types::aggregations::{AggregateParams, DistinctCountParams};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::json_path::JsonPath;
 use segment::types::Filter;
@@ -121,6 +124,9 @@ async fn facet_counts_internal(
         exact,
         shard_id,
         timeout,
+        geo_grid,
+        histogram,
+        pivot,
     } = request;
 
     let shard_selection = ShardSelectorInternal::ShardId(shard_id);
@@ -131,6 +137,12 @@ async fn facet_counts_internal(
         limit: limit as usize,
         filter: filter.map(Filter::try_from).transpose()?,
         exact,
+        geo_grid: geo_grid.map(From::from),
+        histogram: histogram.map(TryFrom::try_from).transpose()?,
+        pivot: pivot
+            .into_iter()
+            .map(TryFrom::try_from)
+            .collect::<Result<_, _>>()?,
     };
 
     let response = toc
@@ -152,6 +164,88 @@ async fn facet_counts_internal(
     Ok(Response::new(response))
 }
 
+async fn aggregate_internal(
+    toc: &TableOfContent,
+    request: AggregatePointsInternal,
+) -> Result<Response<AggregateResponseInternal>, Status> {
+    let timing = Instant::now();
+
+    let AggregatePointsInternal {
+        collection_name,
+        key,
+        filter,
+        shard_id,
+        timeout,
+    } = request;
+
+    let shard_selection = ShardSelectorInternal::ShardId(shard_id);
+
+    // Percentiles are computed from the merged digests by the requesting peer
+    let request = AggregateParams {
+        key: JsonPath::from_str(&key)
+            .map_err(|_| Status::invalid_argument("Failed to parse aggregate key"))?,
+        filter: filter.map(Filter::try_from).transpose()?,
+        percentiles: Vec::new(),
+    };
+
+    let response = toc
+        .aggregate_internal(
+            &collection_name,
+            request,
+            shard_selection,
+            timeout.map(Duration::from_secs),
+        )
+        .await?;
+
+    let response = AggregateResponseInternal {
+        result: Some(From::from(response)),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+async fn distinct_count_internal(
+    toc: &TableOfContent,
+    request: DistinctCountPointsInternal,
+) -> Result<Response<DistinctCountResponseInternal>, Status> {
+    let timing = Instant::now();
+
+    let DistinctCountPointsInternal {
+        collection_name,
+        key,
+        filter,
+        exact,
+        shard_id,
+        timeout,
+    } = request;
+
+    let shard_selection = ShardSelectorInternal::ShardId(shard_id);
+
+    let request = DistinctCountParams {
+        key: JsonPath::from_str(&key)
+            .map_err(|_| Status::invalid_argument("Failed to parse distinct count key"))?,
+        filter: filter.map(Filter::try_from).transpose()?,
+        exact,
+    };
+
+    let response = toc
+        .distinct_count_internal(
+            &collection_name,
+            request,
+            shard_selection,
+            timeout.map(Duration::from_secs),
+        )
+        .await?;
+
+    let response = DistinctCountResponseInternal {
+        result: Some(From::from(response)),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 impl PointsInternalService {
     /// Generates a new `RequestHwCounter` for the request.
     /// This counter is indented to be used for internal requests.
@@ -626,4 +720,22 @@ impl PointsInternal for PointsInternalService {
 
         facet_counts_internal(self.toc.as_ref(), request.into_inner()).await
     }
+
+    async fn aggregate(
+        &self,
+        request: Request<AggregatePointsInternal>,
+    ) -> Result<Response<AggregateResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        aggregate_internal(self.toc.as_ref(), request.into_inner()).await
+    }
+
+    async fn distinct_count(
+        &self,
+        request: Request<DistinctCountPointsInternal>,
+    ) -> Result<Response<DistinctCountResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        distinct_count_internal(self.toc.as_ref(), request.into_inner()).await
+    }
 }